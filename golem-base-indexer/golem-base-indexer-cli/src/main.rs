@@ -7,7 +7,9 @@ use blockscout_service_launcher::database::{
 use clap::{Parser, Subcommand};
 use golem_base_indexer_logic::{
     Indexer, repository,
-    types::{EntitiesFilter, EntityKey, ListEntitiesFilter, PaginationParams},
+    types::{
+        CursorPaginationParams, EntitiesFilter, EntityKey, ListEntitiesFilter, PaginationParams,
+    },
 };
 use migration::Migrator;
 use sea_orm::DatabaseConnection;
@@ -41,16 +43,24 @@ async fn list_entity_keys(db: DatabaseConnection) -> Result<()> {
     let (entities, _) = repository::entities::list_entities(
         &db,
         ListEntitiesFilter {
-            pagination: PaginationParams {
-                page: 0,
-                page_size: i64::MAX as u64,
+            pagination: CursorPaginationParams {
+                pagination: PaginationParams {
+                    page: 0,
+                    page_size: i64::MAX as u64,
+                },
+                cursor: None,
+                with_totals: false,
             },
             entities_filter: EntitiesFilter {
                 status: None,
                 string_attribute: None,
                 numeric_attribute: None,
                 owner: None,
+                query: None,
+                ..Default::default()
             },
+            sort: Default::default(),
+            at_block: None,
         },
     )
     .await?;
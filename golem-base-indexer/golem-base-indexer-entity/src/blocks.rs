@@ -42,6 +42,8 @@ pub struct Model {
 pub enum Relation {
     #[sea_orm(has_many = "super::golem_base_entity_history::Entity")]
     GolemBaseEntityHistory,
+    #[sea_orm(has_one = "super::golem_base_indexed_blocks::Entity")]
+    GolemBaseIndexedBlocks,
     #[sea_orm(has_many = "super::golem_base_operations::Entity")]
     GolemBaseOperations,
 }
@@ -52,6 +54,12 @@ impl Related<super::golem_base_entity_history::Entity> for Entity {
     }
 }
 
+impl Related<super::golem_base_indexed_blocks::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::GolemBaseIndexedBlocks.def()
+    }
+}
+
 impl Related<super::golem_base_operations::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::GolemBaseOperations.def()
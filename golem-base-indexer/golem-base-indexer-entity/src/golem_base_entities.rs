@@ -29,6 +29,8 @@ pub struct Model {
     pub content_type: Option<String>,
     #[sea_orm(column_type = "Decimal(Some((100, 0)))", nullable)]
     pub cost: Option<BigDecimal>,
+    pub created_at_block_number: Option<i64>,
+    pub last_updated_at_block_number: Option<i64>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
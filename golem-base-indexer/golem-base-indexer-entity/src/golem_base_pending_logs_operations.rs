@@ -20,6 +20,12 @@ pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
     pub index: i32,
     pub block_number: i32,
+    pub attempts: i32,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub last_error: Option<String>,
+    pub next_attempt_at: DateTime,
+    pub quarantined_at: Option<DateTime>,
+    pub inserted_at: DateTime,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
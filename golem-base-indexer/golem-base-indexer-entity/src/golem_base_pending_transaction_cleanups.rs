@@ -12,6 +12,11 @@ pub struct Model {
     )]
     pub hash: Vec<u8>,
     pub inserted_at: DateTime,
+    pub attempts: i32,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub last_error: Option<String>,
+    pub next_attempt_at: DateTime,
+    pub quarantined_at: Option<DateTime>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -16,6 +16,11 @@ pub struct Model {
     pub block_number: i64,
     #[sea_orm(primary_key, auto_increment = false)]
     pub index: i64,
+    pub attempts: i32,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub last_error: Option<String>,
+    pub next_attempt_at: DateTime,
+    pub quarantined_at: Option<DateTime>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -7,6 +7,7 @@ pub mod blocks;
 pub mod golem_base_block_stats;
 pub mod golem_base_entities;
 pub mod golem_base_entity_history;
+pub mod golem_base_indexed_blocks;
 pub mod golem_base_numeric_annotations;
 pub mod golem_base_operations;
 pub mod golem_base_pending_logs_events;
@@ -14,6 +15,8 @@ pub mod golem_base_pending_logs_operations;
 pub mod golem_base_pending_transaction_cleanups;
 pub mod golem_base_pending_transaction_operations;
 pub mod golem_base_string_annotations;
+pub mod golem_base_webhook_deliveries;
+pub mod golem_base_webhooks;
 pub mod logs;
 pub mod sea_orm_active_enums;
 pub mod transactions;
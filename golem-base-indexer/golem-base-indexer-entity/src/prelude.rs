@@ -5,12 +5,15 @@ pub use super::{
     golem_base_block_stats::Entity as GolemBaseBlockStats,
     golem_base_entities::Entity as GolemBaseEntities,
     golem_base_entity_history::Entity as GolemBaseEntityHistory,
+    golem_base_indexed_blocks::Entity as GolemBaseIndexedBlocks,
     golem_base_numeric_annotations::Entity as GolemBaseNumericAnnotations,
     golem_base_operations::Entity as GolemBaseOperations,
     golem_base_pending_logs_events::Entity as GolemBasePendingLogsEvents,
     golem_base_pending_logs_operations::Entity as GolemBasePendingLogsOperations,
     golem_base_pending_transaction_cleanups::Entity as GolemBasePendingTransactionCleanups,
     golem_base_pending_transaction_operations::Entity as GolemBasePendingTransactionOperations,
-    golem_base_string_annotations::Entity as GolemBaseStringAnnotations, logs::Entity as Logs,
+    golem_base_string_annotations::Entity as GolemBaseStringAnnotations,
+    golem_base_webhook_deliveries::Entity as GolemBaseWebhookDeliveries,
+    golem_base_webhooks::Entity as GolemBaseWebhooks, logs::Entity as Logs,
     transactions::Entity as Transactions,
 };
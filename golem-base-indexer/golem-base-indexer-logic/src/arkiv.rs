@@ -1,10 +1,14 @@
 use crate::{
-    types::{Block, BlockNumber, Bytes, EntityKey, Timestamp, TxHash},
+    types::{
+        Block, BlockNumber, Bytes, DecodingError, DecodingErrorKind, EntityKey, Timestamp, TxHash,
+    },
     well_known::SECS_PER_BLOCK,
 };
 use alloy_primitives::{keccak256, U256};
+use alloy_rlp::Decodable;
 use alloy_sol_types::SolValue;
 use anyhow::Result;
+use arkiv_storage_tx::StorageTransaction;
 use chrono::Duration;
 
 pub fn block_timestamp(number: BlockNumber, reference_block: &Block) -> Option<Timestamp> {
@@ -41,10 +45,70 @@ pub fn decode_extend_log_data(data: &Bytes) -> Result<u64> {
     Ok(expires_at_block_number.try_into()?)
 }
 
+/// Same as decoding with `StorageTransaction::try_from`, but tells apart the
+/// stage that failed and rejects calldata with bytes left after the RLP payload.
+pub fn decode_storage_tx(input: &[u8]) -> Result<StorageTransaction, DecodingError> {
+    let mut buf = Vec::<u8>::new();
+    brotli::BrotliDecompress(&mut &input[..], &mut buf).map_err(|e| DecodingError {
+        kind: DecodingErrorKind::Compression,
+        message: e.to_string(),
+    })?;
+
+    let mut rest = buf.as_slice();
+    let storage_tx = StorageTransaction::decode(&mut rest).map_err(|e| DecodingError {
+        kind: DecodingErrorKind::Rlp,
+        message: e.to_string(),
+    })?;
+    if !rest.is_empty() {
+        return Err(DecodingError {
+            kind: DecodingErrorKind::TrailingBytes,
+            message: format!("{} bytes left after the storage transaction", rest.len()),
+        });
+    }
+
+    Ok(storage_tx)
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::arkiv::{block_timestamp, block_timestamp_sec, entity_key, Block};
+    use crate::{
+        arkiv::{block_timestamp, block_timestamp_sec, decode_storage_tx, entity_key, Block},
+        types::DecodingErrorKind,
+    };
     use alloy_primitives::{b256, bytes};
+    use arkiv_storage_tx::{Create, StorageTransaction};
+
+    fn compress(data: &[u8]) -> Vec<u8> {
+        let mut buf = Vec::new();
+        brotli::BrotliCompress(&mut &data[..], &mut buf, &Default::default()).unwrap();
+        buf
+    }
+
+    #[test]
+    fn storage_tx_decoding_errors_are_classified() {
+        let storage_tx = StorageTransaction {
+            creates: vec![Create {
+                btl: 10,
+                payload: vec![0x12, 0x34].into(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let rlp = alloy_rlp::encode(storage_tx.clone());
+
+        let decoded = decode_storage_tx(&compress(&rlp)).unwrap();
+        assert_eq!(decoded.creates[0].payload, storage_tx.creates[0].payload);
+
+        let err = decode_storage_tx(b"not brotli at all").unwrap_err();
+        assert_eq!(err.kind, DecodingErrorKind::Compression);
+
+        let err = decode_storage_tx(&compress(&[0xff, 0x00])).unwrap_err();
+        assert_eq!(err.kind, DecodingErrorKind::Rlp);
+
+        let err =
+            decode_storage_tx(&compress(&[rlp.as_slice(), &[0x01, 0x02]].concat())).unwrap_err();
+        assert_eq!(err.kind, DecodingErrorKind::TrailingBytes);
+    }
 
     #[test]
     fn entity_key_calculated_correctly() {
@@ -1,4 +1,4 @@
-use crate::types::Attribute;
+use crate::types::{Attribute, AttributeChange, AttributesDiff};
 use arkiv_storage_tx::Attribute as ArkivAttribute;
 
 impl<T: std::fmt::Debug> From<ArkivAttribute<T>> for Attribute<T> {
@@ -9,3 +9,101 @@ impl<T: std::fmt::Debug> From<ArkivAttribute<T>> for Attribute<T> {
         }
     }
 }
+
+impl<T: std::fmt::Debug + Clone + PartialEq> AttributesDiff<T> {
+    /// Compares two attribute sets. Keys can hold multiple values, so a value is
+    /// only reported as changed when the key has exactly one value on both sides.
+    pub fn new(prev: &[Attribute<T>], current: &[Attribute<T>]) -> Self {
+        let mut added: Vec<_> = current
+            .iter()
+            .filter(|attribute| !prev.contains(attribute))
+            .cloned()
+            .collect();
+        let mut removed: Vec<_> = prev
+            .iter()
+            .filter(|attribute| !current.contains(attribute))
+            .cloned()
+            .collect();
+        let single_value = |attributes: &[Attribute<T>], key: &str| {
+            attributes.iter().filter(|v| v.key == key).count() == 1
+        };
+
+        let changed: Vec<_> = added
+            .iter()
+            .filter(|new| single_value(prev, &new.key) && single_value(current, &new.key))
+            .filter_map(|new| {
+                removed
+                    .iter()
+                    .find(|old| old.key == new.key)
+                    .map(|old| AttributeChange {
+                        key: new.key.clone(),
+                        old_value: old.value.clone(),
+                        new_value: new.value.clone(),
+                    })
+            })
+            .collect();
+        added.retain(|v| !changed.iter().any(|change| change.key == v.key));
+        removed.retain(|v| !changed.iter().any(|change| change.key == v.key));
+
+        Self {
+            added,
+            removed,
+            changed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attribute(key: &str, value: u64) -> Attribute<u64> {
+        Attribute {
+            key: key.to_string(),
+            value,
+        }
+    }
+
+    #[test]
+    fn diffs_attributes() {
+        let prev = [
+            attribute("kept", 1),
+            attribute("changed", 1),
+            attribute("removed", 1),
+            attribute("multi", 1),
+            attribute("multi", 2),
+        ];
+        let current = [
+            attribute("kept", 1),
+            attribute("changed", 2),
+            attribute("added", 1),
+            attribute("multi", 1),
+            attribute("multi", 3),
+        ];
+
+        assert_eq!(
+            AttributesDiff::new(&prev, &current),
+            AttributesDiff {
+                added: vec![attribute("added", 1), attribute("multi", 3)],
+                removed: vec![attribute("removed", 1), attribute("multi", 2)],
+                changed: vec![AttributeChange {
+                    key: "changed".to_string(),
+                    old_value: 1,
+                    new_value: 2,
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn diffs_against_nothing() {
+        let current = [attribute("a", 1)];
+        let diff = AttributesDiff::new(&[], &current);
+        assert_eq!(diff.added, current);
+        assert!(diff.removed.is_empty() && diff.changed.is_empty());
+
+        let diff = AttributesDiff::new(&current, &[]);
+        assert_eq!(diff.removed, current);
+        assert!(diff.added.is_empty() && diff.changed.is_empty());
+    }
+}
@@ -0,0 +1,211 @@
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use similar::{Algorithm, DiffOp, TextDiff};
+
+use crate::types::{ByteRangeChange, DataDiff, JsonPatchOperation};
+
+/// Byte diffs of large payloads fall back to coarser ranges after this long.
+const BYTE_DIFF_DEADLINE: Duration = Duration::from_secs(1);
+/// Line diffs of large payloads fall back to coarser hunks after this long.
+const TEXT_DIFF_DEADLINE: Duration = Duration::from_secs(1);
+
+enum Format {
+    Json,
+    Text,
+    Binary,
+}
+
+impl Format {
+    fn from_content_type(content_type: Option<&str>) -> Self {
+        let mime = content_type
+            .and_then(|v| v.split(';').next())
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        if mime == "application/json" || mime.ends_with("+json") {
+            Self::Json
+        } else if mime.starts_with("text/") {
+            Self::Text
+        } else {
+            Self::Binary
+        }
+    }
+}
+
+impl DataDiff {
+    /// Diffs two payloads according to their content type. Payloads that don't
+    /// decode as their content type are diffed as text, or as bytes if they are
+    /// not valid UTF-8 either.
+    pub fn new(content_type: Option<&str>, from: &[u8], to: &[u8]) -> Self {
+        let format = Format::from_content_type(content_type);
+        if let Format::Json = format {
+            if let Ok(patch) = json_patch(from, to) {
+                return Self::JsonPatch(patch);
+            }
+        }
+        if let Format::Json | Format::Text = format {
+            if let (Ok(from), Ok(to)) = (std::str::from_utf8(from), std::str::from_utf8(to)) {
+                return Self::Unified(
+                    TextDiff::configure()
+                        .timeout(TEXT_DIFF_DEADLINE)
+                        .diff_lines(from, to)
+                        .unified_diff()
+                        .header("from", "to")
+                        .to_string(),
+                );
+            }
+        }
+        Self::ByteRanges(byte_ranges(from, to))
+    }
+}
+
+fn json_patch(from: &[u8], to: &[u8]) -> Result<Vec<JsonPatchOperation>> {
+    let from: serde_json::Value = serde_json::from_slice(from)?;
+    let to: serde_json::Value = serde_json::from_slice(to)?;
+    json_patch::diff(&from, &to)
+        .0
+        .into_iter()
+        .map(|op| {
+            let mut op = serde_json::to_value(op)?;
+            let string = |op: &serde_json::Value, name: &str| {
+                op.get(name)
+                    .and_then(|v| v.as_str())
+                    .map(ToString::to_string)
+            };
+            Ok(JsonPatchOperation {
+                op: string(&op, "op").unwrap_or_default(),
+                path: string(&op, "path").unwrap_or_default(),
+                from: string(&op, "from"),
+                value: op.get_mut("value").map(serde_json::Value::take),
+            })
+        })
+        .collect()
+}
+
+fn byte_ranges(from: &[u8], to: &[u8]) -> Vec<ByteRangeChange> {
+    similar::capture_diff_slices_deadline(
+        Algorithm::Myers,
+        from,
+        to,
+        Some(Instant::now() + BYTE_DIFF_DEADLINE),
+    )
+    .into_iter()
+    .filter_map(|op| {
+        let (from_offset, from_length, to_offset, to_length) = match op {
+            DiffOp::Equal { .. } => return None,
+            DiffOp::Delete {
+                old_index,
+                old_len,
+                new_index,
+            } => (old_index, old_len, new_index, 0),
+            DiffOp::Insert {
+                old_index,
+                new_index,
+                new_len,
+            } => (old_index, 0, new_index, new_len),
+            DiffOp::Replace {
+                old_index,
+                old_len,
+                new_index,
+                new_len,
+            } => (old_index, old_len, new_index, new_len),
+        };
+        Some(ByteRangeChange {
+            from_offset: from_offset as u64,
+            from_length: from_length as u64,
+            to_offset: to_offset as u64,
+            to_length: to_length as u64,
+        })
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn diffs_json_as_patch() {
+        let diff = DataDiff::new(
+            Some("application/json; charset=utf-8"),
+            br#"{"name":"note","tags":["a"],"old":1}"#,
+            br#"{"name":"draft","tags":["a","b"]}"#,
+        );
+        let DataDiff::JsonPatch(mut patch) = diff else {
+            panic!("expected json patch, got {diff:?}");
+        };
+        patch.sort_by(|a, b| a.path.cmp(&b.path));
+        assert_eq!(
+            patch,
+            vec![
+                JsonPatchOperation {
+                    op: "replace".to_string(),
+                    path: "/name".to_string(),
+                    from: None,
+                    value: Some(json!("draft")),
+                },
+                JsonPatchOperation {
+                    op: "remove".to_string(),
+                    path: "/old".to_string(),
+                    from: None,
+                    value: None,
+                },
+                JsonPatchOperation {
+                    op: "add".to_string(),
+                    path: "/tags/1".to_string(),
+                    from: None,
+                    value: Some(json!("b")),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn diffs_text_as_unified_diff() {
+        let diff = DataDiff::new(Some("text/plain"), b"one\ntwo\nthree\n", b"one\n2\nthree\n");
+        assert_eq!(
+            diff,
+            DataDiff::Unified(
+                "--- from\n+++ to\n@@ -1,3 +1,3 @@\n one\n-two\n+2\n three\n".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn falls_back_to_text_for_invalid_json() {
+        let diff = DataDiff::new(Some("application/json"), b"{\n", b"{}\n");
+        assert!(matches!(diff, DataDiff::Unified(_)), "{diff:?}");
+    }
+
+    #[test]
+    fn diffs_binary_as_byte_ranges() {
+        let diff = DataDiff::new(
+            Some("application/octet-stream"),
+            &[0, 1, 2, 3, 4, 5],
+            &[0, 9, 9, 3, 4, 5, 6],
+        );
+        assert_eq!(
+            diff,
+            DataDiff::ByteRanges(vec![
+                ByteRangeChange {
+                    from_offset: 1,
+                    from_length: 2,
+                    to_offset: 1,
+                    to_length: 2,
+                },
+                ByteRangeChange {
+                    from_offset: 6,
+                    from_length: 0,
+                    to_offset: 6,
+                    to_length: 1,
+                },
+            ])
+        );
+
+        let diff = DataDiff::new(Some("text/plain"), &[0xff], &[0xfe]);
+        assert!(matches!(diff, DataDiff::ByteRanges(_)), "{diff:?}");
+    }
+}
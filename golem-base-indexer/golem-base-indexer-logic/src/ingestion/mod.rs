@@ -0,0 +1,63 @@
+mod rpc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use sea_orm::DatabaseConnection;
+use serde::Deserialize;
+use std::sync::Arc;
+use url::Url;
+
+pub use rpc::RpcSource;
+
+/// Fills the `blocks`, `transactions` and `logs` tables the indexer works on.
+#[async_trait]
+pub trait IngestionSource: Send + Sync {
+    async fn ingest(&self) -> Result<()>;
+}
+
+/// Blockscout writes the chain tables itself, so there is nothing to do here.
+pub struct BlockscoutSource;
+
+#[async_trait]
+impl IngestionSource for BlockscoutSource {
+    async fn ingest(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum IngestionSettings {
+    /// Chain data is written by a Blockscout instance sharing the database.
+    #[default]
+    Blockscout,
+    /// Chain data is pulled from the L3 node directly.
+    Rpc(RpcIngestionSettings),
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct RpcIngestionSettings {
+    pub url: Url,
+
+    /// Block to start from when the database holds no blocks yet.
+    #[serde(default)]
+    pub start_block: u64,
+
+    /// Maximum number of blocks fetched per indexer tick.
+    #[serde(default = "default_batch_size")]
+    pub batch_size: u64,
+}
+
+fn default_batch_size() -> u64 {
+    100
+}
+
+impl IngestionSettings {
+    pub fn build_source(&self, db: Arc<DatabaseConnection>) -> Arc<dyn IngestionSource> {
+        match self {
+            Self::Blockscout => Arc::new(BlockscoutSource),
+            Self::Rpc(settings) => Arc::new(RpcSource::new(db, settings.clone())),
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use std::{collections::HashMap, sync::Arc};
+
+use alloy::rpc::client::{ClientBuilder, RpcClient};
+use alloy_primitives::{Bytes as RpcBytes, B256, U256, U64};
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use sea_orm::{DatabaseConnection, TransactionTrait};
+use serde::Deserialize;
+use tracing::instrument;
+
+use super::{IngestionSource, RpcIngestionSettings};
+use crate::{
+    repository,
+    types::{
+        Address, Block, BlockHash, BlockNumber, ChainBlock, ChainLog, ChainTransaction, Timestamp,
+        TxHash,
+    },
+};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcBlock {
+    hash: BlockHash,
+    parent_hash: BlockHash,
+    number: U64,
+    timestamp: U64,
+    miner: Address,
+    gas_limit: U64,
+    gas_used: U64,
+    transactions: Vec<RpcTransaction>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcTransaction {
+    hash: TxHash,
+    transaction_index: U64,
+    from: Address,
+    to: Option<Address>,
+    input: RpcBytes,
+    nonce: U64,
+    gas: U64,
+    gas_price: Option<U256>,
+    #[serde(default)]
+    value: U256,
+    r#type: U64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcReceipt {
+    transaction_hash: TxHash,
+    status: Option<U64>,
+    gas_used: U64,
+    cumulative_gas_used: U64,
+    effective_gas_price: Option<U256>,
+    contract_address: Option<Address>,
+    logs: Vec<RpcLog>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcLog {
+    address: Address,
+    topics: Vec<B256>,
+    data: RpcBytes,
+    log_index: U64,
+}
+
+impl RpcBlock {
+    fn with_receipts(self, receipts: Vec<RpcReceipt>) -> Result<ChainBlock> {
+        let mut receipts: HashMap<TxHash, RpcReceipt> = receipts
+            .into_iter()
+            .map(|receipt| (receipt.transaction_hash, receipt))
+            .collect();
+
+        let transactions = self
+            .transactions
+            .into_iter()
+            .map(|tx| {
+                let receipt = receipts
+                    .remove(&tx.hash)
+                    .ok_or_else(|| anyhow!("Missing receipt for tx {}", tx.hash))?;
+                Ok(ChainTransaction {
+                    hash: tx.hash,
+                    index: tx.transaction_index.to(),
+                    from: tx.from,
+                    to: tx.to,
+                    input: tx.input.0,
+                    nonce: tx.nonce.to(),
+                    gas: tx.gas.to(),
+                    gas_price: receipt
+                        .effective_gas_price
+                        .or(tx.gas_price)
+                        .unwrap_or_default(),
+                    value: tx.value,
+                    r#type: tx.r#type.to(),
+                    status: receipt.status.map(|v| v.to()).unwrap_or(1),
+                    gas_used: receipt.gas_used.to(),
+                    cumulative_gas_used: receipt.cumulative_gas_used.to(),
+                    created_contract_address: receipt.contract_address,
+                    logs: receipt
+                        .logs
+                        .into_iter()
+                        .map(|log| ChainLog {
+                            index: log.log_index.to(),
+                            address: log.address,
+                            topics: log.topics,
+                            data: log.data.0,
+                        })
+                        .collect(),
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(ChainBlock {
+            hash: self.hash,
+            parent_hash: self.parent_hash,
+            number: self.number.to(),
+            timestamp: Timestamp::from_timestamp_secs(self.timestamp.to())
+                .ok_or(anyhow!("Timestamp out of range"))?,
+            miner: self.miner,
+            gas_limit: self.gas_limit.to(),
+            gas_used: self.gas_used.to(),
+            transactions,
+        })
+    }
+}
+
+/// Pulls blocks, txs and receipts from the L3 node into the chain tables,
+/// following the chain by parent hash from the latest stored consensus block.
+pub struct RpcSource {
+    db: Arc<DatabaseConnection>,
+    client: RpcClient,
+    settings: RpcIngestionSettings,
+}
+
+impl RpcSource {
+    pub fn new(db: Arc<DatabaseConnection>, settings: RpcIngestionSettings) -> Self {
+        Self {
+            db,
+            client: ClientBuilder::default().http(settings.url.clone()),
+            settings,
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn get_head(&self) -> Result<BlockNumber> {
+        let head: U64 = self
+            .client
+            .request_noparams("eth_blockNumber")
+            .await
+            .context("Failed to get chain head")?;
+        Ok(head.to())
+    }
+
+    #[instrument(skip(self))]
+    async fn get_block(&self, number: BlockNumber) -> Result<Option<ChainBlock>> {
+        let number = U64::from(number);
+        let block: Option<RpcBlock> = self
+            .client
+            .request("eth_getBlockByNumber", (number, true))
+            .await
+            .context("Failed to get block")?;
+        let block = match block {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+        let receipts: Vec<RpcReceipt> = self
+            .client
+            .request("eth_getBlockReceipts", (number,))
+            .await
+            .context("Failed to get block receipts")?;
+        block.with_receipts(receipts).map(Some)
+    }
+}
+
+#[async_trait]
+impl IngestionSource for RpcSource {
+    #[instrument(skip_all)]
+    async fn ingest(&self) -> Result<()> {
+        let head = self.get_head().await?;
+        let mut parent = repository::blockscout::get_latest_consensus_block(&*self.db).await?;
+        let first = parent
+            .as_ref()
+            .map(|block| block.number + 1)
+            .unwrap_or(self.settings.start_block);
+        let last = head.min(first + self.settings.batch_size.max(1) - 1);
+
+        for number in first..=last {
+            let block = match self.get_block(number).await? {
+                Some(v) => v,
+                None => break,
+            };
+
+            if let Some(parent) = &parent {
+                if parent.hash != block.parent_hash {
+                    tracing::info!(
+                        block_number = parent.number,
+                        "Parent hash mismatch, orphaning blocks"
+                    );
+                    let txn = self.db.begin().await?;
+                    repository::blockscout::orphan_blocks_since(&txn, parent.number).await?;
+                    txn.commit().await?;
+                    return Ok(());
+                }
+            }
+
+            let txn = self.db.begin().await?;
+            repository::blockscout::insert_chain_block(&txn, &block)
+                .await
+                .with_context(|| format!("Ingesting block {number}"))?;
+            txn.commit().await?;
+
+            parent = Some(Block {
+                hash: block.hash,
+                number: block.number,
+                timestamp: block.timestamp,
+            });
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+use anyhow::Result;
+use sea_orm::{
+    sqlx::{Connection, PgConnection},
+    DatabaseConnection,
+};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::repository;
+
+/// Work done by a single indexer replica at a time, each role is elected separately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeaderRole {
+    /// Ingesting chain data, rolling back reorgs and computing block stats.
+    Chain,
+    /// Refreshing materialized views.
+    MaterializedViews,
+}
+
+impl LeaderRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Chain => "chain",
+            Self::MaterializedViews => "materialized_views",
+        }
+    }
+
+    fn lock_key(self) -> i32 {
+        match self {
+            Self::Chain => 1,
+            Self::MaterializedViews => 2,
+        }
+    }
+}
+
+/// Leader election among replicas sharing the database, backed by a session-level
+/// Postgres advisory lock. The lock is held on a connection taken out of the pool,
+/// so that it is released as soon as the leader goes away.
+pub struct Leadership {
+    db: Arc<DatabaseConnection>,
+    role: LeaderRole,
+    conn: Mutex<Option<PgConnection>>,
+}
+
+impl Leadership {
+    pub fn new(db: Arc<DatabaseConnection>, role: LeaderRole) -> Self {
+        Self {
+            db,
+            role,
+            conn: Mutex::new(None),
+        }
+    }
+
+    /// Whether this replica holds the role, taking it over when nobody else does.
+    pub async fn is_leader(&self) -> bool {
+        let mut conn = self.conn.lock().await;
+        if let Some(held) = conn.as_mut() {
+            if held.ping().await.is_ok() {
+                return true;
+            }
+            tracing::warn!(
+                role = self.role.as_str(),
+                "Lost connection holding leadership"
+            );
+            *conn = None;
+        }
+
+        match self.try_acquire().await {
+            Ok(Some(acquired)) => {
+                tracing::info!(role = self.role.as_str(), "Acquired leadership");
+                *conn = Some(acquired);
+                true
+            }
+            Ok(None) => false,
+            Err(e) => {
+                tracing::warn!(
+                    ?e,
+                    role = self.role.as_str(),
+                    "Failed to acquire leadership"
+                );
+                false
+            }
+        }
+    }
+
+    async fn try_acquire(&self) -> Result<Option<PgConnection>> {
+        let mut conn = self.db.get_postgres_connection_pool().acquire().await?;
+        if repository::control::try_lock_leader(&mut conn, self.role.lock_key()).await? {
+            Ok(Some(conn.detach()))
+        } else {
+            Ok(None)
+        }
+    }
+}
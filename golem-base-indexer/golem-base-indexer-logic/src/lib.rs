@@ -1,9 +1,12 @@
 use alloy_sol_types::SolEvent;
 use anyhow::{anyhow, Context, Result};
-use arkiv_storage_tx::{ArkivABI, ChangeOwner, Create, Delete, Extend, StorageTransaction, Update};
+use arkiv_storage_tx::{ArkivABI, ChangeOwner, Create, Delete, Extend, Update};
 use futures::StreamExt;
 use lazy_static::lazy_static;
-use prometheus::{opts, register_counter, register_gauge, Counter, Gauge};
+use prometheus::{
+    opts, register_counter, register_counter_vec, register_gauge, register_gauge_vec, Counter,
+    CounterVec, Gauge, GaugeVec,
+};
 use sea_orm::{ConnectionTrait, DatabaseConnection, DatabaseTransaction, TransactionTrait};
 use serde::Deserialize;
 use serde_with::serde_as;
@@ -16,12 +19,15 @@ use tokio::time::sleep;
 use tracing::{instrument, warn};
 
 use crate::{
-    arkiv::{block_timestamp, block_timestamp_sec, entity_key},
+    arkiv::{block_timestamp, block_timestamp_sec, decode_storage_tx, entity_key},
+    ingestion::{IngestionSettings, IngestionSource},
+    leader::{LeaderRole, Leadership},
     types::{
-        Block, BlockStorageUsage, ConsensusTx, CurrencyAmount, EntityHistoryEntry, EntityKey,
-        EntityStatus, FullNumericAttribute, FullOperationIndex, FullStringAttribute,
-        ListOperationsFilter, LogEventIndex, LogIndex, Operation, OperationData, OperationMetadata,
-        OperationType, OperationsFilter, PaginationParams, Timestamp, TxHash,
+        Block, BlockStorageUsage, ConsensusTx, CurrencyAmount, CursorPaginationParams,
+        EntityHistoryEntry, EntityKey, EntityStatus, FullNumericAttribute, FullOperationIndex,
+        FullStringAttribute, HistoryEntryEvent, IndexerStatus, ListOperationsFilter, LogEventIndex,
+        LogIndex, Operation, OperationData, OperationMetadata, OperationType, OperationsFilter,
+        PaginationParams, QueueItem, Timestamp, TxHash,
     },
 };
 
@@ -31,13 +37,20 @@ pub mod test_utils;
 pub mod arkiv;
 mod attributes;
 mod consensus_tx;
+mod data_diff;
+pub mod ingestion;
+pub mod leader;
 pub mod mat_view_scheduler;
 pub mod model;
 mod operations;
 pub mod pagination;
+pub mod query;
 pub mod repository;
 pub mod services;
+pub mod status;
+pub mod subscriptions;
 pub mod types;
+pub mod webhooks;
 pub mod well_known;
 
 lazy_static! {
@@ -51,11 +64,21 @@ lazy_static! {
         "Number of operations processed.",
     ))
     .unwrap();
+    static ref UNDECODABLE_TX_COUNTER: Counter = register_counter!(opts!(
+        "undecodable_transaction_count",
+        "Number of storage transactions with undecodable calldata.",
+    ))
+    .unwrap();
     static ref TX_REORG_COUNTER: Counter = register_counter!(opts!(
         "processed_transaction_reorg_count",
         "Number of transaction reorgs processed.",
     ))
     .unwrap();
+    static ref BLOCK_REORG_COUNTER: Counter = register_counter!(opts!(
+        "processed_block_reorg_count",
+        "Number of chain reorgs rolled back.",
+    ))
+    .unwrap();
     static ref PENDING_TX_GAUGE: Gauge = register_gauge!(opts!(
         "pending_transactions",
         "Number of transactions to be processed.",
@@ -66,6 +89,60 @@ lazy_static! {
         "Number of transaction reorgs to be processed.",
     ))
     .unwrap();
+    static ref LATEST_BLOCK_GAUGE: Gauge = register_gauge!(opts!(
+        "latest_block_number",
+        "Latest consensus block known to Blockscout.",
+    ))
+    .unwrap();
+    static ref LATEST_PROCESSED_BLOCK_GAUGE: Gauge = register_gauge!(opts!(
+        "latest_processed_block_number",
+        "Latest block up to which all storage transactions are processed.",
+    ))
+    .unwrap();
+    static ref BLOCK_LAG_GAUGE: Gauge = register_gauge!(opts!(
+        "indexer_block_lag",
+        "Number of blocks the indexer is behind the latest consensus block.",
+    ))
+    .unwrap();
+    static ref OLDEST_QUEUED_ITEM_AGE_GAUGE: Gauge = register_gauge!(opts!(
+        "oldest_queued_item_age_seconds",
+        "Time the oldest item has been waiting in a processing queue.",
+    ))
+    .unwrap();
+    static ref LAST_TICK_GAUGE: Gauge = register_gauge!(opts!(
+        "last_tick_timestamp_seconds",
+        "Unix time of the last completed indexer tick.",
+    ))
+    .unwrap();
+    static ref MAT_VIEW_STALENESS_GAUGE: GaugeVec = register_gauge_vec!(
+        opts!(
+            "materialized_view_staleness_seconds",
+            "Time since the materialized view was last refreshed.",
+        ),
+        &["view"]
+    )
+    .unwrap();
+    static ref QUARANTINED_GAUGE: Gauge = register_gauge!(opts!(
+        "quarantined_queue_items",
+        "Number of queue items that ran out of attempts.",
+    ))
+    .unwrap();
+    static ref PROCESSING_FAILURE_COUNTER: CounterVec = register_counter_vec!(
+        opts!(
+            "processing_failure_count",
+            "Number of failed attempts to process a queue item.",
+        ),
+        &["queue", "reason"]
+    )
+    .unwrap();
+    static ref QUARANTINE_COUNTER: CounterVec = register_counter_vec!(
+        opts!(
+            "quarantined_queue_item_count",
+            "Number of queue items quarantined after running out of attempts.",
+        ),
+        &["queue"]
+    )
+    .unwrap();
 }
 
 #[serde_as]
@@ -81,6 +158,14 @@ pub struct IndexerSettings {
 
     #[serde_as(as = "serde_with::DurationSeconds<u64>")]
     pub restart_delay: time::Duration,
+
+    /// Number of most recent indexed blocks checked for lost consensus.
+    pub reorg_depth: u64,
+
+    /// Where chain data comes from.
+    pub ingestion: IngestionSettings,
+
+    pub retries: RetrySettings,
 }
 
 impl Default for IndexerSettings {
@@ -90,44 +175,128 @@ impl Default for IndexerSettings {
             concurrency: 10,
             restart_delay: time::Duration::from_secs(60),
             polling_interval: time::Duration::from_secs(1),
+            reorg_depth: 1000,
+            ingestion: Default::default(),
+            retries: Default::default(),
+        }
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct RetrySettings {
+    /// Queue items that failed this many times are quarantined until requeued.
+    pub max_attempts: u32,
+
+    /// Delay before the first retry, doubled with every further attempt.
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    pub initial_backoff: time::Duration,
+
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    pub max_backoff: time::Duration,
+}
+
+impl Default for RetrySettings {
+    fn default() -> Self {
+        Self {
+            max_attempts: 10,
+            initial_backoff: time::Duration::from_secs(1),
+            max_backoff: time::Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+fn update_status_gauges(status: &IndexerStatus) {
+    PENDING_TX_GAUGE.set(status.pending_transactions as f64);
+    PENDING_TX_REORG_GAUGE.set(status.pending_transaction_cleanups as f64);
+    LATEST_BLOCK_GAUGE.set(status.latest_block.unwrap_or_default() as f64);
+    LATEST_PROCESSED_BLOCK_GAUGE.set(status.latest_processed_block.unwrap_or_default() as f64);
+    BLOCK_LAG_GAUGE.set(status.block_lag() as f64);
+    OLDEST_QUEUED_ITEM_AGE_GAUGE.set(
+        status
+            .oldest_queued_item_at
+            .map(|at| status.age(at).as_secs_f64())
+            .unwrap_or_default(),
+    );
+    if let Some(at) = status.last_tick_at {
+        LAST_TICK_GAUGE.set(at.timestamp() as f64);
+    }
+    for view in &status.materialized_views {
+        if let Some(at) = view.refreshed_at {
+            MAT_VIEW_STALENESS_GAUGE
+                .with_label_values(&[&view.name])
+                .set(status.age(at).as_secs_f64());
         }
     }
 }
 
+/// How long history notifications are kept, listeners fetch them as soon as they're sent.
+const HISTORY_NOTIFICATION_RETENTION: Duration = Duration::from_secs(10 * 60);
+
+/// Coarse classification of processing errors for metrics.
+fn failure_reason(err: &anyhow::Error) -> &'static str {
+    if err.chain().any(|e| e.is::<sea_orm::DbErr>()) {
+        "database"
+    } else if err
+        .chain()
+        .any(|e| e.is::<alloy_sol_types::Error>() || e.is::<alloy_rlp::Error>())
+    {
+        "decoding"
+    } else if err.chain().any(|e| e.is::<std::num::TryFromIntError>()) {
+        "conversion"
+    } else {
+        "other"
+    }
+}
+
 pub struct Indexer {
     db: Arc<DatabaseConnection>,
     settings: IndexerSettings,
+    source: Arc<dyn IngestionSource>,
+    leadership: Leadership,
 }
 
 // FIXME integration tests
 // FIXME test what happens when DB connection fails
 impl Indexer {
     pub fn new(db: Arc<DatabaseConnection>, settings: IndexerSettings) -> Self {
-        Self { db, settings }
+        let source = settings.ingestion.build_source(db.clone());
+        let leadership = Leadership::new(db.clone(), LeaderRole::Chain);
+        Self {
+            db,
+            settings,
+            source,
+            leadership,
+        }
     }
 
     #[instrument(skip_all)]
     pub async fn run(self) -> Result<()> {
         loop {
-            self.tick().await.inspect_err(|e| {
-                tracing::error!(
-                    ?e,
-                    "Failed to index storage txs, exiting (will be restarted)..."
-                )
-            })?;
+            if repository::control::is_paused(&*self.db).await? {
+                tracing::debug!("Indexer paused, skipping tick");
+            } else {
+                self.tick().await.inspect_err(|e| {
+                    tracing::error!(
+                        ?e,
+                        "Failed to index storage txs, exiting (will be restarted)..."
+                    )
+                })?;
+            }
             sleep(self.settings.polling_interval).await;
         }
     }
 
     pub async fn update_gauges(&self) -> ! {
         loop {
-            match repository::blockscout::count_unprocessed_txs(&*self.db).await {
-                Ok(v) => PENDING_TX_GAUGE.set(v as f64),
+            match status::indexer_status(self.db.clone()).await {
+                Ok(v) => update_status_gauges(&v),
                 Err(e) => warn!(?e, "Failed to update metrics"),
             }
 
-            match repository::blockscout::count_txs_for_cleanup(&*self.db).await {
-                Ok(v) => PENDING_TX_REORG_GAUGE.set(v as f64),
+            match repository::queues::count_quarantined(&*self.db).await {
+                Ok(v) => QUARANTINED_GAUGE.set(v as f64),
                 Err(e) => warn!(?e, "Failed to update metrics"),
             }
             sleep(Duration::from_secs(5)).await;
@@ -138,10 +307,10 @@ impl Indexer {
         repository::blockscout::stream_unprocessed_tx_hashes(&*self.db)
             .await?
             .map(|tx| async move {
-                self.handle_tx(tx)
-                    .await
-                    .inspect_err(|e| tracing::warn!(?e, ?tx, "Handling tx failed"))
-                    .unwrap_or_default() // ignore error, it will be retried
+                if let Err(e) = self.handle_tx(tx).await {
+                    tracing::warn!(?e, ?tx, "Handling tx failed");
+                    self.record_failure(QueueItem::Transaction(tx), e).await;
+                }
             })
             .buffer_unordered(self.settings.concurrency)
             .collect::<Vec<_>>()
@@ -154,12 +323,10 @@ impl Indexer {
     pub async fn process_reindexes(&self) -> Result<()> {
         repository::entities::stream_entities_to_reindex(&*self.db)
             .await?
-            .map(|key| {
-                async move {
-                    self.reindex_entity(key)
-                        .await
-                        .inspect_err(|e| tracing::warn!(?e, ?key, "Handling tx reindex failed"))
-                        .unwrap_or_default() // ignore error, it will be retried
+            .map(|key| async move {
+                if let Err(e) = self.reindex_entity(key).await {
+                    tracing::warn!(?e, ?key, "Handling tx reindex failed");
+                    self.record_failure(QueueItem::Reindex(key), e).await;
                 }
             })
             .buffer_unordered(self.settings.concurrency)
@@ -171,8 +338,13 @@ impl Indexer {
 
     #[instrument(skip_all)]
     pub async fn process_block_stats(&self) -> Result<()> {
+        // stats are computed from history written by other replicas, which must not
+        // mark blocks dirty in the meantime, or the update below would clean them
+        let txn = self.db.begin().await?;
+        repository::block::lock_stats(&txn).await?;
+
         let latest_block_number =
-            if let Some(v) = repository::block::latest_block_number(&*self.db).await? {
+            if let Some(v) = repository::block::latest_block_number(&txn).await? {
                 v
             } else {
                 tracing::warn!("No blocks indexed, skipping stats processing");
@@ -180,7 +352,7 @@ impl Indexer {
             };
 
         let oldest_unprocessed_block_number =
-            if let Some(v) = repository::block::oldest_unprocessed_stats(&*self.db).await? {
+            if let Some(v) = repository::block::oldest_unprocessed_stats(&txn).await? {
                 v
             } else {
                 tracing::warn!("No blocks indexed, skipping stats processing");
@@ -193,11 +365,9 @@ impl Indexer {
         tracing::info!("Processing stats for for blocks");
 
         let prev_block_active_bytes = if oldest_unprocessed_block_number > 0 {
-            if let Some(v) = repository::block::total_storage_usage(
-                &*self.db,
-                oldest_unprocessed_block_number - 1,
-            )
-            .await?
+            if let Some(v) =
+                repository::block::total_storage_usage(&txn, oldest_unprocessed_block_number - 1)
+                    .await?
             {
                 v.storage_usage
             } else {
@@ -214,7 +384,7 @@ impl Indexer {
             oldest_unprocessed_block_number + batch_size,
         );
         let batch = oldest_unprocessed_block_number..batch_end;
-        let diffs = repository::block::storage_diff(&*self.db, batch).await?;
+        let diffs = repository::block::storage_diff(&txn, batch).await?;
         let updates = diffs.into_iter().scan(prev_block_active_bytes, |sum, i| {
             *sum += i.storage_diff;
             Some(BlockStorageUsage {
@@ -222,19 +392,36 @@ impl Indexer {
                 storage_usage: (*sum).try_into().unwrap_or_default(),
             })
         });
-        repository::block::update_stats(&*self.db, updates).await?;
+        repository::block::update_stats(&txn, updates).await?;
+        txn.commit().await?;
 
         Ok(())
     }
 
+    /// Records fees of every tx Blockscout included, dropped or moved since the last tick,
+    /// not only storage txs, for the biggest spenders and spend charts.
+    #[instrument(skip_all)]
+    pub async fn process_transaction_fees(&self) -> Result<()> {
+        let batch_size = 1000; // FIXME make it a setting
+        loop {
+            let txn = self.db.begin().await?;
+            let hashes = repository::queues::claim_transaction_fees(&txn, batch_size).await?;
+            if hashes.is_empty() {
+                return Ok(());
+            }
+            repository::transactions::record_fees(&txn, &hashes).await?;
+            txn.commit().await?;
+        }
+    }
+
     pub async fn process_delete_logs(&self) -> Result<()> {
         repository::blockscout::stream_unprocessed_logs(&*self.db)
             .await?
             .for_each_concurrent(self.settings.concurrency, |log| async move {
-                let _ = self
-                    .handle_log(log.clone())
-                    .await
-                    .inspect_err(|e| tracing::warn!(?e, ?log, "Handling log failed"));
+                if let Err(e) = self.handle_log(log.clone()).await {
+                    tracing::warn!(?e, ?log, "Handling log failed");
+                    self.record_failure(QueueItem::Log(log), e).await;
+                }
             })
             .await;
         Ok(())
@@ -242,68 +429,158 @@ impl Indexer {
 
     pub async fn process_logs_events(&self) -> Result<()> {
         let txn = self.db.begin().await?;
-        let affected_entities: Vec<EntityKey> =
-            repository::blockscout::stream_unprocessed_logs_events(&*self.db)
-                .await?
-                .map(|log| {
-                    let txn = &txn;
-                    async move {
-                        self.handle_log_event(txn, log)
-                            .await
-                            .inspect_err(|e| tracing::warn!(?e, "Handling log failed"))
-                            .unwrap_or_default()
-                    }
-                })
-                .buffer_unordered(self.settings.concurrency)
-                .collect()
-                .await;
+        let mut logs =
+            Box::pin(repository::blockscout::stream_unprocessed_logs_events(&*self.db).await?);
+
+        let mut affected_entities = Vec::new();
+        let mut failures = Vec::new();
+        while let Some(log) = logs.next().await {
+            let index = LogIndex {
+                transaction_hash: log.transaction_hash,
+                block_hash: log.block_hash,
+                index: log.index,
+            };
+            // each event gets its own savepoint, a failing one doesn't abort the others
+            let savepoint = txn.begin().await?;
+            match self.handle_log_event(&savepoint, log).await {
+                Ok(key) => {
+                    savepoint.commit().await?;
+                    affected_entities.extend(key);
+                }
+                Err(e) => {
+                    savepoint.rollback().await?;
+                    tracing::warn!(?e, log = ?index, "Handling log failed");
+                    failures.push((index, e));
+                }
+            }
+        }
 
+        // reindexes also mark stats of the blocks with updated costs dirty
         if !affected_entities.is_empty() {
             repository::entities::batch_queue_reindex(&*self.db, affected_entities).await?;
         }
 
         txn.commit().await?;
 
+        // recorded after commit, the transaction may still hold locks on failed items
+        for (log, e) in failures {
+            self.record_failure(QueueItem::LogEvent(log), e).await;
+        }
+
         Ok(())
     }
 
     pub async fn process_tx_cleanups(&self) -> Result<()> {
         let txn = self.db.begin().await?;
-        let affected_entities = repository::blockscout::stream_tx_hashes_for_cleanup(&*self.db)
-            .await?
-            .map(|tx| {
-                let txn = &txn;
-                async move {
-                    self.handle_tx_cleanup(txn, tx)
-                        .await
-                        .inspect_err(|e| tracing::warn!(?e, ?tx, "Handling tx cleanup failed"))
-                        .unwrap_or_default() // ignore error, it will be retried
+        let mut txs =
+            Box::pin(repository::blockscout::stream_tx_hashes_for_cleanup(&*self.db).await?);
+
+        let mut affected_entities = Vec::new();
+        let mut failures = Vec::new();
+        while let Some(tx) = txs.next().await {
+            // each cleanup gets its own savepoint, a failing one doesn't abort the others
+            let savepoint = txn.begin().await?;
+            match self.handle_tx_cleanup(&savepoint, tx).await {
+                Ok(keys) => {
+                    savepoint.commit().await?;
+                    affected_entities.extend(keys);
                 }
-            })
-            .buffer_unordered(self.settings.concurrency)
-            .collect::<Vec<_>>()
-            .await;
+                Err(e) => {
+                    savepoint.rollback().await?;
+                    tracing::warn!(?e, ?tx, "Handling tx cleanup failed");
+                    failures.push((tx, e));
+                }
+            }
+        }
 
+        // entities of txs already rolled back after a reorg have no operations left
         if !affected_entities.is_empty() {
-            repository::entities::batch_queue_reindex(
-                &*self.db,
-                affected_entities.into_iter().flatten().collect(),
-            )
-            .await?;
+            repository::entities::batch_queue_reindex(&*self.db, affected_entities).await?;
         }
 
         txn.commit().await?;
+
+        // recorded after commit, the transaction may still hold locks on failed items
+        for (tx, e) in failures {
+            self.record_failure(QueueItem::TxCleanup(tx), e).await;
+        }
+
         Ok(())
     }
 
+    /// Rolls back everything indexed from the lowest block that lost consensus
+    /// and queues the canonical chain from that point for processing again.
+    #[instrument(skip_all)]
+    pub async fn process_reorgs(&self) -> Result<()> {
+        let fork_point =
+            match repository::block::find_fork_point(&*self.db, self.settings.reorg_depth).await? {
+                Some(v) => v,
+                None => return Ok(()),
+            };
+        tracing::info!(fork_point, "Rolling back operations after reorg");
+
+        let txn = self.db.begin().await?;
+        // items being processed by other replicas may belong to the rolled back blocks
+        repository::queues::lock_processing(&txn).await?;
+        repository::operations::delete_since_block(&txn, fork_point)
+            .await
+            .with_context(|| format!("Rolling back operations since block {fork_point}"))?;
+        repository::block::forget_indexed_blocks(&txn, fork_point).await?;
+        repository::block::mark_stats_dirty(&txn, fork_point).await?;
+        repository::blockscout::requeue_since_block(&txn, fork_point).await?;
+        txn.commit().await?;
+
+        BLOCK_REORG_COUNTER.inc();
+        Ok(())
+    }
+
+    /// Schedules a retry of the failed item, quarantining it once it runs out of attempts.
+    async fn record_failure(&self, item: QueueItem, err: anyhow::Error) {
+        let queue = item.queue().as_str();
+        PROCESSING_FAILURE_COUNTER
+            .with_label_values(&[queue, failure_reason(&err)])
+            .inc();
+
+        match repository::queues::record_failure(
+            &*self.db,
+            &item,
+            format!("{err:#}"),
+            &self.settings.retries,
+        )
+        .await
+        {
+            Ok(true) => {
+                QUARANTINE_COUNTER.with_label_values(&[queue]).inc();
+                tracing::error!(?item, ?err, "Queue item ran out of attempts, quarantined");
+            }
+            Ok(false) => {}
+            Err(e) => tracing::warn!(?e, ?item, "Failed to record processing failure"),
+        }
+    }
+
     #[instrument(skip_all)]
     pub async fn tick(&self) -> Result<()> {
+        // queues are shared with other replicas, the chain itself is followed by the leader only
+        let leader = self.leadership.is_leader().await;
+        if leader {
+            self.source.ingest().await?;
+            self.process_reorgs().await?;
+        }
         self.process_batch_of_transactions().await?;
+        self.process_transaction_fees().await?;
         self.process_delete_logs().await?;
         self.process_tx_cleanups().await?;
         self.process_logs_events().await?;
         self.process_reindexes().await?;
-        self.process_block_stats().await?;
+        if leader {
+            self.process_block_stats().await?;
+            repository::subscriptions::delete_notifications_before(
+                &*self.db,
+                HISTORY_NOTIFICATION_RETENTION,
+            )
+            .await?;
+        }
+        repository::status::record_tick(&*self.db).await?;
 
         Ok(())
     }
@@ -314,6 +591,10 @@ impl Indexer {
         txn: &DatabaseTransaction,
         tx_hash: TxHash,
     ) -> Result<HashSet<EntityKey>> {
+        if !repository::queues::claim(txn, &QueueItem::TxCleanup(tx_hash)).await? {
+            tracing::debug!("Tx cleanup claimed by another replica, skipping");
+            return Ok(Default::default());
+        }
         tracing::info!("Processing tx cleanup after reorg");
 
         let affected_entities: Vec<EntityKey> = repository::entities::find_by_tx_hash(txn, tx_hash)
@@ -340,18 +621,27 @@ impl Indexer {
         txn: &T,
         entity: EntityKey,
     ) -> Result<()> {
+        // operations that already have a history entry were announced to
+        // subscribers and webhooks when it was first built
+        let announced = repository::entities::get_history_operations(txn, entity).await?;
+
         let (ops, _) = repository::operations::list_operations(
             txn,
             ListOperationsFilter {
-                pagination: PaginationParams {
-                    page: 0,
-                    page_size: i64::MAX as u64,
+                pagination: CursorPaginationParams {
+                    pagination: PaginationParams {
+                        page: 0,
+                        page_size: i64::MAX as u64,
+                    },
+                    cursor: None,
+                    with_totals: false,
                 },
                 operation_type: None,
                 operations_filter: OperationsFilter {
                     entity_key: Some(entity),
                     ..Default::default()
                 },
+                sort: Default::default(),
             },
         )
         .await?;
@@ -360,14 +650,24 @@ impl Indexer {
         let mut prev_entry: Option<EntityHistoryEntry> = None;
         let mut active_attributes_index = None;
         let mut entries = Vec::new();
+        let mut new_entries = Vec::new();
         for op in ops {
+            let prev_attributes_index = active_attributes_index;
             active_attributes_index = match op.op.operation {
                 OperationData::Delete => None,
                 OperationData::Extend(_) => active_attributes_index,
                 _ => Some((op.op.metadata.tx_hash, op.op.metadata.index)),
             };
+            // deletes are announced with the attributes the entity had before
+            let attributes_index = match op.op.operation {
+                OperationData::Delete => prev_attributes_index,
+                _ => active_attributes_index,
+            };
 
             let entry = self.build_history_entry(op.op, op.block_timestamp, prev_entry.as_ref());
+            if !announced.contains(&(entry.transaction_hash, entry.op_index)) {
+                new_entries.push((entry.clone(), attributes_index));
+            }
             entries.push(entry.clone());
             prev_entry = Some(entry);
         }
@@ -378,27 +678,54 @@ impl Indexer {
                 .await?;
         }
 
+        for (entry, attributes_index) in new_entries {
+            let (string_attributes, numeric_attributes) = match attributes_index {
+                Some(index) => {
+                    repository::attributes::find_attributes(txn, entity, Some(index)).await?
+                }
+                None => Default::default(),
+            };
+            let event = HistoryEntryEvent {
+                entry,
+                string_attributes,
+                numeric_attributes,
+            };
+            repository::webhooks::enqueue_deliveries(txn, &event).await?;
+            repository::subscriptions::notify(txn, &event).await?;
+        }
+
         Ok(())
     }
 
     #[instrument(skip_all, fields(entity))]
     pub async fn reindex_entity(&self, entity: EntityKey) -> Result<()> {
-        tracing::info!(?entity, "Reprocessing entity");
         let txn = self.db.begin().await?;
+        if !repository::queues::claim(&txn, &QueueItem::Reindex(entity)).await? {
+            tracing::debug!(
+                ?entity,
+                "Entity reindex claimed by another replica, skipping"
+            );
+            return Ok(());
+        }
+        tracing::info!(?entity, "Reprocessing entity");
+        repository::block::mark_entity_stats_dirty(&txn, entity).await?;
         match repository::operations::find_latest_operation(&txn, entity).await? {
             Some(_) => self.reindex_entity_with_ops(&txn, entity).await?,
             None => repository::entities::drop_entity(&txn, entity).await?,
         }
         repository::entities::refresh_entity_based_on_history(&txn, entity).await?;
-        repository::entities::finish_reindex(&txn, entity).await?;
         txn.commit().await?;
         Ok(())
     }
 
     #[instrument(skip(self))]
     async fn handle_tx(&self, tx_hash: TxHash) -> Result<()> {
-        tracing::info!("Processing tx");
         let txn = self.db.begin().await?;
+        if !repository::queues::claim(&txn, &QueueItem::Transaction(tx_hash)).await? {
+            tracing::debug!("Tx claimed by another replica, skipping");
+            return Ok(());
+        }
+        tracing::info!("Processing tx");
 
         let tx = repository::blockscout::get_tx(&txn, tx_hash)
             .await
@@ -408,10 +735,17 @@ impl Indexer {
         repository::block::mark_stats_dirty(&txn, tx.block_number).await?;
 
         let mut op_idx = 0;
-        let storagetx: StorageTransaction = match (&tx.input).try_into() {
+        let storagetx = match decode_storage_tx(&tx.input) {
             Ok(storagetx) => storagetx,
             Err(e) => {
                 tracing::warn!(?e, "Storage tx with undecodable data");
+                repository::transactions::insert_undecodable(&txn, &tx, &e).await?;
+                repository::block::mark_indexed(&txn, tx.block_hash, tx.block_number).await?;
+                repository::transactions::finish_tx_processing(&txn, tx_hash).await?;
+                txn.commit().await?;
+
+                TX_COUNTER.inc();
+                UNDECODABLE_TX_COUNTER.inc();
                 return Ok(());
             }
         };
@@ -468,6 +802,8 @@ impl Indexer {
             op_idx += 1;
         }
 
+        // history entries for these operations are built (and announced to
+        // subscribers and webhooks) when the queued reindex runs
         if !ops.is_empty() {
             repository::entities::batch_queue_reindex(
                 &txn,
@@ -475,6 +811,7 @@ impl Indexer {
             )
             .await?;
             repository::operations::batch_insert_operation(&txn, ops).await?;
+            repository::block::mark_indexed(&txn, tx.block_hash, tx.block_number).await?;
         }
         if !string_attributes.is_empty() {
             repository::attributes::batch_insert_string_attribute(&txn, string_attributes).await?;
@@ -757,6 +1094,10 @@ impl Indexer {
     #[instrument(skip_all, fields(log))]
     async fn handle_log(&self, log: LogIndex) -> Result<()> {
         let txn = self.db.begin().await?;
+        if !repository::queues::claim(&txn, &QueueItem::Log(log.clone())).await? {
+            tracing::debug!("Log claimed by another replica, skipping");
+            return Ok(());
+        }
         let tx = repository::blockscout::get_tx(&txn, log.transaction_hash)
             .await?
             .ok_or(anyhow!("Log with no tx!"))?;
@@ -787,6 +1128,8 @@ impl Indexer {
             operation: OperationData::delete(),
         };
         repository::operations::insert_operation(&txn, op.clone()).await?;
+        repository::block::mark_indexed(&txn, tx.block_hash, tx.block_number).await?;
+        repository::block::mark_stats_dirty(&txn, tx.block_number).await?;
 
         let idx = FullOperationIndex {
             block_number: tx.block_number,
@@ -800,9 +1143,18 @@ impl Indexer {
         )
         .await?;
         let entry = self.build_history_entry(op, tx.block_timestamp, prev_entry.as_ref());
-        repository::entities::batch_insert_history_entry(&txn, vec![entry]).await?;
+        repository::entities::batch_insert_history_entry(&txn, vec![entry.clone()]).await?;
         repository::entities::refresh_entity_based_on_history(&txn, entity_key).await?;
 
+        let (string_attributes, numeric_attributes) =
+            repository::attributes::find_attributes(&txn, entity_key, None).await?;
+        let event = HistoryEntryEvent {
+            entry,
+            string_attributes,
+            numeric_attributes,
+        };
+        repository::webhooks::enqueue_deliveries(&txn, &event).await?;
+        repository::subscriptions::notify(&txn, &event).await?;
         repository::attributes::deactivate_attributes(&txn, entity_key).await?;
         repository::logs::finish_log_processing(&txn, tx.hash, tx.block_hash, log.index).await?;
         txn.commit().await?;
@@ -816,7 +1168,16 @@ impl Indexer {
         &self,
         txn: &DatabaseTransaction,
         log: LogEventIndex,
-    ) -> Result<EntityKey> {
+    ) -> Result<Option<EntityKey>> {
+        let index = LogIndex {
+            transaction_hash: log.transaction_hash,
+            block_hash: log.block_hash,
+            index: log.index,
+        };
+        if !repository::queues::claim(txn, &QueueItem::LogEvent(index)).await? {
+            tracing::debug!("Log event claimed by another replica, skipping");
+            return Ok(None);
+        }
         tracing::info!(
             "Processing event log for tx_hash={}, op_index={}",
             log.transaction_hash,
@@ -864,8 +1225,6 @@ impl Indexer {
             tracing::warn!(?log.transaction_hash, log.op_index, "Replacing current operation cost ({}) with a new value ({})", current_cost.to_string(), cost.to_string());
         }
 
-        repository::block::mark_stats_dirty(txn, op.metadata.block_number).await?;
-
         // Set cost and update operation
         op.metadata.cost = Some(cost);
         let entity_key = op.metadata.entity_key;
@@ -880,6 +1239,6 @@ impl Indexer {
         )
         .await?;
 
-        Ok(entity_key)
+        Ok(Some(entity_key))
     }
 }
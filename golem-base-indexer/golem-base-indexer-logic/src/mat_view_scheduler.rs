@@ -5,6 +5,11 @@ use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, Statement};
 use tokio::time::{sleep, Duration};
 use tracing::instrument;
 
+use crate::{
+    leader::{LeaderRole, Leadership},
+    repository,
+};
+
 const MINUTE: Duration = Duration::from_secs(60);
 const HALF_HOUR: Duration = Duration::from_secs(60 * 30);
 
@@ -16,11 +21,13 @@ pub struct MatViewSettings {
 #[derive(Clone)]
 pub struct MatViewScheduler {
     db: Arc<DatabaseConnection>,
+    leadership: Arc<Leadership>,
 }
 
 impl MatViewScheduler {
     pub fn new(db: Arc<DatabaseConnection>) -> Self {
-        Self { db }
+        let leadership = Arc::new(Leadership::new(db.clone(), LeaderRole::MaterializedViews));
+        Self { db, leadership }
     }
 
     pub fn get_mat_view_settings(&self) -> Vec<MatViewSettings> {
@@ -31,30 +38,6 @@ impl MatViewScheduler {
                 delay: MINUTE,
             },
             // Leaderboards
-            MatViewSettings {
-                name: "golem_base_leaderboard_biggest_spenders".to_string(),
-                delay: HALF_HOUR,
-            },
-            MatViewSettings {
-                name: "golem_base_leaderboard_data_owned".to_string(),
-                delay: HALF_HOUR,
-            },
-            MatViewSettings {
-                name: "golem_base_leaderboard_effectively_largest_entities".to_string(),
-                delay: HALF_HOUR,
-            },
-            MatViewSettings {
-                name: "golem_base_leaderboard_entities_created".to_string(),
-                delay: HALF_HOUR,
-            },
-            MatViewSettings {
-                name: "golem_base_leaderboard_entities_owned".to_string(),
-                delay: HALF_HOUR,
-            },
-            MatViewSettings {
-                name: "golem_base_leaderboard_largest_entities".to_string(),
-                delay: HALF_HOUR,
-            },
             MatViewSettings {
                 name: "golem_base_leaderboard_top_accounts".to_string(),
                 delay: HALF_HOUR,
@@ -86,10 +69,15 @@ impl MatViewScheduler {
         for view in views {
             let scheduler = self.clone();
 
+            // only the leader refreshes, other replicas keep checking whether it's still around
             tokio::spawn(async move {
                 loop {
-                    scheduler.refresh_named_view(&view.name).await;
-                    sleep(view.delay).await;
+                    if scheduler.leadership.is_leader().await {
+                        scheduler.refresh_named_view(&view.name).await;
+                        sleep(view.delay).await;
+                    } else {
+                        sleep(MINUTE).await;
+                    }
                 }
             });
         }
@@ -97,14 +85,25 @@ impl MatViewScheduler {
         Ok(())
     }
 
+    pub fn is_scheduled(&self, view: &str) -> bool {
+        self.get_mat_view_settings().iter().any(|v| v.name == view)
+    }
+
     pub async fn refresh_named_view(&self, view: &str) {
+        let _ = self
+            .try_refresh_named_view(view)
+            .await
+            .inspect_err(|e| tracing::error!(?e, "Failed to refresh materialized view"));
+    }
+
+    pub async fn try_refresh_named_view(&self, view: &str) -> Result<()> {
         tracing::info!("Running refresh named view {view}");
 
         let sql = format!("REFRESH MATERIALIZED VIEW CONCURRENTLY {view}");
-        let _ = self
-            .db
+        self.db
             .execute(Statement::from_string(DatabaseBackend::Postgres, sql))
-            .await
-            .inspect_err(|e| tracing::error!(?e, "Failed to refresh materialized view"));
+            .await?;
+        repository::status::record_view_refresh(&*self.db, view).await?;
+        Ok(())
     }
 }
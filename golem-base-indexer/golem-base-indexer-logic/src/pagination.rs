@@ -1,7 +1,206 @@
-use anyhow::{Context, Result};
-use sea_orm::{ConnectionTrait, Paginator, SelectorTrait};
+use alloy_primitives::{hex, U256};
+use anyhow::{anyhow, Context, Result};
+use sea_orm::{
+    Condition, ConnectionTrait, EntityTrait, Paginator, PaginatorTrait, QueryFilter, QuerySelect,
+    Select, SelectModel, SelectTwo, SelectTwoModel, Selector, SelectorTrait,
+};
 
-use crate::types::{PaginationMetadata, PaginationParams};
+use crate::types::{
+    CursorPaginationMetadata, CursorPaginationParams, EntityCursor, EntityKey, FullOperationIndex,
+    OperationCursor, PaginationMetadata, PaginationParams,
+};
+
+/// Position of an item that can be handed out to clients as an opaque string.
+pub trait Cursor: Sized {
+    fn encode(&self) -> String;
+    fn decode(value: &str) -> Result<Self>;
+}
+
+/// Same as the entity key when there's no sort value, so cursors of listings
+/// ordered by key stay plain keys.
+impl Cursor for EntityCursor {
+    fn encode(&self) -> String {
+        let mut bytes = self.key.to_vec();
+        if let Some(value) = self.value {
+            bytes.extend_from_slice(&value.to_be_bytes::<32>());
+        }
+        hex::encode(bytes)
+    }
+
+    fn decode(value: &str) -> Result<Self> {
+        let bytes = hex::decode(value).map_err(|_| anyhow!("Invalid cursor"))?;
+        let (key, value) = match bytes.len() {
+            32 => (&bytes[..], None),
+            64 => (&bytes[..32], Some(U256::from_be_slice(&bytes[32..]))),
+            _ => return Err(anyhow!("Invalid cursor")),
+        };
+        Ok(Self {
+            key: EntityKey::from_slice(key),
+            value,
+        })
+    }
+}
+
+impl Cursor for FullOperationIndex {
+    fn encode(&self) -> String {
+        let mut bytes = Vec::with_capacity(24);
+        bytes.extend_from_slice(&self.block_number.to_be_bytes());
+        bytes.extend_from_slice(&self.tx_index.to_be_bytes());
+        bytes.extend_from_slice(&self.op_index.to_be_bytes());
+        hex::encode(bytes)
+    }
+
+    fn decode(value: &str) -> Result<Self> {
+        let bytes: [u8; 24] = hex::decode(value)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or(anyhow!("Invalid cursor"))?;
+        let part = |i: usize| u64::from_be_bytes(bytes[i * 8..(i + 1) * 8].try_into().unwrap());
+        Ok(Self {
+            block_number: part(0),
+            tx_index: part(1),
+            op_index: part(2),
+        })
+    }
+}
+
+/// Queries that can be paginated with [`paginate_cursor`].
+pub trait CursorQuery: QueryFilter + QuerySelect + Clone {
+    type Selector: SelectorTrait + Send + Sync;
+
+    fn into_selector(self) -> Selector<Self::Selector>;
+}
+
+impl<E> CursorQuery for Select<E>
+where
+    E: EntityTrait,
+    E::Model: Sync,
+{
+    type Selector = SelectModel<E::Model>;
+
+    fn into_selector(self) -> Selector<Self::Selector> {
+        self.into_model()
+    }
+}
+
+impl<E, F> CursorQuery for SelectTwo<E, F>
+where
+    E: EntityTrait,
+    F: EntityTrait,
+    E::Model: Sync,
+    F::Model: Sync,
+{
+    type Selector = SelectTwoModel<E::Model, F::Model>;
+
+    fn into_selector(self) -> Selector<Self::Selector> {
+        self.into_model()
+    }
+}
+
+/// Same as the operation index when there's no cost, so cursors of listings
+/// sorted by time stay plain indexes.
+impl Cursor for OperationCursor {
+    fn encode(&self) -> String {
+        let mut encoded = self.index.encode();
+        if let Some(cost) = self.cost {
+            encoded.push_str(&hex::encode(cost.to_be_bytes::<32>()));
+        }
+        encoded
+    }
+
+    fn decode(value: &str) -> Result<Self> {
+        // cursors come from clients, so don't assume byte 48 is a char boundary
+        let (index, cost) = match value.as_bytes() {
+            bytes if bytes.len() == 48 => (value, None),
+            bytes if bytes.len() == 112 => {
+                let (index, cost) = bytes.split_at(48);
+                let index = std::str::from_utf8(index).map_err(|_| anyhow!("Invalid cursor"))?;
+                let cost = hex::decode(cost).map_err(|_| anyhow!("Invalid cursor"))?;
+                (index, Some(U256::from_be_slice(&cost)))
+            }
+            _ => return Err(anyhow!("Invalid cursor")),
+        };
+        Ok(Self {
+            cost,
+            index: FullOperationIndex::decode(index)?,
+        })
+    }
+}
+
+/// Paginate items either by page number or by cursor.
+/// `query` must be ordered by the cursor key, `after_cursor` must select items
+/// positioned strictly after the given cursor and `cursor_of` extracts the cursor
+/// from a converted item.
+pub async fn paginate_cursor<C, Q, K, D>(
+    db: &C,
+    query: Q,
+    pagination: CursorPaginationParams<K>,
+    after_cursor: impl Fn(&K) -> Condition,
+    convert: impl Fn(<Q::Selector as SelectorTrait>::Item) -> Result<D>,
+    cursor_of: impl Fn(&D) -> K,
+) -> Result<(Vec<D>, CursorPaginationMetadata<K>)>
+where
+    C: ConnectionTrait,
+    Q: CursorQuery,
+{
+    let CursorPaginationParams {
+        pagination,
+        cursor,
+        with_totals,
+    } = pagination;
+    let page_size = pagination.page_size;
+
+    let totals = if with_totals {
+        let paginator = query.clone().into_selector().paginate(db, page_size);
+        let total_items = paginator
+            .num_items()
+            .await
+            .context("Failed to count items")?;
+        let total_pages = paginator
+            .num_pages()
+            .await
+            .context("Failed to get number of pages")?;
+        Some(PaginationMetadata {
+            pagination: pagination.clone(),
+            total_pages,
+            total_items,
+        })
+    } else {
+        None
+    };
+
+    let query = match cursor {
+        Some(cursor) => query.filter(after_cursor(&cursor)),
+        None => query.offset(pagination.page.saturating_sub(1).saturating_mul(page_size)),
+    };
+    // fetch one extra item to know whether there is a next page
+    let limit = page_size.saturating_add(1).min(i64::MAX as u64);
+    let mut items = query
+        .limit(limit)
+        .into_selector()
+        .all(db)
+        .await
+        .context("Failed to fetch page")?
+        .into_iter()
+        .map(convert)
+        .collect::<Result<Vec<_>>>()?;
+
+    let next_cursor = if items.len() as u64 > page_size {
+        items.truncate(page_size as usize);
+        items.last().map(cursor_of)
+    } else {
+        None
+    };
+
+    Ok((
+        items,
+        CursorPaginationMetadata {
+            page_size,
+            totals,
+            next_cursor,
+        },
+    ))
+}
 
 pub async fn paginate<'a, C, S>(
     paginator: Paginator<'a, C, S>,
@@ -58,3 +257,36 @@ where
         pagination_metadata,
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn operation_cursors_roundtrip() {
+        let cursor = OperationCursor {
+            cost: Some(U256::from(1234)),
+            index: FullOperationIndex {
+                block_number: 1,
+                tx_index: 2,
+                op_index: 3,
+            },
+        };
+        assert_eq!(OperationCursor::decode(&cursor.encode()).unwrap(), cursor);
+        let cursor = OperationCursor {
+            cost: None,
+            ..cursor
+        };
+        assert_eq!(OperationCursor::decode(&cursor.encode()).unwrap(), cursor);
+    }
+
+    #[test]
+    fn rejects_non_ascii_operation_cursors() {
+        // 112 bytes with a multibyte char straddling byte 48
+        let cursor = format!("{}é{}", "0".repeat(47), "0".repeat(63));
+        assert_eq!(cursor.len(), 112);
+        assert!(!cursor.is_char_boundary(48));
+        assert!(OperationCursor::decode(&cursor).is_err());
+    }
+}
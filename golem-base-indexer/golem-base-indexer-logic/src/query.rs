@@ -0,0 +1,576 @@
+//! Attribute query language used to filter entities.
+//!
+//! Grammar (lowest to highest precedence):
+//!
+//! ```text
+//! query      := or
+//! or         := and ("||" and)*
+//! and        := not ("&&" not)*
+//! not        := "!" not | primary
+//! primary    := "(" query ")" | predicate
+//! predicate  := key operator value
+//! operator   := "=" | "!=" | "<" | "<=" | ">" | ">=" | "~" | "!~"
+//! value      := string | number
+//! ```
+//!
+//! Strings are double-quoted and support `\"`, `\\`, `\n` and `\t` escapes.
+//! Numbers are unsigned 64-bit integers. `~` and `!~` perform glob matching
+//! against string attributes, where `*` matches any sequence of characters,
+//! `?` matches a single character and `\` escapes the next character.
+//!
+//! Queries are limited to [`MAX_QUERY_LENGTH`] characters and to
+//! [`MAX_NESTING_DEPTH`] levels of parentheses and negations.
+
+use std::{fmt, str::FromStr};
+
+/// Maximum length of a query, in characters.
+pub const MAX_QUERY_LENGTH: usize = 4096;
+
+/// Maximum number of nested parentheses and negations in a query.
+pub const MAX_NESTING_DEPTH: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Query {
+    And(Box<Query>, Box<Query>),
+    Or(Box<Query>, Box<Query>),
+    Not(Box<Query>),
+    Predicate(Predicate),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Predicate {
+    pub key: String,
+    pub operator: Operator,
+    pub value: Value,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Glob,
+    NotGlob,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    String(String),
+    Number(u64),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub position: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    fn new(position: usize, message: impl Into<String>) -> Self {
+        Self {
+            position,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let op = match self {
+            Operator::Eq => "=",
+            Operator::Ne => "!=",
+            Operator::Lt => "<",
+            Operator::Le => "<=",
+            Operator::Gt => ">",
+            Operator::Ge => ">=",
+            Operator::Glob => "~",
+            Operator::NotGlob => "!~",
+        };
+        f.write_str(op)
+    }
+}
+
+impl FromStr for Query {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let end = s.chars().count();
+        if end > MAX_QUERY_LENGTH {
+            return Err(ParseError::new(
+                MAX_QUERY_LENGTH,
+                format!("Query is longer than {MAX_QUERY_LENGTH} characters"),
+            ));
+        }
+        let tokens = tokenize(s)?;
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            end,
+            depth: 0,
+        };
+        let query = parser.parse_or()?;
+        match parser.peek() {
+            None => Ok(query),
+            Some((_, position)) => Err(ParseError::new(*position, "Unexpected token")),
+        }
+    }
+}
+
+/// Converts a glob pattern into a pattern for SQL `LIKE` with `\` as the
+/// escape character.
+pub fn glob_to_like(pattern: &str) -> String {
+    let mut res = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' => res.push('%'),
+            '?' => res.push('_'),
+            '%' | '_' => {
+                res.push('\\');
+                res.push(c);
+            }
+            '\\' => match chars.next() {
+                Some(next @ ('%' | '_' | '\\')) => {
+                    res.push('\\');
+                    res.push(next);
+                }
+                Some(next) => res.push(next),
+                None => res.push_str("\\\\"),
+            },
+            c => res.push(c),
+        }
+    }
+    res
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    Operator(Operator),
+    Key(String),
+    String(String),
+    Number(u64),
+}
+
+fn is_key_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || c == '$'
+}
+
+fn is_key_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '$' | '.' | '-')
+}
+
+fn tokenize(s: &str) -> Result<Vec<(Token, usize)>, ParseError> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let start = i;
+        let next = chars.get(i + 1).copied();
+        let token = match chars[i] {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '(' => {
+                i += 1;
+                Token::LParen
+            }
+            ')' => {
+                i += 1;
+                Token::RParen
+            }
+            '&' if next == Some('&') => {
+                i += 2;
+                Token::And
+            }
+            '|' if next == Some('|') => {
+                i += 2;
+                Token::Or
+            }
+            '!' if next == Some('=') => {
+                i += 2;
+                Token::Operator(Operator::Ne)
+            }
+            '!' if next == Some('~') => {
+                i += 2;
+                Token::Operator(Operator::NotGlob)
+            }
+            '!' => {
+                i += 1;
+                Token::Not
+            }
+            '=' => {
+                i += 1;
+                Token::Operator(Operator::Eq)
+            }
+            '~' => {
+                i += 1;
+                Token::Operator(Operator::Glob)
+            }
+            '<' if next == Some('=') => {
+                i += 2;
+                Token::Operator(Operator::Le)
+            }
+            '<' => {
+                i += 1;
+                Token::Operator(Operator::Lt)
+            }
+            '>' if next == Some('=') => {
+                i += 2;
+                Token::Operator(Operator::Ge)
+            }
+            '>' => {
+                i += 1;
+                Token::Operator(Operator::Gt)
+            }
+            '"' => {
+                i += 1;
+                let mut value = String::new();
+                loop {
+                    match chars.get(i) {
+                        None => return Err(ParseError::new(start, "Unterminated string")),
+                        Some('"') => {
+                            i += 1;
+                            break;
+                        }
+                        Some('\\') => {
+                            let escaped = match chars.get(i + 1) {
+                                Some('"') => '"',
+                                Some('\\') => '\\',
+                                Some('n') => '\n',
+                                Some('t') => '\t',
+                                _ => return Err(ParseError::new(i, "Invalid escape sequence")),
+                            };
+                            value.push(escaped);
+                            i += 2;
+                        }
+                        Some(c) => {
+                            value.push(*c);
+                            i += 1;
+                        }
+                    }
+                }
+                Token::String(value)
+            }
+            c if c.is_ascii_digit() => {
+                while chars.get(i).is_some_and(|c| c.is_ascii_digit()) {
+                    i += 1;
+                }
+                let literal: String = chars[start..i].iter().collect();
+                let value = literal
+                    .parse()
+                    .map_err(|_| ParseError::new(start, "Number out of range"))?;
+                Token::Number(value)
+            }
+            c if is_key_start(c) => {
+                while chars.get(i).is_some_and(|c| is_key_char(*c)) {
+                    i += 1;
+                }
+                Token::Key(chars[start..i].iter().collect())
+            }
+            c => {
+                return Err(ParseError::new(
+                    start,
+                    format!("Unexpected character '{c}'"),
+                ))
+            }
+        };
+        tokens.push((token, start));
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    end: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&(Token, usize)> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<(Token, usize)> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn next_or_eof(&mut self, expected: &str) -> Result<(Token, usize), ParseError> {
+        self.next()
+            .ok_or_else(|| ParseError::new(self.end, format!("Expected {expected}")))
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek().is_some_and(|(t, _)| t == token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn enter(&mut self, position: usize) -> Result<(), ParseError> {
+        if self.depth == MAX_NESTING_DEPTH {
+            return Err(ParseError::new(
+                position,
+                format!("Query is nested deeper than {MAX_NESTING_DEPTH} levels"),
+            ));
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    fn parse_or(&mut self) -> Result<Query, ParseError> {
+        let mut lhs = self.parse_and()?;
+        while self.eat(&Token::Or) {
+            let rhs = self.parse_and()?;
+            lhs = Query::Or(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<Query, ParseError> {
+        let mut lhs = self.parse_not()?;
+        while self.eat(&Token::And) {
+            let rhs = self.parse_not()?;
+            lhs = Query::And(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_not(&mut self) -> Result<Query, ParseError> {
+        match self.peek() {
+            Some(&(Token::Not, position)) => {
+                self.enter(position)?;
+                self.pos += 1;
+                let query = Query::Not(Box::new(self.parse_not()?));
+                self.depth -= 1;
+                Ok(query)
+            }
+            _ => self.parse_primary(),
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<Query, ParseError> {
+        match self.next_or_eof("attribute key or '('")? {
+            (Token::LParen, position) => {
+                self.enter(position)?;
+                let query = self.parse_or()?;
+                self.depth -= 1;
+                match self.next_or_eof("')'")? {
+                    (Token::RParen, _) => Ok(query),
+                    (_, position) => Err(ParseError::new(position, "Expected ')'")),
+                }
+            }
+            (Token::Key(key), _) => self.parse_predicate(key),
+            (_, position) => Err(ParseError::new(position, "Expected attribute key or '('")),
+        }
+    }
+
+    fn parse_predicate(&mut self, key: String) -> Result<Query, ParseError> {
+        let operator = match self.next_or_eof("operator")? {
+            (Token::Operator(operator), _) => operator,
+            (_, position) => return Err(ParseError::new(position, "Expected operator")),
+        };
+        let value = match self.next_or_eof("value")? {
+            (Token::String(value), _) => Value::String(value),
+            (Token::Number(_), position)
+                if matches!(operator, Operator::Glob | Operator::NotGlob) =>
+            {
+                return Err(ParseError::new(
+                    position,
+                    format!("Operator '{operator}' requires a string value"),
+                ))
+            }
+            (Token::Number(value), _) => Value::Number(value),
+            (_, position) => return Err(ParseError::new(position, "Expected value")),
+        };
+        Ok(Query::Predicate(Predicate {
+            key,
+            operator,
+            value,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn pred(key: &str, operator: Operator, value: Value) -> Query {
+        Query::Predicate(Predicate {
+            key: key.to_string(),
+            operator,
+            value,
+        })
+    }
+
+    fn str_val(v: &str) -> Value {
+        Value::String(v.to_string())
+    }
+
+    #[test]
+    fn parses_predicates() {
+        assert_eq!(
+            "type = \"note\"".parse::<Query>().unwrap(),
+            pred("type", Operator::Eq, str_val("note"))
+        );
+        for (op, operator) in [
+            ("=", Operator::Eq),
+            ("!=", Operator::Ne),
+            ("<", Operator::Lt),
+            ("<=", Operator::Le),
+            (">", Operator::Gt),
+            (">=", Operator::Ge),
+        ] {
+            assert_eq!(
+                format!("priority{op}3").parse::<Query>().unwrap(),
+                pred("priority", operator, Value::Number(3))
+            );
+        }
+        assert_eq!(
+            "tag !~ \"urgent*\"".parse::<Query>().unwrap(),
+            pred("tag", Operator::NotGlob, str_val("urgent*"))
+        );
+    }
+
+    #[test]
+    fn respects_precedence() {
+        let a = || pred("a", Operator::Eq, Value::Number(1));
+        let b = || pred("b", Operator::Eq, Value::Number(2));
+        let c = || pred("c", Operator::Eq, Value::Number(3));
+        let and = |l, r| Query::And(Box::new(l), Box::new(r));
+        let or = |l, r| Query::Or(Box::new(l), Box::new(r));
+        let not = |q| Query::Not(Box::new(q));
+
+        assert_eq!(
+            "a = 1 || b = 2 && c = 3".parse::<Query>().unwrap(),
+            or(a(), and(b(), c()))
+        );
+        assert_eq!(
+            "a = 1 && b = 2 || c = 3".parse::<Query>().unwrap(),
+            or(and(a(), b()), c())
+        );
+        assert_eq!(
+            "(a = 1 || b = 2) && c = 3".parse::<Query>().unwrap(),
+            and(or(a(), b()), c())
+        );
+        assert_eq!(
+            "!a = 1 && b = 2".parse::<Query>().unwrap(),
+            and(not(a()), b())
+        );
+        assert_eq!(
+            "!(a = 1 && b = 2)".parse::<Query>().unwrap(),
+            not(and(a(), b()))
+        );
+        assert_eq!(
+            "a = 1 || b = 2 || c = 3".parse::<Query>().unwrap(),
+            or(or(a(), b()), c())
+        );
+    }
+
+    #[test]
+    fn handles_string_escapes() {
+        assert_eq!(
+            r#"name = "say \"hi\" \\ bye\n""#.parse::<Query>().unwrap(),
+            pred("name", Operator::Eq, str_val("say \"hi\" \\ bye\n"))
+        );
+        assert_eq!(
+            r#"name = "a && b || !(c)""#.parse::<Query>().unwrap(),
+            pred("name", Operator::Eq, str_val("a && b || !(c)"))
+        );
+    }
+
+    #[test]
+    fn converts_glob_to_like() {
+        assert_eq!(glob_to_like("urgent*"), "urgent%");
+        assert_eq!(glob_to_like("a?c"), "a_c");
+        assert_eq!(glob_to_like("100%_done"), "100\\%\\_done");
+        assert_eq!(glob_to_like(r"literal\*star"), "literal*star");
+        assert_eq!(glob_to_like(r"back\\slash"), r"back\\slash");
+        assert_eq!(glob_to_like(r"trailing\"), r"trailing\\");
+    }
+
+    #[test]
+    fn reports_errors() {
+        let err = |s: &str| s.parse::<Query>().unwrap_err();
+
+        assert_eq!(
+            err("").to_string(),
+            "Expected attribute key or '(' at position 0"
+        );
+        assert_eq!(err("a = ").to_string(), "Expected value at position 4");
+        assert_eq!(err("a 1").to_string(), "Expected operator at position 2");
+        assert_eq!(err("(a = 1").to_string(), "Expected ')' at position 6");
+        assert_eq!(err("a = 1)").to_string(), "Unexpected token at position 5");
+        assert_eq!(
+            err("a = \"x").to_string(),
+            "Unterminated string at position 4"
+        );
+        assert_eq!(
+            err("a = \"\\x\"").to_string(),
+            "Invalid escape sequence at position 5"
+        );
+        assert_eq!(
+            err("a = 1 & b = 2").to_string(),
+            "Unexpected character '&' at position 6"
+        );
+        assert_eq!(
+            err("a ~ 1").to_string(),
+            "Operator '~' requires a string value at position 4"
+        );
+        assert_eq!(
+            err("a = 99999999999999999999").to_string(),
+            "Number out of range at position 4"
+        );
+    }
+
+    #[test]
+    fn limits_query_size() {
+        let nested = |depth: usize| format!("{}a = 1{}", "(".repeat(depth), ")".repeat(depth));
+        let negated = |depth: usize| format!("{}a = 1", "!".repeat(depth));
+
+        assert!(nested(MAX_NESTING_DEPTH).parse::<Query>().is_ok());
+        assert!(negated(MAX_NESTING_DEPTH).parse::<Query>().is_ok());
+        assert_eq!(
+            nested(MAX_NESTING_DEPTH + 1)
+                .parse::<Query>()
+                .unwrap_err()
+                .to_string(),
+            "Query is nested deeper than 32 levels at position 32"
+        );
+        assert_eq!(
+            format!("({})", negated(MAX_NESTING_DEPTH))
+                .parse::<Query>()
+                .unwrap_err()
+                .to_string(),
+            "Query is nested deeper than 32 levels at position 32"
+        );
+        assert_eq!(
+            nested(100_000).parse::<Query>().unwrap_err().to_string(),
+            "Query is longer than 4096 characters at position 4096"
+        );
+    }
+}
@@ -3,15 +3,19 @@ use golem_base_indexer_entity::{golem_base_numeric_annotations, golem_base_strin
 use sea_orm::{
     prelude::*,
     ActiveValue::{NotSet, Set},
-    DbBackend, FromQueryResult, Statement,
+    DbBackend, FromQueryResult, QueryOrder, Statement,
 };
+use std::collections::HashMap;
 use tracing::instrument;
 
 use crate::{
+    pagination::paginate_try_from,
     repository::sql,
     types::{
-        EntityKey, FullNumericAttribute, FullStringAttribute, NumericAttribute,
-        NumericAttributeWithRelations, StringAttribute, StringAttributeWithRelations, TxHash,
+        AttributeKeyStats, AttributeKeysFilter, AttributeValueCount, AttributeValuesFilter,
+        BlockNumber, EntityKey, FullNumericAttribute, FullStringAttribute, NumericAttribute,
+        NumericAttributeStats, NumericAttributeWithRelations, PaginationMetadata, StringAttribute,
+        StringAttributeWithRelations, TxHash,
     },
 };
 
@@ -56,6 +60,94 @@ impl TryFrom<DbNumericAttributeWithRelations> for NumericAttributeWithRelations
     }
 }
 
+#[derive(FromQueryResult)]
+struct DbEntityStringAttributeWithRelations {
+    pub entity_key: Vec<u8>,
+    pub key: String,
+    pub value: String,
+    pub related_entities: i64,
+}
+
+#[derive(FromQueryResult)]
+struct DbEntityNumericAttributeWithRelations {
+    pub entity_key: Vec<u8>,
+    pub key: String,
+    pub value: Decimal,
+    pub related_entities: i64,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct DbAttributeKeyStats {
+    pub kind: String,
+    pub key: String,
+    pub active_entities: i64,
+    pub first_seen_block: i64,
+    pub last_seen_block: i64,
+}
+
+impl TryFrom<DbAttributeKeyStats> for AttributeKeyStats {
+    type Error = anyhow::Error;
+
+    fn try_from(value: DbAttributeKeyStats) -> Result<Self> {
+        Ok(Self {
+            kind: value.kind.parse()?,
+            key: value.key,
+            active_entities: value.active_entities.try_into()?,
+            first_seen_block: value.first_seen_block.try_into()?,
+            last_seen_block: value.last_seen_block.try_into()?,
+        })
+    }
+}
+
+#[derive(Debug, FromQueryResult)]
+struct DbAttributeValueCount {
+    pub value: String,
+    pub active_entities: i64,
+}
+
+impl TryFrom<DbAttributeValueCount> for AttributeValueCount {
+    type Error = anyhow::Error;
+
+    fn try_from(value: DbAttributeValueCount) -> Result<Self> {
+        Ok(Self {
+            value: value.value,
+            active_entities: value.active_entities.try_into()?,
+        })
+    }
+}
+
+#[derive(FromQueryResult)]
+struct DbNumericAttributeStats {
+    pub key: String,
+    pub active_entities: i64,
+    pub min: Option<Decimal>,
+    pub max: Option<Decimal>,
+    pub p25: Option<Decimal>,
+    pub p50: Option<Decimal>,
+    pub p75: Option<Decimal>,
+    pub p90: Option<Decimal>,
+    pub p99: Option<Decimal>,
+}
+
+impl TryFrom<DbNumericAttributeStats> for NumericAttributeStats {
+    type Error = anyhow::Error;
+
+    fn try_from(value: DbNumericAttributeStats) -> Result<Self> {
+        let convert = |v: Option<Decimal>| v.map(u64::try_from).transpose();
+        Ok(Self {
+            key: value.key,
+            active_entities: value.active_entities.try_into()?,
+            min: convert(value.min)?,
+            max: convert(value.max)?,
+            p25: convert(value.p25)?,
+            p50: convert(value.p50)?,
+            p75: convert(value.p75)?,
+            p90: convert(value.p90)?,
+            p99: convert(value.p99)?,
+        })
+    }
+}
+
 impl From<golem_base_string_annotations::Model> for StringAttribute {
     fn from(value: golem_base_string_annotations::Model) -> Self {
         Self {
@@ -232,6 +324,167 @@ pub async fn find_active_numeric_attributes<T: ConnectionTrait>(
     .collect::<Result<Vec<_>>>()
 }
 
+/// Active string attributes of many entities at once, grouped by entity key.
+#[instrument(skip(db))]
+pub async fn find_entities_active_string_attributes<T: ConnectionTrait>(
+    db: &T,
+    entity_keys: &[EntityKey],
+) -> Result<HashMap<EntityKey, Vec<StringAttributeWithRelations>>> {
+    let entity_keys: Vec<Vec<u8>> = entity_keys.iter().map(|v| v.as_slice().into()).collect();
+    let mut attributes: HashMap<_, Vec<_>> = HashMap::new();
+    for v in
+        DbEntityStringAttributeWithRelations::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            sql::GET_ENTITIES_STRING_ANNOTATIONS_WITH_RELATIONS,
+            [entity_keys.into()],
+        ))
+        .all(db)
+        .await
+        .context("Finding active string attributes of entities")?
+    {
+        let entity_key = v.entity_key.as_slice().try_into()?;
+        let attribute = DbStringAttributeWithRelations {
+            key: v.key,
+            value: v.value,
+            related_entities: v.related_entities,
+        };
+        attributes
+            .entry(entity_key)
+            .or_default()
+            .push(attribute.try_into()?);
+    }
+    Ok(attributes)
+}
+
+/// Active numeric attributes of many entities at once, grouped by entity key.
+#[instrument(skip(db))]
+pub async fn find_entities_active_numeric_attributes<T: ConnectionTrait>(
+    db: &T,
+    entity_keys: &[EntityKey],
+) -> Result<HashMap<EntityKey, Vec<NumericAttributeWithRelations>>> {
+    let entity_keys: Vec<Vec<u8>> = entity_keys.iter().map(|v| v.as_slice().into()).collect();
+    let mut attributes: HashMap<_, Vec<_>> = HashMap::new();
+    for v in
+        DbEntityNumericAttributeWithRelations::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            sql::GET_ENTITIES_NUMERIC_ANNOTATIONS_WITH_RELATIONS,
+            [entity_keys.into()],
+        ))
+        .all(db)
+        .await
+        .context("Finding active numeric attributes of entities")?
+    {
+        let entity_key = v.entity_key.as_slice().try_into()?;
+        let attribute = DbNumericAttributeWithRelations {
+            key: v.key,
+            value: v.value,
+            related_entities: v.related_entities,
+        };
+        attributes
+            .entry(entity_key)
+            .or_default()
+            .push(attribute.try_into()?);
+    }
+    Ok(attributes)
+}
+
+/// Finds string attributes the entity had at the end of the given block.
+#[instrument(skip(db))]
+pub async fn find_string_attributes_at_block<T: ConnectionTrait>(
+    db: &T,
+    entity_key: EntityKey,
+    block_number: BlockNumber,
+) -> Result<Vec<StringAttributeWithRelations>> {
+    let entity_key: Vec<u8> = entity_key.as_slice().into();
+    let block_number: i64 = block_number.try_into()?;
+    DbStringAttributeWithRelations::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        sql::GET_STRING_ANNOTATIONS_WITH_RELATIONS_AT_BLOCK,
+        [entity_key.into(), block_number.into()],
+    ))
+    .all(db)
+    .await
+    .context("Finding string attributes at block")?
+    .into_iter()
+    .map(TryInto::<StringAttributeWithRelations>::try_into)
+    .collect::<Result<Vec<_>>>()
+}
+
+/// Finds numeric attributes the entity had at the end of the given block.
+#[instrument(skip(db))]
+pub async fn find_numeric_attributes_at_block<T: ConnectionTrait>(
+    db: &T,
+    entity_key: EntityKey,
+    block_number: BlockNumber,
+) -> Result<Vec<NumericAttributeWithRelations>> {
+    let entity_key: Vec<u8> = entity_key.as_slice().into();
+    let block_number: i64 = block_number.try_into()?;
+    DbNumericAttributeWithRelations::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        sql::GET_NUMERIC_ANNOTATIONS_WITH_RELATIONS_AT_BLOCK,
+        [entity_key.into(), block_number.into()],
+    ))
+    .all(db)
+    .await
+    .context("Finding numeric attributes at block")?
+    .into_iter()
+    .map(TryInto::<NumericAttributeWithRelations>::try_into)
+    .collect::<Result<Vec<_>>>()
+}
+
+/// Finds attributes of the entity, either the ones set by the given operation
+/// or, when no operation is given, the currently active ones.
+#[instrument(skip(db))]
+pub async fn find_attributes<T: ConnectionTrait>(
+    db: &T,
+    entity_key: EntityKey,
+    index: Option<(TxHash, u64)>,
+) -> Result<(Vec<StringAttribute>, Vec<NumericAttribute>)> {
+    let entity_key: Vec<u8> = entity_key.as_slice().into();
+
+    let mut string_query = golem_base_string_annotations::Entity::find()
+        .filter(golem_base_string_annotations::Column::EntityKey.eq(entity_key.clone()))
+        .order_by_asc(golem_base_string_annotations::Column::Id);
+    let mut numeric_query = golem_base_numeric_annotations::Entity::find()
+        .filter(golem_base_numeric_annotations::Column::EntityKey.eq(entity_key))
+        .order_by_asc(golem_base_numeric_annotations::Column::Id);
+
+    match index {
+        Some((tx_hash, op_index)) => {
+            let tx_hash: Vec<u8> = tx_hash.as_slice().into();
+            string_query = string_query
+                .filter(golem_base_string_annotations::Column::OperationTxHash.eq(tx_hash.clone()))
+                .filter(golem_base_string_annotations::Column::OperationIndex.eq(op_index));
+            numeric_query = numeric_query
+                .filter(golem_base_numeric_annotations::Column::OperationTxHash.eq(tx_hash))
+                .filter(golem_base_numeric_annotations::Column::OperationIndex.eq(op_index));
+        }
+        None => {
+            string_query =
+                string_query.filter(golem_base_string_annotations::Column::Active.eq(true));
+            numeric_query =
+                numeric_query.filter(golem_base_numeric_annotations::Column::Active.eq(true));
+        }
+    }
+
+    let string_attributes = string_query
+        .all(db)
+        .await
+        .context("Finding string attributes")?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+    let numeric_attributes = numeric_query
+        .all(db)
+        .await
+        .context("Finding numeric attributes")?
+        .into_iter()
+        .map(TryInto::try_into)
+        .collect::<Result<_>>()?;
+
+    Ok((string_attributes, numeric_attributes))
+}
+
 #[instrument(skip(db))]
 pub async fn activate_attributes<T: ConnectionTrait>(
     db: &T,
@@ -317,3 +570,56 @@ pub async fn batch_insert_numeric_attribute<T: ConnectionTrait>(
 
     Ok(())
 }
+
+/// Attribute keys by the number of entities they are active on.
+#[instrument(skip(db))]
+pub async fn list_attribute_keys<T: ConnectionTrait>(
+    db: &T,
+    filter: AttributeKeysFilter,
+) -> Result<(Vec<AttributeKeyStats>, PaginationMetadata)> {
+    let paginator = DbAttributeKeyStats::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        sql::LIST_ANNOTATION_KEYS,
+        [filter.kind.map(|v| v.as_str()).into(), filter.prefix.into()],
+    ))
+    .paginate(db, filter.pagination.page_size);
+
+    paginate_try_from(paginator, filter.pagination).await
+}
+
+/// Active values of an attribute key, the most common first.
+#[instrument(skip(db))]
+pub async fn list_attribute_values<T: ConnectionTrait>(
+    db: &T,
+    filter: AttributeValuesFilter,
+) -> Result<(Vec<AttributeValueCount>, PaginationMetadata)> {
+    let paginator = DbAttributeValueCount::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        sql::LIST_ANNOTATION_VALUES,
+        [
+            filter.kind.as_str().into(),
+            filter.key.into(),
+            filter.prefix.into(),
+        ],
+    ))
+    .paginate(db, filter.pagination.page_size);
+
+    paginate_try_from(paginator, filter.pagination).await
+}
+
+#[instrument(skip(db))]
+pub async fn numeric_attribute_stats<T: ConnectionTrait>(
+    db: &T,
+    key: String,
+) -> Result<NumericAttributeStats> {
+    DbNumericAttributeStats::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        sql::NUMERIC_ANNOTATION_STATS,
+        [key.into()],
+    ))
+    .one(db)
+    .await
+    .context("Failed to get numeric attribute stats")?
+    .expect("Aggregate will always return a row")
+    .try_into()
+}
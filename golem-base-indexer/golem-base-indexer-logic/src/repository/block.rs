@@ -7,8 +7,8 @@ use sea_orm::{prelude::*, DbBackend, FromQueryResult, QueryOrder, QuerySelect, S
 use tracing::instrument;
 
 use crate::types::{
-    BlockConsensusInfo, BlockEntitiesCount, BlockNewData, BlockNumber, BlockStorageDiff,
-    BlockStorageUsage, ConsensusBlocksInfo,
+    BlockConsensusInfo, BlockEntitiesCount, BlockHash, BlockNewData, BlockNumber, BlockStorageDiff,
+    BlockStorageUsage, ConsensusBlocksInfo, EntityKey,
 };
 
 use super::sql;
@@ -43,6 +43,11 @@ struct DbBlockNumber {
     pub block_number: i64,
 }
 
+#[derive(Debug, FromQueryResult)]
+struct DbForkPoint {
+    pub block_number: Option<i64>,
+}
+
 #[derive(Debug, FromQueryResult)]
 struct DbBlockStorageDiff {
     pub block_number: i64,
@@ -243,6 +248,83 @@ pub async fn mark_stats_dirty<T: ConnectionTrait>(db: &T, block_number: BlockNum
     Ok(())
 }
 
+/// Marks stats dirty from the first block the entity's history or operations touch.
+#[instrument(skip(db))]
+pub async fn mark_entity_stats_dirty<T: ConnectionTrait>(db: &T, entity: EntityKey) -> Result<()> {
+    let entity: Vec<u8> = entity.as_slice().into();
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        sql::MARK_ENTITY_STATS_DIRTY,
+        [entity.into()],
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Waits for transactions marking stats dirty and keeps new ones from doing so
+/// until the current transaction ends.
+#[instrument(skip(db))]
+pub async fn lock_stats<T: ConnectionTrait>(db: &T) -> Result<()> {
+    db.execute_unprepared(sql::LOCK_BLOCK_STATS)
+        .await
+        .context("Failed to lock block stats")?;
+    Ok(())
+}
+
+#[instrument(skip(db))]
+pub async fn mark_indexed<T: ConnectionTrait>(
+    db: &T,
+    hash: BlockHash,
+    number: BlockNumber,
+) -> Result<()> {
+    let number: i64 = number.try_into()?;
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        sql::MARK_BLOCK_INDEXED,
+        [hash.as_slice().into(), number.into()],
+    ))
+    .await
+    .context("Failed to mark block as indexed")?;
+
+    Ok(())
+}
+
+/// Finds the lowest indexed block that is no longer part of the canonical
+/// chain, looking only at the `depth` most recent indexed blocks.
+#[instrument(skip(db))]
+pub async fn find_fork_point<T: ConnectionTrait>(
+    db: &T,
+    depth: u64,
+) -> Result<Option<BlockNumber>> {
+    let depth: i64 = depth.try_into()?;
+    DbForkPoint::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        sql::FIND_FORK_POINT,
+        [depth.into()],
+    ))
+    .one(db)
+    .await
+    .context("Failed to find fork point")?
+    .and_then(|v| v.block_number)
+    .map(|v| v.try_into().context("Failed to convert block number"))
+    .transpose()
+}
+
+#[instrument(skip(db))]
+pub async fn forget_indexed_blocks<T: ConnectionTrait>(db: &T, since: BlockNumber) -> Result<()> {
+    let since: i64 = since.try_into()?;
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        sql::FORGET_INDEXED_BLOCKS,
+        [since.into()],
+    ))
+    .await
+    .context("Failed to forget indexed blocks")?;
+
+    Ok(())
+}
+
 pub fn consensus_info(
     block_number: BlockNumber,
     blocks_info: ConsensusBlocksInfo,
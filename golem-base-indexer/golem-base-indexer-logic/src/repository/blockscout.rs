@@ -1,4 +1,7 @@
-use crate::{repository::sql::GET_TX_BY_HASH, types::Block};
+use crate::{
+    repository::sql::GET_TX_BY_HASH,
+    types::{Block, ChainBlock},
+};
 use anyhow::{anyhow, Context, Result};
 use futures::{Stream, StreamExt};
 use golem_base_indexer_entity::{
@@ -214,6 +217,11 @@ pub async fn stream_tx_hashes_for_cleanup<T: StreamTrait + ConnectionTrait>(
     db: &T,
 ) -> Result<impl Stream<Item = TxHash> + '_> {
     Ok(golem_base_pending_transaction_cleanups::Entity::find()
+        .filter(golem_base_pending_transaction_cleanups::Column::QuarantinedAt.is_null())
+        .filter(
+            Expr::col(golem_base_pending_transaction_cleanups::Column::NextAttemptAt)
+                .lte(Expr::cust("now()")),
+        )
         .limit(100)
         .stream(db)
         .await
@@ -256,6 +264,117 @@ pub async fn get_current_block<T: ConnectionTrait>(db: &T) -> Result<Option<Bloc
     .transpose()
 }
 
+#[instrument(skip(db))]
+pub async fn get_latest_consensus_block<T: ConnectionTrait>(db: &T) -> Result<Option<Block>> {
+    DbBlock::find_by_statement(Statement::from_string(
+        DbBackend::Postgres,
+        "select hash, number, timestamp from blocks where consensus order by number desc limit 1",
+    ))
+    .one(db)
+    .await
+    .context("Failed to get latest consensus block")?
+    .map(TryInto::try_into)
+    .transpose()
+}
+
+/// Writes a block fetched from the chain along with its txs and logs.
+/// Txs already known from an orphaned block are attached to this one.
+#[instrument(skip_all, fields(block_number = block.number))]
+pub async fn insert_chain_block<T: ConnectionTrait>(db: &T, block: &ChainBlock) -> Result<()> {
+    let block_number: i64 = block.number.try_into()?;
+    let block_hash: Vec<u8> = block.hash.as_slice().into();
+    let timestamp = block.timestamp.naive_utc();
+
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        sql::INSERT_CHAIN_BLOCK,
+        [
+            i64::try_from(block.gas_limit)?.into(),
+            i64::try_from(block.gas_used)?.into(),
+            block_hash.clone().into(),
+            block.miner.as_slice().into(),
+            block_number.into(),
+            block.parent_hash.as_slice().into(),
+            timestamp.into(),
+        ],
+    ))
+    .await
+    .context("Failed to insert block")?;
+
+    for tx in &block.transactions {
+        db.execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            sql::UPSERT_CHAIN_TRANSACTION,
+            [
+                tx.hash.as_slice().into(),
+                i32::try_from(tx.index)?.into(),
+                tx.from.as_slice().into(),
+                tx.to.map(|v| v.to_vec()).into(),
+                tx.created_contract_address.map(|v| v.to_vec()).into(),
+                tx.input.to_vec().into(),
+                i64::try_from(tx.nonce)?.into(),
+                i64::try_from(tx.gas)?.into(),
+                tx.gas_price.to_string().into(),
+                tx.value.to_string().into(),
+                i32::try_from(tx.r#type)?.into(),
+                i32::try_from(tx.status)?.into(),
+                i64::try_from(tx.gas_used)?.into(),
+                i64::try_from(tx.cumulative_gas_used)?.into(),
+                block_hash.clone().into(),
+                block_number.into(),
+                timestamp.into(),
+            ],
+        ))
+        .await
+        .with_context(|| format!("Failed to upsert tx {}", tx.hash))?;
+
+        for log in &tx.logs {
+            let topic = |i: usize| log.topics.get(i).map(|v| v.to_vec());
+            db.execute(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                sql::INSERT_CHAIN_LOG,
+                [
+                    log.data.to_vec().into(),
+                    i32::try_from(log.index)?.into(),
+                    topic(0).into(),
+                    topic(1).into(),
+                    topic(2).into(),
+                    topic(3).into(),
+                    log.address.as_slice().into(),
+                    tx.hash.as_slice().into(),
+                    block_hash.clone().into(),
+                    block_number.into(),
+                ],
+            ))
+            .await
+            .with_context(|| format!("Failed to insert log {} of tx {}", log.index, tx.hash))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Marks blocks from the given one onwards as no longer canonical and
+/// detaches their txs, the same way Blockscout handles a reorg.
+#[instrument(skip(db))]
+pub async fn orphan_blocks_since<T: ConnectionTrait>(
+    db: &T,
+    block_number: BlockNumber,
+) -> Result<()> {
+    let block_number: i64 = block_number.try_into()?;
+    for query in [sql::ORPHAN_BLOCKS_SINCE, sql::DETACH_ORPHANED_TRANSACTIONS] {
+        db.execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            query,
+            [block_number.into()],
+        ))
+        .await
+        .context("Failed to orphan blocks")?;
+    }
+
+    Ok(())
+}
+
 #[instrument(skip(db))]
 pub(super) async fn get_block<T: ConnectionTrait>(
     db: &T,
@@ -274,6 +393,34 @@ pub(super) async fn get_block<T: ConnectionTrait>(
     .transpose()
 }
 
+/// Queues storage txs, fees of all txs and logs of canonical blocks from the given block onwards
+/// for processing again and drops pending logs of blocks that lost consensus.
+#[instrument(skip(db))]
+pub async fn requeue_since_block<T: ConnectionTrait>(
+    db: &T,
+    block_number: BlockNumber,
+) -> Result<()> {
+    let block_number: i64 = block_number.try_into()?;
+    for query in [
+        sql::DROP_PENDING_LOGS_OPERATIONS_OFF_CHAIN,
+        sql::DROP_PENDING_LOGS_EVENTS_OFF_CHAIN,
+        sql::REQUEUE_TRANSACTIONS_SINCE_BLOCK,
+        sql::REQUEUE_TRANSACTION_FEES_SINCE_BLOCK,
+        sql::REQUEUE_LOGS_OPERATIONS_SINCE_BLOCK,
+        sql::REQUEUE_LOGS_EVENTS_SINCE_BLOCK,
+    ] {
+        db.execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            query,
+            [block_number.into()],
+        ))
+        .await
+        .context("Failed to requeue txs and logs")?;
+    }
+
+    Ok(())
+}
+
 #[instrument(skip(db))]
 pub async fn count_unprocessed_txs<T: StreamTrait + ConnectionTrait>(db: &T) -> Result<u64> {
     golem_base_pending_transaction_operations::Entity::find()
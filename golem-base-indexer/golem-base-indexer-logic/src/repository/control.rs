@@ -0,0 +1,48 @@
+use anyhow::{Context, Result};
+use sea_orm::{prelude::*, sqlx::PgConnection, DbBackend, FromQueryResult, Statement};
+use tracing::instrument;
+
+use super::sql;
+
+#[derive(FromQueryResult)]
+struct DbPaused {
+    paused: bool,
+}
+
+#[instrument(skip(db))]
+pub async fn is_paused<T: ConnectionTrait>(db: &T) -> Result<bool> {
+    let res = DbPaused::find_by_statement(Statement::from_string(
+        DbBackend::Postgres,
+        sql::GET_INDEXER_PAUSED,
+    ))
+    .one(db)
+    .await
+    .context("Failed to get indexer state")?;
+    Ok(res.is_some_and(|v| v.paused))
+}
+
+#[instrument(skip(db))]
+pub async fn set_paused<T: ConnectionTrait>(db: &T, paused: bool) -> Result<()> {
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        sql::SET_INDEXER_PAUSED,
+        [paused.into()],
+    ))
+    .await
+    .context("Failed to set indexer state")?;
+    Ok(())
+}
+
+/// Namespace of the advisory locks electing leaders between indexer replicas.
+const LEADER_LOCK_NAMESPACE: i32 = 0x6762_0001;
+
+/// Takes the leader lock for the lifetime of the connection's session, without waiting.
+#[instrument(skip(conn))]
+pub async fn try_lock_leader(conn: &mut PgConnection, role: i32) -> Result<bool> {
+    sea_orm::sqlx::query_scalar(sql::TRY_ADVISORY_LOCK)
+        .bind(LEADER_LOCK_NAMESPACE)
+        .bind(role)
+        .fetch_one(conn)
+        .await
+        .context("Failed to take leader lock")
+}
@@ -1,29 +1,42 @@
+use alloy_primitives::U256;
 use anyhow::{anyhow, Context, Result};
 use futures::{Stream, StreamExt};
 use golem_base_indexer_entity::{
-    golem_base_entities, golem_base_entity_history, golem_base_numeric_annotations,
-    golem_base_string_annotations, sea_orm_active_enums::GolemBaseEntityStatusType,
+    blocks, golem_base_entities, golem_base_entity_history, golem_base_numeric_annotations,
+    golem_base_string_annotations,
+    sea_orm_active_enums::{GolemBaseEntityStatusType, GolemBaseOperationType},
 };
 use sea_orm::{
     entity::prelude::*,
-    sea_query::OnConflict,
+    sea_query::{
+        Alias, Asterisk, Expr, Func, JoinType, LikeExpr, OnConflict, Order, SelectStatement,
+        SimpleExpr,
+    },
     sqlx::types::chrono::Utc,
     ActiveValue::{NotSet, Set},
-    Condition, DbBackend, FromQueryResult, Iterable, QueryOrder, Statement, StreamTrait,
+    Condition, DbBackend, FromQueryResult, Iterable, QueryOrder, QuerySelect, QueryTrait,
+    Statement, StreamTrait,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
 };
-use std::str::FromStr;
 use tracing::instrument;
 
 use crate::{
     arkiv::{block_timestamp, block_timestamp_sec},
     model::entity_data_size_histogram,
-    pagination::{paginate, paginate_try_from},
+    pagination::paginate_cursor,
+    query::{self, glob_to_like, Operator, Predicate, Query},
     repository::sql,
     types::{
-        Address, Block, BlockNumber, Bytes, CurrencyAmount, EntitiesAverages, EntitiesFilter,
-        Entity, EntityDataHistogram, EntityHistoryEntry, EntityHistoryFilter, EntityKey,
-        EntityStatus, EntityWithExpTimestamp, FullEntity, FullOperationIndex, ListEntitiesFilter,
-        OperationFilter, PaginationMetadata, TxHash,
+        Address, AttributesDiff, Block, BlockNumber, Bytes, CurrencyAmount,
+        CursorPaginationMetadata, DataDiff, DataTooLargeToDiff, EntitiesAverages, EntitiesFilter,
+        EntitiesSort, Entity, EntityCursor, EntityDataDiff, EntityDataDiffFilter,
+        EntityDataHistogram, EntityHistoryEntry, EntityHistoryEntryWithAttributes,
+        EntityHistoryFilter, EntityKey, EntitySortField, EntityStatus, EntityWithExpTimestamp,
+        FullEntity, FullOperationIndex, ListEntitiesFilter, OperationFilter, OperationType,
+        ReindexScope, SortDirection, TxHash, MAX_DIFF_PAYLOAD_SIZE,
     },
 };
 
@@ -301,8 +314,221 @@ pub async fn get_entity<T: ConnectionTrait>(db: &T, key: EntityKey) -> Result<Op
         .transpose()
 }
 
-fn filtered_entities(filter: EntitiesFilter) -> Select<golem_base_entities::Entity> {
-    let mut q = golem_base_entities::Entity::find().order_by_asc(golem_base_entities::Column::Key);
+fn compare<C: ColumnTrait>(
+    column: C,
+    operator: Operator,
+    value: sea_orm::Value,
+) -> Result<SimpleExpr> {
+    Ok(match operator {
+        Operator::Eq => column.eq(value),
+        Operator::Ne => column.ne(value),
+        Operator::Lt => column.lt(value),
+        Operator::Le => column.lte(value),
+        Operator::Gt => column.gt(value),
+        Operator::Ge => column.gte(value),
+        Operator::Glob | Operator::NotGlob => {
+            return Err(anyhow!("Operator '{operator}' requires a string value"))
+        }
+    })
+}
+
+/// Latest history entries of every entity up to and including the given block.
+/// Extends can be skipped, as they don't change the active attributes.
+fn history_at_block(block_number: BlockNumber, with_extends: bool) -> Result<SelectStatement> {
+    use golem_base_entity_history::{Column, Entity};
+    let block_number: i64 = block_number.try_into()?;
+    let mut q = sea_orm::sea_query::Query::select()
+        .column(Asterisk)
+        .distinct_on([Column::EntityKey])
+        .from(Entity)
+        .and_where(Column::BlockNumber.lte(block_number))
+        .order_by(Column::EntityKey, Order::Asc)
+        .order_by(Column::BlockNumber, Order::Desc)
+        .order_by(Column::TxIndex, Order::Desc)
+        .order_by(Column::OpIndex, Order::Desc)
+        .to_owned();
+    if !with_extends {
+        q.and_where(Column::Operation.ne(GolemBaseOperationType::Extend));
+    }
+    Ok(q)
+}
+
+/// Rows of `golem_base_entities` as they were at the end of the given block.
+fn entities_at_block(block_number: BlockNumber) -> Result<SelectStatement> {
+    use golem_base_entity_history::Column;
+    let latest = Alias::new("latest");
+    let create = Alias::new("create");
+    Ok(sea_orm::sea_query::Query::select()
+        .expr_as(
+            Expr::col((latest.clone(), Column::EntityKey)),
+            Alias::new("key"),
+        )
+        .column((latest.clone(), Column::Data))
+        .column((latest.clone(), Column::Status))
+        .column((latest.clone(), Column::Owner))
+        .expr_as(
+            Expr::col((create.clone(), Column::Sender)),
+            Alias::new("creator"),
+        )
+        .expr_as(
+            Expr::col((create.clone(), Column::TransactionHash)),
+            Alias::new("created_at_tx_hash"),
+        )
+        .expr_as(
+            Expr::col((latest.clone(), Column::TransactionHash)),
+            Alias::new("last_updated_at_tx_hash"),
+        )
+        .column((latest.clone(), Column::ExpiresAtBlockNumber))
+        .expr_as(
+            Expr::col((create.clone(), Column::BlockTimestamp))
+                .if_null(Expr::col((latest.clone(), Column::BlockTimestamp))),
+            Alias::new("inserted_at"),
+        )
+        .expr_as(
+            Expr::col((latest.clone(), Column::BlockTimestamp)),
+            Alias::new("updated_at"),
+        )
+        .column((latest.clone(), Column::ContentType))
+        .expr_as(
+            Expr::col((latest.clone(), Column::TotalCost)),
+            Alias::new("cost"),
+        )
+        .expr_as(
+            Expr::col((create.clone(), Column::BlockNumber)),
+            Alias::new("created_at_block_number"),
+        )
+        .expr_as(
+            Expr::col((latest.clone(), Column::BlockNumber)),
+            Alias::new("last_updated_at_block_number"),
+        )
+        .from_subquery(history_at_block(block_number, true)?, latest.clone())
+        .join_as(
+            JoinType::LeftJoin,
+            golem_base_entity_history::Entity,
+            create.clone(),
+            Condition::all()
+                .add(
+                    Expr::col((create.clone(), Column::EntityKey))
+                        .equals((latest, Column::EntityKey)),
+                )
+                .add(
+                    Expr::col((create, Column::Operation))
+                        .eq(GolemBaseOperationType::Create.as_enum()),
+                ),
+        )
+        .to_owned())
+}
+
+/// Rows of an attributes table that were active at the end of the given block.
+fn attributes_at_block<E: EntityTrait>(
+    table: E,
+    block_number: BlockNumber,
+) -> Result<SelectStatement> {
+    use golem_base_entity_history::Column;
+    let attribute = Alias::new("attribute");
+    let op = Alias::new("op");
+    let column = |name: &str| Expr::col((attribute.clone(), Alias::new(name)));
+    Ok(sea_orm::sea_query::Query::select()
+        .columns(
+            [
+                "id",
+                "entity_key",
+                "operation_tx_hash",
+                "operation_index",
+                "key",
+                "value",
+                "inserted_at",
+            ]
+            .map(|name| (attribute.clone(), Alias::new(name))),
+        )
+        .expr_as(Expr::val(true), Alias::new("active"))
+        .from_as(table, attribute.clone())
+        .join_subquery(
+            JoinType::InnerJoin,
+            history_at_block(block_number, false)?,
+            op.clone(),
+            Condition::all()
+                .add(Expr::col((op.clone(), Column::EntityKey)).eq(column("entity_key")))
+                .add(
+                    Expr::col((op.clone(), Column::TransactionHash))
+                        .eq(column("operation_tx_hash")),
+                )
+                .add(Expr::col((op, Column::OpIndex)).eq(column("operation_index"))),
+        )
+        .to_owned())
+}
+
+/// Selects from the table, or at a past block from a subquery aliased with the
+/// table name, so that the same filters apply to both.
+fn snapshot<E: EntityTrait>(
+    table: E,
+    at_block: Option<BlockNumber>,
+    at: impl FnOnce(BlockNumber) -> Result<SelectStatement>,
+) -> Result<Select<E>> {
+    let mut q = E::find();
+    if let Some(block_number) = at_block {
+        QueryTrait::query(&mut q)
+            .from_clear()
+            .from_subquery(at(block_number)?, table);
+    }
+    Ok(q)
+}
+
+fn predicate_condition(predicate: &Predicate, at_block: Option<BlockNumber>) -> Result<SimpleExpr> {
+    let entity_keys = match &predicate.value {
+        query::Value::String(value) => {
+            use golem_base_string_annotations::{Column, Entity};
+            let like = || LikeExpr::new(glob_to_like(value)).escape('\\');
+            let condition = match predicate.operator {
+                Operator::Glob => Column::Value.like(like()),
+                Operator::NotGlob => Column::Value.not_like(like()),
+                operator => compare(Column::Value, operator, value.into())?,
+            };
+            snapshot(Entity, at_block, |n| attributes_at_block(Entity, n))?
+                .select_only()
+                .column(Column::EntityKey)
+                .filter(Column::Active.eq(true))
+                .filter(Column::Key.eq(&predicate.key))
+                .filter(condition)
+                .into_query()
+        }
+        query::Value::Number(value) => {
+            use golem_base_numeric_annotations::{Column, Entity};
+            snapshot(Entity, at_block, |n| attributes_at_block(Entity, n))?
+                .select_only()
+                .column(Column::EntityKey)
+                .filter(Column::Active.eq(true))
+                .filter(Column::Key.eq(&predicate.key))
+                .filter(compare(Column::Value, predicate.operator, (*value).into())?)
+                .into_query()
+        }
+    };
+
+    Ok(Expr::col((
+        golem_base_entities::Entity,
+        golem_base_entities::Column::Key,
+    ))
+    .in_subquery(entity_keys))
+}
+
+fn query_condition(query: &Query, at_block: Option<BlockNumber>) -> Result<SimpleExpr> {
+    Ok(match query {
+        Query::And(lhs, rhs) => {
+            query_condition(lhs, at_block)?.and(query_condition(rhs, at_block)?)
+        }
+        Query::Or(lhs, rhs) => query_condition(lhs, at_block)?.or(query_condition(rhs, at_block)?),
+        Query::Not(query) => query_condition(query, at_block)?.not(),
+        Query::Predicate(predicate) => predicate_condition(predicate, at_block)?,
+    })
+}
+
+/// Entities matching the filter, as they are now or, when `at_block` is given,
+/// as they were at the end of that block.
+fn filtered_entities(
+    filter: EntitiesFilter,
+    at_block: Option<BlockNumber>,
+) -> Result<Select<golem_base_entities::Entity>> {
+    let mut q = snapshot(golem_base_entities::Entity, at_block, entities_at_block)?;
 
     if let Some(status) = filter.status {
         let status: GolemBaseEntityStatusType = status.into();
@@ -310,17 +536,25 @@ fn filtered_entities(filter: EntitiesFilter) -> Select<golem_base_entities::Enti
     }
 
     if let Some(ann) = filter.string_attribute {
-        q = q
-            .left_join(golem_base_string_annotations::Entity)
-            .filter(golem_base_string_annotations::Column::Key.eq(ann.key))
-            .filter(golem_base_string_annotations::Column::Value.eq(ann.value));
+        use golem_base_string_annotations::{Column, Entity};
+        let entity_keys = snapshot(Entity, at_block, |n| attributes_at_block(Entity, n))?
+            .select_only()
+            .column(Column::EntityKey)
+            .filter(Column::Key.eq(ann.key))
+            .filter(Column::Value.eq(ann.value))
+            .into_query();
+        q = q.filter(golem_base_entities::Column::Key.in_subquery(entity_keys));
     }
 
     if let Some(ann) = filter.numeric_attribute {
-        q = q
-            .left_join(golem_base_numeric_annotations::Entity)
-            .filter(golem_base_numeric_annotations::Column::Key.eq(ann.key))
-            .filter(golem_base_numeric_annotations::Column::Value.eq(ann.value));
+        use golem_base_numeric_annotations::{Column, Entity};
+        let entity_keys = snapshot(Entity, at_block, |n| attributes_at_block(Entity, n))?
+            .select_only()
+            .column(Column::EntityKey)
+            .filter(Column::Key.eq(ann.key))
+            .filter(Column::Value.eq(ann.value))
+            .into_query();
+        q = q.filter(golem_base_entities::Column::Key.in_subquery(entity_keys));
     }
 
     if let Some(owner) = filter.owner {
@@ -328,23 +562,182 @@ fn filtered_entities(filter: EntitiesFilter) -> Select<golem_base_entities::Enti
         q = q.filter(golem_base_entities::Column::Owner.eq(owner));
     }
 
-    q
+    if let Some(query) = filter.query {
+        q = q.filter(query_condition(&query, at_block)?);
+    }
+
+    if let Some(content_type) = filter.content_type {
+        q = q.filter(golem_base_entities::Column::ContentType.eq(content_type));
+    }
+
+    if let Some(creator) = filter.creator {
+        let creator: Vec<u8> = creator.as_slice().into();
+        q = q.filter(golem_base_entities::Column::Creator.eq(creator));
+    }
+
+    let bounds = [
+        (
+            sort_expr(EntitySortField::ExpiresAtBlock),
+            filter.min_expires_at_block_number,
+            filter.max_expires_at_block_number,
+        ),
+        (
+            sort_expr(EntitySortField::DataSize),
+            filter.min_data_size,
+            filter.max_data_size,
+        ),
+        (
+            sort_expr(EntitySortField::CreatedAtBlock),
+            filter.min_created_at_block_number,
+            filter.max_created_at_block_number,
+        ),
+    ];
+    for (expr, min, max) in bounds {
+        if let Some(min) = min {
+            q = q.filter(Expr::expr(expr.clone()).gte(i64::try_from(min)?));
+        }
+        if let Some(max) = max {
+            q = q.filter(Expr::expr(expr).lte(i64::try_from(max)?));
+        }
+    }
+
+    if filter.created_from.is_some() || filter.created_to.is_some() {
+        let mut blocks = blocks::Entity::find()
+            .select_only()
+            .column(blocks::Column::Number)
+            .filter(blocks::Column::Consensus.eq(true));
+        if let Some(from) = filter.created_from {
+            blocks = blocks.filter(blocks::Column::Timestamp.gte(from.naive_utc()));
+        }
+        if let Some(to) = filter.created_to {
+            blocks = blocks.filter(blocks::Column::Timestamp.lt(to.naive_utc()));
+        }
+        q = q.filter(
+            golem_base_entities::Column::CreatedAtBlockNumber.in_subquery(blocks.into_query()),
+        );
+    }
+
+    Ok(q)
+}
+
+/// Value entities are sorted by, with nulls where the entity has none.
+fn sort_expr(field: EntitySortField) -> SimpleExpr {
+    use golem_base_entities::{Column, Entity};
+    let column = match field {
+        EntitySortField::CreatedAtBlock => Column::CreatedAtBlockNumber,
+        EntitySortField::UpdatedAtBlock => Column::LastUpdatedAtBlockNumber,
+        EntitySortField::ExpiresAtBlock => Column::ExpiresAtBlockNumber,
+        EntitySortField::Cost => Column::Cost,
+        EntitySortField::DataSize => {
+            return Func::cust(Alias::new("octet_length"))
+                .arg(Expr::col((Entity, Column::Data)))
+                .into()
+        }
+    };
+    Expr::col((Entity, column)).into()
+}
+
+fn sort_value(field: EntitySortField, entity: &golem_base_entities::Model) -> Result<Option<U256>> {
+    let block = |v: Option<i64>| v.map(|v| u64::try_from(v).map(U256::from)).transpose();
+    Ok(match field {
+        EntitySortField::CreatedAtBlock => block(entity.created_at_block_number)?,
+        EntitySortField::UpdatedAtBlock => block(entity.last_updated_at_block_number)?,
+        EntitySortField::ExpiresAtBlock => block(entity.expires_at_block_number)?,
+        EntitySortField::DataSize => entity.data.as_ref().map(|v| U256::from(v.len())),
+        EntitySortField::Cost => entity
+            .cost
+            .as_ref()
+            .map(|v| U256::from_str(&v.to_plain_string()))
+            .transpose()?,
+    })
+}
+
+/// Entities positioned strictly after the cursor. Nulls sort last in ascending
+/// order and first in descending one, same as they do in postgres.
+fn after_entity(sort: EntitiesSort, cursor: &EntityCursor) -> Condition {
+    let key_column = golem_base_entities::Column::Key;
+    let key = cursor.key.as_slice();
+    let after_key = match sort.direction {
+        SortDirection::Asc => key_column.gt(key),
+        SortDirection::Desc => key_column.lt(key),
+    };
+    let Some(field) = sort.field else {
+        return Condition::all().add(after_key);
+    };
+
+    let expr = || Expr::expr(sort_expr(field));
+    let value = cursor.value.map(|v| -> sea_orm::Value {
+        match field {
+            EntitySortField::Cost => BigDecimal::from_str(&v.to_string())
+                .expect("Integers are valid decimals")
+                .into(),
+            _ => v.saturating_to::<i64>().into(),
+        }
+    });
+    match (sort.direction, value) {
+        (SortDirection::Asc, Some(value)) => Condition::any()
+            .add(expr().gt(value.clone()))
+            .add(Condition::all().add(expr().eq(value)).add(after_key))
+            .add(expr().is_null()),
+        (SortDirection::Asc, None) => Condition::all().add(expr().is_null()).add(after_key),
+        (SortDirection::Desc, Some(value)) => Condition::any()
+            .add(expr().lt(value.clone()))
+            .add(Condition::all().add(expr().eq(value)).add(after_key)),
+        (SortDirection::Desc, None) => Condition::any()
+            .add(expr().is_not_null())
+            .add(Condition::all().add(expr().is_null()).add(after_key)),
+    }
 }
 
 #[instrument(skip(db))]
 pub async fn list_entities<T: ConnectionTrait>(
     db: &T,
     filter: ListEntitiesFilter,
-) -> Result<(Vec<Entity>, PaginationMetadata)> {
-    let q = filtered_entities(filter.entities_filter);
-    let paginator = q.paginate(db, filter.pagination.page_size);
+) -> Result<(Vec<Entity>, CursorPaginationMetadata<EntityCursor>)> {
+    let sort = filter.sort;
+    let order = match sort.direction {
+        SortDirection::Asc => Order::Asc,
+        SortDirection::Desc => Order::Desc,
+    };
+    let mut q = filtered_entities(filter.entities_filter, filter.at_block)?;
+    if let Some(field) = sort.field {
+        q = q.order_by(sort_expr(field), order.clone());
+    }
+    q = q.order_by(golem_base_entities::Column::Key, order);
+
+    let (items, pagination) = paginate_cursor(
+        db,
+        q,
+        filter.pagination,
+        |cursor| after_entity(sort, cursor),
+        |model| {
+            let value = sort
+                .field
+                .map(|field| sort_value(field, &model))
+                .transpose()?
+                .flatten();
+            Ok((Entity::try_from(model)?, value))
+        },
+        |(entity, value)| EntityCursor {
+            value: *value,
+            key: entity.key,
+        },
+    )
+    .await?;
 
-    paginate_try_from(paginator, filter.pagination).await
+    Ok((
+        items.into_iter().map(|(entity, _)| entity).collect(),
+        pagination,
+    ))
 }
 
 #[instrument(skip(db))]
-pub async fn count_entities<T: ConnectionTrait>(db: &T, filter: EntitiesFilter) -> Result<u64> {
-    let q = filtered_entities(filter);
+pub async fn count_entities<T: ConnectionTrait>(
+    db: &T,
+    filter: EntitiesFilter,
+    at_block: Option<BlockNumber>,
+) -> Result<u64> {
+    let q = filtered_entities(filter, at_block)?;
     q.count(db).await.context("Failed to count entities")
 }
 
@@ -352,9 +745,11 @@ pub async fn count_entities<T: ConnectionTrait>(db: &T, filter: EntitiesFilter)
 pub async fn get_full_entity<T: ConnectionTrait>(
     db: &T,
     key: EntityKey,
+    at_block: Option<BlockNumber>,
 ) -> Result<Option<FullEntity>> {
     let dbkey: Vec<u8> = key.as_slice().into();
-    let entity = golem_base_entities::Entity::find_by_id(dbkey.clone())
+    let entity = snapshot(golem_base_entities::Entity, at_block, entities_at_block)?
+        .filter(golem_base_entities::Column::Key.eq(dbkey))
         .one(db)
         .await
         .with_context(|| format!("Failed to get entity: {key:?}"))?;
@@ -373,21 +768,158 @@ pub async fn get_full_entity<T: ConnectionTrait>(
         None => None,
     };
 
+    let (updated_at_tx_hash, updated_at_operation_index, updated_at_block) = match at_block {
+        Some(block_number) => {
+            let before_next_block = FullOperationIndex {
+                block_number: block_number.saturating_add(1),
+                tx_index: 0,
+                op_index: 0,
+            };
+            let latest_entry = get_latest_entity_history_entry(db, key, Some(before_next_block))
+                .await?
+                .ok_or(anyhow!("Entity with no history"))?;
+            (
+                latest_entry.transaction_hash,
+                latest_entry.op_index,
+                Block {
+                    hash: latest_entry.block_hash,
+                    number: latest_entry.block_number,
+                    timestamp: latest_entry.block_timestamp,
+                },
+            )
+        }
+        None => {
+            let latest_operation = super::operations::find_latest_operation(db, key)
+                .await?
+                .ok_or(anyhow!("Entity with no operations"))?;
+            let latest_op_block =
+                super::blockscout::get_block(db, latest_operation.metadata.block_hash)
+                    .await?
+                    .ok_or(anyhow!("Operation with invalid block"))?;
+            (
+                latest_operation.metadata.tx_hash,
+                latest_operation.metadata.index,
+                latest_op_block,
+            )
+        }
+    };
+
+    full_entity(
+        entity,
+        &current_block,
+        create_operation.as_ref().map(|v| v.metadata.index),
+        create_block,
+        (
+            updated_at_tx_hash,
+            updated_at_operation_index,
+            updated_at_block,
+        ),
+    )
+    .map(Some)
+}
+
+#[derive(FromQueryResult)]
+struct DbEntityOperationInfo {
+    key: Vec<u8>,
+    created_at_operation_index: Option<i64>,
+    created_at_block_hash: Option<Vec<u8>>,
+    created_at_block_number: Option<i64>,
+    created_at_timestamp: Option<DateTime>,
+    updated_at_tx_hash: Vec<u8>,
+    updated_at_operation_index: i64,
+    updated_at_block_hash: Vec<u8>,
+    updated_at_block_number: i64,
+    updated_at_timestamp: DateTime,
+}
+
+/// Looks up the current state of many entities at once. Keys that don't exist are skipped,
+/// the rest are returned in the order they were requested.
+#[instrument(skip(db))]
+pub async fn get_full_entities<T: ConnectionTrait>(
+    db: &T,
+    keys: &[EntityKey],
+) -> Result<Vec<FullEntity>> {
+    let dbkeys: Vec<Vec<u8>> = keys.iter().map(|v| v.as_slice().into()).collect();
+    let entities = golem_base_entities::Entity::find()
+        .filter(golem_base_entities::Column::Key.is_in(dbkeys.clone()))
+        .all(db)
+        .await
+        .context("Failed to get entities")?;
+    if entities.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let current_block = super::blockscout::get_current_block(db)
+        .await?
+        .ok_or(anyhow!("No blocks indexed yet"))?;
+    let mut operation_info: HashMap<_, _> =
+        DbEntityOperationInfo::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            sql::FIND_ENTITIES_OPERATION_INFO,
+            [dbkeys.into()],
+        ))
+        .all(db)
+        .await
+        .context("Failed to find entities operations")?
+        .into_iter()
+        .map(|v| (v.key.clone(), v))
+        .collect();
+    let mut entities: HashMap<_, _> = entities.into_iter().map(|v| (v.key.clone(), v)).collect();
+
+    keys.iter()
+        .filter_map(|key| entities.remove(key.as_slice()))
+        .map(|entity| {
+            let info = operation_info
+                .remove(&entity.key)
+                .ok_or(anyhow!("Entity with no operations"))?;
+            let create_block = match (
+                info.created_at_block_hash,
+                info.created_at_block_number,
+                info.created_at_timestamp,
+            ) {
+                (Some(hash), Some(number), Some(timestamp)) => Some(Block {
+                    hash: hash.as_slice().try_into()?,
+                    number: number.try_into()?,
+                    timestamp: timestamp.and_utc(),
+                }),
+                _ => None,
+            };
+            full_entity(
+                entity,
+                &current_block,
+                info.created_at_operation_index
+                    .map(TryInto::try_into)
+                    .transpose()?,
+                create_block,
+                (
+                    info.updated_at_tx_hash.as_slice().try_into()?,
+                    info.updated_at_operation_index.try_into()?,
+                    Block {
+                        hash: info.updated_at_block_hash.as_slice().try_into()?,
+                        number: info.updated_at_block_number.try_into()?,
+                        timestamp: info.updated_at_timestamp.and_utc(),
+                    },
+                ),
+            )
+        })
+        .collect()
+}
+
+fn full_entity(
+    entity: golem_base_entities::Model,
+    current_block: &Block,
+    created_at_operation_index: Option<u64>,
+    create_block: Option<Block>,
+    (updated_at_tx_hash, updated_at_operation_index, updated_at_block): (TxHash, u64, Block),
+) -> Result<FullEntity> {
     let expires_at_timestamp = entity
         .expires_at_block_number
-        .and_then(|v| block_timestamp(v as u64, &current_block));
+        .and_then(|v| block_timestamp(v as u64, current_block));
     let expires_at_timestamp_sec = entity
         .expires_at_block_number
-        .and_then(|v| block_timestamp_sec(v as u64, &current_block));
+        .and_then(|v| block_timestamp_sec(v as u64, current_block));
 
-    let latest_operation = super::operations::find_latest_operation(db, key)
-        .await?
-        .ok_or(anyhow!("Entity with no operations"))?;
-    let latest_op_block = super::blockscout::get_block(db, latest_operation.metadata.block_hash)
-        .await?
-        .ok_or(anyhow!("Operation with invalid block"))?;
-
-    Ok(Some(FullEntity {
+    Ok(FullEntity {
         key: entity.key.as_slice().try_into()?,
         content_type: entity.content_type,
         data: entity.data.map(|v| v.into()),
@@ -396,13 +928,13 @@ pub async fn get_full_entity<T: ConnectionTrait>(
             .created_at_tx_hash
             .map(|v| v.as_slice().try_into())
             .transpose()?,
-        created_at_operation_index: create_operation.as_ref().map(|v| v.metadata.index),
+        created_at_operation_index,
         created_at_block_number: create_block.as_ref().map(|v| v.number),
         created_at_timestamp: create_block.as_ref().map(|v| v.timestamp),
-        updated_at_tx_hash: latest_operation.metadata.tx_hash,
-        updated_at_operation_index: latest_operation.metadata.index,
-        updated_at_block_number: latest_op_block.number,
-        updated_at_timestamp: latest_op_block.timestamp,
+        updated_at_tx_hash,
+        updated_at_operation_index,
+        updated_at_block_number: updated_at_block.number,
+        updated_at_timestamp: updated_at_block.timestamp,
         expires_at_block_number: entity
             .expires_at_block_number
             .map(TryInto::try_into)
@@ -419,7 +951,7 @@ pub async fn get_full_entity<T: ConnectionTrait>(
             .map(|v| CurrencyAmount::from_str(&v.to_plain_string()))
             .transpose()?
             .unwrap_or(CurrencyAmount::ZERO),
-    }))
+    })
 }
 
 #[instrument(skip(db))]
@@ -448,6 +980,28 @@ pub async fn drop_entity<T: ConnectionTrait>(db: &T, entity: EntityKey) -> Resul
     Ok(())
 }
 
+fn history_older_than(index: FullOperationIndex) -> Condition {
+    use golem_base_entity_history::Column;
+    let FullOperationIndex {
+        block_number,
+        tx_index,
+        op_index,
+    } = index;
+    Condition::any()
+        .add(Column::BlockNumber.lt(block_number))
+        .add(
+            Column::BlockNumber
+                .eq(block_number)
+                .and(Column::TxIndex.lt(tx_index)),
+        )
+        .add(
+            Column::BlockNumber
+                .eq(block_number)
+                .and(Column::TxIndex.eq(tx_index))
+                .and(Column::OpIndex.lt(op_index)),
+        )
+}
+
 #[instrument(skip(db))]
 pub async fn get_latest_entity_history_entry<T: ConnectionTrait>(
     db: &T,
@@ -459,28 +1013,8 @@ pub async fn get_latest_entity_history_entry<T: ConnectionTrait>(
     let mut q = golem_base_entity_history::Entity::find()
         .filter(golem_base_entity_history::Column::EntityKey.eq(entity_key));
 
-    if let Some(FullOperationIndex {
-        block_number,
-        tx_index,
-        op_index,
-    }) = older_than
-    {
-        use golem_base_entity_history::Column;
-        q = q.filter(
-            Condition::any()
-                .add(Column::BlockNumber.lt(block_number))
-                .add(
-                    Column::BlockNumber
-                        .eq(block_number)
-                        .and(Column::TxIndex.lt(tx_index)),
-                )
-                .add(
-                    Column::BlockNumber
-                        .eq(block_number)
-                        .and(Column::TxIndex.eq(tx_index))
-                        .and(Column::OpIndex.lt(op_index)),
-                ),
-        )
+    if let Some(older_than) = older_than {
+        q = q.filter(history_older_than(older_than));
     }
 
     q.order_by_desc(golem_base_entity_history::Column::BlockNumber)
@@ -538,25 +1072,131 @@ pub async fn get_oldest_entity_history_entry<T: ConnectionTrait>(
 pub async fn get_entity_history<T: ConnectionTrait>(
     db: &T,
     filter: EntityHistoryFilter,
-) -> Result<(Vec<EntityHistoryEntry>, PaginationMetadata)> {
+) -> Result<(
+    Vec<EntityHistoryEntry>,
+    CursorPaginationMetadata<FullOperationIndex>,
+)> {
+    use golem_base_entity_history::Column;
     let entity_key: Vec<u8> = filter.entity_key.as_slice().into();
 
-    let paginator = golem_base_entity_history::Entity::find()
-        .filter(golem_base_entity_history::Column::EntityKey.eq(entity_key))
-        .order_by_asc(golem_base_entity_history::Column::BlockNumber)
-        .order_by_asc(golem_base_entity_history::Column::TxIndex)
-        .order_by_asc(golem_base_entity_history::Column::OpIndex)
-        .paginate(db, filter.pagination.page_size);
+    let mut q = golem_base_entity_history::Entity::find().filter(Column::EntityKey.eq(entity_key));
+    if let Some(max_block_number) = filter.max_block_number {
+        let max_block_number: i64 = max_block_number.try_into()?;
+        q = q.filter(Column::BlockNumber.lte(max_block_number));
+    }
+    let q = q
+        .order_by_asc(Column::BlockNumber)
+        .order_by_asc(Column::TxIndex)
+        .order_by_asc(Column::OpIndex);
+
+    paginate_cursor(
+        db,
+        q,
+        filter.pagination,
+        |cursor| {
+            Condition::all().add(
+                Expr::tuple([
+                    Expr::col(Column::BlockNumber).into(),
+                    Expr::col(Column::TxIndex).into(),
+                    Expr::col(Column::OpIndex).into(),
+                ])
+                .gt(Expr::tuple([
+                    Expr::value(cursor.block_number),
+                    Expr::value(cursor.tx_index),
+                    Expr::value(cursor.op_index),
+                ])),
+            )
+        },
+        EntityHistoryEntry::try_new,
+        |entry| FullOperationIndex {
+            block_number: entry.block_number,
+            tx_index: entry.tx_index,
+            op_index: entry.op_index,
+        },
+    )
+    .await
+}
 
-    let (items, pagination_metadata) = paginate(paginator, filter.pagination).await?;
+/// Finds the operation that set the attributes the entity had right before the
+/// given position. Extends keep the attributes, every other operation replaces them.
+async fn find_attributes_operation<T: ConnectionTrait>(
+    db: &T,
+    entity_key: EntityKey,
+    older_than: FullOperationIndex,
+) -> Result<Option<(TxHash, u64)>> {
+    use golem_base_entity_history::{Column, Entity};
+    Entity::find()
+        .filter(Column::EntityKey.eq(entity_key.as_slice()))
+        .filter(Column::Operation.ne(GolemBaseOperationType::Extend))
+        .filter(history_older_than(older_than))
+        .order_by_desc(Column::BlockNumber)
+        .order_by_desc(Column::TxIndex)
+        .order_by_desc(Column::OpIndex)
+        .one(db)
+        .await
+        .context("Failed to find attributes operation")?
+        .map(|entry| {
+            Ok((
+                entry.transaction_hash.as_slice().try_into()?,
+                entry.op_index.try_into()?,
+            ))
+        })
+        .transpose()
+}
 
-    Ok((
-        items
-            .into_iter()
-            .map(EntityHistoryEntry::try_new)
-            .collect::<Result<Vec<_>>>()?,
-        pagination_metadata,
-    ))
+/// Adds to every history entry the attributes the entity had after it and how
+/// they differ from the ones it had before. Entries of the same entity must be
+/// ordered by position.
+#[instrument(skip_all)]
+pub async fn with_attributes<T: ConnectionTrait>(
+    db: &T,
+    entries: Vec<EntityHistoryEntry>,
+) -> Result<Vec<EntityHistoryEntryWithAttributes>> {
+    let mut states = HashMap::new();
+    let mut items = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let key = entry.entity_key;
+        let (prev_string_attributes, prev_numeric_attributes) = match states.remove(&key) {
+            Some(state) => state,
+            None => {
+                let position = FullOperationIndex {
+                    block_number: entry.block_number,
+                    tx_index: entry.tx_index,
+                    op_index: entry.op_index,
+                };
+                match find_attributes_operation(db, key, position).await? {
+                    Some(index) => super::attributes::find_attributes(db, key, Some(index)).await?,
+                    None => Default::default(),
+                }
+            }
+        };
+        let (string_attributes, numeric_attributes) = match entry.operation {
+            OperationType::Extend => (
+                prev_string_attributes.clone(),
+                prev_numeric_attributes.clone(),
+            ),
+            _ => {
+                let index = (entry.transaction_hash, entry.op_index);
+                super::attributes::find_attributes(db, key, Some(index)).await?
+            }
+        };
+
+        states.insert(key, (string_attributes.clone(), numeric_attributes.clone()));
+        items.push(EntityHistoryEntryWithAttributes {
+            entry,
+            string_attributes_diff: AttributesDiff::new(
+                &prev_string_attributes,
+                &string_attributes,
+            ),
+            numeric_attributes_diff: AttributesDiff::new(
+                &prev_numeric_attributes,
+                &numeric_attributes,
+            ),
+            string_attributes,
+            numeric_attributes,
+        });
+    }
+    Ok(items)
 }
 
 #[instrument(skip(db))]
@@ -594,6 +1234,28 @@ pub async fn batch_insert_history_entry<T: ConnectionTrait>(
     Ok(())
 }
 
+/// Operations of the entity that currently have a history entry, as
+/// `(transaction_hash, op_index)`.
+#[instrument(skip(db))]
+pub async fn get_history_operations<T: ConnectionTrait>(
+    db: &T,
+    entity: EntityKey,
+) -> Result<HashSet<(TxHash, u64)>> {
+    let entity: Vec<u8> = entity.as_slice().into();
+    golem_base_entity_history::Entity::find()
+        .select_only()
+        .column(golem_base_entity_history::Column::TransactionHash)
+        .column(golem_base_entity_history::Column::OpIndex)
+        .filter(golem_base_entity_history::Column::EntityKey.eq(entity))
+        .into_tuple::<(Vec<u8>, i64)>()
+        .all(db)
+        .await
+        .context("Failed to get entity history operations")?
+        .into_iter()
+        .map(|(hash, index)| Ok((TxHash::from_slice(&hash), index.try_into()?)))
+        .collect()
+}
+
 #[instrument(skip(db))]
 pub async fn delete_history<T: ConnectionTrait>(db: &T, entity: EntityKey) -> Result<()> {
     let entity: Vec<u8> = entity.as_slice().into();
@@ -619,6 +1281,49 @@ pub async fn get_entity_history_entry<T: ConnectionTrait>(
         .transpose()
 }
 
+/// Diffs the entity data between two of its operations. Returns `None` if
+/// either operation doesn't exist or belongs to another entity.
+#[instrument(skip(db))]
+pub async fn get_entity_data_diff<T: ConnectionTrait>(
+    db: &T,
+    filter: EntityDataDiffFilter,
+) -> Result<Option<EntityDataDiff>> {
+    let (from, to) = futures::try_join!(
+        get_entity_history_entry(db, filter.from.tx_hash, filter.from.op_index),
+        get_entity_history_entry(db, filter.to.tx_hash, filter.to.op_index),
+    )?;
+    let (Some(from), Some(to)) = (from, to) else {
+        return Ok(None);
+    };
+    if from.entity_key != filter.key || to.entity_key != filter.key {
+        return Ok(None);
+    }
+
+    let from_data = from.data.unwrap_or_default();
+    let to_data = to.data.unwrap_or_default();
+    if let Some(size) = [from_data.len(), to_data.len()]
+        .into_iter()
+        .find(|size| *size > MAX_DIFF_PAYLOAD_SIZE)
+    {
+        return Err(DataTooLargeToDiff { size }.into());
+    }
+    let content_type = to.content_type.clone().or(from.content_type.clone());
+    let (from_size, to_size) = (from_data.len() as u64, to_data.len() as u64);
+    // diffing is CPU bound, it would stall other requests served by this worker
+    let diff = tokio::task::spawn_blocking(move || {
+        DataDiff::new(content_type.as_deref(), &from_data, &to_data)
+    })
+    .await
+    .context("Failed to diff entity data")?;
+    Ok(Some(EntityDataDiff {
+        diff,
+        from_size,
+        to_size,
+        from_content_type: from.content_type,
+        to_content_type: to.content_type,
+    }))
+}
+
 #[instrument(skip(db))]
 pub async fn update_entity_history_entry<T: ConnectionTrait>(
     db: &T,
@@ -644,6 +1349,10 @@ pub async fn refresh_entity_based_on_history<T: ConnectionTrait>(
         let creator = create_op
             .as_ref()
             .map(|op| op.metadata.sender.as_slice().into());
+        let created_at_block_number = create_op
+            .as_ref()
+            .map(|op| op.metadata.block_number.try_into())
+            .transpose()?;
         let entity = golem_base_entities::ActiveModel {
             key: Set(key.as_slice().into()),
             data: Set(latest_entry.data.map(Into::into)),
@@ -663,6 +1372,8 @@ pub async fn refresh_entity_based_on_history<T: ConnectionTrait>(
                 .total_cost
                 .map(|cost_u256| BigDecimal::from_str(&cost_u256.to_string()))
                 .transpose()?),
+            created_at_block_number: Set(created_at_block_number),
+            last_updated_at_block_number: Set(Some(latest_entry.block_number.try_into()?)),
         };
         golem_base_entities::Entity::insert(entity)
             .on_conflict(
@@ -676,6 +1387,7 @@ pub async fn refresh_entity_based_on_history<T: ConnectionTrait>(
     } else {
         drop_entity(db, key).await?;
     }
+    super::search::refresh_entity_search(db, key).await?;
 
     Ok(())
 }
@@ -729,6 +1441,28 @@ pub async fn batch_queue_reindex<T: ConnectionTrait>(db: &T, keys: Vec<EntityKey
     Ok(())
 }
 
+/// Returns the number of queued entities.
+#[instrument(skip(db))]
+pub async fn queue_reindex<T: ConnectionTrait>(db: &T, scope: ReindexScope) -> Result<u64> {
+    let (query, values): (_, Vec<sea_orm::Value>) = match scope {
+        ReindexScope::Entity(key) => (sql::QUEUE_REINDEX_ENTITY, vec![key.as_slice().into()]),
+        ReindexScope::Owner(owner) => (sql::QUEUE_REINDEX_OWNER, vec![owner.as_slice().into()]),
+        ReindexScope::Blocks { from, to } => (
+            sql::QUEUE_REINDEX_BLOCK_RANGE,
+            vec![i64::try_from(from)?.into(), i64::try_from(to)?.into()],
+        ),
+    };
+    let res = db
+        .execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            query,
+            values,
+        ))
+        .await
+        .context("Failed to queue reindex")?;
+    Ok(res.rows_affected())
+}
+
 #[instrument(skip(db))]
 pub async fn stream_entities_to_reindex<T: StreamTrait + ConnectionTrait>(
     db: &T,
@@ -751,16 +1485,3 @@ pub async fn stream_entities_to_reindex<T: StreamTrait + ConnectionTrait>(
         }
     }))
 }
-
-#[instrument(skip(db))]
-pub async fn finish_reindex<T: ConnectionTrait>(db: &T, key: EntityKey) -> Result<()> {
-    let key: Vec<u8> = key.as_slice().into();
-    db.execute(Statement::from_sql_and_values(
-        DbBackend::Postgres,
-        sql::FINISH_REINDEX,
-        [key.into()],
-    ))
-    .await?;
-
-    Ok(())
-}
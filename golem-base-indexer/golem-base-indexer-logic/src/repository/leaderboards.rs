@@ -4,12 +4,13 @@ use crate::{
     types::{
         CurrencyAmount, EntityWithExpTimestamp, LeaderboardBiggestSpendersItem,
         LeaderboardDataOwnedItem, LeaderboardEffectivelyLargestEntitiesItem,
-        LeaderboardEntitiesCreatedItem, LeaderboardEntitiesOwnedItem,
-        LeaderboardLargestEntitiesItem, LeaderboardTopAccountsItem, PaginationMetadata,
-        PaginationParams,
+        LeaderboardEntitiesCreatedItem, LeaderboardEntitiesOwnedItem, LeaderboardFilter,
+        LeaderboardLargestEntitiesItem, LeaderboardTopAccountsItem, LeaderboardWindow,
+        PaginationMetadata, PaginationParams,
     },
 };
 use anyhow::{anyhow, Context, Result};
+use chrono::Utc;
 use golem_base_indexer_entity::{
     golem_base_entities, sea_orm_active_enums::GolemBaseEntityStatusType,
 };
@@ -159,17 +160,62 @@ impl TryFrom<DbLeaderboardEffectivelyLargestEntitiesItem>
     }
 }
 
+#[derive(Debug, FromQueryResult)]
+struct DbBlockRange {
+    from_block: Option<i64>,
+    to_block: Option<i64>,
+}
+
+/// Time bounds of the window, as values for the hourly rollup queries.
+fn window_bounds(window: LeaderboardWindow) -> [Value; 2] {
+    let (from, to) = window.bounds(Utc::now());
+    [from.naive_utc().into(), to.naive_utc().into()]
+}
+
+/// Range of blocks produced within the window, as values for queries filtering
+/// entities by their creation block. Both are null when not filtering.
+async fn window_block_range<T: ConnectionTrait>(
+    db: &T,
+    window: Option<LeaderboardWindow>,
+) -> Result<[Value; 2]> {
+    let Some(window) = window else {
+        return Ok([Option::<i64>::None.into(), Option::<i64>::None.into()]);
+    };
+    let range = DbBlockRange::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        sql::BLOCK_RANGE_BETWEEN_TIMESTAMPS,
+        window_bounds(window),
+    ))
+    .one(db)
+    .await
+    .context("Failed to get blocks within leaderboard window")?
+    .expect("Block range will always return a row");
+
+    Ok(match (range.from_block, range.to_block) {
+        (Some(from), Some(to)) => [Some(from).into(), Some(to).into()],
+        // no blocks in the window, so nothing was created within it
+        _ => [Some(1i64).into(), Some(0i64).into()],
+    })
+}
+
 #[instrument(skip(db))]
 pub async fn leaderboard_biggest_spenders<T: ConnectionTrait>(
     db: &T,
-    pagination: PaginationParams,
+    filter: LeaderboardFilter,
 ) -> Result<(Vec<LeaderboardBiggestSpendersItem>, PaginationMetadata)> {
-    let stmt = Statement::from_string(db.get_database_backend(), sql::LEADERBOARD_BIGGEST_SPENDERS);
+    let stmt = match filter.window {
+        Some(window) => Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            sql::LEADERBOARD_BIGGEST_SPENDERS_IN_WINDOW,
+            window_bounds(window),
+        ),
+        None => Statement::from_string(DbBackend::Postgres, sql::LEADERBOARD_BIGGEST_SPENDERS),
+    };
 
     let paginator =
-        DbBiggestSpendersItem::find_by_statement(stmt).paginate(db, pagination.page_size);
+        DbBiggestSpendersItem::find_by_statement(stmt).paginate(db, filter.pagination.page_size);
 
-    paginate_try_from(paginator, pagination)
+    paginate_try_from(paginator, filter.pagination)
         .await
         .context("Failed to fetch biggest spenders")
 }
@@ -193,64 +239,80 @@ pub async fn leaderboard_top_accounts<T: ConnectionTrait>(
 #[instrument(skip(db))]
 pub async fn leaderboard_entities_created<T: ConnectionTrait>(
     db: &T,
-    filter: PaginationParams,
+    filter: LeaderboardFilter,
 ) -> Result<(Vec<LeaderboardEntitiesCreatedItem>, PaginationMetadata)> {
-    let paginator =
-        DbLeaderboardEntitiesCreatedItem::find_by_statement(Statement::from_sql_and_values(
-            db.get_database_backend(),
-            sql::LEADERBOARD_ENTITIES_CREATED,
-            [],
-        ))
-        .paginate(db, filter.page_size);
+    let stmt = match filter.window {
+        Some(window) => Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            sql::LEADERBOARD_ENTITIES_CREATED_IN_WINDOW,
+            window_bounds(window),
+        ),
+        None => Statement::from_string(DbBackend::Postgres, sql::LEADERBOARD_ENTITIES_CREATED),
+    };
+    let paginator = DbLeaderboardEntitiesCreatedItem::find_by_statement(stmt)
+        .paginate(db, filter.pagination.page_size);
 
-    paginate_try_from(paginator, filter).await
+    paginate_try_from(paginator, filter.pagination).await
 }
 
 #[instrument(skip(db))]
 pub async fn leaderboard_entities_owned<T: ConnectionTrait>(
     db: &T,
-    filter: PaginationParams,
+    filter: LeaderboardFilter,
 ) -> Result<(Vec<LeaderboardEntitiesOwnedItem>, PaginationMetadata)> {
-    let paginator = DbLeaderboardEntitiesOwnedItem::find_by_statement(
-        Statement::from_sql_and_values(DbBackend::Postgres, sql::LEADERBOARD_ENTITIES_OWNED, []),
-    )
-    .paginate(db, filter.page_size);
+    let stmt = match filter.window {
+        Some(window) => Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            sql::LEADERBOARD_ENTITIES_OWNED_IN_WINDOW,
+            window_bounds(window),
+        ),
+        None => Statement::from_string(DbBackend::Postgres, sql::LEADERBOARD_ENTITIES_OWNED),
+    };
+    let paginator = DbLeaderboardEntitiesOwnedItem::find_by_statement(stmt)
+        .paginate(db, filter.pagination.page_size);
 
-    paginate_try_from(paginator, filter).await
+    paginate_try_from(paginator, filter.pagination).await
 }
 
 #[instrument(skip(db))]
 pub async fn leaderboard_data_owned<T: ConnectionTrait>(
     db: &T,
-    filter: PaginationParams,
+    filter: LeaderboardFilter,
 ) -> Result<(Vec<LeaderboardDataOwnedItem>, PaginationMetadata)> {
-    let paginator = DbLeaderboardDataOwnedItem::find_by_statement(Statement::from_sql_and_values(
-        DbBackend::Postgres,
-        sql::LEADERBOARD_DATA_OWNED,
-        [],
-    ))
-    .paginate(db, filter.page_size);
+    let stmt = match filter.window {
+        Some(window) => Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            sql::LEADERBOARD_DATA_OWNED_IN_WINDOW,
+            window_bounds(window),
+        ),
+        None => Statement::from_string(DbBackend::Postgres, sql::LEADERBOARD_DATA_OWNED),
+    };
+    let paginator = DbLeaderboardDataOwnedItem::find_by_statement(stmt)
+        .paginate(db, filter.pagination.page_size);
 
-    paginate_try_from(paginator, filter).await
+    paginate_try_from(paginator, filter.pagination).await
 }
 
 #[instrument(skip(db))]
 pub async fn leaderboard_largest_entities<T: ConnectionTrait>(
     db: &T,
-    filter: PaginationParams,
+    filter: LeaderboardFilter,
 ) -> Result<(Vec<LeaderboardLargestEntitiesItem>, PaginationMetadata)> {
-    let paginator = DbLeaderboardLargestEntitiesItem::find_by_statement(
-        Statement::from_sql_and_values(DbBackend::Postgres, sql::LEADERBOARD_LARGEST_ENTITIES, []),
-    )
-    .paginate(db, filter.page_size);
+    let paginator =
+        DbLeaderboardLargestEntitiesItem::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            sql::LEADERBOARD_LARGEST_ENTITIES,
+            window_block_range(db, filter.window).await?,
+        ))
+        .paginate(db, filter.pagination.page_size);
 
-    paginate_try_from(paginator, filter).await
+    paginate_try_from(paginator, filter.pagination).await
 }
 
 #[instrument(skip(db))]
 pub async fn leaderboard_effectively_largest_entities<T: ConnectionTrait>(
     db: &T,
-    filter: PaginationParams,
+    filter: LeaderboardFilter,
 ) -> Result<(
     Vec<LeaderboardEffectivelyLargestEntitiesItem>,
     PaginationMetadata,
@@ -259,32 +321,35 @@ pub async fn leaderboard_effectively_largest_entities<T: ConnectionTrait>(
         Statement::from_sql_and_values(
             DbBackend::Postgres,
             sql::LEADERBOARD_EFFECTIVELY_LARGEST_ENTITIES,
-            [],
+            window_block_range(db, filter.window).await?,
         ),
     )
-    .paginate(db, filter.page_size);
+    .paginate(db, filter.pagination.page_size);
 
-    paginate_try_from(paginator, filter).await
+    paginate_try_from(paginator, filter.pagination).await
 }
 
-// NOTE: This leaderboard does not use materialized view and queries `golem_base_entities` and
-// `blocks` directly. If performance becomes an issue this can possibly be refactored for some
-// improvement.
+// NOTE: This leaderboard queries `golem_base_entities` and `blocks` directly. If performance
+// becomes an issue this can possibly be refactored for some improvement.
 #[instrument(skip(db))]
 pub async fn leaderboard_entities_by_btl<T: ConnectionTrait>(
     db: &T,
-    filter: PaginationParams,
+    filter: LeaderboardFilter,
 ) -> Result<(Vec<EntityWithExpTimestamp>, PaginationMetadata)> {
-    let paginator = golem_base_entities::Entity::find()
+    let mut query = golem_base_entities::Entity::find()
         .filter(golem_base_entities::Column::Status.eq(GolemBaseEntityStatusType::Active))
-        .order_by_desc(golem_base_entities::Column::ExpiresAtBlockNumber)
-        .paginate(db, filter.page_size);
+        .order_by_desc(golem_base_entities::Column::ExpiresAtBlockNumber);
+    if filter.window.is_some() {
+        let [from, to] = window_block_range(db, filter.window).await?;
+        query = query.filter(golem_base_entities::Column::CreatedAtBlockNumber.between(from, to));
+    }
+    let paginator = query.paginate(db, filter.pagination.page_size);
 
     let reference_block = super::blockscout::get_current_block(db)
         .await?
         .ok_or(anyhow!("No blocks indexed yet"))?;
 
-    let (entities, pagination_metadata) = paginate(paginator, filter).await?;
+    let (entities, pagination_metadata) = paginate(paginator, filter.pagination).await?;
 
     Ok((
         entities
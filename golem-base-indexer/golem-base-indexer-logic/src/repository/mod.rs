@@ -2,10 +2,16 @@ pub mod address;
 pub mod attributes;
 pub mod block;
 pub mod blockscout;
+pub mod control;
 pub mod entities;
 pub mod leaderboards;
 pub mod logs;
 pub mod operations;
+pub mod queues;
+pub mod search;
 mod sql;
+pub mod status;
+pub mod subscriptions;
 pub mod timeseries;
 pub mod transactions;
+pub mod webhooks;
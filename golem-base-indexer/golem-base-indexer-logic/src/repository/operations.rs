@@ -5,20 +5,21 @@ use golem_base_indexer_entity::{
 };
 use sea_orm::{
     prelude::*,
+    sea_query::{Func, Order, SimpleExpr},
     ActiveValue::{NotSet, Set},
-    DbBackend, FromQueryResult, QueryOrder, QuerySelect, Statement,
+    Condition, DbBackend, FromQueryResult, QueryOrder, QuerySelect, QueryTrait, Statement,
 };
 use std::str::FromStr;
 use tracing::instrument;
 
 use crate::{
     arkiv::{block_timestamp, block_timestamp_sec},
-    pagination::paginate_try_from,
+    pagination::paginate_cursor,
     types::{
-        Block, BlockNumberOrHashFilter, CurrencyAmount, EntityKey, FullOperationIndex,
-        ListOperationsFilter, Operation, OperationData, OperationMetadata, OperationType,
-        OperationView, OperationsCount, OperationsFilter, PaginationMetadata, PaginationParams,
-        TxHash,
+        Block, BlockNumber, BlockNumberOrHashFilter, CurrencyAmount, CursorPaginationMetadata,
+        CursorPaginationParams, EntityKey, FullOperationIndex, ListOperationsFilter, Operation,
+        OperationCursor, OperationData, OperationMetadata, OperationSortField, OperationType,
+        OperationView, OperationsCount, OperationsFilter, OperationsSort, SortDirection, TxHash,
     },
 };
 
@@ -51,9 +52,10 @@ enum DbBlockNumberOrHash {
 
 #[derive(Debug)]
 struct DbListOperationsFilter {
-    pub pagination: PaginationParams,
+    pub pagination: CursorPaginationParams<OperationCursor>,
     pub operation_type: Option<GolemBaseOperationType>,
     pub operations_filter: DbOperationsFilter,
+    pub sort: OperationsSort,
 }
 
 #[derive(Debug)]
@@ -62,6 +64,16 @@ struct DbOperationsFilter {
     pub sender: Option<Vec<u8>>,
     pub block_number_or_hash: Option<DbBlockNumberOrHash>,
     pub transaction_hash: Option<Vec<u8>>,
+    pub max_block_number: Option<i64>,
+    pub recipient: Option<Vec<u8>>,
+    pub content_type: Option<String>,
+    pub operation_types: Vec<GolemBaseOperationType>,
+    pub from_block: Option<i64>,
+    pub to_block: Option<i64>,
+    pub min_cost: Option<BigDecimal>,
+    pub max_cost: Option<BigDecimal>,
+    pub from_timestamp: Option<DateTime>,
+    pub to_timestamp: Option<DateTime>,
 }
 
 #[derive(Debug, FromQueryResult)]
@@ -117,6 +129,7 @@ impl TryFrom<ListOperationsFilter> for DbListOperationsFilter {
             pagination: v.pagination,
             operation_type: v.operation_type.map(|op| op.into()),
             operations_filter: v.operations_filter.try_into()?,
+            sort: v.sort,
         })
     }
 }
@@ -130,6 +143,22 @@ impl TryFrom<OperationsFilter> for DbOperationsFilter {
             sender: v.sender.map(|s| s.as_slice().into()),
             block_number_or_hash: v.block_number_or_hash.map(TryInto::try_into).transpose()?,
             transaction_hash: v.transaction_hash.map(|hash| hash.as_slice().into()),
+            max_block_number: v.max_block_number.map(TryInto::try_into).transpose()?,
+            recipient: v.recipient.map(|r| r.as_slice().into()),
+            content_type: v.content_type,
+            operation_types: v.operation_types.into_iter().map(Into::into).collect(),
+            from_block: v.from_block.map(TryInto::try_into).transpose()?,
+            to_block: v.to_block.map(TryInto::try_into).transpose()?,
+            min_cost: v
+                .min_cost
+                .map(|cost| BigDecimal::from_str(&cost.to_string()))
+                .transpose()?,
+            max_cost: v
+                .max_cost
+                .map(|cost| BigDecimal::from_str(&cost.to_string()))
+                .transpose()?,
+            from_timestamp: v.from_timestamp.map(|ts| ts.naive_utc()),
+            to_timestamp: v.to_timestamp.map(|ts| ts.naive_utc()),
         })
     }
 }
@@ -327,14 +356,101 @@ fn filtered_operations(filter: DbOperationsFilter) -> Select<golem_base_operatio
     if let Some(transaction_hash) = filter.transaction_hash {
         q = q.filter(golem_base_operations::Column::TransactionHash.eq(transaction_hash));
     }
+    if let Some(max_block_number) = filter.max_block_number {
+        q = q.filter(golem_base_operations::Column::BlockNumber.lte(max_block_number));
+    }
+    if let Some(recipient) = filter.recipient {
+        q = q.filter(golem_base_operations::Column::Recipient.eq(recipient));
+    }
+    if let Some(content_type) = filter.content_type {
+        q = q.filter(golem_base_operations::Column::ContentType.eq(content_type));
+    }
+    if !filter.operation_types.is_empty() {
+        q = q.filter(golem_base_operations::Column::Operation.is_in(filter.operation_types));
+    }
+    if let Some(from_block) = filter.from_block {
+        q = q.filter(golem_base_operations::Column::BlockNumber.gte(from_block));
+    }
+    if let Some(to_block) = filter.to_block {
+        q = q.filter(golem_base_operations::Column::BlockNumber.lte(to_block));
+    }
+    if let Some(min_cost) = filter.min_cost {
+        q = q.filter(Expr::expr(cost_expr()).gte(min_cost));
+    }
+    if let Some(max_cost) = filter.max_cost {
+        q = q.filter(Expr::expr(cost_expr()).lte(max_cost));
+    }
+    if filter.from_timestamp.is_some() || filter.to_timestamp.is_some() {
+        let mut blocks = blocks::Entity::find()
+            .select_only()
+            .column(blocks::Column::Hash);
+        if let Some(from) = filter.from_timestamp {
+            blocks = blocks.filter(blocks::Column::Timestamp.gte(from));
+        }
+        if let Some(to) = filter.to_timestamp {
+            blocks = blocks.filter(blocks::Column::Timestamp.lt(to));
+        }
+        q = q.filter(golem_base_operations::Column::BlockHash.in_subquery(blocks.into_query()));
+    }
     q
 }
 
+/// Cost operations are sorted and filtered by. Operations without one are
+/// listed as costing nothing, so they are treated the same.
+fn cost_expr() -> SimpleExpr {
+    Func::coalesce([
+        Expr::col((
+            golem_base_operations::Entity,
+            golem_base_operations::Column::Cost,
+        ))
+        .into(),
+        Expr::value(0),
+    ])
+    .into()
+}
+
+/// Operations positioned strictly after the cursor.
+fn after_operation(sort: OperationsSort, cursor: &OperationCursor) -> Condition {
+    use golem_base_operations::{Column, Entity};
+    let position = Expr::tuple([
+        Expr::col((Entity, Column::BlockNumber)).into(),
+        Expr::col((Entity, Column::TxIndex)).into(),
+        Expr::col((Entity, Column::Index)).into(),
+    ]);
+    let index = Expr::tuple([
+        Expr::value(cursor.index.block_number),
+        Expr::value(cursor.index.tx_index),
+        Expr::value(cursor.index.op_index),
+    ]);
+    let after_index = match sort.direction {
+        SortDirection::Asc => position.gt(index),
+        SortDirection::Desc => position.lt(index),
+    };
+    if sort.field == OperationSortField::Time {
+        return Condition::all().add(after_index);
+    }
+
+    let cost = BigDecimal::from_str(&cursor.cost.unwrap_or_default().to_string())
+        .expect("Integers are valid decimals");
+    let after_cost = match sort.direction {
+        SortDirection::Asc => Expr::expr(cost_expr()).gt(cost.clone()),
+        SortDirection::Desc => Expr::expr(cost_expr()).lt(cost.clone()),
+    };
+    Condition::any().add(after_cost).add(
+        Condition::all()
+            .add(Expr::expr(cost_expr()).eq(cost))
+            .add(after_index),
+    )
+}
+
 #[instrument(skip(db))]
 pub async fn list_operations<T: ConnectionTrait>(
     db: &T,
     filter: ListOperationsFilter,
-) -> Result<(Vec<OperationView>, PaginationMetadata)> {
+) -> Result<(
+    Vec<OperationView>,
+    CursorPaginationMetadata<OperationCursor>,
+)> {
     let blocks_joined = matches!(
         filter.operations_filter.block_number_or_hash,
         Some(BlockNumberOrHashFilter::Number(_))
@@ -351,15 +467,39 @@ pub async fn list_operations<T: ConnectionTrait>(
     } else {
         query.inner_join(blocks::Entity)
     };
-    let query_with_blocks = query.select_also(blocks::Entity);
-
-    let paginator = query_with_blocks
-        .order_by_asc(golem_base_operations::Column::BlockNumber)
-        .order_by_asc(golem_base_operations::Column::TxIndex)
-        .order_by_asc(golem_base_operations::Column::Index)
-        .paginate(db, filter.pagination.page_size);
-
-    paginate_try_from(paginator, filter.pagination).await
+    let sort = filter.sort;
+    let order = match sort.direction {
+        SortDirection::Asc => Order::Asc,
+        SortDirection::Desc => Order::Desc,
+    };
+    let mut query_with_blocks = query.select_also(blocks::Entity);
+    if sort.field == OperationSortField::Cost {
+        query_with_blocks = query_with_blocks.order_by(cost_expr(), order.clone());
+    }
+    let query_with_blocks = query_with_blocks
+        .order_by(golem_base_operations::Column::BlockNumber, order.clone())
+        .order_by(golem_base_operations::Column::TxIndex, order.clone())
+        .order_by(golem_base_operations::Column::Index, order);
+
+    paginate_cursor(
+        db,
+        query_with_blocks,
+        filter.pagination,
+        |cursor| after_operation(sort, cursor),
+        OperationView::try_from,
+        |view| OperationCursor {
+            cost: match sort.field {
+                OperationSortField::Time => None,
+                OperationSortField::Cost => Some(view.op.metadata.cost.unwrap_or_default()),
+            },
+            index: FullOperationIndex {
+                block_number: view.op.metadata.block_number,
+                tx_index: view.op.metadata.tx_index,
+                op_index: view.op.metadata.index,
+            },
+        },
+    )
+    .await
 }
 
 impl TryFrom<(golem_base_operations::Model, Option<blocks::Model>)> for OperationView {
@@ -488,9 +628,44 @@ pub async fn delete_by_tx_hash<T: ConnectionTrait>(db: &T, tx_hash: TxHash) -> R
         .exec(db)
         .await?;
     golem_base_operations::Entity::delete_many()
-        .filter(golem_base_operations::Column::TransactionHash.eq(db_tx_hash))
+        .filter(golem_base_operations::Column::TransactionHash.eq(db_tx_hash.clone()))
         .exec(db)
         .await?;
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        sql::DELETE_UNDECODABLE_TRANSACTION,
+        [db_tx_hash.into()],
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Deletes operations, their annotations and history entries from the given
+/// block onwards and queues the affected entities for reindexing.
+#[instrument(skip(db))]
+pub async fn delete_since_block<T: ConnectionTrait>(
+    db: &T,
+    block_number: BlockNumber,
+) -> Result<()> {
+    let block_number: i64 = block_number.try_into()?;
+    for query in [
+        sql::QUEUE_REINDEX_SINCE_BLOCK,
+        sql::DELETE_STRING_ANNOTATIONS_SINCE_BLOCK,
+        sql::DELETE_NUMERIC_ANNOTATIONS_SINCE_BLOCK,
+        sql::DELETE_HISTORY_SINCE_BLOCK,
+        sql::DELETE_OPERATIONS_SINCE_BLOCK,
+        sql::DELETE_UNDECODABLE_TRANSACTIONS_SINCE_BLOCK,
+        sql::DELETE_TRANSACTION_FEES_SINCE_BLOCK,
+    ] {
+        db.execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            query,
+            [block_number.into()],
+        ))
+        .await
+        .context("Failed to roll back operations")?;
+    }
+
     Ok(())
 }
 
@@ -0,0 +1,345 @@
+use alloy_primitives::B256;
+use anyhow::{Context, Result};
+use sea_orm::{prelude::*, DbBackend, FromQueryResult, Statement};
+use tracing::instrument;
+
+use crate::{
+    pagination::paginate_try_from,
+    repository::sql,
+    types::{
+        LogIndex, PaginationMetadata, PaginationParams, ProcessingQueue, QuarantinedItem,
+        QueueDepths, QueueItem, TxHash,
+    },
+    RetrySettings,
+};
+
+#[derive(FromQueryResult)]
+struct DbQuarantined {
+    quarantined: bool,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct DbQuarantinedItem {
+    queue: String,
+    key: Vec<u8>,
+    block_hash: Option<Vec<u8>>,
+    block_number: Option<i64>,
+    index: Option<i64>,
+    attempts: i32,
+    last_error: Option<String>,
+    quarantined_at: chrono::NaiveDateTime,
+}
+
+#[derive(FromQueryResult)]
+struct DbLocked {
+    locked: bool,
+}
+
+#[derive(FromQueryResult)]
+struct DbTxHash {
+    hash: Vec<u8>,
+}
+
+#[derive(FromQueryResult)]
+struct DbCount {
+    count: i64,
+}
+
+#[derive(FromQueryResult)]
+struct DbQueueDepths {
+    pending_transactions: i64,
+    pending_transaction_cleanups: i64,
+    pending_logs: i64,
+    pending_logs_events: i64,
+    entities_to_reindex: i64,
+}
+
+impl TryFrom<DbQuarantinedItem> for QuarantinedItem {
+    type Error = anyhow::Error;
+
+    fn try_from(value: DbQuarantinedItem) -> Result<Self> {
+        Ok(Self {
+            queue: value.queue.parse()?,
+            key: value.key.as_slice().try_into()?,
+            block_hash: value
+                .block_hash
+                .map(|v| v.as_slice().try_into())
+                .transpose()?,
+            block_number: value.block_number.map(TryInto::try_into).transpose()?,
+            index: value.index.map(TryInto::try_into).transpose()?,
+            attempts: value.attempts.try_into()?,
+            last_error: value.last_error,
+            quarantined_at: value.quarantined_at.and_utc(),
+        })
+    }
+}
+
+fn log_values(log: &LogIndex) -> Result<Vec<Value>> {
+    Ok(vec![
+        log.transaction_hash.as_slice().into(),
+        log.block_hash.as_slice().into(),
+        i64::try_from(log.index)?.into(),
+    ])
+}
+
+/// Namespace of the advisory locks sharding reindexes between indexer replicas.
+const REINDEX_LOCK_NAMESPACE: i32 = 0x6762_0002;
+
+/// Namespace of the advisory lock reorg rollbacks take to keep tx and log processing
+/// out. Only the indexer takes it, so Blockscout writes to the queues aren't blocked.
+const PROCESSING_LOCK_NAMESPACE: i32 = 0x6762_0003;
+
+/// Claims the item for the current transaction, so that other indexer replicas skip it
+/// until the transaction ends. Returns false when the item is already taken, or gone.
+/// A tx cleanup also waits for a replica still processing the tx to finish first.
+#[instrument(skip(db))]
+pub async fn claim<T: ConnectionTrait>(db: &T, item: &QueueItem) -> Result<bool> {
+    if !matches!(item, QueueItem::Reindex(_)) {
+        advisory_xact_lock(
+            db,
+            sql::ADVISORY_XACT_LOCK_SHARED,
+            PROCESSING_LOCK_NAMESPACE,
+        )
+        .await?;
+    }
+    let (query, values): (_, Vec<Value>) = match item {
+        QueueItem::Transaction(hash) => (sql::CLAIM_TRANSACTION, vec![hash.as_slice().into()]),
+        QueueItem::TxCleanup(hash) => {
+            (sql::CLAIM_TRANSACTION_CLEANUP, vec![hash.as_slice().into()])
+        }
+        QueueItem::Log(log) => (sql::CLAIM_LOG, log_values(log)?),
+        QueueItem::LogEvent(log) => (sql::CLAIM_LOG_EVENT, log_values(log)?),
+        QueueItem::Reindex(key) => {
+            // every queued row of the entity is claimed at once, the lock keeps
+            // two replicas from rebuilding the same entity concurrently
+            let shard = i32::from_be_bytes(key[..4].try_into()?);
+            if !try_advisory_xact_lock(db, REINDEX_LOCK_NAMESPACE, shard).await? {
+                return Ok(false);
+            }
+            (sql::CLAIM_REINDEX, vec![key.as_slice().into()])
+        }
+    };
+
+    let res = db
+        .query_all(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            query,
+            values,
+        ))
+        .await
+        .context("Failed to claim queue item")?;
+    if res.is_empty() {
+        return Ok(false);
+    }
+
+    if let QueueItem::TxCleanup(hash) = item {
+        db.query_all(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            sql::AWAIT_TRANSACTION_PROCESSING,
+            [hash.as_slice().into()],
+        ))
+        .await
+        .context("Failed to wait for tx processing")?;
+    }
+    Ok(true)
+}
+
+/// Waits for txs and logs claimed by other replicas and keeps new ones from being
+/// claimed until the current transaction ends.
+#[instrument(skip(db))]
+pub async fn lock_processing<T: ConnectionTrait>(db: &T) -> Result<()> {
+    advisory_xact_lock(db, sql::ADVISORY_XACT_LOCK, PROCESSING_LOCK_NAMESPACE).await
+}
+
+/// Claims up to `limit` queued tx fees for the current transaction, skipping the ones
+/// other replicas hold. Reorg rollbacks wait for them like for the other queues.
+#[instrument(skip(db))]
+pub async fn claim_transaction_fees<T: ConnectionTrait>(db: &T, limit: u64) -> Result<Vec<TxHash>> {
+    advisory_xact_lock(
+        db,
+        sql::ADVISORY_XACT_LOCK_SHARED,
+        PROCESSING_LOCK_NAMESPACE,
+    )
+    .await?;
+    let limit: i64 = limit.try_into()?;
+    DbTxHash::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        sql::CLAIM_TRANSACTION_FEES,
+        [limit.into()],
+    ))
+    .all(db)
+    .await
+    .context("Failed to claim tx fees")?
+    .into_iter()
+    .map(|v| Ok(v.hash.as_slice().try_into()?))
+    .collect()
+}
+
+async fn advisory_xact_lock<T: ConnectionTrait>(db: &T, query: &str, namespace: i32) -> Result<()> {
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        query,
+        [namespace.into(), 0.into()],
+    ))
+    .await
+    .context("Failed to take advisory lock")?;
+    Ok(())
+}
+
+async fn try_advisory_xact_lock<T: ConnectionTrait>(
+    db: &T,
+    namespace: i32,
+    key: i32,
+) -> Result<bool> {
+    let res = DbLocked::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        sql::TRY_ADVISORY_XACT_LOCK,
+        [namespace.into(), key.into()],
+    ))
+    .one(db)
+    .await
+    .context("Failed to take advisory lock")?;
+    Ok(res.is_some_and(|v| v.locked))
+}
+
+/// Records a failed attempt and schedules the next one with exponential backoff.
+/// Returns whether the item ran out of attempts and got quarantined.
+#[instrument(skip(db, error))]
+pub async fn record_failure<T: ConnectionTrait>(
+    db: &T,
+    item: &QueueItem,
+    error: String,
+    retries: &RetrySettings,
+) -> Result<bool> {
+    let (query, mut values): (_, Vec<Value>) = match item {
+        QueueItem::Transaction(hash) => (
+            sql::FAIL_TRANSACTION_PROCESSING,
+            vec![hash.as_slice().into()],
+        ),
+        QueueItem::TxCleanup(hash) => (sql::FAIL_TRANSACTION_CLEANUP, vec![hash.as_slice().into()]),
+        QueueItem::Log(log) => (sql::FAIL_LOG_PROCESSING, log_values(log)?),
+        QueueItem::LogEvent(log) => (sql::FAIL_LOG_EVENT_PROCESSING, log_values(log)?),
+        QueueItem::Reindex(key) => (sql::FAIL_REINDEX, vec![key.as_slice().into()]),
+    };
+    values.extend([
+        error.into(),
+        retries.initial_backoff.as_secs_f64().into(),
+        retries.max_backoff.as_secs_f64().into(),
+        retries.max_attempts.into(),
+    ]);
+
+    let res = DbQuarantined::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        query,
+        values,
+    ))
+    .all(db)
+    .await
+    .context("Failed to record processing failure")?;
+
+    Ok(res.iter().any(|v| v.quarantined))
+}
+
+#[instrument(skip(db))]
+pub async fn list_quarantined<T: ConnectionTrait>(
+    db: &T,
+    queue: Option<ProcessingQueue>,
+    pagination: PaginationParams,
+) -> Result<(Vec<QuarantinedItem>, PaginationMetadata)> {
+    let paginator = DbQuarantinedItem::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        sql::QUARANTINED_ITEMS,
+        [
+            queue.map(|v| v.as_str().to_string()).into(),
+            Option::<Vec<u8>>::None.into(),
+        ],
+    ))
+    .paginate(db, pagination.page_size);
+
+    paginate_try_from(paginator, pagination)
+        .await
+        .context("Failed to list quarantined items")
+}
+
+/// Quarantined items of one tx, or of one entity for reindexes.
+#[instrument(skip(db))]
+pub async fn get_quarantined<T: ConnectionTrait>(
+    db: &T,
+    queue: ProcessingQueue,
+    key: B256,
+) -> Result<Vec<QuarantinedItem>> {
+    let key: Vec<u8> = key.as_slice().into();
+    DbQuarantinedItem::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        sql::QUARANTINED_ITEMS,
+        [queue.as_str().into(), key.into()],
+    ))
+    .all(db)
+    .await
+    .context("Failed to get quarantined items")?
+    .into_iter()
+    .map(TryInto::try_into)
+    .collect()
+}
+
+#[instrument(skip(db))]
+pub async fn count_quarantined<T: ConnectionTrait>(db: &T) -> Result<u64> {
+    DbCount::find_by_statement(Statement::from_string(
+        DbBackend::Postgres,
+        sql::COUNT_QUARANTINED_ITEMS,
+    ))
+    .one(db)
+    .await
+    .context("Failed to count quarantined items")?
+    .map(|v| v.count.try_into())
+    .transpose()?
+    .ok_or(anyhow::anyhow!("Count returned no rows"))
+}
+
+/// Number of items in each processing queue, quarantined ones included.
+#[instrument(skip(db))]
+pub async fn queue_depths<T: ConnectionTrait>(db: &T) -> Result<QueueDepths> {
+    let depths = DbQueueDepths::find_by_statement(Statement::from_string(
+        DbBackend::Postgres,
+        sql::QUEUE_DEPTHS,
+    ))
+    .one(db)
+    .await
+    .context("Failed to get queue depths")?
+    .ok_or(anyhow::anyhow!("Queue depths returned no rows"))?;
+
+    Ok(QueueDepths {
+        pending_transactions: depths.pending_transactions.try_into()?,
+        pending_transaction_cleanups: depths.pending_transaction_cleanups.try_into()?,
+        pending_logs: depths.pending_logs.try_into()?,
+        pending_logs_events: depths.pending_logs_events.try_into()?,
+        entities_to_reindex: depths.entities_to_reindex.try_into()?,
+        quarantined: count_quarantined(db).await?,
+    })
+}
+
+/// Gives quarantined items a fresh retry budget. Returns the number of requeued items.
+#[instrument(skip(db))]
+pub async fn requeue_quarantined<T: ConnectionTrait>(
+    db: &T,
+    queue: ProcessingQueue,
+    key: B256,
+) -> Result<u64> {
+    let query = match queue {
+        ProcessingQueue::Transactions => sql::REQUEUE_QUARANTINED_TRANSACTION,
+        ProcessingQueue::TransactionCleanups => sql::REQUEUE_QUARANTINED_TRANSACTION_CLEANUP,
+        ProcessingQueue::Logs => sql::REQUEUE_QUARANTINED_LOGS,
+        ProcessingQueue::LogsEvents => sql::REQUEUE_QUARANTINED_LOGS_EVENTS,
+        ProcessingQueue::Reindexes => sql::REQUEUE_QUARANTINED_REINDEX,
+    };
+    let key: Vec<u8> = key.as_slice().into();
+    let res = db
+        .execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            query,
+            [key.into()],
+        ))
+        .await
+        .context("Failed to requeue quarantined items")?;
+    Ok(res.rows_affected())
+}
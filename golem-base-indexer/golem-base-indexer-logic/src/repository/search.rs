@@ -0,0 +1,61 @@
+use anyhow::{Context, Result};
+use sea_orm::{prelude::*, DbBackend, FromQueryResult, Statement};
+use tracing::instrument;
+
+use crate::{
+    pagination::paginate_try_from,
+    repository::sql,
+    types::{EntityKey, PaginationMetadata, SearchFilter, SearchResult},
+};
+
+#[derive(Debug, FromQueryResult)]
+struct DbSearchResult {
+    entity_key: Vec<u8>,
+    owner: Option<Vec<u8>>,
+    content_type: Option<String>,
+    rank: f64,
+    snippet: String,
+}
+
+impl TryFrom<DbSearchResult> for SearchResult {
+    type Error = anyhow::Error;
+
+    fn try_from(value: DbSearchResult) -> Result<Self> {
+        Ok(Self {
+            entity_key: value.entity_key.as_slice().try_into()?,
+            owner: value.owner.map(|v| v.as_slice().try_into()).transpose()?,
+            content_type: value.content_type,
+            rank: value.rank,
+            snippet: value.snippet,
+        })
+    }
+}
+
+/// Brings the searchable text of the entity in line with its current row and
+/// active string annotations. Entities that aren't active are not searchable.
+#[instrument(skip(db))]
+pub async fn refresh_entity_search<T: ConnectionTrait>(db: &T, key: EntityKey) -> Result<()> {
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        sql::REFRESH_ENTITY_SEARCH,
+        [key.as_slice().into()],
+    ))
+    .await
+    .context("Failed to refresh entity search")?;
+    Ok(())
+}
+
+#[instrument(skip(db))]
+pub async fn search_entities<T: ConnectionTrait>(
+    db: &T,
+    filter: SearchFilter,
+) -> Result<(Vec<SearchResult>, PaginationMetadata)> {
+    let paginator = DbSearchResult::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        sql::SEARCH_ENTITIES,
+        [filter.query.into()],
+    ))
+    .paginate(db, filter.pagination.page_size);
+
+    paginate_try_from(paginator, filter.pagination).await
+}
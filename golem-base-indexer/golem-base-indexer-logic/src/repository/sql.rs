@@ -39,6 +39,9 @@ WITH pendings AS MATERIALIZED (
     block_number
   FROM
     golem_base_pending_logs_events
+  WHERE
+    quarantined_at IS NULL
+    AND next_attempt_at <= now()
   ORDER BY
     block_number ASC
   LIMIT
@@ -51,7 +54,9 @@ WITH pendings AS MATERIALIZED (
     INNER JOIN transactions ON transactions.hash = output.transaction_hash
     AND transactions.status = 1
   WHERE
-    output.transaction_hash IN (
+    output.quarantined_at IS NULL
+    AND output.next_attempt_at <= now()
+    AND output.transaction_hash IN (
       SELECT
         transaction_hash
       FROM
@@ -126,6 +131,8 @@ from golem_base_pending_logs_operations as pendings
 where
     golem_base_pending_transaction_cleanups is null
     and transactions.status = 1
+    and pendings.quarantined_at is null
+    and pendings.next_attempt_at <= now()
 order by
     pendings.block_number asc,
     pendings.index asc
@@ -142,6 +149,8 @@ where
     and transactions.to_address_hash in ($1, $2) 
     and transactions.status = 1
     and transactions.block_hash is not null
+    and pendings.quarantined_at is null
+    and pendings.next_attempt_at <= now()
 order by
     pendings.block_number asc,
     pendings.index asc
@@ -212,6 +221,41 @@ order by
 limit 1;
 "#;
 
+pub const FIND_ENTITIES_OPERATION_INFO: &str = r#"
+select
+    requested.key,
+    created.index as created_at_operation_index,
+    created_block.hash as created_at_block_hash,
+    created_block.number as created_at_block_number,
+    created_block.timestamp as created_at_timestamp,
+    latest.transaction_hash as updated_at_tx_hash,
+    latest.index as updated_at_operation_index,
+    latest_block.hash as updated_at_block_hash,
+    latest_block.number as updated_at_block_number,
+    latest_block.timestamp as updated_at_timestamp
+from unnest($1::bytea[]) as requested (key)
+left join golem_base_operations created
+    on created.entity_key = requested.key
+    and created.operation = 'create'
+left join blocks created_block
+    on created_block.hash = created.block_hash
+inner join lateral (
+    select o.transaction_hash, o.index, o.block_hash
+    from golem_base_operations o
+    inner join transactions t
+        on t.hash = o.transaction_hash
+    where
+        o.entity_key = requested.key
+    order by
+        t.block_number desc,
+        t.index desc,
+        o.index desc
+    limit 1
+) latest on true
+inner join blocks latest_block
+    on latest_block.hash = latest.block_hash
+"#;
+
 pub const COUNT_ENTITIES_BY_OWNER: &str = r#"
 select
     (select count(*) from golem_base_operations where operation = 'create' and sender = $1) as created_entities,
@@ -273,6 +317,90 @@ where
 group by key, value
 "#;
 
+pub const GET_ENTITIES_STRING_ANNOTATIONS_WITH_RELATIONS: &str = r#"
+select
+    a.entity_key,
+    a.key,
+    a.value,
+    count(*) as related_entities
+from golem_base_string_annotations as a
+join golem_base_string_annotations as related using (key, value)
+where
+    a.active = 't'
+    and related.active = 't'
+    and a.entity_key = any($1)
+group by a.entity_key, key, value
+"#;
+
+pub const GET_ENTITIES_NUMERIC_ANNOTATIONS_WITH_RELATIONS: &str = r#"
+select
+    a.entity_key,
+    a.key,
+    a.value,
+    count(*) as related_entities
+from golem_base_numeric_annotations as a
+join golem_base_numeric_annotations as related using (key, value)
+where
+    a.active = 't'
+    and related.active = 't'
+    and a.entity_key = any($1)
+group by a.entity_key, key, value
+"#;
+
+pub const GET_STRING_ANNOTATIONS_WITH_RELATIONS_AT_BLOCK: &str = r#"
+with active_ops as (
+    select distinct on (entity_key) entity_key, transaction_hash, op_index
+    from golem_base_entity_history
+    where
+        block_number <= $2
+        and operation != 'extend'
+    order by entity_key, block_number desc, tx_index desc, op_index desc
+),
+active as (
+    select a.entity_key, a.key, a.value
+    from golem_base_string_annotations as a
+    join active_ops as op
+        on op.entity_key = a.entity_key
+        and op.transaction_hash = a.operation_tx_hash
+        and op.op_index = a.operation_index
+)
+select
+    a.key,
+    a.value,
+    count(*) as related_entities
+from active as a
+join active as related using (key, value)
+where a.entity_key = $1
+group by key, value
+"#;
+
+pub const GET_NUMERIC_ANNOTATIONS_WITH_RELATIONS_AT_BLOCK: &str = r#"
+with active_ops as (
+    select distinct on (entity_key) entity_key, transaction_hash, op_index
+    from golem_base_entity_history
+    where
+        block_number <= $2
+        and operation != 'extend'
+    order by entity_key, block_number desc, tx_index desc, op_index desc
+),
+active as (
+    select a.entity_key, a.key, a.value
+    from golem_base_numeric_annotations as a
+    join active_ops as op
+        on op.entity_key = a.entity_key
+        and op.transaction_hash = a.operation_tx_hash
+        and op.op_index = a.operation_index
+)
+select
+    a.key,
+    a.value,
+    count(*) as related_entities
+from active as a
+join active as related using (key, value)
+where a.entity_key = $1
+group by key, value
+"#;
+
 pub const TOTAL_STORAGE_USAGE_BY_BLOCK: &str = r#"
 select block_number, storage_usage from golem_base_block_stats where block_number = $1;
 "#;
@@ -387,12 +515,160 @@ FROM golem_base_entities
 WHERE status = 'active';
 "#;
 
+pub const ADDRESS_STORAGE_FORECAST: &str = r#"
+WITH latest_block AS (
+    SELECT number, timestamp
+    FROM blocks
+    WHERE consensus
+    ORDER BY number DESC
+    LIMIT 1
+),
+hourly_expirations AS (
+    SELECT
+        DATE_TRUNC(
+            'hour',
+            latest_block.timestamp
+                + (entities.expires_at_block_number - latest_block.number) * '2 seconds'::INTERVAL
+                + INTERVAL '1 hour'
+        ) AS expires_at,
+        SUM(COALESCE(LENGTH(entities.data), 0)) AS bytes_expiring
+    FROM golem_base_entities entities
+    CROSS JOIN latest_block
+    WHERE entities.owner = $1
+        AND entities.status = 'active'
+        AND entities.expires_at_block_number > latest_block.number
+    GROUP BY 1
+),
+forecast AS (
+    SELECT
+        DATE_TRUNC('hour', NOW() AT TIME ZONE 'UTC')::timestamp AS timestamp,
+        COALESCE(SUM(bytes_expiring), 0)::BIGINT AS total_storage
+    FROM hourly_expirations
+    UNION ALL
+    SELECT
+        expires_at,
+        (SUM(bytes_expiring) OVER (ORDER BY expires_at DESC ROWS UNBOUNDED PRECEDING) - bytes_expiring)::BIGINT
+    FROM hourly_expirations
+    WHERE expires_at > DATE_TRUNC('hour', NOW() AT TIME ZONE 'UTC')
+)
+SELECT timestamp, total_storage
+FROM forecast
+WHERE timestamp <= $2
+ORDER BY timestamp
+"#;
+
+pub const CHART_BREAKDOWN_SERIES: &str = r#"
+WITH valued AS (
+    SELECT
+        hour,
+        value AS series,
+        SUM(CASE WHEN $2 THEN data_bytes_change ELSE entities_change END) AS change
+    FROM golem_base_breakdown_timeseries_hourly
+    WHERE kind = $6 AND key = $7 AND ($1::bytea IS NULL OR address = $1)
+    GROUP BY 1, 2
+),
+everything AS (
+    SELECT
+        hour,
+        SUM(CASE WHEN $2 THEN data_bytes_change ELSE entities_change END) AS change
+    FROM golem_base_address_timeseries_hourly
+    WHERE $1::bytea IS NULL OR address = $1
+    GROUP BY 1
+),
+hourly AS (
+    SELECT hour, series, change FROM valued
+    UNION ALL
+    -- entities without a content type or the annotation
+    SELECT everything.hour, NULL, everything.change - COALESCE(SUM(valued.change), 0)
+    FROM everything
+    LEFT JOIN valued USING (hour)
+    GROUP BY everything.hour, everything.change
+),
+top_series AS (
+    SELECT series
+    FROM hourly
+    WHERE series IS NOT NULL
+    GROUP BY series
+    HAVING SUM(change) > 0
+    ORDER BY SUM(change) DESC, series
+    LIMIT $3
+),
+totals AS (
+    SELECT
+        hour,
+        series,
+        GREATEST(
+            SUM(SUM(change)) OVER (PARTITION BY series ORDER BY hour ROWS UNBOUNDED PRECEDING),
+            0
+        )::BIGINT AS value
+    FROM (
+        SELECT
+            hour,
+            CASE WHEN series IN (SELECT series FROM top_series) THEN series END AS series,
+            change
+        FROM hourly
+    ) AS grouped
+    GROUP BY hour, series
+)
+SELECT hour AS timestamp, series, value
+FROM totals
+WHERE hour >= COALESCE($4::timestamp, '-infinity') AND ($5::timestamp IS NULL OR hour < $5)
+UNION ALL
+(
+    SELECT DISTINCT ON (series) hour, series, value
+    FROM totals
+    WHERE hour < COALESCE($4::timestamp, '-infinity')
+    ORDER BY series, hour DESC
+)
+ORDER BY timestamp
+"#;
+
 pub const ADDRESS_LEADERBOARD_RANKS: &str = r#"
+WITH stats AS (
+    SELECT * FROM golem_base_address_leaderboard_stats WHERE address = $1
+)
 SELECT
-    (SELECT rank FROM golem_base_leaderboard_biggest_spenders WHERE address = $1) AS biggest_spenders,
-    (SELECT rank FROM golem_base_leaderboard_entities_created WHERE address = $1) AS entities_created,
-    (SELECT rank FROM golem_base_leaderboard_entities_owned WHERE address = $1) AS entities_owned,
-    (SELECT rank FROM golem_base_leaderboard_data_owned WHERE address = $1) AS data_owned,
+    (
+        SELECT 1 + (
+            SELECT COUNT(*) FROM golem_base_address_leaderboard_stats other
+            WHERE other.total_fees > stats.total_fees
+                OR (other.total_fees = stats.total_fees AND other.address < stats.address)
+        )
+        FROM stats WHERE stats.total_fees > 0
+    ) AS biggest_spenders,
+    (
+        SELECT 1 + (
+            SELECT COUNT(*) FROM golem_base_address_leaderboard_stats other
+            WHERE other.entities_created_count > stats.entities_created_count
+                OR (
+                    other.entities_created_count = stats.entities_created_count
+                    AND (
+                        other.first_created_at < stats.first_created_at
+                        OR (other.first_created_at = stats.first_created_at AND other.address < stats.address)
+                    )
+                )
+        )
+        FROM stats WHERE stats.entities_created_count > 0
+    ) AS entities_created,
+    (
+        SELECT 1 + (
+            SELECT COUNT(*) FROM golem_base_address_leaderboard_stats other
+            WHERE other.entities_owned_count > stats.entities_owned_count
+                OR (other.entities_owned_count = stats.entities_owned_count AND other.address < stats.address)
+        )
+        FROM stats WHERE stats.entities_owned_count > 0
+    ) AS entities_owned,
+    (
+        SELECT 1 + (
+            SELECT COUNT(*) FROM golem_base_address_leaderboard_stats other
+            WHERE other.entities_owned_count > 0
+                AND (
+                    other.data_owned > stats.data_owned
+                    OR (other.data_owned = stats.data_owned AND other.address < stats.address)
+                )
+        )
+        FROM stats WHERE stats.entities_owned_count > 0
+    ) AS data_owned,
     (SELECT rank FROM golem_base_leaderboard_top_accounts WHERE address = $1) AS top_accounts;
 "#;
 
@@ -410,81 +686,215 @@ ORDER BY
 
 pub const LEADERBOARD_BIGGEST_SPENDERS: &str = r#"
 SELECT
-    rank,
+    ROW_NUMBER() OVER(ORDER BY total_fees DESC, address ASC) AS rank,
     address,
-    total_fees
+    CAST(total_fees AS TEXT) AS total_fees
 FROM
-    golem_base_leaderboard_biggest_spenders
+    golem_base_address_leaderboard_stats AS stats
+WHERE
+    total_fees > 0
 ORDER BY
-    rank ASC
+    -- qualified, as the bare name refers to the text column above
+    stats.total_fees DESC,
+    address ASC
 "#;
 
 pub const LEADERBOARD_ENTITIES_CREATED: &str = r#"
 SELECT
-    rank,
+    ROW_NUMBER() OVER(ORDER BY entities_created_count DESC, first_created_at ASC, address ASC) AS rank,
     address,
     entities_created_count
 FROM
-    golem_base_leaderboard_entities_created
+    golem_base_address_leaderboard_stats
+WHERE
+    entities_created_count > 0
 ORDER BY
-    rank ASC
+    entities_created_count DESC,
+    first_created_at ASC,
+    address ASC
 "#;
 
 pub const LEADERBOARD_ENTITIES_OWNED: &str = r#"
 SELECT
-    rank,
+    ROW_NUMBER() OVER(ORDER BY entities_owned_count DESC, address ASC) AS rank,
     address,
-    entities_count
+    entities_owned_count AS entities_count
 FROM
-    golem_base_leaderboard_entities_owned
+    golem_base_address_leaderboard_stats
+WHERE
+    entities_owned_count > 0
 ORDER BY
-    rank ASC
+    entities_owned_count DESC,
+    address ASC
 "#;
 
 pub const LEADERBOARD_DATA_OWNED: &str = r#"
 SELECT
-    rank,
+    ROW_NUMBER() OVER(ORDER BY data_owned DESC, address ASC) AS rank,
     address,
-    data_size
+    data_owned AS data_size
 FROM
-    golem_base_leaderboard_data_owned
+    golem_base_address_leaderboard_stats
+WHERE
+    entities_owned_count > 0
 ORDER BY
-    rank ASC
+    data_owned DESC,
+    address ASC
+"#;
+
+pub const LEADERBOARD_BIGGEST_SPENDERS_IN_WINDOW: &str = r#"
+SELECT
+    ROW_NUMBER() OVER(ORDER BY SUM(total_fees) DESC, address ASC) AS rank,
+    address,
+    CAST(SUM(total_fees) AS TEXT) AS total_fees
+FROM
+    golem_base_address_leaderboard_hourly_stats
+WHERE
+    hour >= $1
+    AND hour < $2
+GROUP BY
+    address
+HAVING
+    SUM(total_fees) > 0
+ORDER BY
+    SUM(total_fees) DESC,
+    address ASC
+"#;
+
+pub const LEADERBOARD_ENTITIES_CREATED_IN_WINDOW: &str = r#"
+SELECT
+    ROW_NUMBER() OVER(
+        ORDER BY SUM(entities_created_count) DESC, MIN(hour) FILTER (WHERE entities_created_count > 0) ASC, address ASC
+    ) AS rank,
+    address,
+    CAST(SUM(entities_created_count) AS BIGINT) AS entities_created_count
+FROM
+    golem_base_address_leaderboard_hourly_stats
+WHERE
+    hour >= $1
+    AND hour < $2
+GROUP BY
+    address
+HAVING
+    SUM(entities_created_count) > 0
+ORDER BY
+    SUM(entities_created_count) DESC,
+    MIN(hour) FILTER (WHERE entities_created_count > 0) ASC,
+    address ASC
+"#;
+
+pub const LEADERBOARD_ENTITIES_OWNED_IN_WINDOW: &str = r#"
+SELECT
+    ROW_NUMBER() OVER(ORDER BY SUM(entities_owned_change) DESC, address ASC) AS rank,
+    address,
+    CAST(SUM(entities_owned_change) AS BIGINT) AS entities_count
+FROM
+    golem_base_address_leaderboard_hourly_stats
+WHERE
+    hour >= $1
+    AND hour < $2
+GROUP BY
+    address
+HAVING
+    SUM(entities_owned_change) > 0
+ORDER BY
+    SUM(entities_owned_change) DESC,
+    address ASC
+"#;
+
+pub const LEADERBOARD_DATA_OWNED_IN_WINDOW: &str = r#"
+SELECT
+    ROW_NUMBER() OVER(ORDER BY SUM(data_owned_change) DESC, address ASC) AS rank,
+    address,
+    CAST(SUM(data_owned_change) AS BIGINT) AS data_size
+FROM
+    golem_base_address_leaderboard_hourly_stats
+WHERE
+    hour >= $1
+    AND hour < $2
+GROUP BY
+    address
+HAVING
+    SUM(data_owned_change) > 0
+ORDER BY
+    SUM(data_owned_change) DESC,
+    address ASC
+"#;
+
+pub const BLOCK_RANGE_BETWEEN_TIMESTAMPS: &str = r#"
+SELECT
+    (
+        SELECT number FROM blocks
+        WHERE consensus AND timestamp >= $1
+        ORDER BY timestamp ASC, number ASC
+        LIMIT 1
+    ) AS from_block,
+    (
+        SELECT number FROM blocks
+        WHERE consensus AND timestamp < $2
+        ORDER BY timestamp DESC, number DESC
+        LIMIT 1
+    ) AS to_block
 "#;
 
 pub const LEADERBOARD_LARGEST_ENTITIES: &str = r#"
 SELECT
-    rank,
-    entity_key,
-    data_size
+    ROW_NUMBER() OVER(ORDER BY LENGTH(data) DESC, key ASC) AS rank,
+    key AS entity_key,
+    LENGTH(data) AS data_size
 FROM
-    golem_base_leaderboard_largest_entities
+    golem_base_entities
+WHERE
+    status = 'active'
+    AND data IS NOT NULL
+    AND ($1::bigint IS NULL OR created_at_block_number BETWEEN $1 AND $2)
 ORDER BY
-    rank ASC
+    LENGTH(data) DESC,
+    key ASC
 "#;
 
 pub const LEADERBOARD_EFFECTIVELY_LARGEST_ENTITIES: &str = r#"
 SELECT
-    rank,
-    entity_key,
-    data_size,
-    lifespan
+    ROW_NUMBER() OVER(
+        ORDER BY OCTET_LENGTH(data) * COALESCE(expires_at_block_number - created_at_block_number, 0) DESC, key ASC
+    ) AS rank,
+    key AS entity_key,
+    OCTET_LENGTH(data) AS data_size,
+    COALESCE(expires_at_block_number - created_at_block_number, 0) AS lifespan
 FROM
-    golem_base_leaderboard_effectively_largest_entities
+    golem_base_entities
+WHERE
+    status = 'active'
+    AND data IS NOT NULL
+    AND created_at_block_number IS NOT NULL
+    AND ($1::bigint IS NULL OR created_at_block_number BETWEEN $1 AND $2)
 ORDER BY
-    rank ASC
+    OCTET_LENGTH(data) * COALESCE(expires_at_block_number - created_at_block_number, 0) DESC,
+    key ASC
 "#;
 
 pub const QUEUE_REINDEX_PREFIX: &str = r#"
 insert into golem_base_entities_to_reindex (key) values
 "#;
 
-pub const GET_ENTITIES_TO_REINDEX: &str = r#"
-select distinct key from golem_base_entities_to_reindex
+pub const QUEUE_REINDEX_ENTITY: &str = r#"
+insert into golem_base_entities_to_reindex (key)
+select distinct entity_key from golem_base_operations where entity_key = $1
 "#;
 
-pub const FINISH_REINDEX: &str = r#"
-delete from golem_base_entities_to_reindex where key = $1
+pub const QUEUE_REINDEX_OWNER: &str = r#"
+insert into golem_base_entities_to_reindex (key)
+select key from golem_base_entities where owner = $1
+"#;
+
+pub const QUEUE_REINDEX_BLOCK_RANGE: &str = r#"
+insert into golem_base_entities_to_reindex (key)
+select distinct entity_key from golem_base_operations where block_number between $1 and $2
+"#;
+
+pub const GET_ENTITIES_TO_REINDEX: &str = r#"
+select distinct key from golem_base_entities_to_reindex
+where quarantined_at is null and next_attempt_at <= now()
 "#;
 
 pub const OLDEST_UNPROCESSED_BLOCK_STATS: &str = r#"
@@ -504,5 +914,809 @@ on conflict (block_number) do update set is_dirty = false, storage_usage = EXCLU
 "#;
 
 pub const MARK_STATS_DIRTY: &str = r#"
-update golem_base_block_stats set is_dirty = true where block_number >= $1;
+update golem_base_block_stats set is_dirty = true
+where block_number in (
+    select block_number from golem_base_block_stats
+    where block_number >= $1
+    order by block_number
+    for update
+)
+"#;
+
+pub const MARK_ENTITY_STATS_DIRTY: &str = r#"
+update golem_base_block_stats set is_dirty = true
+where block_number in (
+    select block_number from golem_base_block_stats
+    where block_number >= (
+        select min(block_number) from (
+            select block_number from golem_base_entity_history where entity_key = $1
+            union all
+            select block_number from golem_base_operations where entity_key = $1
+        ) entity_blocks
+    )
+    order by block_number
+    for update
+)
+"#;
+
+pub const LOCK_BLOCK_STATS: &str = r#"
+lock table golem_base_block_stats in share row exclusive mode
+"#;
+
+pub const ENQUEUE_WEBHOOK_DELIVERIES: &str = r#"
+insert into golem_base_webhook_deliveries (webhook_id, event, payload)
+select id, $1, $2
+from golem_base_webhooks
+where (entity_key is null or entity_key = $3)
+    and (owner is null or owner = $4 or owner = $5)
+    and (
+        annotation_key is null
+        or (annotation_key, annotation_value) in (
+            select * from unnest($6::text[], $7::text[])
+        )
+    )
+"#;
+
+// Claimed deliveries aren't due for other replicas until the lease in $3 runs
+// out, finishing or failing them reschedules them for good.
+pub const CLAIM_DUE_WEBHOOK_DELIVERIES: &str = r#"
+with claimed as (
+    update golem_base_webhook_deliveries
+    set next_attempt_at = now() + make_interval(secs => $3)
+    where id in (
+        select id
+        from golem_base_webhook_deliveries
+        where delivered_at is null
+            and attempts < $1
+            and next_attempt_at <= now()
+        order by next_attempt_at, id
+        limit $2
+        for update skip locked
+    )
+    returning id, webhook_id, event, payload, attempts
+)
+select
+    claimed.id,
+    webhooks.url,
+    webhooks.secret,
+    claimed.event,
+    claimed.payload,
+    claimed.attempts
+from claimed
+inner join golem_base_webhooks webhooks on webhooks.id = claimed.webhook_id
+order by claimed.id
+"#;
+
+pub const FINISH_WEBHOOK_DELIVERY: &str = r#"
+update golem_base_webhook_deliveries
+set delivered_at = now(), attempts = attempts + 1, last_error = null
+where id = $1
+"#;
+
+pub const FAIL_WEBHOOK_DELIVERY: &str = r#"
+update golem_base_webhook_deliveries
+set attempts = attempts + 1,
+    last_error = $2,
+    next_attempt_at = now() + make_interval(secs => $3)
+where id = $1
+"#;
+
+pub const REDELIVER_WEBHOOK_DELIVERY: &str = r#"
+update golem_base_webhook_deliveries
+set attempts = 0, next_attempt_at = now()
+where id = $1
+    and delivered_at is null
+    and attempts >= $2
+"#;
+
+// Exhausted deliveries are kept for the retention period too, so they can
+// still be inspected and redelivered.
+pub const DELETE_EXPIRED_WEBHOOK_DELIVERIES: &str = r#"
+delete from golem_base_webhook_deliveries
+where inserted_at < now() - make_interval(secs => $2)
+    and (delivered_at is not null or attempts >= $1)
+"#;
+
+// Listeners only receive the notification once the transaction commits.
+pub const INSERT_HISTORY_NOTIFICATION: &str = r#"
+with inserted as (
+    insert into golem_base_history_notifications (
+        transaction_hash,
+        op_index,
+        string_attribute_keys,
+        string_attribute_values,
+        numeric_attribute_keys,
+        numeric_attribute_values
+    )
+    values ($2, $3, $4, $5, $6, $7::text[]::numeric[])
+    returning id
+)
+select pg_notify($1, id::text) from inserted
+"#;
+
+pub const GET_HISTORY_NOTIFICATION: &str = r#"
+select
+    transaction_hash,
+    op_index,
+    string_attribute_keys,
+    string_attribute_values,
+    numeric_attribute_keys,
+    numeric_attribute_values::text[] as numeric_attribute_values
+from golem_base_history_notifications
+where id = $1
+"#;
+
+pub const DELETE_HISTORY_NOTIFICATIONS_BEFORE: &str = r#"
+delete from golem_base_history_notifications
+where inserted_at < now() - make_interval(secs => $1)
+"#;
+
+pub const MARK_BLOCK_INDEXED: &str = r#"
+insert into golem_base_indexed_blocks (hash, number) values ($1, $2)
+on conflict do nothing
+"#;
+
+pub const FIND_FORK_POINT: &str = r#"
+select min(indexed.number) as block_number
+from golem_base_indexed_blocks indexed
+-- a block that is gone is no longer canonical either
+left join blocks on blocks.hash = indexed.hash
+where indexed.number >= (select max(number) from golem_base_indexed_blocks) - $1
+    and blocks.consensus is not true
+"#;
+
+pub const FORGET_INDEXED_BLOCKS: &str = r#"
+delete from golem_base_indexed_blocks where number >= $1
+"#;
+
+pub const QUEUE_REINDEX_SINCE_BLOCK: &str = r#"
+insert into golem_base_entities_to_reindex (key)
+select distinct entity_key from golem_base_operations where block_number >= $1
+"#;
+
+pub const DELETE_STRING_ANNOTATIONS_SINCE_BLOCK: &str = r#"
+delete from golem_base_string_annotations annotations
+using golem_base_operations ops
+where annotations.operation_tx_hash = ops.transaction_hash
+    and annotations.operation_index = ops.index
+    and ops.block_number >= $1
+"#;
+
+pub const DELETE_NUMERIC_ANNOTATIONS_SINCE_BLOCK: &str = r#"
+delete from golem_base_numeric_annotations annotations
+using golem_base_operations ops
+where annotations.operation_tx_hash = ops.transaction_hash
+    and annotations.operation_index = ops.index
+    and ops.block_number >= $1
+"#;
+
+pub const DELETE_HISTORY_SINCE_BLOCK: &str = r#"
+delete from golem_base_entity_history where block_number >= $1
+"#;
+
+pub const DELETE_OPERATIONS_SINCE_BLOCK: &str = r#"
+delete from golem_base_operations where block_number >= $1
+"#;
+
+pub const DELETE_UNDECODABLE_TRANSACTION: &str = r#"
+delete from golem_base_undecodable_transactions where hash = $1
+"#;
+
+pub const DELETE_UNDECODABLE_TRANSACTIONS_SINCE_BLOCK: &str = r#"
+delete from golem_base_undecodable_transactions where block_number >= $1
+"#;
+
+pub const INSERT_UNDECODABLE_TRANSACTION: &str = r#"
+insert into golem_base_undecodable_transactions
+    (hash, sender, block_hash, block_number, tx_index, error_kind, error)
+values ($1, $2, $3, $4, $5, $6, $7)
+on conflict (hash) do update set
+    sender = excluded.sender,
+    block_hash = excluded.block_hash,
+    block_number = excluded.block_number,
+    tx_index = excluded.tx_index,
+    error_kind = excluded.error_kind,
+    error = excluded.error
+"#;
+
+pub const CLAIM_TRANSACTION_FEES: &str = r#"
+delete from golem_base_pending_transaction_fees
+where hash in (
+    select hash from golem_base_pending_transaction_fees
+    limit $1
+    for update skip locked
+)
+returning hash
+"#;
+
+pub const RECORD_TRANSACTION_FEES: &str = r#"
+with recorded as (
+    insert into golem_base_transaction_fees (hash, sender, block_number, block_timestamp, fee)
+    select
+        t.hash,
+        t.from_address_hash,
+        t.block_number,
+        coalesce(t.block_timestamp, blocks.timestamp),
+        t.cumulative_gas_used * t.gas_price
+    from transactions t
+    left join blocks on blocks.hash = t.block_hash
+    where t.hash = any($1::bytea[])
+        and t.block_hash is not null
+        and blocks.consensus is not false
+        and t.cumulative_gas_used > 0
+        and t.gas_price > 0
+    returning sender, block_timestamp, fee
+), totals as (
+    insert into golem_base_address_leaderboard_stats as stats (address, total_fees)
+    select sender, sum(fee) from recorded
+    group by sender
+    order by sender
+    on conflict (address) do update set
+        total_fees = stats.total_fees + excluded.total_fees
+)
+insert into golem_base_address_leaderboard_hourly_stats as stats (hour, address, total_fees)
+select date_trunc('hour', block_timestamp), sender, sum(fee)
+from recorded
+where block_timestamp is not null
+group by 1, 2
+order by 1, 2
+on conflict (hour, address) do update set
+    total_fees = stats.total_fees + excluded.total_fees
+"#;
+
+pub const DELETE_TRANSACTION_FEES: &str = r#"
+with deleted as (
+    delete from golem_base_transaction_fees where hash = any($1::bytea[])
+    returning sender, block_timestamp, fee
+), totals as (
+    update golem_base_address_leaderboard_stats stats
+    set total_fees = stats.total_fees - deleted.fees
+    from (select sender, sum(fee) as fees from deleted group by sender) deleted
+    where stats.address = deleted.sender
+)
+update golem_base_address_leaderboard_hourly_stats stats
+set total_fees = stats.total_fees - deleted.fees
+from (
+    select date_trunc('hour', block_timestamp) as hour, sender, sum(fee) as fees
+    from deleted
+    group by 1, 2
+) deleted
+where stats.hour = deleted.hour and stats.address = deleted.sender
+"#;
+
+pub const DELETE_TRANSACTION_FEES_SINCE_BLOCK: &str = r#"
+with deleted as (
+    delete from golem_base_transaction_fees where block_number >= $1
+    returning sender, block_timestamp, fee
+), totals as (
+    update golem_base_address_leaderboard_stats stats
+    set total_fees = stats.total_fees - deleted.fees
+    from (select sender, sum(fee) as fees from deleted group by sender) deleted
+    where stats.address = deleted.sender
+)
+update golem_base_address_leaderboard_hourly_stats stats
+set total_fees = stats.total_fees - deleted.fees
+from (
+    select date_trunc('hour', block_timestamp) as hour, sender, sum(fee) as fees
+    from deleted
+    group by 1, 2
+) deleted
+where stats.hour = deleted.hour and stats.address = deleted.sender
+"#;
+
+pub const LIST_UNDECODABLE_TRANSACTIONS: &str = r#"
+select
+    hash,
+    sender,
+    block_hash,
+    block_number,
+    tx_index,
+    error_kind,
+    error
+from golem_base_undecodable_transactions
+where $1::bytea is null or sender = $1
+order by block_number desc, tx_index desc
+"#;
+
+pub const REQUEUE_TRANSACTIONS_SINCE_BLOCK: &str = r#"
+insert into golem_base_pending_transaction_operations (hash, block_number, index)
+select t.hash, t.block_number, t.index
+from transactions t
+inner join blocks on blocks.hash = t.block_hash
+where blocks.consensus
+    and t.block_number >= $1
+    and golem_base_is_storage_transaction(t.to_address_hash, t.status, t.input)
+on conflict do nothing
+"#;
+
+pub const REQUEUE_TRANSACTION_FEES_SINCE_BLOCK: &str = r#"
+insert into golem_base_pending_transaction_fees (hash)
+select t.hash
+from transactions t
+inner join blocks on blocks.hash = t.block_hash
+where blocks.consensus
+    and t.block_number >= $1
+on conflict do nothing
+"#;
+
+pub const REQUEUE_LOGS_OPERATIONS_SINCE_BLOCK: &str = r#"
+insert into golem_base_pending_logs_operations (transaction_hash, block_hash, index, block_number)
+select logs.transaction_hash, logs.block_hash, logs.index, logs.block_number
+from logs
+inner join blocks on blocks.hash = logs.block_hash
+inner join transactions t on t.hash = logs.transaction_hash
+where blocks.consensus
+    and logs.block_number >= $1
+    and golem_base_is_operations_log(logs.address_hash, logs.first_topic)
+    and golem_base_is_operations_log_sender(t.to_address_hash)
+on conflict do nothing
+"#;
+
+pub const REQUEUE_LOGS_EVENTS_SINCE_BLOCK: &str = r#"
+insert into golem_base_pending_logs_events (transaction_hash, block_hash, index, block_number)
+select logs.transaction_hash, logs.block_hash, logs.index, logs.block_number
+from logs
+inner join blocks on blocks.hash = logs.block_hash
+where blocks.consensus
+    and logs.block_number >= $1
+    and golem_base_is_events_log(logs.address_hash, logs.first_topic)
+on conflict do nothing
+"#;
+
+pub const DROP_PENDING_LOGS_OPERATIONS_OFF_CHAIN: &str = r#"
+delete from golem_base_pending_logs_operations pendings
+using blocks
+where blocks.hash = pendings.block_hash
+    and pendings.block_number >= $1
+    and blocks.consensus is not true
+"#;
+
+pub const DROP_PENDING_LOGS_EVENTS_OFF_CHAIN: &str = r#"
+delete from golem_base_pending_logs_events pendings
+using blocks
+where blocks.hash = pendings.block_hash
+    and pendings.block_number >= $1
+    and blocks.consensus is not true
+"#;
+
+pub const INSERT_CHAIN_BLOCK: &str = r#"
+insert into blocks (
+    consensus, gas_limit, gas_used, hash, miner_hash, nonce, number, parent_hash,
+    timestamp, inserted_at, updated_at
+)
+values (true, $1, $2, $3, $4, '\x', $5, $6, $7, now(), now())
+on conflict (hash) do update set
+    consensus = true,
+    updated_at = now()
+"#;
+
+pub const UPSERT_CHAIN_TRANSACTION: &str = r#"
+insert into transactions (
+    hash, index, from_address_hash, to_address_hash, created_contract_address_hash,
+    input, nonce, gas, gas_price, value, r, s, v, type, status, gas_used,
+    cumulative_gas_used, block_hash, block_number, block_timestamp, block_consensus,
+    inserted_at, updated_at
+)
+values (
+    $1, $2, $3, $4, $5, $6, $7, $8, $9::numeric, $10::numeric, 0, 0, 0, $11, $12, $13,
+    $14, $15, $16, $17, true, now(), now()
+)
+on conflict (hash) do update set
+    index = excluded.index,
+    gas_price = excluded.gas_price,
+    status = excluded.status,
+    gas_used = excluded.gas_used,
+    cumulative_gas_used = excluded.cumulative_gas_used,
+    block_hash = excluded.block_hash,
+    block_number = excluded.block_number,
+    block_timestamp = excluded.block_timestamp,
+    block_consensus = true,
+    updated_at = now()
+"#;
+
+pub const INSERT_CHAIN_LOG: &str = r#"
+insert into logs (
+    data, index, first_topic, second_topic, third_topic, fourth_topic, address_hash,
+    transaction_hash, block_hash, block_number, inserted_at, updated_at
+)
+values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, now(), now())
+on conflict do nothing
+"#;
+
+pub const ORPHAN_BLOCKS_SINCE: &str = r#"
+update blocks
+set consensus = false, updated_at = now()
+where number >= $1 and consensus
+"#;
+
+pub const DETACH_ORPHANED_TRANSACTIONS: &str = r#"
+update transactions
+set
+    old_block_hash = transactions.block_hash,
+    block_hash = null,
+    block_number = null,
+    block_timestamp = null,
+    block_consensus = false,
+    index = null,
+    status = null,
+    gas_used = null,
+    cumulative_gas_used = null,
+    updated_at = now()
+from blocks
+where blocks.hash = transactions.block_hash
+    and blocks.number >= $1
+    and not blocks.consensus
+"#;
+
+pub const FAIL_TRANSACTION_PROCESSING: &str = r#"
+update golem_base_pending_transaction_operations
+set attempts = attempts + 1,
+    last_error = $2,
+    next_attempt_at = now() + make_interval(secs => least($3 * power(2, least(attempts, 32)), $4)),
+    quarantined_at = case when attempts + 1 >= $5 then now() end
+where hash = $1
+returning quarantined_at is not null as quarantined
+"#;
+
+pub const FAIL_TRANSACTION_CLEANUP: &str = r#"
+update golem_base_pending_transaction_cleanups
+set attempts = attempts + 1,
+    last_error = $2,
+    next_attempt_at = now() + make_interval(secs => least($3 * power(2, least(attempts, 32)), $4)),
+    quarantined_at = case when attempts + 1 >= $5 then now() end
+where hash = $1
+returning quarantined_at is not null as quarantined
+"#;
+
+pub const FAIL_LOG_PROCESSING: &str = r#"
+update golem_base_pending_logs_operations
+set attempts = attempts + 1,
+    last_error = $4,
+    next_attempt_at = now() + make_interval(secs => least($5 * power(2, least(attempts, 32)), $6)),
+    quarantined_at = case when attempts + 1 >= $7 then now() end
+where transaction_hash = $1 and block_hash = $2 and index = $3
+returning quarantined_at is not null as quarantined
+"#;
+
+pub const FAIL_LOG_EVENT_PROCESSING: &str = r#"
+update golem_base_pending_logs_events
+set attempts = attempts + 1,
+    last_error = $4,
+    next_attempt_at = now() + make_interval(secs => least($5 * power(2, least(attempts, 32)), $6)),
+    quarantined_at = case when attempts + 1 >= $7 then now() end
+where transaction_hash = $1 and block_hash = $2 and index = $3
+returning quarantined_at is not null as quarantined
+"#;
+
+pub const FAIL_REINDEX: &str = r#"
+update golem_base_entities_to_reindex
+set attempts = attempts + 1,
+    last_error = $2,
+    next_attempt_at = now() + make_interval(secs => least($3 * power(2, least(attempts, 32)), $4)),
+    quarantined_at = case when attempts + 1 >= $5 then now() end
+where key = $1
+returning quarantined_at is not null as quarantined
+"#;
+
+pub const QUARANTINED_ITEMS: &str = r#"
+select * from (
+    select
+        'transactions' as queue,
+        hash as key,
+        null::bytea as block_hash,
+        block_number,
+        index,
+        attempts,
+        last_error,
+        quarantined_at
+    from golem_base_pending_transaction_operations
+    where quarantined_at is not null
+
+    union all
+
+    select
+        'transaction_cleanups' as queue,
+        hash as key,
+        null::bytea as block_hash,
+        null::bigint as block_number,
+        null::bigint as index,
+        attempts,
+        last_error,
+        quarantined_at
+    from golem_base_pending_transaction_cleanups
+    where quarantined_at is not null
+
+    union all
+
+    select
+        'logs' as queue,
+        transaction_hash as key,
+        block_hash,
+        block_number::bigint,
+        index::bigint,
+        attempts,
+        last_error,
+        quarantined_at
+    from golem_base_pending_logs_operations
+    where quarantined_at is not null
+
+    union all
+
+    select
+        'logs_events' as queue,
+        transaction_hash as key,
+        block_hash,
+        block_number::bigint,
+        index::bigint,
+        attempts,
+        last_error,
+        quarantined_at
+    from golem_base_pending_logs_events
+    where quarantined_at is not null
+
+    union all
+
+    (
+        select distinct on (key)
+            'reindexes' as queue,
+            key,
+            null::bytea as block_hash,
+            null::bigint as block_number,
+            null::bigint as index,
+            attempts,
+            last_error,
+            quarantined_at
+        from golem_base_entities_to_reindex
+        where quarantined_at is not null
+        order by key, attempts desc
+    )
+) items
+where ($1::text is null or queue = $1)
+    and ($2::bytea is null or key = $2)
+order by quarantined_at desc, queue, key, index
+"#;
+
+pub const COUNT_QUARANTINED_ITEMS: &str = r#"
+select
+    (select count(*) from golem_base_pending_transaction_operations where quarantined_at is not null)
+    + (select count(*) from golem_base_pending_transaction_cleanups where quarantined_at is not null)
+    + (select count(*) from golem_base_pending_logs_operations where quarantined_at is not null)
+    + (select count(*) from golem_base_pending_logs_events where quarantined_at is not null)
+    + (select count(distinct key) from golem_base_entities_to_reindex where quarantined_at is not null)
+    as count
+"#;
+
+pub const QUEUE_DEPTHS: &str = r#"
+select
+    (select count(*) from golem_base_pending_transaction_operations) as pending_transactions,
+    (select count(*) from golem_base_pending_transaction_cleanups) as pending_transaction_cleanups,
+    (select count(*) from golem_base_pending_logs_operations) as pending_logs,
+    (select count(*) from golem_base_pending_logs_events) as pending_logs_events,
+    (select count(distinct key) from golem_base_entities_to_reindex) as entities_to_reindex
+"#;
+
+pub const GET_INDEXER_PAUSED: &str = r#"
+select paused from golem_base_indexer_control
+"#;
+
+pub const SET_INDEXER_PAUSED: &str = r#"
+update golem_base_indexer_control set paused = $1, updated_at = now()
+"#;
+
+pub const REQUEUE_QUARANTINED_TRANSACTION: &str = r#"
+update golem_base_pending_transaction_operations
+set attempts = 0, next_attempt_at = now(), quarantined_at = null
+where hash = $1 and quarantined_at is not null
+"#;
+
+pub const REQUEUE_QUARANTINED_TRANSACTION_CLEANUP: &str = r#"
+update golem_base_pending_transaction_cleanups
+set attempts = 0, next_attempt_at = now(), quarantined_at = null
+where hash = $1 and quarantined_at is not null
+"#;
+
+pub const REQUEUE_QUARANTINED_LOGS: &str = r#"
+update golem_base_pending_logs_operations
+set attempts = 0, next_attempt_at = now(), quarantined_at = null
+where transaction_hash = $1 and quarantined_at is not null
+"#;
+
+pub const REQUEUE_QUARANTINED_LOGS_EVENTS: &str = r#"
+update golem_base_pending_logs_events
+set attempts = 0, next_attempt_at = now(), quarantined_at = null
+where transaction_hash = $1 and quarantined_at is not null
+"#;
+
+pub const REQUEUE_QUARANTINED_REINDEX: &str = r#"
+update golem_base_entities_to_reindex
+set attempts = 0, next_attempt_at = now(), quarantined_at = null
+where key = $1 and quarantined_at is not null
+"#;
+
+pub const RECORD_INDEXER_TICK: &str = r#"
+update golem_base_indexer_control set last_tick_at = now()
+"#;
+
+pub const RECORD_MATERIALIZED_VIEW_REFRESH: &str = r#"
+insert into golem_base_materialized_view_refreshes (name, refreshed_at)
+values ($1, now())
+on conflict (name) do update set refreshed_at = excluded.refreshed_at
+"#;
+
+pub const MATERIALIZED_VIEW_REFRESHES: &str = r#"
+select name, refreshed_at from golem_base_materialized_view_refreshes
+"#;
+
+pub const INDEXER_PROGRESS: &str = r#"
+with latest as (
+    select max(number) as number from blocks where consensus
+)
+select
+    now()::timestamp as checked_at,
+    (select number from latest) as latest_block,
+    coalesce(
+        (
+            select min(block_number) - 1
+            from golem_base_pending_transaction_operations
+            where quarantined_at is null
+        ),
+        (select number from latest)
+    ) as latest_processed_block,
+    (
+        select min(inserted_at) from (
+            select inserted_at from golem_base_pending_transaction_operations
+            where quarantined_at is null
+            union all
+            select inserted_at from golem_base_pending_transaction_cleanups
+            where quarantined_at is null
+            union all
+            select inserted_at from golem_base_pending_logs_operations
+            where quarantined_at is null
+            union all
+            select inserted_at from golem_base_pending_logs_events
+            where quarantined_at is null
+            union all
+            select inserted_at from golem_base_entities_to_reindex
+            where quarantined_at is null
+        ) queued
+    ) as oldest_queued_item_at,
+    (select last_tick_at from golem_base_indexer_control) as last_tick_at
+"#;
+
+pub const CLAIM_TRANSACTION: &str = r#"
+select hash from golem_base_pending_transaction_operations
+where hash = $1 and quarantined_at is null and next_attempt_at <= now()
+for update skip locked
+"#;
+
+pub const AWAIT_TRANSACTION_PROCESSING: &str = r#"
+select hash from golem_base_pending_transaction_operations
+where hash = $1
+for update
+"#;
+
+pub const CLAIM_TRANSACTION_CLEANUP: &str = r#"
+select hash from golem_base_pending_transaction_cleanups
+where hash = $1 and quarantined_at is null and next_attempt_at <= now()
+for update skip locked
+"#;
+
+pub const CLAIM_LOG: &str = r#"
+select transaction_hash from golem_base_pending_logs_operations
+where transaction_hash = $1 and block_hash = $2 and index = $3
+    and quarantined_at is null and next_attempt_at <= now()
+for update skip locked
+"#;
+
+pub const CLAIM_LOG_EVENT: &str = r#"
+select transaction_hash from golem_base_pending_logs_events
+where transaction_hash = $1 and block_hash = $2 and index = $3
+    and quarantined_at is null and next_attempt_at <= now()
+for update skip locked
+"#;
+
+pub const CLAIM_REINDEX: &str = r#"
+delete from golem_base_entities_to_reindex
+where ctid in (
+    select ctid from golem_base_entities_to_reindex
+    where key = $1
+    for update skip locked
+)
+returning key
+"#;
+
+pub const TRY_ADVISORY_XACT_LOCK: &str = r#"
+select pg_try_advisory_xact_lock($1, $2) as locked
+"#;
+
+pub const TRY_ADVISORY_LOCK: &str = r#"
+select pg_try_advisory_lock($1, $2) as locked
+"#;
+
+pub const ADVISORY_XACT_LOCK: &str = r#"
+select pg_advisory_xact_lock($1, $2)
+"#;
+
+pub const ADVISORY_XACT_LOCK_SHARED: &str = r#"
+select pg_advisory_xact_lock_shared($1, $2)
+"#;
+
+pub const REFRESH_ENTITY_SEARCH: &str = r#"
+with entity as (
+    select key, data, content_type
+    from golem_base_entities
+    where key = $1 and status = 'active'
+), deleted as (
+    delete from golem_base_entity_search
+    where key = $1 and not exists (select 1 from entity)
+)
+insert into golem_base_entity_search (key, annotations, payload)
+select
+    entity.key,
+    -- capped like the payload, a tsvector can't exceed 1MB
+    left(coalesce((
+        select string_agg(annotations.value, ' ' order by annotations.id)
+        from golem_base_string_annotations annotations
+        where annotations.entity_key = entity.key and annotations.active
+    ), ''), 65536),
+    golem_base_searchable_payload(entity.data, entity.content_type)
+from entity
+on conflict (key) do update set
+    annotations = excluded.annotations,
+    payload = excluded.payload
+"#;
+
+pub const SEARCH_ENTITIES: &str = r#"
+select
+    search.key as entity_key,
+    entities.owner,
+    entities.content_type,
+    ts_rank_cd(search.document, query)::double precision as rank,
+    ts_headline(
+        'simple',
+        replace(replace(replace(
+            concat_ws(E'\n', nullif(search.annotations, ''), search.payload),
+            '&', '&amp;'), '<', '&lt;'), '>', '&gt;'),
+        query,
+        'StartSel=<mark>, StopSel=</mark>, MinWords=10, MaxWords=30'
+    ) as snippet
+from golem_base_entity_search search
+    cross join websearch_to_tsquery('simple', $1) query
+    inner join golem_base_entities entities on entities.key = search.key
+where search.document @@ query
+order by rank desc, search.key asc
+"#;
+
+pub const LIST_ANNOTATION_KEYS: &str = r#"
+select kind, key, active_entities, first_seen_block, last_seen_block
+from golem_base_annotation_keys
+where
+    ($1::text is null or kind = $1)
+    and ($2::text is null or starts_with(key, $2))
+order by active_entities desc, kind, key
+"#;
+
+pub const LIST_ANNOTATION_VALUES: &str = r#"
+select value, active_entities
+from golem_base_annotation_values
+where
+    kind = $1
+    and key = $2
+    and ($3::text is null or starts_with(value, $3))
+order by active_entities desc, value
+"#;
+
+pub const NUMERIC_ANNOTATION_STATS: &str = r#"
+select
+    $1::text as key,
+    count(distinct entity_key) as active_entities,
+    min(value) as min,
+    max(value) as max,
+    percentile_disc(0.25) within group (order by value) as p25,
+    percentile_disc(0.5) within group (order by value) as p50,
+    percentile_disc(0.75) within group (order by value) as p75,
+    percentile_disc(0.9) within group (order by value) as p90,
+    percentile_disc(0.99) within group (order by value) as p99
+from golem_base_numeric_annotations
+where key = $1 and active
 "#;
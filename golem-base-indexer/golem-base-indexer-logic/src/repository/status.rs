@@ -0,0 +1,92 @@
+use anyhow::{Context, Result};
+use sea_orm::{prelude::*, DbBackend, FromQueryResult, Statement, StreamTrait};
+use std::collections::HashMap;
+use tracing::instrument;
+
+use super::{blockscout, control, sql};
+use crate::types::{IndexerStatus, MaterializedViewStatus};
+
+#[derive(FromQueryResult)]
+struct DbIndexerProgress {
+    checked_at: chrono::NaiveDateTime,
+    latest_block: Option<i64>,
+    latest_processed_block: Option<i64>,
+    oldest_queued_item_at: Option<chrono::NaiveDateTime>,
+    last_tick_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(FromQueryResult)]
+struct DbMaterializedViewRefresh {
+    name: String,
+    refreshed_at: chrono::NaiveDateTime,
+}
+
+#[instrument(skip(db))]
+pub async fn record_tick<T: ConnectionTrait>(db: &T) -> Result<()> {
+    db.execute(Statement::from_string(
+        DbBackend::Postgres,
+        sql::RECORD_INDEXER_TICK,
+    ))
+    .await
+    .context("Failed to record indexer tick")?;
+    Ok(())
+}
+
+#[instrument(skip(db))]
+pub async fn record_view_refresh<T: ConnectionTrait>(db: &T, view: &str) -> Result<()> {
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        sql::RECORD_MATERIALIZED_VIEW_REFRESH,
+        [view.into()],
+    ))
+    .await
+    .context("Failed to record materialized view refresh")?;
+    Ok(())
+}
+
+/// Progress of the indexer, with refresh times of the given materialized views.
+#[instrument(skip(db))]
+pub async fn indexer_status<T: StreamTrait + ConnectionTrait>(
+    db: &T,
+    views: Vec<String>,
+) -> Result<IndexerStatus> {
+    let progress = DbIndexerProgress::find_by_statement(Statement::from_string(
+        DbBackend::Postgres,
+        sql::INDEXER_PROGRESS,
+    ))
+    .one(db)
+    .await
+    .context("Failed to get indexer progress")?
+    .ok_or(anyhow::anyhow!("Indexer progress returned no rows"))?;
+
+    let mut refreshes: HashMap<_, _> = DbMaterializedViewRefresh::find_by_statement(
+        Statement::from_string(DbBackend::Postgres, sql::MATERIALIZED_VIEW_REFRESHES),
+    )
+    .all(db)
+    .await
+    .context("Failed to get materialized view refreshes")?
+    .into_iter()
+    .map(|v| (v.name, v.refreshed_at.and_utc()))
+    .collect();
+
+    Ok(IndexerStatus {
+        checked_at: progress.checked_at.and_utc(),
+        latest_block: progress.latest_block.map(TryInto::try_into).transpose()?,
+        latest_processed_block: progress
+            .latest_processed_block
+            .map(TryInto::try_into)
+            .transpose()?,
+        pending_transactions: blockscout::count_unprocessed_txs(db).await?,
+        pending_transaction_cleanups: blockscout::count_txs_for_cleanup(db).await?,
+        oldest_queued_item_at: progress.oldest_queued_item_at.map(|v| v.and_utc()),
+        last_tick_at: progress.last_tick_at.map(|v| v.and_utc()),
+        paused: control::is_paused(db).await?,
+        materialized_views: views
+            .into_iter()
+            .map(|name| MaterializedViewStatus {
+                refreshed_at: refreshes.remove(&name),
+                name,
+            })
+            .collect(),
+    })
+}
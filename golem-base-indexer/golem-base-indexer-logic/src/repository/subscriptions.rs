@@ -0,0 +1,122 @@
+use anyhow::{Context, Result};
+use sea_orm::{prelude::*, DbBackend, FromQueryResult, Statement};
+use std::time::Duration;
+use tracing::instrument;
+
+use crate::{
+    repository::{entities, sql},
+    types::{HistoryEntryEvent, NumericAttribute, StringAttribute, TxHash},
+};
+
+/// Postgres channel the ids of new history notifications are sent to.
+pub const HISTORY_NOTIFICATIONS_CHANNEL: &str = "golem_base_history_entries";
+
+#[derive(FromQueryResult)]
+struct DbHistoryNotification {
+    transaction_hash: Vec<u8>,
+    op_index: i64,
+    string_attribute_keys: Vec<String>,
+    string_attribute_values: Vec<String>,
+    numeric_attribute_keys: Vec<String>,
+    numeric_attribute_values: Vec<String>,
+}
+
+/// Stores the event and notifies listeners of every replica once the
+/// surrounding transaction commits.
+#[instrument(skip_all)]
+pub async fn notify<T: ConnectionTrait>(db: &T, event: &HistoryEntryEvent) -> Result<()> {
+    let (string_keys, string_values): (Vec<String>, Vec<String>) = event
+        .string_attributes
+        .iter()
+        .map(|v| (v.key.clone(), v.value.clone()))
+        .unzip();
+    let (numeric_keys, numeric_values): (Vec<String>, Vec<String>) = event
+        .numeric_attributes
+        .iter()
+        .map(|v| (v.key.clone(), v.value.to_string()))
+        .unzip();
+
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        sql::INSERT_HISTORY_NOTIFICATION,
+        [
+            HISTORY_NOTIFICATIONS_CHANNEL.into(),
+            event.entry.transaction_hash.as_slice().to_vec().into(),
+            event.entry.op_index.into(),
+            string_keys.into(),
+            string_values.into(),
+            numeric_keys.into(),
+            numeric_values.into(),
+        ],
+    ))
+    .await
+    .context("Failed to insert history notification")?;
+
+    Ok(())
+}
+
+/// Returns `None` if the notification was cleaned up or its history entry
+/// was rolled back since.
+#[instrument(skip(db))]
+pub async fn get_notification<T: ConnectionTrait>(
+    db: &T,
+    id: u64,
+) -> Result<Option<HistoryEntryEvent>> {
+    let Some(notification) =
+        DbHistoryNotification::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            sql::GET_HISTORY_NOTIFICATION,
+            [id.into()],
+        ))
+        .one(db)
+        .await
+        .context("Failed to get history notification")?
+    else {
+        return Ok(None);
+    };
+
+    let tx_hash = TxHash::from_slice(&notification.transaction_hash);
+    let Some(entry) =
+        entities::get_entity_history_entry(db, tx_hash, notification.op_index.try_into()?).await?
+    else {
+        return Ok(None);
+    };
+
+    let string_attributes = notification
+        .string_attribute_keys
+        .into_iter()
+        .zip(notification.string_attribute_values)
+        .map(|(key, value)| StringAttribute { key, value })
+        .collect();
+    let numeric_attributes = notification
+        .numeric_attribute_keys
+        .into_iter()
+        .zip(notification.numeric_attribute_values)
+        .map(|(key, value)| {
+            Ok(NumericAttribute {
+                key,
+                value: value.parse()?,
+            })
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(Some(HistoryEntryEvent {
+        entry,
+        string_attributes,
+        numeric_attributes,
+    }))
+}
+
+/// Listeners fetch notifications as soon as they're sent, older ones are of no use.
+#[instrument(skip(db))]
+pub async fn delete_notifications_before<T: ConnectionTrait>(db: &T, age: Duration) -> Result<u64> {
+    let res = db
+        .execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            sql::DELETE_HISTORY_NOTIFICATIONS_BEFORE,
+            [age.as_secs_f64().into()],
+        ))
+        .await
+        .context("Failed to delete history notifications")?;
+    Ok(res.rows_affected())
+}
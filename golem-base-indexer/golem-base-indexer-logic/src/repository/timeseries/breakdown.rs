@@ -0,0 +1,165 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::{Duration, DurationRound, NaiveDateTime, NaiveTime, Utc};
+use sea_orm::{prelude::*, DbBackend, FromQueryResult, Statement};
+use std::collections::BTreeMap;
+use tracing::instrument;
+
+use crate::{
+    repository::sql,
+    types::{Address, ChartBreakdown, ChartGroupBy, ChartPoint, ChartSeries},
+};
+
+use super::common::*;
+
+/// Name of the series everything outside of the largest series is summed up in.
+pub const OTHER_SERIES: &str = "other";
+
+#[derive(Debug, Clone, Copy)]
+pub enum BreakdownMetric {
+    DataUsage,
+    EntityCount,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct DbChartBreakdownPoint {
+    pub timestamp: NaiveDateTime,
+    pub series: Option<String>,
+    pub value: i64,
+}
+
+#[instrument(skip(db))]
+pub async fn timeseries_breakdown<T: ConnectionTrait>(
+    db: &T,
+    from: Option<String>,
+    to: Option<String>,
+    resolution: ChartResolution,
+    address: Option<Address>,
+    metric: BreakdownMetric,
+    breakdown: &ChartBreakdown,
+) -> Result<Vec<ChartSeries>> {
+    let (from, to, period) = match resolution {
+        ChartResolution::Day => {
+            let (from_date, to_date) = parse_date_range(from, to)?;
+            (
+                from_date.map(|v| v.and_time(NaiveTime::MIN)),
+                to_date.map(|v| v.and_time(NaiveTime::MIN)),
+                Duration::days(1),
+            )
+        }
+        ChartResolution::Hour => {
+            let (from_datetime, to_datetime) = parse_datetime_range(from, to)?;
+            (from_datetime, to_datetime, Duration::hours(1))
+        }
+        _ => return Err(anyhow!("Unsupported chart resolution")),
+    };
+
+    let (kind, key) = match &breakdown.group_by {
+        ChartGroupBy::ContentType => ("content_type", ""),
+        ChartGroupBy::StringAnnotation(key) => ("string_annotation", key.as_str()),
+    };
+    let values: Vec<Value> = vec![
+        address.map(|v| v.to_vec()).into(),
+        matches!(metric, BreakdownMetric::DataUsage).into(),
+        (breakdown.limit as i64).into(),
+        from.into(),
+        to.into(),
+        kind.into(),
+        key.into(),
+    ];
+
+    let results = DbChartBreakdownPoint::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        sql::CHART_BREAKDOWN_SERIES,
+        values,
+    ))
+    .all(db)
+    .await
+    .context("Failed to get chart breakdown")?;
+
+    Ok(generate_series(results, from, to, period))
+}
+
+/// Pads every series to the same periods, each one holding the last value
+/// known by its end. The largest series go first and "other" last.
+fn generate_series(
+    db_results: Vec<DbChartBreakdownPoint>,
+    from: Option<NaiveDateTime>,
+    to: Option<NaiveDateTime>,
+    period: Duration,
+) -> Vec<ChartSeries> {
+    let daily = period == Duration::days(1);
+    let period_start = |v: NaiveDateTime| {
+        if daily {
+            v.date().and_time(NaiveTime::MIN)
+        } else {
+            v.duration_trunc(Duration::hours(1)).unwrap_or(v)
+        }
+    };
+    let date_format = if daily { "%Y-%m-%d" } else { "%Y-%m-%d %H:%M" };
+
+    let now = Utc::now().naive_utc();
+    let start_time = match from {
+        Some(dt) => dt,
+        None => period_start(
+            db_results
+                .iter()
+                .map(|row| row.timestamp)
+                .min()
+                .unwrap_or(now),
+        ),
+    };
+    let end_time = match to {
+        Some(dt) => dt,
+        None if daily => period_start(now),
+        None => now,
+    };
+
+    let mut rows: BTreeMap<Option<String>, Vec<(NaiveDateTime, i64)>> = BTreeMap::new();
+    for row in db_results {
+        rows.entry(row.series)
+            .or_default()
+            .push((row.timestamp, row.value));
+    }
+
+    let mut series: Vec<(ChartSeries, i64)> = rows
+        .into_iter()
+        .map(|(name, rows)| {
+            let mut rows = rows.into_iter().peekable();
+            let mut points = Vec::new();
+            let mut last_known_value = 0;
+            let mut current_time = start_time;
+
+            while current_time < end_time {
+                let next_time = current_time + period;
+                while let Some((_, value)) = rows.next_if(|(timestamp, _)| *timestamp < next_time) {
+                    last_known_value = value;
+                }
+
+                points.push(ChartPoint {
+                    date: current_time.format(date_format).to_string(),
+                    date_to: next_time.format(date_format).to_string(),
+                    value: last_known_value.to_string(),
+                });
+
+                current_time = next_time;
+            }
+
+            let name = name.unwrap_or_else(|| OTHER_SERIES.to_string());
+            (
+                ChartSeries {
+                    name,
+                    chart: points,
+                },
+                last_known_value,
+            )
+        })
+        .collect();
+
+    series.sort_by(|(a, a_value), (b, b_value)| {
+        (a.name == OTHER_SERIES)
+            .cmp(&(b.name == OTHER_SERIES))
+            .then(b_value.cmp(a_value))
+            .then(a.name.cmp(&b.name))
+    });
+    series.into_iter().map(|(series, _)| series).collect()
+}
@@ -1,7 +1,24 @@
 use anyhow::{anyhow, Result};
 use chrono::{NaiveDate, NaiveDateTime, Utc};
+use sea_query::{Expr, Iden, IntoIden, Query, SelectStatement};
 
-#[derive(Debug)]
+use crate::types::Address;
+
+#[derive(Clone, Copy, Iden)]
+pub(super) enum GolemBaseAddressTimeseriesHourly {
+    Table,
+    Address,
+    Hour,
+    DataBytesChange,
+    EntitiesChange,
+    Creates,
+    Updates,
+    Deletes,
+    Extends,
+    Changeowners,
+}
+
+#[derive(Debug, Clone, Copy)]
 pub enum ChartResolution {
     Day,
     Hour,
@@ -96,3 +113,25 @@ pub(super) fn parse_datetime_range(
 
     Ok((from_datetime, to_datetime))
 }
+
+/// Running total of one of the hourly per-address changes, shaped like the
+/// `(timestamp, total)` rows of the global timeseries views.
+pub(super) fn address_running_total(
+    address: Address,
+    change: GolemBaseAddressTimeseriesHourly,
+    timestamp: impl IntoIden,
+    total: impl IntoIden,
+) -> SelectStatement {
+    Query::select()
+        .expr_as(Expr::col(GolemBaseAddressTimeseriesHourly::Hour), timestamp)
+        .expr_as(
+            Expr::cust(format!(
+                "GREATEST(SUM({}) OVER (ORDER BY hour ROWS UNBOUNDED PRECEDING), 0)::BIGINT",
+                change.to_string()
+            )),
+            total,
+        )
+        .from(GolemBaseAddressTimeseriesHourly::Table)
+        .and_where(Expr::col(GolemBaseAddressTimeseriesHourly::Address).eq(address.to_vec()))
+        .to_owned()
+}
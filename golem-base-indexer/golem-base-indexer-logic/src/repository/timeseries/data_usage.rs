@@ -1,11 +1,13 @@
 use anyhow::{anyhow, Context, Result};
 use chrono::{Duration, NaiveDate, NaiveDateTime, Utc};
 use sea_orm::{prelude::*, DbBackend, FromQueryResult, Statement};
-use sea_query::{ExprTrait, Iden, PostgresQueryBuilder, Query, SelectStatement};
+use sea_query::{
+    ExprTrait, Iden, IntoIden, IntoTableRef, PostgresQueryBuilder, Query, SelectStatement, TableRef,
+};
 use std::collections::HashMap;
 use tracing::instrument;
 
-use crate::types::{ChartInfo, ChartPoint};
+use crate::types::{Address, ChartInfo, ChartPoint};
 
 use super::common::*;
 
@@ -34,11 +36,12 @@ pub async fn timeseries_data_usage<T: ConnectionTrait>(
     from: Option<String>,
     to: Option<String>,
     resolution: ChartResolution,
+    address: Option<Address>,
 ) -> Result<(Vec<ChartPoint>, ChartInfo)> {
     let chart = match resolution {
         ChartResolution::Day => {
             let (from_date, to_date) = parse_date_range(from, to)?;
-            let query = build_query_data_usage_daily(from_date, to_date);
+            let query = build_query_data_usage_daily(from_date, to_date, address);
             let results = DbChartDataUsageDaily::find_by_statement(Statement::from_string(
                 DbBackend::Postgres,
                 query.to_string(PostgresQueryBuilder),
@@ -48,7 +51,7 @@ pub async fn timeseries_data_usage<T: ConnectionTrait>(
             .context("Failed to get data usage timeseries")?;
 
             let initial_value = if let Some(from_date) = from_date {
-                let lookback_query = build_query_data_usage_daily_last_value(from_date);
+                let lookback_query = build_query_data_usage_daily_last_value(from_date, address);
                 let lookback_result =
                     DbChartDataUsageDaily::find_by_statement(Statement::from_string(
                         DbBackend::Postgres,
@@ -67,7 +70,7 @@ pub async fn timeseries_data_usage<T: ConnectionTrait>(
         }
         ChartResolution::Hour => {
             let (from_datetime, to_datetime) = parse_datetime_range(from, to)?;
-            let query = build_query_data_usage_hourly(from_datetime, to_datetime);
+            let query = build_query_data_usage_hourly(from_datetime, to_datetime, address);
             let results = DbChartDataUsageHourly::find_by_statement(Statement::from_string(
                 DbBackend::Postgres,
                 query.to_string(PostgresQueryBuilder),
@@ -77,7 +80,7 @@ pub async fn timeseries_data_usage<T: ConnectionTrait>(
             .context("Failed to get data usage timeseries")?;
 
             let initial_value = if let Some(from_dt) = from_datetime {
-                let lookback_query = build_query_data_usage_hourly_last_value(from_dt);
+                let lookback_query = build_query_data_usage_hourly_last_value(from_dt, address);
                 let lookback_result =
                     DbChartDataUsageHourly::find_by_statement(Statement::from_string(
                         DbBackend::Postgres,
@@ -106,7 +109,25 @@ pub async fn timeseries_data_usage<T: ConnectionTrait>(
     Ok((chart, info))
 }
 
-fn build_query_data_usage_daily_last_value(before_date: NaiveDate) -> SelectStatement {
+fn data_usage_source(address: Option<Address>) -> TableRef {
+    match address {
+        Some(address) => TableRef::SubQuery(
+            address_running_total(
+                address,
+                GolemBaseAddressTimeseriesHourly::DataBytesChange,
+                GolemBaseTimeseriesDataUsage::Timestamp,
+                GolemBaseTimeseriesDataUsage::ActiveDataBytes,
+            ),
+            GolemBaseTimeseriesDataUsage::Table.into_iden(),
+        ),
+        None => GolemBaseTimeseriesDataUsage::Table.into_table_ref(),
+    }
+}
+
+fn build_query_data_usage_daily_last_value(
+    before_date: NaiveDate,
+    address: Option<Address>,
+) -> SelectStatement {
     Query::select()
         .expr_as(
             Expr::col(GolemBaseTimeseriesDataUsage::Timestamp).cast_as("date"),
@@ -116,7 +137,7 @@ fn build_query_data_usage_daily_last_value(before_date: NaiveDate) -> SelectStat
             Expr::max(Expr::col(GolemBaseTimeseriesDataUsage::ActiveDataBytes)),
             GolemBaseTimeseriesDataUsage::ActiveDataBytes,
         )
-        .from(GolemBaseTimeseriesDataUsage::Table)
+        .from(data_usage_source(address))
         .and_where(
             Expr::col(GolemBaseTimeseriesDataUsage::Timestamp)
                 .cast_as("date")
@@ -128,7 +149,11 @@ fn build_query_data_usage_daily_last_value(before_date: NaiveDate) -> SelectStat
         .to_owned()
 }
 
-fn build_query_data_usage_daily(from: Option<NaiveDate>, to: Option<NaiveDate>) -> SelectStatement {
+fn build_query_data_usage_daily(
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    address: Option<Address>,
+) -> SelectStatement {
     let mut query = Query::select()
         .expr_as(
             Expr::col(GolemBaseTimeseriesDataUsage::Timestamp).cast_as("date"),
@@ -138,7 +163,7 @@ fn build_query_data_usage_daily(from: Option<NaiveDate>, to: Option<NaiveDate>)
             Expr::max(Expr::col(GolemBaseTimeseriesDataUsage::ActiveDataBytes)),
             GolemBaseTimeseriesDataUsage::ActiveDataBytes,
         )
-        .from(GolemBaseTimeseriesDataUsage::Table)
+        .from(data_usage_source(address))
         .group_by_col("timestamp")
         .order_by("timestamp", sea_query::Order::Asc)
         .to_owned();
@@ -171,13 +196,16 @@ fn build_query_data_usage_daily(from: Option<NaiveDate>, to: Option<NaiveDate>)
     query
 }
 
-fn build_query_data_usage_hourly_last_value(before_datetime: NaiveDateTime) -> SelectStatement {
+fn build_query_data_usage_hourly_last_value(
+    before_datetime: NaiveDateTime,
+    address: Option<Address>,
+) -> SelectStatement {
     Query::select()
         .columns([
             GolemBaseTimeseriesDataUsage::Timestamp,
             GolemBaseTimeseriesDataUsage::ActiveDataBytes,
         ])
-        .from(GolemBaseTimeseriesDataUsage::Table)
+        .from(data_usage_source(address))
         .and_where(Expr::col(GolemBaseTimeseriesDataUsage::Timestamp).lt(before_datetime))
         .order_by(
             GolemBaseTimeseriesDataUsage::Timestamp,
@@ -190,13 +218,14 @@ fn build_query_data_usage_hourly_last_value(before_datetime: NaiveDateTime) -> S
 fn build_query_data_usage_hourly(
     from: Option<NaiveDateTime>,
     to: Option<NaiveDateTime>,
+    address: Option<Address>,
 ) -> SelectStatement {
     let mut query = Query::select()
         .columns([
             GolemBaseTimeseriesDataUsage::Timestamp,
             GolemBaseTimeseriesDataUsage::ActiveDataBytes,
         ])
-        .from(GolemBaseTimeseriesDataUsage::Table)
+        .from(data_usage_source(address))
         .order_by(
             GolemBaseTimeseriesDataUsage::Timestamp,
             sea_query::Order::Asc,
@@ -1,11 +1,13 @@
 use anyhow::{anyhow, Context, Result};
 use chrono::{Duration, NaiveDate, NaiveDateTime, Utc};
 use sea_orm::{prelude::*, DbBackend, FromQueryResult, Statement};
-use sea_query::{ExprTrait, Iden, PostgresQueryBuilder, Query, SelectStatement};
+use sea_query::{
+    ExprTrait, Iden, IntoIden, IntoTableRef, PostgresQueryBuilder, Query, SelectStatement, TableRef,
+};
 use std::collections::HashMap;
 use tracing::instrument;
 
-use crate::types::{ChartInfo, ChartPoint};
+use crate::types::{Address, ChartInfo, ChartPoint};
 
 use super::common::*;
 
@@ -39,11 +41,12 @@ pub async fn timeseries_entity_count<T: ConnectionTrait>(
     from: Option<String>,
     to: Option<String>,
     resolution: ChartResolution,
+    address: Option<Address>,
 ) -> Result<(Vec<ChartPoint>, ChartInfo)> {
     let chart = match resolution {
         ChartResolution::Day => {
             let (from_date, to_date) = parse_date_range(from, to)?;
-            let query = build_query_entity_count_daily(from_date, to_date);
+            let query = build_query_entity_count_daily(from_date, to_date, address);
             let results = DbChartEntityCountDaily::find_by_statement(Statement::from_string(
                 DbBackend::Postgres,
                 query.to_string(PostgresQueryBuilder),
@@ -53,7 +56,7 @@ pub async fn timeseries_entity_count<T: ConnectionTrait>(
             .context("Failed to get entity count timeseries")?;
 
             let initial_value = if let Some(from_date) = from_date {
-                let lookback_query = build_query_entity_count_daily_last_value(from_date);
+                let lookback_query = build_query_entity_count_daily_last_value(from_date, address);
                 let lookback_result =
                     DbChartEntityCountDaily::find_by_statement(Statement::from_string(
                         DbBackend::Postgres,
@@ -72,7 +75,7 @@ pub async fn timeseries_entity_count<T: ConnectionTrait>(
         }
         ChartResolution::Hour => {
             let (from_datetime, to_datetime) = parse_datetime_range(from, to)?;
-            let query = build_query_entity_count_hourly(from_datetime, to_datetime);
+            let query = build_query_entity_count_hourly(from_datetime, to_datetime, address);
             let results = DbChartEntityCountHourly::find_by_statement(Statement::from_string(
                 DbBackend::Postgres,
                 query.to_string(PostgresQueryBuilder),
@@ -82,7 +85,7 @@ pub async fn timeseries_entity_count<T: ConnectionTrait>(
             .context("Failed to get entity count timeseries")?;
 
             let initial_value = if let Some(from_dt) = from_datetime {
-                let lookback_query = build_query_entity_count_hourly_last_value(from_dt);
+                let lookback_query = build_query_entity_count_hourly_last_value(from_dt, address);
                 let lookback_result =
                     DbChartEntityCountHourly::find_by_statement(Statement::from_string(
                         DbBackend::Postgres,
@@ -111,7 +114,25 @@ pub async fn timeseries_entity_count<T: ConnectionTrait>(
     Ok((chart, info))
 }
 
-fn build_query_entity_count_daily_last_value(before_date: NaiveDate) -> SelectStatement {
+fn entity_count_source(address: Option<Address>) -> TableRef {
+    match address {
+        Some(address) => TableRef::SubQuery(
+            address_running_total(
+                address,
+                GolemBaseAddressTimeseriesHourly::EntitiesChange,
+                GolemBaseTimeseriesEntityCount::Timestamp,
+                GolemBaseTimeseriesEntityCount::TotalEntities,
+            ),
+            GolemBaseTimeseriesEntityCount::Table.into_iden(),
+        ),
+        None => GolemBaseTimeseriesEntityCount::Table.into_table_ref(),
+    }
+}
+
+fn build_query_entity_count_daily_last_value(
+    before_date: NaiveDate,
+    address: Option<Address>,
+) -> SelectStatement {
     Query::select()
         .expr_as(
             Expr::col(GolemBaseTimeseriesEntityCount::Timestamp).cast_as("date"),
@@ -121,7 +142,7 @@ fn build_query_entity_count_daily_last_value(before_date: NaiveDate) -> SelectSt
             Expr::max(Expr::col(GolemBaseTimeseriesEntityCount::TotalEntities)),
             GolemBaseTimeseriesEntityCount::TotalEntities,
         )
-        .from(GolemBaseTimeseriesEntityCount::Table)
+        .from(entity_count_source(address))
         .and_where(
             Expr::col(GolemBaseTimeseriesEntityCount::Timestamp)
                 .cast_as("date")
@@ -136,6 +157,7 @@ fn build_query_entity_count_daily_last_value(before_date: NaiveDate) -> SelectSt
 fn build_query_entity_count_daily(
     from: Option<NaiveDate>,
     to: Option<NaiveDate>,
+    address: Option<Address>,
 ) -> SelectStatement {
     let mut query = Query::select()
         .expr_as(
@@ -146,7 +168,7 @@ fn build_query_entity_count_daily(
             Expr::max(Expr::col(GolemBaseTimeseriesEntityCount::TotalEntities)),
             GolemBaseTimeseriesEntityCount::TotalEntities,
         )
-        .from(GolemBaseTimeseriesEntityCount::Table)
+        .from(entity_count_source(address))
         .group_by_col("timestamp")
         .order_by("timestamp", sea_query::Order::Asc)
         .to_owned();
@@ -179,13 +201,16 @@ fn build_query_entity_count_daily(
     query
 }
 
-fn build_query_entity_count_hourly_last_value(before_datetime: NaiveDateTime) -> SelectStatement {
+fn build_query_entity_count_hourly_last_value(
+    before_datetime: NaiveDateTime,
+    address: Option<Address>,
+) -> SelectStatement {
     Query::select()
         .columns([
             GolemBaseTimeseriesEntityCount::Timestamp,
             GolemBaseTimeseriesEntityCount::TotalEntities,
         ])
-        .from(GolemBaseTimeseriesEntityCount::Table)
+        .from(entity_count_source(address))
         .and_where(Expr::col(GolemBaseTimeseriesEntityCount::Timestamp).lt(before_datetime))
         .order_by(
             GolemBaseTimeseriesEntityCount::Timestamp,
@@ -198,13 +223,14 @@ fn build_query_entity_count_hourly_last_value(before_datetime: NaiveDateTime) ->
 fn build_query_entity_count_hourly(
     from: Option<NaiveDateTime>,
     to: Option<NaiveDateTime>,
+    address: Option<Address>,
 ) -> SelectStatement {
     let mut query = Query::select()
         .columns([
             GolemBaseTimeseriesEntityCount::Timestamp,
             GolemBaseTimeseriesEntityCount::TotalEntities,
         ])
-        .from(GolemBaseTimeseriesEntityCount::Table)
+        .from(entity_count_source(address))
         .order_by(
             GolemBaseTimeseriesEntityCount::Timestamp,
             sea_query::Order::Asc,
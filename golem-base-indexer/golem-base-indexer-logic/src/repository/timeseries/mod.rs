@@ -1,8 +1,10 @@
 pub mod block_gas_usage_limit;
 pub mod block_operations;
 pub mod block_transactions;
+pub mod breakdown;
 mod common;
 pub mod data_usage;
 pub mod entity_count;
 pub mod operation_count;
+pub mod spend;
 pub mod storage_forecast;
@@ -1,11 +1,14 @@
 use anyhow::{anyhow, Context, Result};
 use chrono::{Duration, NaiveDate, NaiveDateTime, Utc};
 use sea_orm::{prelude::*, DbBackend, FromQueryResult, Statement};
-use sea_query::{ExprTrait, Iden, PostgresQueryBuilder, Query, SelectStatement};
+use sea_query::{
+    ExprTrait, Iden, IntoIden, IntoTableRef, PostgresQueryBuilder, Query, SelectStatement,
+    TableRef, UnionType,
+};
 use std::collections::HashMap;
 use tracing::instrument;
 
-use crate::types::{ChartInfo, ChartPoint, OperationType};
+use crate::types::{Address, ChartInfo, ChartPoint, OperationType};
 
 use super::common::*;
 
@@ -41,6 +44,61 @@ impl OperationType {
     }
 }
 
+fn operation_count_source(address: Option<Address>) -> TableRef {
+    let Some(address) = address else {
+        return GolemBaseTimeseriesOperationCount::Table.into_table_ref();
+    };
+
+    let mut counts = [
+        (
+            OperationType::Create,
+            GolemBaseAddressTimeseriesHourly::Creates,
+        ),
+        (
+            OperationType::Update,
+            GolemBaseAddressTimeseriesHourly::Updates,
+        ),
+        (
+            OperationType::Delete,
+            GolemBaseAddressTimeseriesHourly::Deletes,
+        ),
+        (
+            OperationType::Extend,
+            GolemBaseAddressTimeseriesHourly::Extends,
+        ),
+        (
+            OperationType::ChangeOwner,
+            GolemBaseAddressTimeseriesHourly::Changeowners,
+        ),
+    ]
+    .into_iter()
+    .map(|(operation, count)| {
+        Query::select()
+            .expr_as(
+                Expr::col(GolemBaseAddressTimeseriesHourly::Hour),
+                GolemBaseTimeseriesOperationCount::Timestamp,
+            )
+            .expr_as(
+                Expr::val(operation.as_sql_string()),
+                GolemBaseTimeseriesOperationCount::Operation,
+            )
+            .expr_as(
+                Expr::col(count),
+                GolemBaseTimeseriesOperationCount::OperationCount,
+            )
+            .from(GolemBaseAddressTimeseriesHourly::Table)
+            .and_where(Expr::col(GolemBaseAddressTimeseriesHourly::Address).eq(address.to_vec()))
+            .and_where(Expr::col(count).gt(0))
+            .to_owned()
+    });
+
+    let mut query = counts.next().expect("operation types are not empty");
+    for count in counts {
+        query.union(UnionType::All, count);
+    }
+    TableRef::SubQuery(query, GolemBaseTimeseriesOperationCount::Table.into_iden())
+}
+
 #[instrument(skip(db))]
 pub async fn timeseries_operation_count<T: ConnectionTrait>(
     db: &T,
@@ -48,11 +106,12 @@ pub async fn timeseries_operation_count<T: ConnectionTrait>(
     to: Option<String>,
     resolution: ChartResolution,
     operation: Option<OperationType>,
+    address: Option<Address>,
 ) -> Result<(Vec<ChartPoint>, ChartInfo)> {
     let chart = match resolution {
         ChartResolution::Day => {
             let (from_date, to_date) = parse_date_range(from, to)?;
-            let query = build_query_operation_count_daily(from_date, to_date, operation);
+            let query = build_query_operation_count_daily(from_date, to_date, operation, address);
             let results = DbChartOperationCountDaily::find_by_statement(Statement::from_string(
                 DbBackend::Postgres,
                 query.to_string(PostgresQueryBuilder),
@@ -62,7 +121,8 @@ pub async fn timeseries_operation_count<T: ConnectionTrait>(
             .context("Failed to get operation count timeseries daily")?;
 
             let initial_value = if let Some(from_date) = from_date {
-                let lookback_query = build_query_operation_count_daily_last_value(from_date);
+                let lookback_query =
+                    build_query_operation_count_daily_last_value(from_date, address);
                 let lookback_result =
                     DbChartOperationCountDaily::find_by_statement(Statement::from_string(
                         DbBackend::Postgres,
@@ -81,7 +141,8 @@ pub async fn timeseries_operation_count<T: ConnectionTrait>(
         }
         ChartResolution::Hour => {
             let (from_datetime, to_datetime) = parse_datetime_range(from, to)?;
-            let query = build_query_operation_count_hourly(from_datetime, to_datetime, operation);
+            let query =
+                build_query_operation_count_hourly(from_datetime, to_datetime, operation, address);
             let results = DbChartOperationCountHourly::find_by_statement(Statement::from_string(
                 DbBackend::Postgres,
                 query.to_string(PostgresQueryBuilder),
@@ -92,7 +153,7 @@ pub async fn timeseries_operation_count<T: ConnectionTrait>(
 
             let initial_value = if let Some(from_dt) = from_datetime {
                 let lookback_query =
-                    build_query_operation_count_hourly_last_value(from_dt, operation);
+                    build_query_operation_count_hourly_last_value(from_dt, operation, address);
                 let lookback_result =
                     DbChartOperationCountHourly::find_by_statement(Statement::from_string(
                         DbBackend::Postgres,
@@ -126,7 +187,10 @@ pub async fn timeseries_operation_count<T: ConnectionTrait>(
     Ok((chart, info))
 }
 
-fn build_query_operation_count_daily_last_value(before_date: NaiveDate) -> SelectStatement {
+fn build_query_operation_count_daily_last_value(
+    before_date: NaiveDate,
+    address: Option<Address>,
+) -> SelectStatement {
     Query::select()
         .expr_as(
             Expr::col(GolemBaseTimeseriesOperationCount::Timestamp).cast_as("date"),
@@ -136,7 +200,7 @@ fn build_query_operation_count_daily_last_value(before_date: NaiveDate) -> Selec
             Expr::max(Expr::col(GolemBaseTimeseriesOperationCount::OperationCount)),
             GolemBaseTimeseriesOperationCount::OperationCount,
         )
-        .from(GolemBaseTimeseriesOperationCount::Table)
+        .from(operation_count_source(address))
         .and_where(
             Expr::col(GolemBaseTimeseriesOperationCount::Timestamp)
                 .cast_as("date")
@@ -152,13 +216,14 @@ fn build_query_operation_count_daily(
     from: Option<NaiveDate>,
     to: Option<NaiveDate>,
     operation: Option<OperationType>,
+    address: Option<Address>,
 ) -> SelectStatement {
     let mut query = Query::select()
         .expr_as(
             Expr::col(GolemBaseTimeseriesOperationCount::Timestamp).cast_as("date"),
             "timestamp",
         )
-        .from(GolemBaseTimeseriesOperationCount::Table)
+        .from(operation_count_source(address))
         .group_by_col("timestamp")
         .order_by("timestamp", sea_query::Order::Asc)
         .to_owned();
@@ -209,10 +274,11 @@ fn build_query_operation_count_daily(
 fn build_query_operation_count_hourly_last_value(
     before_datetime: NaiveDateTime,
     operation: Option<OperationType>,
+    address: Option<Address>,
 ) -> SelectStatement {
     let mut query = Query::select()
         .columns([GolemBaseTimeseriesOperationCount::Timestamp])
-        .from(GolemBaseTimeseriesOperationCount::Table)
+        .from(operation_count_source(address))
         .and_where(Expr::col(GolemBaseTimeseriesOperationCount::Timestamp).lt(before_datetime))
         .group_by_col("timestamp")
         .order_by(
@@ -244,11 +310,12 @@ fn build_query_operation_count_hourly(
     from: Option<NaiveDateTime>,
     to: Option<NaiveDateTime>,
     operation: Option<OperationType>,
+    address: Option<Address>,
 ) -> SelectStatement {
     let mut query = Query::select()
         .columns([GolemBaseTimeseriesOperationCount::Timestamp])
         .group_by_col("timestamp")
-        .from(GolemBaseTimeseriesOperationCount::Table)
+        .from(operation_count_source(address))
         .order_by(
             GolemBaseTimeseriesOperationCount::Timestamp,
             sea_query::Order::Asc,
@@ -0,0 +1,267 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::{Duration, NaiveDate, NaiveDateTime, Utc};
+use sea_orm::{prelude::*, DbBackend, FromQueryResult, Statement};
+use sea_query::{ExprTrait, Iden, PostgresQueryBuilder, Query, SelectStatement};
+use std::collections::HashMap;
+use tracing::instrument;
+
+use crate::types::{Address, ChartInfo, ChartPoint};
+
+use super::common::*;
+
+#[derive(Iden)]
+pub enum GolemBaseAddressLeaderboardHourlyStats {
+    Table,
+    Hour,
+    Address,
+    TotalFees,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct DbChartSpendDaily {
+    pub timestamp: NaiveDate,
+    pub total_fees: String,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct DbChartSpendHourly {
+    pub timestamp: NaiveDateTime,
+    pub total_fees: String,
+}
+
+#[instrument(skip(db))]
+pub async fn timeseries_spend<T: ConnectionTrait>(
+    db: &T,
+    from: Option<String>,
+    to: Option<String>,
+    resolution: ChartResolution,
+    address: Option<Address>,
+) -> Result<(Vec<ChartPoint>, ChartInfo)> {
+    let chart = match resolution {
+        ChartResolution::Day => {
+            let (from_date, to_date) = parse_date_range(from, to)?;
+            let query = build_query_spend_daily(from_date, to_date, address);
+            let results = DbChartSpendDaily::find_by_statement(Statement::from_string(
+                DbBackend::Postgres,
+                query.to_string(PostgresQueryBuilder),
+            ))
+            .all(db)
+            .await
+            .context("Failed to get spend timeseries")?;
+
+            generate_points_spend_daily(results, from_date, to_date)
+        }
+        ChartResolution::Hour => {
+            let (from_datetime, to_datetime) = parse_datetime_range(from, to)?;
+            let query = build_query_spend_hourly(from_datetime, to_datetime, address);
+            let results = DbChartSpendHourly::find_by_statement(Statement::from_string(
+                DbBackend::Postgres,
+                query.to_string(PostgresQueryBuilder),
+            ))
+            .all(db)
+            .await
+            .context("Failed to get spend timeseries")?;
+
+            generate_points_spend_hourly(results, from_datetime, to_datetime)
+        }
+        _ => return Err(anyhow!("Unsupported chart resolution")),
+    };
+
+    let info = ChartInfo {
+        id: "golemBaseSpend".to_string(),
+        title: "Spend over time".to_string(),
+        description: "Transaction fees paid over time".to_string(),
+    };
+
+    Ok((chart, info))
+}
+
+fn build_query_spend_daily(
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    address: Option<Address>,
+) -> SelectStatement {
+    let mut query = Query::select()
+        .expr_as(
+            Expr::col(GolemBaseAddressLeaderboardHourlyStats::Hour).cast_as("date"),
+            "timestamp",
+        )
+        .expr_as(
+            Expr::sum(Expr::col(GolemBaseAddressLeaderboardHourlyStats::TotalFees)).cast_as("text"),
+            GolemBaseAddressLeaderboardHourlyStats::TotalFees,
+        )
+        .from(GolemBaseAddressLeaderboardHourlyStats::Table)
+        .and_where_option(address.map(|address| {
+            Expr::col(GolemBaseAddressLeaderboardHourlyStats::Address).eq(address.to_vec())
+        }))
+        .group_by_col("timestamp")
+        .order_by("timestamp", sea_query::Order::Asc)
+        .to_owned();
+
+    match (from, to) {
+        (Some(from_date), Some(to_date)) => {
+            query.and_where(
+                Expr::col(GolemBaseAddressLeaderboardHourlyStats::Hour)
+                    .cast_as("date")
+                    .between(from_date, to_date),
+            );
+        }
+        (Some(from_date), None) => {
+            query.and_where(
+                Expr::col(GolemBaseAddressLeaderboardHourlyStats::Hour)
+                    .cast_as("date")
+                    .gte(from_date),
+            );
+        }
+        (None, Some(to_date)) => {
+            query.and_where(
+                Expr::col(GolemBaseAddressLeaderboardHourlyStats::Hour)
+                    .cast_as("date")
+                    .lte(to_date),
+            );
+        }
+        (None, None) => {}
+    }
+
+    query
+}
+
+fn build_query_spend_hourly(
+    from: Option<NaiveDateTime>,
+    to: Option<NaiveDateTime>,
+    address: Option<Address>,
+) -> SelectStatement {
+    let mut query = Query::select()
+        .expr_as(
+            Expr::col(GolemBaseAddressLeaderboardHourlyStats::Hour),
+            "timestamp",
+        )
+        .expr_as(
+            Expr::sum(Expr::col(GolemBaseAddressLeaderboardHourlyStats::TotalFees)).cast_as("text"),
+            GolemBaseAddressLeaderboardHourlyStats::TotalFees,
+        )
+        .from(GolemBaseAddressLeaderboardHourlyStats::Table)
+        .and_where_option(address.map(|address| {
+            Expr::col(GolemBaseAddressLeaderboardHourlyStats::Address).eq(address.to_vec())
+        }))
+        .group_by_col(GolemBaseAddressLeaderboardHourlyStats::Hour)
+        .order_by(
+            GolemBaseAddressLeaderboardHourlyStats::Hour,
+            sea_query::Order::Asc,
+        )
+        .to_owned();
+
+    match (from, to) {
+        (Some(from_datetime), Some(to_datetime)) => {
+            query.and_where(
+                Expr::col(GolemBaseAddressLeaderboardHourlyStats::Hour)
+                    .between(from_datetime, to_datetime),
+            );
+        }
+        (Some(from_datetime), None) => {
+            query.and_where(
+                Expr::col(GolemBaseAddressLeaderboardHourlyStats::Hour).gte(from_datetime),
+            );
+        }
+        (None, Some(to_datetime)) => {
+            query.and_where(
+                Expr::col(GolemBaseAddressLeaderboardHourlyStats::Hour).lte(to_datetime),
+            );
+        }
+        (None, None) => {}
+    }
+
+    query
+}
+
+/// Unlike the running totals of the other charts, periods without any
+/// transactions are zero.
+fn generate_points_spend_daily(
+    db_results: Vec<DbChartSpendDaily>,
+    from_date: Option<NaiveDate>,
+    to_date: Option<NaiveDate>,
+) -> Vec<ChartPoint> {
+    let data_map: HashMap<NaiveDate, String> = db_results
+        .into_iter()
+        .map(|row| (row.timestamp, row.total_fees))
+        .collect();
+
+    let start_date = match from_date {
+        Some(date) => date,
+        None => data_map
+            .keys()
+            .min()
+            .copied()
+            .unwrap_or_else(|| Utc::now().naive_utc().date()),
+    };
+
+    let end_date = match to_date {
+        Some(date) => date,
+        None => Utc::now().naive_utc().date(),
+    };
+
+    let mut points = Vec::new();
+    let mut current_date = start_date;
+
+    while current_date < end_date {
+        let next_date = current_date + Duration::days(1);
+
+        points.push(ChartPoint {
+            date: current_date.format("%Y-%m-%d").to_string(),
+            date_to: next_date.format("%Y-%m-%d").to_string(),
+            value: data_map
+                .get(&current_date)
+                .cloned()
+                .unwrap_or_else(|| "0".to_string()),
+        });
+
+        current_date = next_date;
+    }
+
+    points
+}
+
+fn generate_points_spend_hourly(
+    db_results: Vec<DbChartSpendHourly>,
+    from_datetime: Option<NaiveDateTime>,
+    to_datetime: Option<NaiveDateTime>,
+) -> Vec<ChartPoint> {
+    let data_map: HashMap<NaiveDateTime, String> = db_results
+        .into_iter()
+        .map(|row| (row.timestamp, row.total_fees))
+        .collect();
+
+    let start_time = match from_datetime {
+        Some(dt) => dt,
+        None => data_map
+            .keys()
+            .min()
+            .copied()
+            .unwrap_or_else(|| Utc::now().naive_utc()),
+    };
+
+    let end_time = match to_datetime {
+        Some(dt) => dt,
+        None => Utc::now().naive_utc(),
+    };
+
+    let mut points = Vec::new();
+    let mut current_time = start_time;
+
+    while current_time < end_time {
+        let next_hour = current_time + Duration::hours(1);
+
+        points.push(ChartPoint {
+            date: current_time.format("%Y-%m-%d %H:%M").to_string(),
+            date_to: next_hour.format("%Y-%m-%d %H:%M").to_string(),
+            value: data_map
+                .get(&current_time)
+                .cloned()
+                .unwrap_or_else(|| "0".to_string()),
+        });
+
+        current_time = next_hour;
+    }
+
+    points
+}
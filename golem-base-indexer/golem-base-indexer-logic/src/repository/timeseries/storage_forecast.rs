@@ -5,7 +5,10 @@ use sea_query::{Iden, PostgresQueryBuilder, Query, SelectStatement};
 use std::collections::HashMap;
 use tracing::instrument;
 
-use crate::types::{ChartInfo, ChartPoint};
+use crate::{
+    repository::sql,
+    types::{Address, ChartInfo, ChartPoint},
+};
 
 use super::common::*;
 
@@ -27,20 +30,19 @@ pub async fn timeseries_storage_forecast<T: ConnectionTrait>(
     db: &T,
     to: &str,
     resolution: ChartResolution,
+    address: Option<Address>,
 ) -> Result<(Vec<ChartPoint>, ChartInfo)> {
     let chart = match resolution {
         ChartResolution::Day | ChartResolution::Week | ChartResolution::Month => {
             let (_, to_date) = parse_date_range(None, Some(to.to_string()))?;
-            let query = build_query_storage_forecast_hourly(
+            let statement = storage_forecast_statement(
                 to_date.unwrap().and_hms_opt(23, 59, 59).unwrap(),
+                address,
             );
-            let results = DbChartStorageForecastHourly::find_by_statement(Statement::from_string(
-                DbBackend::Postgres,
-                query.to_string(PostgresQueryBuilder),
-            ))
-            .all(db)
-            .await
-            .context("Failed to get storage forecast timeseries")?;
+            let results = DbChartStorageForecastHourly::find_by_statement(statement)
+                .all(db)
+                .await
+                .context("Failed to get storage forecast timeseries")?;
 
             let interval = match resolution {
                 ChartResolution::Week => Duration::days(7),
@@ -52,14 +54,11 @@ pub async fn timeseries_storage_forecast<T: ConnectionTrait>(
         }
         ChartResolution::Hour => {
             let (_, to_datetime) = parse_datetime_range(None, Some(to.to_string()))?;
-            let query = build_query_storage_forecast_hourly(to_datetime.unwrap());
-            let results = DbChartStorageForecastHourly::find_by_statement(Statement::from_string(
-                DbBackend::Postgres,
-                query.to_string(PostgresQueryBuilder),
-            ))
-            .all(db)
-            .await
-            .context("Failed to get storage forecast timeseries")?;
+            let statement = storage_forecast_statement(to_datetime.unwrap(), address);
+            let results = DbChartStorageForecastHourly::find_by_statement(statement)
+                .all(db)
+                .await
+                .context("Failed to get storage forecast timeseries")?;
 
             generate_points_storage_forecast_hourly(results, to_datetime.unwrap())?
         }
@@ -74,6 +73,22 @@ pub async fn timeseries_storage_forecast<T: ConnectionTrait>(
     Ok((chart, info))
 }
 
+/// The global forecast comes from the materialized view, while the forecast for
+/// a single owner is computed from their currently active entities.
+fn storage_forecast_statement(to_datetime: NaiveDateTime, address: Option<Address>) -> Statement {
+    match address {
+        Some(address) => Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            sql::ADDRESS_STORAGE_FORECAST,
+            [address.as_slice().into(), to_datetime.into()],
+        ),
+        None => Statement::from_string(
+            DbBackend::Postgres,
+            build_query_storage_forecast_hourly(to_datetime).to_string(PostgresQueryBuilder),
+        ),
+    }
+}
+
 fn build_query_storage_forecast_hourly(to_datetime: NaiveDateTime) -> SelectStatement {
     Query::select()
         .columns([
@@ -2,13 +2,17 @@ use anyhow::{Context, Result};
 use golem_base_indexer_entity::transactions::{
     self, Entity as TransactionsEntity, Model as TransactionsModel,
 };
-use sea_orm::{entity::prelude::*, Condition, QueryOrder, Statement};
+use sea_orm::{entity::prelude::*, Condition, DbBackend, FromQueryResult, QueryOrder, Statement};
 use std::str::FromStr;
 use tracing::instrument;
 
+use super::sql;
 use crate::{
     pagination::paginate_try_from,
-    types::{CurrencyAmount, PaginationMetadata, PaginationParams, Transaction, TxHash},
+    types::{
+        ConsensusTx, CurrencyAmount, DecodingError, PaginationMetadata, PaginationParams,
+        Transaction, TxHash, UndecodableTransaction, UndecodableTransactionsFilter,
+    },
     well_known::{
         DEPOSIT_CONTRACT_ADDRESS, GOLEM_BASE_STORAGE_PROCESSOR_ADDRESS, L1_BLOCK_CONTRACT_ADDRESS,
         L1_BLOCK_CONTRACT_SENDER_ADDRESS,
@@ -61,6 +65,35 @@ impl TryFrom<TransactionsModel> for Transaction {
     }
 }
 
+#[derive(Debug, FromQueryResult)]
+struct DbUndecodableTransaction {
+    hash: Vec<u8>,
+    sender: Vec<u8>,
+    block_hash: Vec<u8>,
+    block_number: i64,
+    tx_index: i32,
+    error_kind: String,
+    error: String,
+}
+
+impl TryFrom<DbUndecodableTransaction> for UndecodableTransaction {
+    type Error = anyhow::Error;
+
+    fn try_from(value: DbUndecodableTransaction) -> Result<Self> {
+        Ok(Self {
+            hash: value.hash.as_slice().try_into()?,
+            sender: value.sender.as_slice().try_into()?,
+            block_hash: value.block_hash.as_slice().try_into()?,
+            block_number: value.block_number.try_into()?,
+            index: value.tx_index.try_into()?,
+            error: DecodingError {
+                kind: value.error_kind.parse()?,
+                message: value.error,
+            },
+        })
+    }
+}
+
 #[instrument(skip(db))]
 pub async fn finish_tx_processing<T: ConnectionTrait>(db: &T, tx_hash: TxHash) -> Result<()> {
     let tx_hash: Vec<u8> = tx_hash.as_slice().into();
@@ -94,6 +127,67 @@ pub async fn finish_tx_cleanup<T: ConnectionTrait>(db: &T, tx_hash: TxHash) -> R
     Ok(())
 }
 
+#[instrument(skip(db, tx))]
+pub async fn insert_undecodable<T: ConnectionTrait>(
+    db: &T,
+    tx: &ConsensusTx,
+    error: &DecodingError,
+) -> Result<()> {
+    let block_number: i64 = tx.block_number.try_into()?;
+    let index: i32 = tx.index.try_into()?;
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        sql::INSERT_UNDECODABLE_TRANSACTION,
+        [
+            tx.hash.as_slice().into(),
+            tx.from_address_hash.as_slice().into(),
+            tx.block_hash.as_slice().into(),
+            block_number.into(),
+            index.into(),
+            error.kind.as_str().into(),
+            error.message.clone().into(),
+        ],
+    ))
+    .await
+    .context("Failed to insert undecodable transaction")?;
+    Ok(())
+}
+
+/// Records what the txs paid for their senders' spend again, taking back what was
+/// recorded for them before. Txs no longer in a canonical block pay nothing.
+#[instrument(skip(db))]
+pub async fn record_fees<T: ConnectionTrait>(db: &T, hashes: &[TxHash]) -> Result<()> {
+    let hashes: Vec<Vec<u8>> = hashes.iter().map(|v| v.as_slice().into()).collect();
+    for query in [sql::DELETE_TRANSACTION_FEES, sql::RECORD_TRANSACTION_FEES] {
+        db.execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            query,
+            [hashes.clone().into()],
+        ))
+        .await
+        .context("Failed to record transaction fees")?;
+    }
+    Ok(())
+}
+
+#[instrument(skip(db))]
+pub async fn list_undecodable<T: ConnectionTrait>(
+    db: &T,
+    filter: UndecodableTransactionsFilter,
+) -> Result<(Vec<UndecodableTransaction>, PaginationMetadata)> {
+    let sender: Option<Vec<u8>> = filter.sender.map(|v| v.as_slice().into());
+    let paginator = DbUndecodableTransaction::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        sql::LIST_UNDECODABLE_TRANSACTIONS,
+        [sender.into()],
+    ))
+    .paginate(db, filter.pagination.page_size);
+
+    paginate_try_from(paginator, filter.pagination)
+        .await
+        .context("Failed to list undecodable transactions")
+}
+
 #[instrument(skip(db))]
 pub async fn list_custom_contract_transactions<T: ConnectionTrait>(
     db: &T,
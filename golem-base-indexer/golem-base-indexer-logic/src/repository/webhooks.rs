@@ -0,0 +1,282 @@
+use anyhow::{Context, Result};
+use golem_base_indexer_entity::{golem_base_webhook_deliveries, golem_base_webhooks};
+use sea_orm::{prelude::*, ActiveValue::Set, DbBackend, FromQueryResult, QueryOrder, Statement};
+use std::time::Duration;
+use tracing::instrument;
+
+use crate::{
+    pagination::paginate_try_from,
+    repository::sql,
+    types::{
+        ExhaustedWebhookDelivery, HistoryEntryEvent, NewWebhook, PaginationMetadata,
+        PaginationParams, StringAttribute, Webhook, WebhookDelivery, WebhookEvent, WebhookFilter,
+    },
+};
+
+#[derive(FromQueryResult)]
+struct DbWebhookDelivery {
+    id: i64,
+    url: String,
+    secret: String,
+    event: String,
+    payload: Json,
+    attempts: i32,
+}
+
+impl TryFrom<golem_base_webhooks::Model> for Webhook {
+    type Error = anyhow::Error;
+
+    fn try_from(value: golem_base_webhooks::Model) -> Result<Self> {
+        Ok(Self {
+            id: value.id.try_into()?,
+            url: value.url,
+            filter: WebhookFilter {
+                owner: value.owner.map(|v| v.as_slice().try_into()).transpose()?,
+                entity_key: value
+                    .entity_key
+                    .map(|v| v.as_slice().try_into())
+                    .transpose()?,
+                annotation: value
+                    .annotation_key
+                    .zip(value.annotation_value)
+                    .map(|(key, value)| StringAttribute { key, value }),
+            },
+            created_at: value.inserted_at.and_utc(),
+        })
+    }
+}
+
+impl TryFrom<DbWebhookDelivery> for WebhookDelivery {
+    type Error = anyhow::Error;
+
+    fn try_from(value: DbWebhookDelivery) -> Result<Self> {
+        Ok(Self {
+            id: value.id.try_into()?,
+            url: value.url,
+            secret: value.secret,
+            event: value.event,
+            payload: value.payload,
+            attempts: value.attempts.try_into()?,
+        })
+    }
+}
+
+impl TryFrom<golem_base_webhook_deliveries::Model> for ExhaustedWebhookDelivery {
+    type Error = anyhow::Error;
+
+    fn try_from(value: golem_base_webhook_deliveries::Model) -> Result<Self> {
+        Ok(Self {
+            id: value.id.try_into()?,
+            webhook_id: value.webhook_id.try_into()?,
+            event: value.event,
+            payload: value.payload,
+            attempts: value.attempts.try_into()?,
+            last_error: value.last_error,
+            created_at: value.inserted_at.and_utc(),
+        })
+    }
+}
+
+#[instrument(skip(db))]
+pub async fn create_webhook<T: ConnectionTrait>(db: &T, webhook: NewWebhook) -> Result<Webhook> {
+    let (annotation_key, annotation_value) =
+        webhook.filter.annotation.map(|v| (v.key, v.value)).unzip();
+    golem_base_webhooks::ActiveModel {
+        url: Set(webhook.url),
+        secret: Set(webhook.secret),
+        owner: Set(webhook.filter.owner.map(|v| v.as_slice().into())),
+        entity_key: Set(webhook.filter.entity_key.map(|v| v.as_slice().into())),
+        annotation_key: Set(annotation_key),
+        annotation_value: Set(annotation_value),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .context("Failed to insert webhook")?
+    .try_into()
+}
+
+#[instrument(skip(db))]
+pub async fn list_webhooks<T: ConnectionTrait>(
+    db: &T,
+    pagination: PaginationParams,
+) -> Result<(Vec<Webhook>, PaginationMetadata)> {
+    let paginator = golem_base_webhooks::Entity::find()
+        .order_by_asc(golem_base_webhooks::Column::Id)
+        .paginate(db, pagination.page_size);
+
+    paginate_try_from(paginator, pagination)
+        .await
+        .context("Failed to list webhooks")
+}
+
+/// Returns whether the webhook existed.
+#[instrument(skip(db))]
+pub async fn delete_webhook<T: ConnectionTrait>(db: &T, id: u64) -> Result<bool> {
+    let id: i64 = id.try_into()?;
+    let res = golem_base_webhooks::Entity::delete_by_id(id)
+        .exec(db)
+        .await
+        .context("Failed to delete webhook")?;
+    Ok(res.rows_affected > 0)
+}
+
+/// Queues a delivery of the event to every webhook whose filter matches it.
+#[instrument(skip_all)]
+pub async fn enqueue_deliveries<T: ConnectionTrait>(
+    db: &T,
+    event: &HistoryEntryEvent,
+) -> Result<()> {
+    let entry = &event.entry;
+    let webhook_event = WebhookEvent::from(entry);
+    let (keys, values): (Vec<String>, Vec<String>) = event
+        .string_attributes
+        .iter()
+        .map(|v| (v.key.clone(), v.value.clone()))
+        .chain(
+            event
+                .numeric_attributes
+                .iter()
+                .map(|v| (v.key.clone(), v.value.to_string())),
+        )
+        .unzip();
+    let payload = serde_json::json!({
+        "event": webhook_event.as_str(),
+        "entity_key": entry.entity_key,
+        "block_number": entry.block_number,
+        "block_hash": entry.block_hash,
+        "transaction_hash": entry.transaction_hash,
+        "tx_index": entry.tx_index,
+        "op_index": entry.op_index,
+        "block_timestamp": entry.block_timestamp.to_rfc3339(),
+        "sender": entry.sender,
+        "owner": entry.owner,
+        "prev_owner": entry.prev_owner,
+        "expires_at_block_number": entry.expires_at_block_number,
+    });
+
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        sql::ENQUEUE_WEBHOOK_DELIVERIES,
+        [
+            webhook_event.as_str().into(),
+            payload.into(),
+            entry.entity_key.as_slice().to_vec().into(),
+            entry.owner.map(|v| v.as_slice().to_vec()).into(),
+            entry.prev_owner.map(|v| v.as_slice().to_vec()).into(),
+            keys.into(),
+            values.into(),
+        ],
+    ))
+    .await
+    .context("Failed to enqueue webhook deliveries")?;
+
+    Ok(())
+}
+
+/// Claims deliveries that are due for `lease`, so that other replicas skip them
+/// while they're being sent.
+#[instrument(skip(db))]
+pub async fn claim_due_deliveries<T: ConnectionTrait>(
+    db: &T,
+    max_attempts: u32,
+    limit: u64,
+    lease: Duration,
+) -> Result<Vec<WebhookDelivery>> {
+    DbWebhookDelivery::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        sql::CLAIM_DUE_WEBHOOK_DELIVERIES,
+        [
+            max_attempts.into(),
+            limit.into(),
+            lease.as_secs_f64().into(),
+        ],
+    ))
+    .all(db)
+    .await
+    .context("Failed to claim due webhook deliveries")?
+    .into_iter()
+    .map(TryInto::try_into)
+    .collect()
+}
+
+#[instrument(skip(db))]
+pub async fn finish_delivery<T: ConnectionTrait>(db: &T, id: u64) -> Result<()> {
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        sql::FINISH_WEBHOOK_DELIVERY,
+        [id.into()],
+    ))
+    .await
+    .context("Failed to mark webhook delivery as delivered")?;
+    Ok(())
+}
+
+/// Records a failed attempt and schedules the next one after `retry_in`.
+#[instrument(skip(db))]
+pub async fn fail_delivery<T: ConnectionTrait>(
+    db: &T,
+    id: u64,
+    error: String,
+    retry_in: Duration,
+) -> Result<()> {
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        sql::FAIL_WEBHOOK_DELIVERY,
+        [id.into(), error.into(), retry_in.as_secs_f64().into()],
+    ))
+    .await
+    .context("Failed to record webhook delivery failure")?;
+    Ok(())
+}
+
+#[instrument(skip(db))]
+pub async fn list_exhausted_deliveries<T: ConnectionTrait>(
+    db: &T,
+    max_attempts: u32,
+    pagination: PaginationParams,
+) -> Result<(Vec<ExhaustedWebhookDelivery>, PaginationMetadata)> {
+    let max_attempts: i32 = max_attempts.try_into()?;
+    let paginator = golem_base_webhook_deliveries::Entity::find()
+        .filter(golem_base_webhook_deliveries::Column::DeliveredAt.is_null())
+        .filter(golem_base_webhook_deliveries::Column::Attempts.gte(max_attempts))
+        .order_by_asc(golem_base_webhook_deliveries::Column::Id)
+        .paginate(db, pagination.page_size);
+
+    paginate_try_from(paginator, pagination)
+        .await
+        .context("Failed to list exhausted webhook deliveries")
+}
+
+/// Resets the attempts of an exhausted delivery, so it's sent again right away.
+/// Returns whether such a delivery existed.
+#[instrument(skip(db))]
+pub async fn redeliver<T: ConnectionTrait>(db: &T, id: u64, max_attempts: u32) -> Result<bool> {
+    let res = db
+        .execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            sql::REDELIVER_WEBHOOK_DELIVERY,
+            [id.into(), max_attempts.into()],
+        ))
+        .await
+        .context("Failed to redeliver webhook delivery")?;
+    Ok(res.rows_affected() > 0)
+}
+
+/// Deletes delivered and exhausted deliveries queued more than `retention` ago.
+#[instrument(skip(db))]
+pub async fn delete_expired_deliveries<T: ConnectionTrait>(
+    db: &T,
+    max_attempts: u32,
+    retention: Duration,
+) -> Result<u64> {
+    let res = db
+        .execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            sql::DELETE_EXPIRED_WEBHOOK_DELIVERIES,
+            [max_attempts.into(), retention.as_secs_f64().into()],
+        ))
+        .await
+        .context("Failed to delete expired webhook deliveries")?;
+    Ok(res.rows_affected())
+}
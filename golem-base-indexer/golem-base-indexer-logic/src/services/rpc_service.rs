@@ -22,6 +22,8 @@ impl TryFrom<RpcBlock> for ConsensusBlockInfo {
     }
 }
 
+const CONSENSUS_BLOCKS_INFO_KEY: &str = "consensus_blocks_info";
+
 #[derive(Debug, Clone)]
 pub struct RpcService {
     pub client: RpcClient,
@@ -69,24 +71,15 @@ impl RpcService {
         })
     }
 
+    /// Failures aren't cached, so the next call retries the RPC.
     pub async fn get_consensus_blocks_info_cached(&self) -> Result<ConsensusBlocksInfo> {
-        let key = "consensus_blocks_info".to_string();
-
-        let cache = self.cache.clone();
         let s = self.clone();
 
-        let res = cache
-            .get_with(key.clone(), async move {
-                match s.get_consensus_blocks_info().await {
-                    Ok(info) => info,
-                    Err(e) => {
-                        tracing::error!(%e, "failed to get consensus blocks info");
-                        Default::default()
-                    }
-                }
+        self.cache
+            .try_get_with(CONSENSUS_BLOCKS_INFO_KEY.to_string(), async move {
+                s.get_consensus_blocks_info().await
             })
-            .await;
-
-        Ok(res)
+            .await
+            .map_err(|e| anyhow!("failed to get consensus blocks info: {e:#}"))
     }
 }
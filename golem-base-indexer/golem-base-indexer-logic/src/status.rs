@@ -0,0 +1,141 @@
+use anyhow::Result;
+use sea_orm::DatabaseConnection;
+use serde::Deserialize;
+use serde_with::serde_as;
+use std::{sync::Arc, time::Duration};
+
+use crate::{mat_view_scheduler::MatViewScheduler, repository, types::IndexerStatus};
+
+/// Thresholds past which the indexer is no longer considered ready.
+#[serde_as]
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReadinessSettings {
+    /// Blocks the indexer may fall behind the latest consensus block.
+    pub max_block_lag: u64,
+
+    /// How long an item may wait in a processing queue.
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    pub max_queue_age: Duration,
+
+    /// How long ago the last indexer tick may have completed.
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    pub max_tick_age: Duration,
+
+    /// How long ago each materialized view may have been refreshed.
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    pub max_materialized_view_staleness: Duration,
+}
+
+impl Default for ReadinessSettings {
+    fn default() -> Self {
+        Self {
+            max_block_lag: 100,
+            max_queue_age: Duration::from_secs(10 * 60),
+            max_tick_age: Duration::from_secs(5 * 60),
+            max_materialized_view_staleness: Duration::from_secs(2 * 60 * 60),
+        }
+    }
+}
+
+/// Status of the indexer and of every scheduled materialized view.
+pub async fn indexer_status(db: Arc<DatabaseConnection>) -> Result<IndexerStatus> {
+    let views = MatViewScheduler::new(db.clone())
+        .get_mat_view_settings()
+        .into_iter()
+        .map(|v| v.name)
+        .collect();
+    repository::status::indexer_status(&*db, views).await
+}
+
+impl IndexerStatus {
+    /// Reasons for the indexer not to be ready, empty when it is.
+    pub fn problems(&self, settings: &ReadinessSettings) -> Vec<String> {
+        let mut problems = vec![];
+
+        let lag = self.block_lag();
+        if lag > settings.max_block_lag {
+            problems.push(format!("indexer is {lag} blocks behind"));
+        }
+        if let Some(at) = self.oldest_queued_item_at {
+            let age = self.age(at);
+            if age > settings.max_queue_age {
+                problems.push(format!(
+                    "oldest queued item is waiting for {}s",
+                    age.as_secs()
+                ));
+            }
+        }
+        match self.last_tick_at {
+            Some(at) if self.age(at) > settings.max_tick_age => problems.push(format!(
+                "last indexer tick completed {}s ago",
+                self.age(at).as_secs()
+            )),
+            Some(_) => {}
+            None => problems.push("indexer has not completed a tick yet".to_string()),
+        }
+        for view in &self.materialized_views {
+            match view.refreshed_at {
+                Some(at) if self.age(at) > settings.max_materialized_view_staleness => problems
+                    .push(format!(
+                        "materialized view {} was refreshed {}s ago",
+                        view.name,
+                        self.age(at).as_secs()
+                    )),
+                Some(_) => {}
+                None => problems.push(format!(
+                    "materialized view {} has not been refreshed yet",
+                    view.name
+                )),
+            }
+        }
+
+        problems
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{MaterializedViewStatus, Timestamp};
+    use chrono::TimeDelta;
+
+    fn status(now: Timestamp) -> IndexerStatus {
+        IndexerStatus {
+            checked_at: now,
+            latest_block: Some(1000),
+            latest_processed_block: Some(990),
+            pending_transactions: 3,
+            pending_transaction_cleanups: 0,
+            oldest_queued_item_at: Some(now - TimeDelta::seconds(5)),
+            last_tick_at: Some(now - TimeDelta::seconds(1)),
+            paused: false,
+            materialized_views: vec![MaterializedViewStatus {
+                name: "golem_base_timeseries_data_usage".to_string(),
+                refreshed_at: Some(now - TimeDelta::minutes(10)),
+            }],
+        }
+    }
+
+    #[test]
+    fn readiness_problems_respect_thresholds() {
+        let settings = ReadinessSettings::default();
+        let now = chrono::Utc::now();
+        assert_eq!(status(now).problems(&settings), Vec::<String>::new());
+
+        let mut lagging = status(now);
+        lagging.latest_processed_block = Some(800);
+        lagging.oldest_queued_item_at = Some(now - TimeDelta::hours(1));
+        lagging.last_tick_at = None;
+        lagging.materialized_views[0].refreshed_at = Some(now - TimeDelta::days(1));
+        assert_eq!(
+            lagging.problems(&settings),
+            vec![
+                "indexer is 200 blocks behind",
+                "oldest queued item is waiting for 3600s",
+                "indexer has not completed a tick yet",
+                "materialized view golem_base_timeseries_data_usage was refreshed 86400s ago",
+            ]
+        );
+    }
+}
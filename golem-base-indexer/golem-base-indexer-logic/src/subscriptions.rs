@@ -0,0 +1,46 @@
+use anyhow::{Context, Result};
+use sea_orm::{sqlx::postgres::PgListener, DatabaseConnection};
+use std::sync::Arc;
+
+use crate::{repository, types::HistoryEntryEvent};
+
+/// Receives entity history entries once the database transaction
+/// that wrote them has been committed.
+pub trait HistorySubscriber: Send + Sync {
+    fn notify(&self, event: HistoryEntryEvent);
+}
+
+/// Passes the history entries committed by any indexer replica sharing the
+/// database to the subscriber, as they're announced over `LISTEN`/`NOTIFY`.
+pub struct HistoryListener {
+    db: Arc<DatabaseConnection>,
+    subscriber: Arc<dyn HistorySubscriber>,
+}
+
+impl HistoryListener {
+    pub fn new(db: Arc<DatabaseConnection>, subscriber: Arc<dyn HistorySubscriber>) -> Self {
+        Self { db, subscriber }
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        let mut listener = PgListener::connect_with(self.db.get_postgres_connection_pool())
+            .await
+            .context("Failed to connect history listener")?;
+        listener
+            .listen(repository::subscriptions::HISTORY_NOTIFICATIONS_CHANNEL)
+            .await
+            .context("Failed to listen for history notifications")?;
+
+        loop {
+            let notification = listener.recv().await?;
+            let id: u64 = notification
+                .payload()
+                .parse()
+                .context("Invalid history notification")?;
+            match repository::subscriptions::get_notification(&*self.db, id).await? {
+                Some(event) => self.subscriber.notify(event),
+                None => tracing::debug!(id, "History notification is gone, skipping"),
+            }
+        }
+    }
+}
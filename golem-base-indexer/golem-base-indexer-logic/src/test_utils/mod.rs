@@ -149,6 +149,39 @@ pub async fn insert_data<T: ConnectionTrait>(txn: &T, block: Block) -> Result<()
     insert_data_multi(txn, vec![block]).await
 }
 
+/// Replaces blocks from the given number onwards with a fork the way Blockscout
+/// does, by flipping their consensus without touching their transactions.
+pub async fn lose_consensus<T: ConnectionTrait>(txn: &T, from_block: BlockNumber) -> Result<()> {
+    txn.execute(Statement::from_sql_and_values(
+        txn.get_database_backend(),
+        "update blocks set consensus = false where number >= $1",
+        [from_block.into()],
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Moves an already inserted transaction to another block, e.g. when it gets
+/// included again on the new branch after a reorg.
+pub async fn move_transaction<T: ConnectionTrait>(
+    txn: &T,
+    tx_hash: TxHash,
+    block_hash: BlockHash,
+    block_number: BlockNumber,
+) -> Result<()> {
+    txn.execute(Statement::from_sql_and_values(
+        txn.get_database_backend(),
+        "update transactions set block_hash = $2, block_number = $3 where hash = $1",
+        [
+            tx_hash.as_slice().into(),
+            block_hash.as_slice().into(),
+            block_number.into(),
+        ],
+    ))
+    .await?;
+    Ok(())
+}
+
 pub async fn insert_gas_transactions<T: ConnectionTrait>(
     client: &T,
     sender: Address,
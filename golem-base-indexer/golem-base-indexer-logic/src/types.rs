@@ -1,11 +1,13 @@
-use alloy_primitives::B256;
-use chrono::{DateTime, Utc};
+use alloy_primitives::{B256, U256};
+use chrono::{DateTime, DurationRound, TimeDelta, Utc};
 
 pub use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash, U256 as CurrencyAmount};
 pub use alloy_rlp::Bytes;
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use serde::{Deserialize, Serialize};
 
+use crate::query::Query;
+
 pub type Timestamp = DateTime<Utc>;
 
 pub type EntityKey = B256;
@@ -24,12 +26,27 @@ pub struct AttributeWithRelations<T: std::fmt::Debug> {
     pub related_entities: u64,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Attribute<T: std::fmt::Debug> {
     pub key: String,
     pub value: T,
 }
 
+/// Value of a single-valued attribute replaced by an operation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AttributeChange<T: std::fmt::Debug> {
+    pub key: String,
+    pub old_value: T,
+    pub new_value: T,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AttributesDiff<T: std::fmt::Debug> {
+    pub added: Vec<Attribute<T>>,
+    pub removed: Vec<Attribute<T>>,
+    pub changed: Vec<AttributeChange<T>>,
+}
+
 impl<T: std::fmt::Debug> From<FullAttribute<T>> for Attribute<T> {
     fn from(v: FullAttribute<T>) -> Self {
         v.attribute
@@ -42,6 +59,82 @@ pub type NumericAttribute = Attribute<u64>;
 pub type FullStringAttribute = FullAttribute<String>;
 pub type FullNumericAttribute = FullAttribute<u64>;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeKind {
+    String,
+    Numeric,
+}
+
+impl AttributeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::String => "string",
+            Self::Numeric => "numeric",
+        }
+    }
+}
+
+impl core::str::FromStr for AttributeKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "string" => Ok(Self::String),
+            "numeric" => Ok(Self::Numeric),
+            _ => Err(anyhow!("Expected string or numeric")),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AttributeKeysFilter {
+    /// Both kinds when not set.
+    pub kind: Option<AttributeKind>,
+    pub prefix: Option<String>,
+    pub pagination: PaginationParams,
+}
+
+/// Attribute key with the number of entities it is active on, and the range of
+/// blocks it was set in.
+#[derive(Debug, Clone)]
+pub struct AttributeKeyStats {
+    pub kind: AttributeKind,
+    pub key: String,
+    pub active_entities: u64,
+    pub first_seen_block: BlockNumber,
+    pub last_seen_block: BlockNumber,
+}
+
+#[derive(Debug, Clone)]
+pub struct AttributeValuesFilter {
+    pub kind: AttributeKind,
+    pub key: String,
+    pub prefix: Option<String>,
+    pub pagination: PaginationParams,
+}
+
+/// Active value of an attribute key, numeric ones in their decimal form.
+#[derive(Debug, Clone)]
+pub struct AttributeValueCount {
+    pub value: String,
+    pub active_entities: u64,
+}
+
+/// Distribution of the active values of a numeric attribute key. Percentiles
+/// are values that exist, not interpolated ones.
+#[derive(Debug, Clone)]
+pub struct NumericAttributeStats {
+    pub key: String,
+    pub active_entities: u64,
+    pub min: Option<u64>,
+    pub max: Option<u64>,
+    pub p25: Option<u64>,
+    pub p50: Option<u64>,
+    pub p75: Option<u64>,
+    pub p90: Option<u64>,
+    pub p99: Option<u64>,
+}
+
 pub type StringAttributeWithRelations = AttributeWithRelations<String>;
 pub type NumericAttributeWithRelations = AttributeWithRelations<u64>;
 
@@ -164,6 +257,46 @@ pub struct ConsensusTx {
     pub index: u64,
 }
 
+/// Block fetched from the chain, with transactions merged with their receipts.
+#[derive(Debug, Clone)]
+pub struct ChainBlock {
+    pub hash: BlockHash,
+    pub parent_hash: BlockHash,
+    pub number: BlockNumber,
+    pub timestamp: Timestamp,
+    pub miner: Address,
+    pub gas_limit: u64,
+    pub gas_used: u64,
+    pub transactions: Vec<ChainTransaction>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ChainTransaction {
+    pub hash: TxHash,
+    pub index: u64,
+    pub from: Address,
+    pub to: Option<Address>,
+    pub input: Bytes,
+    pub nonce: u64,
+    pub gas: u64,
+    pub gas_price: CurrencyAmount,
+    pub value: CurrencyAmount,
+    pub r#type: u64,
+    pub status: u64,
+    pub gas_used: u64,
+    pub cumulative_gas_used: u64,
+    pub created_contract_address: Option<Address>,
+    pub logs: Vec<ChainLog>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ChainLog {
+    pub index: u64,
+    pub address: Address,
+    pub topics: Vec<B256>,
+    pub data: Bytes,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EntityStatus {
     Active,
@@ -248,9 +381,10 @@ impl core::str::FromStr for BlockNumberOrHashFilter {
 
 #[derive(Debug, Clone)]
 pub struct ListOperationsFilter {
-    pub pagination: PaginationParams,
+    pub pagination: CursorPaginationParams<OperationCursor>,
     pub operation_type: Option<OperationType>,
     pub operations_filter: OperationsFilter,
+    pub sort: OperationsSort,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -259,6 +393,57 @@ pub struct OperationsFilter {
     pub sender: Option<Address>,
     pub block_number_or_hash: Option<BlockNumberOrHashFilter>,
     pub transaction_hash: Option<TxHash>,
+    /// Hides operations from blocks newer than this one.
+    pub max_block_number: Option<BlockNumber>,
+    pub recipient: Option<Address>,
+    pub content_type: Option<String>,
+    /// Only operations of these types, all of them when empty.
+    pub operation_types: Vec<OperationType>,
+    // block and cost bounds are inclusive
+    pub from_block: Option<BlockNumber>,
+    pub to_block: Option<BlockNumber>,
+    pub min_cost: Option<CurrencyAmount>,
+    pub max_cost: Option<CurrencyAmount>,
+    /// Only operations from blocks at or after this time.
+    pub from_timestamp: Option<Timestamp>,
+    /// Only operations from blocks before this time.
+    pub to_timestamp: Option<Timestamp>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OperationSortField {
+    #[default]
+    Time,
+    Cost,
+}
+
+impl core::str::FromStr for OperationSortField {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "time" => Ok(Self::Time),
+            "cost" => Ok(Self::Cost),
+            _ => Err(anyhow!("Expected time or cost")),
+        }
+    }
+}
+
+/// Order of an operation listing. Operations are ordered by their position in
+/// the chain when sorted by time, which also breaks ties between equal costs.
+/// Operations without a cost, like deletes, sort as costing nothing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OperationsSort {
+    pub field: OperationSortField,
+    pub direction: SortDirection,
+}
+
+/// Position of an operation in a sorted listing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperationCursor {
+    /// Only set when the listing is sorted by cost.
+    pub cost: Option<U256>,
+    pub index: FullOperationIndex,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -280,15 +465,94 @@ pub struct PaginationMetadata {
 #[derive(Debug, Clone)]
 pub struct EntityHistoryFilter {
     pub entity_key: EntityKey,
-    pub pagination: PaginationParams,
+    pub pagination: CursorPaginationParams<FullOperationIndex>,
+    /// Hides operations from blocks newer than this one.
+    pub max_block_number: Option<BlockNumber>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct EntitiesFilter {
     pub status: Option<EntityStatus>,
     pub string_attribute: Option<StringAttribute>,
     pub numeric_attribute: Option<NumericAttribute>,
     pub owner: Option<Address>,
+    pub query: Option<Query>,
+    pub content_type: Option<String>,
+    pub creator: Option<Address>,
+    // min and max bounds are inclusive
+    pub min_expires_at_block_number: Option<BlockNumber>,
+    pub max_expires_at_block_number: Option<BlockNumber>,
+    pub min_data_size: Option<u64>,
+    pub max_data_size: Option<u64>,
+    pub min_created_at_block_number: Option<BlockNumber>,
+    pub max_created_at_block_number: Option<BlockNumber>,
+    /// Only entities created at or after this time.
+    pub created_from: Option<Timestamp>,
+    /// Only entities created before this time.
+    pub created_to: Option<Timestamp>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntitySortField {
+    CreatedAtBlock,
+    UpdatedAtBlock,
+    ExpiresAtBlock,
+    DataSize,
+    Cost,
+}
+
+impl core::str::FromStr for EntitySortField {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "created_block" => Ok(Self::CreatedAtBlock),
+            "updated_block" => Ok(Self::UpdatedAtBlock),
+            "expires_at_block" => Ok(Self::ExpiresAtBlock),
+            "data_size" => Ok(Self::DataSize),
+            "cost" => Ok(Self::Cost),
+            _ => Err(anyhow!(
+                "Expected created_block, updated_block, expires_at_block, data_size or cost"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl core::str::FromStr for SortDirection {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "asc" => Ok(Self::Asc),
+            "desc" => Ok(Self::Desc),
+            _ => Err(anyhow!("Expected asc or desc")),
+        }
+    }
+}
+
+/// Order of an entity listing. Entities are ordered by key when there's no sort
+/// field, and the key breaks ties otherwise. Entities without a value for the
+/// field sort as if it was the largest one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EntitiesSort {
+    pub field: Option<EntitySortField>,
+    pub direction: SortDirection,
+}
+
+/// Position of an entity in a sorted listing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityCursor {
+    /// Value of the sort field, not set when the entity has none or the listing
+    /// is ordered by key.
+    pub value: Option<U256>,
+    pub key: EntityKey,
 }
 
 #[derive(Debug, Clone)]
@@ -297,10 +561,67 @@ pub struct PaginationParams {
     pub page_size: u64,
 }
 
+/// Time window a leaderboard is ranked over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeaderboardWindow {
+    Day,
+    Week,
+    Month,
+    Custom {
+        from: Timestamp,
+        to: Option<Timestamp>,
+    },
+}
+
+impl LeaderboardWindow {
+    /// Start and end of the window at `now`, widened to whole hours as that's the
+    /// resolution leaderboards are rolled up at.
+    pub fn bounds(&self, now: Timestamp) -> (Timestamp, Timestamp) {
+        let (from, to) = match *self {
+            Self::Day => (now - TimeDelta::days(1), now),
+            Self::Week => (now - TimeDelta::weeks(1), now),
+            Self::Month => (now - TimeDelta::days(30), now),
+            Self::Custom { from, to } => (from, to.unwrap_or(now)),
+        };
+        let hour = TimeDelta::hours(1);
+        let from = from.duration_trunc(hour).unwrap_or(from);
+        let to = match to.duration_trunc(hour) {
+            Ok(truncated) if truncated < to => truncated + hour,
+            _ => to,
+        };
+        (from, to)
+    }
+}
+
 #[derive(Debug, Clone)]
-pub struct ListEntitiesFilter {
+pub struct LeaderboardFilter {
+    pub pagination: PaginationParams,
+    /// Ranks over all time when not set.
+    pub window: Option<LeaderboardWindow>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CursorPaginationParams<C> {
     pub pagination: PaginationParams,
+    /// Position of the last item of the previous page. When set, `pagination.page` is ignored.
+    pub cursor: Option<C>,
+    pub with_totals: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct CursorPaginationMetadata<C> {
+    pub page_size: u64,
+    pub totals: Option<PaginationMetadata>,
+    pub next_cursor: Option<C>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ListEntitiesFilter {
+    pub pagination: CursorPaginationParams<EntityCursor>,
     pub entities_filter: EntitiesFilter,
+    pub sort: EntitiesSort,
+    /// Lists entities as they were at the end of this block instead of their latest state.
+    pub at_block: Option<BlockNumber>,
 }
 
 #[derive(Debug, Clone)]
@@ -373,6 +694,168 @@ pub struct EntityHistoryEntry {
     pub total_cost: Option<CurrencyAmount>,
 }
 
+/// History entry committed by the indexer, together with the attributes
+/// the entity carried at that point.
+#[derive(Debug, Clone)]
+pub struct HistoryEntryEvent {
+    pub entry: EntityHistoryEntry,
+    pub string_attributes: Vec<StringAttribute>,
+    pub numeric_attributes: Vec<NumericAttribute>,
+}
+
+/// History entry with the attributes the entity had after the operation and
+/// how they differ from the ones it had before.
+#[derive(Debug, Clone)]
+pub struct EntityHistoryEntryWithAttributes {
+    pub entry: EntityHistoryEntry,
+    pub string_attributes: Vec<StringAttribute>,
+    pub numeric_attributes: Vec<NumericAttribute>,
+    pub string_attributes_diff: AttributesDiff<String>,
+    pub numeric_attributes_diff: AttributesDiff<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonPatchOperation {
+    pub op: String,
+    pub path: String,
+    pub from: Option<String>,
+    pub value: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ByteRangeChange {
+    pub from_offset: u64,
+    pub from_length: u64,
+    pub to_offset: u64,
+    pub to_length: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataDiff {
+    JsonPatch(Vec<JsonPatchOperation>),
+    Unified(String),
+    ByteRanges(Vec<ByteRangeChange>),
+}
+
+#[derive(Debug, Clone)]
+pub struct EntityDataDiffFilter {
+    pub key: EntityKey,
+    pub from: OperationFilter,
+    pub to: OperationFilter,
+}
+
+#[derive(Debug, Clone)]
+pub struct EntityDataDiff {
+    pub from_content_type: Option<String>,
+    pub to_content_type: Option<String>,
+    pub from_size: u64,
+    pub to_size: u64,
+    pub diff: DataDiff,
+}
+
+/// Largest payload, in bytes, that entity data is diffed against.
+pub const MAX_DIFF_PAYLOAD_SIZE: usize = 1024 * 1024;
+
+/// Returned when a payload to diff is larger than [`MAX_DIFF_PAYLOAD_SIZE`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataTooLargeToDiff {
+    pub size: usize,
+}
+
+impl std::fmt::Display for DataTooLargeToDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "payload of {} bytes is larger than the {MAX_DIFF_PAYLOAD_SIZE} bytes that can be diffed",
+            self.size
+        )
+    }
+}
+
+impl std::error::Error for DataTooLargeToDiff {}
+
+/// Lifecycle event an entity history entry represents for webhook consumers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookEvent {
+    Created,
+    Updated,
+    Extended,
+    OwnerChanged,
+    Deleted,
+    Expired,
+}
+
+impl WebhookEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Created => "entity.created",
+            Self::Updated => "entity.updated",
+            Self::Extended => "entity.extended",
+            Self::OwnerChanged => "entity.owner_changed",
+            Self::Deleted => "entity.deleted",
+            Self::Expired => "entity.expired",
+        }
+    }
+}
+
+impl From<&EntityHistoryEntry> for WebhookEvent {
+    fn from(entry: &EntityHistoryEntry) -> Self {
+        match (entry.operation, entry.status) {
+            (OperationType::Create, _) => Self::Created,
+            (OperationType::Update, _) => Self::Updated,
+            (OperationType::Extend, _) => Self::Extended,
+            (OperationType::ChangeOwner, _) => Self::OwnerChanged,
+            (OperationType::Delete, EntityStatus::Expired) => Self::Expired,
+            (OperationType::Delete, _) => Self::Deleted,
+        }
+    }
+}
+
+/// Webhooks with no filter set are called for every entity.
+#[derive(Debug, Clone, Default)]
+pub struct WebhookFilter {
+    pub owner: Option<Address>,
+    pub entity_key: Option<EntityKey>,
+    pub annotation: Option<StringAttribute>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewWebhook {
+    pub url: String,
+    pub secret: String,
+    pub filter: WebhookFilter,
+}
+
+#[derive(Debug, Clone)]
+pub struct Webhook {
+    pub id: u64,
+    pub url: String,
+    pub filter: WebhookFilter,
+    pub created_at: Timestamp,
+}
+
+#[derive(Debug, Clone)]
+pub struct WebhookDelivery {
+    pub id: u64,
+    pub url: String,
+    pub secret: String,
+    pub event: String,
+    pub payload: serde_json::Value,
+    pub attempts: u32,
+}
+
+/// A delivery that failed `max_attempts` times and won't be retried on its own.
+#[derive(Debug, Clone)]
+pub struct ExhaustedWebhookDelivery {
+    pub id: u64,
+    pub webhook_id: u64,
+    pub event: String,
+    pub payload: serde_json::Value,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    pub created_at: Timestamp,
+}
+
 #[derive(Debug, Clone)]
 pub struct BlockEntitiesCount {
     pub create_count: u64,
@@ -443,6 +926,26 @@ pub struct ChartInfo {
     pub description: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChartSeries {
+    pub name: String,
+    pub chart: Vec<ChartPoint>,
+}
+
+/// What a chart is split into series by.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChartGroupBy {
+    ContentType,
+    StringAnnotation(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChartBreakdown {
+    pub group_by: ChartGroupBy,
+    /// Number of largest series returned, the rest is summed up as "other".
+    pub limit: u64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlockTransactionPoint {
     pub block_number: BlockNumber,
@@ -519,6 +1022,24 @@ pub struct LeaderboardEffectivelyLargestEntitiesItem {
     pub lifespan: BlockNumber,
 }
 
+#[derive(Debug, Clone)]
+pub struct SearchFilter {
+    /// Words to look for, in web search syntax (quoted phrases, `or`, `-word`).
+    pub query: String,
+    pub pagination: PaginationParams,
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchResult {
+    pub entity_key: EntityKey,
+    pub owner: Option<Address>,
+    pub content_type: Option<String>,
+    pub rank: f64,
+    /// Matching fragments with the matches wrapped in `<mark>` tags and the
+    /// rest of the text HTML-escaped.
+    pub snippet: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct LogIndex {
     pub transaction_hash: TxHash,
@@ -536,6 +1057,199 @@ pub struct LogEventIndex {
     pub data: Vec<u8>,
 }
 
+/// Processing queues whose failing items are retried and eventually quarantined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessingQueue {
+    Transactions,
+    TransactionCleanups,
+    Logs,
+    LogsEvents,
+    Reindexes,
+}
+
+impl ProcessingQueue {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Transactions => "transactions",
+            Self::TransactionCleanups => "transaction_cleanups",
+            Self::Logs => "logs",
+            Self::LogsEvents => "logs_events",
+            Self::Reindexes => "reindexes",
+        }
+    }
+}
+
+impl core::str::FromStr for ProcessingQueue {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "transactions" => Ok(Self::Transactions),
+            "transaction_cleanups" => Ok(Self::TransactionCleanups),
+            "logs" => Ok(Self::Logs),
+            "logs_events" => Ok(Self::LogsEvents),
+            "reindexes" => Ok(Self::Reindexes),
+            _ => Err(anyhow!(
+                "Expected transactions, transaction_cleanups, logs, logs_events or reindexes"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum QueueItem {
+    Transaction(TxHash),
+    TxCleanup(TxHash),
+    Log(LogIndex),
+    LogEvent(LogIndex),
+    Reindex(EntityKey),
+}
+
+impl QueueItem {
+    pub fn queue(&self) -> ProcessingQueue {
+        match self {
+            Self::Transaction(_) => ProcessingQueue::Transactions,
+            Self::TxCleanup(_) => ProcessingQueue::TransactionCleanups,
+            Self::Log(_) => ProcessingQueue::Logs,
+            Self::LogEvent(_) => ProcessingQueue::LogsEvents,
+            Self::Reindex(_) => ProcessingQueue::Reindexes,
+        }
+    }
+}
+
+/// Queue item that ran out of attempts. Keyed by the tx hash, or the entity
+/// key for reindexes; log queues also carry the log position.
+#[derive(Debug, Clone)]
+pub struct QuarantinedItem {
+    pub queue: ProcessingQueue,
+    pub key: B256,
+    pub block_hash: Option<BlockHash>,
+    pub block_number: Option<BlockNumber>,
+    pub index: Option<u64>,
+    pub attempts: u64,
+    pub last_error: Option<String>,
+    pub quarantined_at: Timestamp,
+}
+
+/// Entities to queue for reindexing on operator request.
+#[derive(Debug, Clone)]
+pub enum ReindexScope {
+    Entity(EntityKey),
+    Owner(Address),
+    /// Every entity with an operation in the inclusive block range.
+    Blocks {
+        from: BlockNumber,
+        to: BlockNumber,
+    },
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct QueueDepths {
+    pub pending_transactions: u64,
+    pub pending_transaction_cleanups: u64,
+    pub pending_logs: u64,
+    pub pending_logs_events: u64,
+    pub entities_to_reindex: u64,
+    pub quarantined: u64,
+}
+
+/// Snapshot of how far the indexer is behind the chain.
+#[derive(Debug, Clone)]
+pub struct IndexerStatus {
+    pub checked_at: Timestamp,
+    pub latest_block: Option<BlockNumber>,
+    /// Latest block up to which all storage transactions are processed.
+    pub latest_processed_block: Option<BlockNumber>,
+    pub pending_transactions: u64,
+    pub pending_transaction_cleanups: u64,
+    /// When the oldest item still waiting in any processing queue was enqueued.
+    /// Quarantined items are not waiting and don't count.
+    pub oldest_queued_item_at: Option<Timestamp>,
+    pub last_tick_at: Option<Timestamp>,
+    pub paused: bool,
+    pub materialized_views: Vec<MaterializedViewStatus>,
+}
+
+impl IndexerStatus {
+    pub fn block_lag(&self) -> u64 {
+        self.latest_block
+            .unwrap_or_default()
+            .saturating_sub(self.latest_processed_block.unwrap_or_default())
+    }
+
+    /// Time elapsed between `at` and the moment the status was taken.
+    pub fn age(&self, at: Timestamp) -> std::time::Duration {
+        (self.checked_at - at).to_std().unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MaterializedViewStatus {
+    pub name: String,
+    pub refreshed_at: Option<Timestamp>,
+}
+
+/// Stage at which storage tx calldata failed to decode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodingErrorKind {
+    Compression,
+    Rlp,
+    TrailingBytes,
+}
+
+impl DecodingErrorKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Compression => "compression",
+            Self::Rlp => "rlp",
+            Self::TrailingBytes => "trailing_bytes",
+        }
+    }
+}
+
+impl core::str::FromStr for DecodingErrorKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "compression" => Ok(Self::Compression),
+            "rlp" => Ok(Self::Rlp),
+            "trailing_bytes" => Ok(Self::TrailingBytes),
+            _ => Err(anyhow!("Expected compression, rlp or trailing_bytes")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodingError {
+    pub kind: DecodingErrorKind,
+    pub message: String,
+}
+
+impl std::fmt::Display for DecodingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.kind.as_str(), self.message)
+    }
+}
+
+impl std::error::Error for DecodingError {}
+
+#[derive(Debug, Clone)]
+pub struct UndecodableTransaction {
+    pub hash: TxHash,
+    pub sender: Address,
+    pub block_hash: BlockHash,
+    pub block_number: BlockNumber,
+    pub index: u64,
+    pub error: DecodingError,
+}
+
+#[derive(Debug, Clone)]
+pub struct UndecodableTransactionsFilter {
+    pub pagination: PaginationParams,
+    pub sender: Option<Address>,
+}
+
 #[derive(Clone, Debug)]
 pub struct EntityDataHistogram {
     pub bucket: u64,
@@ -557,6 +1271,58 @@ pub struct ConsensusBlocksInfo {
     pub finalized: ConsensusBlockInfo,
 }
 
+/// Chain head a read is confirmed against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Confirmation {
+    Latest,
+    Safe,
+    Finalized,
+}
+
+impl Confirmation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Latest => "latest",
+            Self::Safe => "safe",
+            Self::Finalized => "finalized",
+        }
+    }
+}
+
+impl core::str::FromStr for Confirmation {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "latest" => Ok(Self::Latest),
+            "safe" => Ok(Self::Safe),
+            "finalized" => Ok(Self::Finalized),
+            _ => Err(anyhow!("Expected latest, safe or finalized")),
+        }
+    }
+}
+
+impl ConsensusBlocksInfo {
+    pub fn head(&self, confirmation: Confirmation) -> BlockNumber {
+        match confirmation {
+            Confirmation::Latest => self.latest.block_number,
+            Confirmation::Safe => self.safe.block_number,
+            Confirmation::Finalized => self.finalized.block_number,
+        }
+    }
+
+    /// Highest confirmation level reached by the block.
+    pub fn finality(&self, block_number: BlockNumber) -> Confirmation {
+        if block_number <= self.finalized.block_number {
+            Confirmation::Finalized
+        } else if block_number <= self.safe.block_number {
+            Confirmation::Safe
+        } else {
+            Confirmation::Latest
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct ConsensusGasInfo {
     pub gas_used: CurrencyAmount,
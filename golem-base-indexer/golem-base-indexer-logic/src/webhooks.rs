@@ -0,0 +1,341 @@
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use alloy_primitives::hex;
+use anyhow::{anyhow, Context, Result};
+use futures::StreamExt;
+use hmac::{Hmac, Mac};
+use reqwest::{
+    dns::{Addrs, Name, Resolve, Resolving},
+    header::CONTENT_TYPE,
+    redirect,
+};
+use sea_orm::DatabaseConnection;
+use serde::Deserialize;
+use serde_with::serde_as;
+use sha2::Sha256;
+use tokio::time::sleep;
+use tracing::instrument;
+
+use crate::{repository, types::WebhookDelivery};
+
+pub const EVENT_HEADER: &str = "x-golem-base-event";
+pub const DELIVERY_HEADER: &str = "x-golem-base-delivery";
+pub const SIGNATURE_HEADER: &str = "x-golem-base-signature";
+
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+#[serde_as]
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct WebhookSettings {
+    pub enabled: bool,
+
+    pub concurrency: usize,
+
+    pub batch_size: u64,
+
+    /// Deliveries that failed this many times are not retried anymore.
+    pub max_attempts: u32,
+
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    pub polling_interval: Duration,
+
+    /// Delay before the first retry, doubled with every further attempt.
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    pub initial_backoff: Duration,
+
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    pub max_backoff: Duration,
+
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    pub request_timeout: Duration,
+
+    /// How long delivered and exhausted deliveries are kept before being deleted.
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    pub retention: Duration,
+
+    /// Lets webhooks call loopback, private and link-local addresses, e.g. services
+    /// running next to the indexer. Off by default, as they're registered over the API.
+    pub allow_private_targets: bool,
+}
+
+impl Default for WebhookSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            concurrency: 10,
+            batch_size: 100,
+            max_attempts: 10,
+            polling_interval: Duration::from_secs(1),
+            initial_backoff: Duration::from_secs(10),
+            max_backoff: Duration::from_secs(60 * 60),
+            request_timeout: Duration::from_secs(10),
+            retention: Duration::from_secs(7 * 24 * 60 * 60),
+            allow_private_targets: false,
+        }
+    }
+}
+
+/// Signs the request body with the webhook secret, the receiver verifies
+/// it by computing the same HMAC-SHA256 over the raw body.
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Whether the address is reachable from the public internet, as opposed to
+/// loopback, private, link-local and other special purpose ranges.
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || ip.is_multicast()
+                // shared address space (RFC 6598)
+                || (a == 100 && (64..128).contains(&b))
+                || a == 0)
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public_ip(ip.into()),
+            None => {
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_unique_local()
+                    || ip.is_unicast_link_local()
+                    || ip.is_multicast())
+            }
+        },
+    }
+}
+
+/// Rejects webhook urls pointing at the indexer's own network by address or
+/// by a local name. Other names are checked when they're resolved for delivery.
+pub fn check_target(url: &str) -> Result<()> {
+    let url: url::Url = url.parse()?;
+    let public = match url.host() {
+        Some(url::Host::Ipv4(ip)) => is_public_ip(ip.into()),
+        Some(url::Host::Ipv6(ip)) => is_public_ip(ip.into()),
+        Some(url::Host::Domain(domain)) => {
+            let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+            domain != "localhost" && !domain.ends_with(".localhost")
+        }
+        None => false,
+    };
+    if !public {
+        return Err(anyhow!("Webhook url must point at a public address"));
+    }
+    Ok(())
+}
+
+/// Resolves webhook hosts to their public addresses only, so a name can't be
+/// used to reach the indexer's own network.
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((name.as_str(), 0))
+                .await?
+                .filter(|addr| is_public_ip(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(format!("{} has no public address", name.as_str()).into());
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Delivers queued webhook calls, retrying failed ones with exponential backoff.
+pub struct WebhookDispatcher {
+    db: Arc<DatabaseConnection>,
+    client: reqwest::Client,
+    settings: WebhookSettings,
+}
+
+impl WebhookDispatcher {
+    pub fn new(db: Arc<DatabaseConnection>, settings: WebhookSettings) -> Result<Self> {
+        let mut client = reqwest::Client::builder()
+            .timeout(settings.request_timeout)
+            .redirect(redirect::Policy::none());
+        if !settings.allow_private_targets {
+            client = client.dns_resolver(Arc::new(PublicResolver));
+        }
+        let client = client.build().context("Building webhook http client")?;
+        Ok(Self {
+            db,
+            client,
+            settings,
+        })
+    }
+
+    #[instrument(skip_all)]
+    pub async fn run(&self) -> Result<()> {
+        let mut next_cleanup = Instant::now();
+        loop {
+            self.tick().await.inspect_err(|e| {
+                tracing::error!(
+                    ?e,
+                    "Failed to deliver webhooks, exiting (will be restarted)..."
+                )
+            })?;
+            if Instant::now() >= next_cleanup {
+                let _ = self
+                    .cleanup()
+                    .await
+                    .inspect_err(|e| tracing::warn!(?e, "Failed to clean up webhook deliveries"));
+                next_cleanup = Instant::now() + CLEANUP_INTERVAL;
+            }
+            sleep(self.settings.polling_interval).await;
+        }
+    }
+
+    /// Deletes deliveries that are done with, delivered or exhausted, once
+    /// they're older than the retention period.
+    pub async fn cleanup(&self) -> Result<u64> {
+        let deleted = repository::webhooks::delete_expired_deliveries(
+            &*self.db,
+            self.settings.max_attempts,
+            self.settings.retention,
+        )
+        .await?;
+        if deleted > 0 {
+            tracing::info!(deleted, "Deleted expired webhook deliveries");
+        }
+        Ok(deleted)
+    }
+
+    pub async fn tick(&self) -> Result<()> {
+        // long enough for the whole batch to be sent, even if every request times out
+        let rounds = self
+            .settings
+            .batch_size
+            .div_ceil(self.settings.concurrency.max(1) as u64);
+        let lease = self
+            .settings
+            .request_timeout
+            .saturating_mul(u32::try_from(rounds + 1).unwrap_or(u32::MAX));
+        let deliveries = repository::webhooks::claim_due_deliveries(
+            &*self.db,
+            self.settings.max_attempts,
+            self.settings.batch_size,
+            lease,
+        )
+        .await?;
+
+        futures::stream::iter(deliveries)
+            .for_each_concurrent(self.settings.concurrency, |delivery| async move {
+                let id = delivery.id;
+                let _ = self
+                    .deliver(delivery)
+                    .await
+                    .inspect_err(|e| tracing::warn!(?e, id, "Handling webhook delivery failed"));
+            })
+            .await;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all, fields(id = delivery.id, url = delivery.url))]
+    async fn deliver(&self, delivery: WebhookDelivery) -> Result<()> {
+        if !self.settings.allow_private_targets {
+            if let Err(err) = check_target(&delivery.url) {
+                return repository::webhooks::fail_delivery(
+                    &*self.db,
+                    delivery.id,
+                    err.to_string(),
+                    self.backoff(delivery.attempts),
+                )
+                .await;
+            }
+        }
+        let body = serde_json::to_vec(&delivery.payload)?;
+        let signature = sign(&delivery.secret, &body);
+        let res = self
+            .client
+            .post(&delivery.url)
+            .header(CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, &delivery.event)
+            .header(DELIVERY_HEADER, delivery.id.to_string())
+            .header(SIGNATURE_HEADER, signature)
+            .body(body)
+            .send()
+            .await
+            .and_then(|res| res.error_for_status());
+
+        match res {
+            Ok(_) => repository::webhooks::finish_delivery(&*self.db, delivery.id).await,
+            Err(err) => {
+                tracing::info!(
+                    ?err,
+                    attempts = delivery.attempts,
+                    "Webhook delivery failed"
+                );
+                repository::webhooks::fail_delivery(
+                    &*self.db,
+                    delivery.id,
+                    err.to_string(),
+                    self.backoff(delivery.attempts),
+                )
+                .await
+            }
+        }
+    }
+
+    fn backoff(&self, attempts: u32) -> Duration {
+        self.settings
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempts))
+            .min(self.settings.max_backoff)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signs_with_hmac_sha256() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            sign("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn rejects_non_public_targets() {
+        for url in [
+            "http://127.0.0.1/hook",
+            "http://10.1.2.3/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://100.64.0.1/hook",
+            "http://[::1]/hook",
+            "http://[fe80::1]/hook",
+            "http://[fd00::1]/hook",
+            "http://[::ffff:192.168.0.1]/hook",
+            "http://localhost:8080/hook",
+            "http://api.localhost/hook",
+        ] {
+            assert!(check_target(url).is_err(), "{url}");
+        }
+        for url in [
+            "https://example.com/hook",
+            "http://8.8.8.8/hook",
+            "http://[2001:4860:4860::8888]/hook",
+        ] {
+            assert!(check_target(url).is_ok(), "{url}");
+        }
+    }
+}
@@ -10,6 +10,27 @@ mod m20251119_080215_add_creator;
 mod m20251119_090215_lockless;
 mod m20251201_215928_add_total_cost;
 mod m20251215_142514_block_stats;
+mod m20261017_091204_add_golem_base_operations_position_idx;
+mod m20261017_134512_webhooks;
+mod m20261017_162348_indexed_blocks;
+mod m20261017_181530_standalone_chain_tables;
+mod m20261017_203045_dead_letter_queue;
+mod m20261017_221530_undecodable_transactions;
+mod m20261017_232045_indexer_control;
+mod m20261017_235510_indexer_status;
+mod m20261018_004512_incremental_leaderboards;
+mod m20261018_013045_leaderboard_rollups;
+mod m20261018_022015_address_timeseries_rollups;
+mod m20261018_031040_entity_search;
+mod m20261018_035510_annotation_catalog;
+mod m20261018_042250_entity_sorting;
+mod m20261018_045130_operation_filters;
+mod m20261018_052740_chart_breakdown_rollups;
+mod m20261018_061520_queue_predicates;
+mod m20261018_064210_tx_cleanup_retries;
+mod m20261018_071530_history_notifications;
+mod m20261018_074020_owned_leaderboard_rollups;
+mod m20261018_081530_webhook_delivery_retention;
 
 pub struct Migrator;
 
@@ -26,6 +47,26 @@ impl MigratorTrait for Migrator {
             Box::new(m20251119_090215_lockless::Migration),
             Box::new(m20251201_215928_add_total_cost::Migration),
             Box::new(m20251215_142514_block_stats::Migration),
+            Box::new(m20261017_091204_add_golem_base_operations_position_idx::Migration),
+            Box::new(m20261017_134512_webhooks::Migration),
+            Box::new(m20261017_162348_indexed_blocks::Migration),
+            Box::new(m20261017_203045_dead_letter_queue::Migration),
+            Box::new(m20261017_221530_undecodable_transactions::Migration),
+            Box::new(m20261017_232045_indexer_control::Migration),
+            Box::new(m20261017_235510_indexer_status::Migration),
+            Box::new(m20261018_004512_incremental_leaderboards::Migration),
+            Box::new(m20261018_013045_leaderboard_rollups::Migration),
+            Box::new(m20261018_022015_address_timeseries_rollups::Migration),
+            Box::new(m20261018_031040_entity_search::Migration),
+            Box::new(m20261018_035510_annotation_catalog::Migration),
+            Box::new(m20261018_042250_entity_sorting::Migration),
+            Box::new(m20261018_045130_operation_filters::Migration),
+            Box::new(m20261018_052740_chart_breakdown_rollups::Migration),
+            Box::new(m20261018_061520_queue_predicates::Migration),
+            Box::new(m20261018_064210_tx_cleanup_retries::Migration),
+            Box::new(m20261018_071530_history_notifications::Migration),
+            Box::new(m20261018_074020_owned_leaderboard_rollups::Migration),
+            Box::new(m20261018_081530_webhook_delivery_retention::Migration),
         ]
     }
 
@@ -34,6 +75,25 @@ impl MigratorTrait for Migrator {
     }
 }
 
+/// Migrator for running without Blockscout, creating the chain tables
+/// Blockscout would otherwise provide before the indexer's own migrations.
+pub struct StandaloneMigrator;
+
+#[async_trait::async_trait]
+impl MigratorTrait for StandaloneMigrator {
+    fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+        let mut migrations: Vec<Box<dyn MigrationTrait>> = vec![Box::new(
+            m20261017_181530_standalone_chain_tables::Migration,
+        )];
+        migrations.extend(Migrator::migrations());
+        migrations
+    }
+
+    fn migration_table_name() -> DynIden {
+        Migrator::migration_table_name()
+    }
+}
+
 pub async fn from_sql(manager: &SchemaManager<'_>, content: &str) -> Result<(), DbErr> {
     let stmts: Vec<&str> = content.split(';').collect();
     let txn = manager.get_connection().begin().await?;
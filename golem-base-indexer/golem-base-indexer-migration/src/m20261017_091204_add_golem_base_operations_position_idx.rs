@@ -0,0 +1,35 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        CREATE INDEX IF NOT EXISTS golem_base_operations_block_number_tx_index_index_idx ON golem_base_operations (block_number, tx_index, index);
+        DROP INDEX IF EXISTS golem_base_operations_block_number_idx;
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        CREATE INDEX IF NOT EXISTS golem_base_operations_block_number_idx ON golem_base_operations (block_number);
+        DROP INDEX IF EXISTS golem_base_operations_block_number_tx_index_index_idx;
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
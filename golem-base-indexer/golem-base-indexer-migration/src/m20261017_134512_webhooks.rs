@@ -0,0 +1,61 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        CREATE TABLE golem_base_webhooks (
+            id bigserial NOT NULL primary key,
+            url text NOT NULL,
+            secret text NOT NULL,
+            owner bytea,
+            entity_key bytea,
+            annotation_key text,
+            annotation_value text,
+            inserted_at timestamp without time zone DEFAULT now() NOT NULL,
+
+            CONSTRAINT golem_base_webhooks_check_annotation CHECK ((annotation_key IS NULL) = (annotation_value IS NULL))
+        );
+
+        CREATE TABLE golem_base_webhook_deliveries (
+            id bigserial NOT NULL primary key,
+            webhook_id bigint NOT NULL references golem_base_webhooks(id) ON DELETE CASCADE,
+            event text NOT NULL,
+            payload jsonb NOT NULL,
+            attempts integer NOT NULL DEFAULT 0,
+            next_attempt_at timestamp without time zone DEFAULT now() NOT NULL,
+            last_error text,
+            delivered_at timestamp without time zone,
+            inserted_at timestamp without time zone DEFAULT now() NOT NULL
+        );
+
+        CREATE INDEX golem_base_webhook_deliveries_pending_idx
+            ON golem_base_webhook_deliveries (next_attempt_at)
+            WHERE delivered_at IS NULL;
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        DROP TABLE golem_base_webhook_deliveries;
+        DROP TABLE golem_base_webhooks;
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
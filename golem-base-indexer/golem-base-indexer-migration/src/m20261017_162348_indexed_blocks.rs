@@ -0,0 +1,86 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        -- Blocks the indexed operations come from, compared against blocks.consensus to detect reorgs
+        CREATE TABLE golem_base_indexed_blocks (
+            hash bytea NOT NULL primary key references blocks(hash),
+            number bigint NOT NULL,
+            inserted_at timestamp without time zone DEFAULT now() NOT NULL
+        );
+
+        CREATE INDEX golem_base_indexed_blocks_number_idx ON golem_base_indexed_blocks (number);
+
+        -- Backfill from already indexed operations
+        INSERT INTO golem_base_indexed_blocks (hash, number)
+        SELECT DISTINCT block_hash, block_number
+        FROM golem_base_operations;
+
+        -- Fees of transactions in blocks that lost consensus no longer count
+        DROP MATERIALIZED VIEW golem_base_leaderboard_biggest_spenders;
+
+        CREATE MATERIALIZED VIEW golem_base_leaderboard_biggest_spenders AS
+        SELECT
+            ROW_NUMBER() OVER(ORDER BY SUM(cumulative_gas_used * gas_price) DESC) AS rank,
+            from_address_hash AS address,
+            CAST(SUM(cumulative_gas_used * gas_price) AS TEXT) AS total_fees
+        FROM transactions
+        WHERE
+            cumulative_gas_used IS NOT NULL
+            AND cumulative_gas_used > 0
+            AND gas_price IS NOT NULL
+            AND gas_price > 0
+            AND NOT EXISTS (
+                SELECT 1 FROM blocks
+                WHERE blocks.hash = transactions.block_hash AND NOT blocks.consensus
+            )
+        GROUP BY from_address_hash
+        ORDER BY SUM(cumulative_gas_used * gas_price) DESC;
+
+        CREATE UNIQUE INDEX golem_base_leaderboard_biggest_spenders_output_index ON golem_base_leaderboard_biggest_spenders (rank);
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        DROP MATERIALIZED VIEW golem_base_leaderboard_biggest_spenders;
+
+        CREATE MATERIALIZED VIEW golem_base_leaderboard_biggest_spenders AS
+        SELECT
+            ROW_NUMBER() OVER(ORDER BY SUM(cumulative_gas_used * gas_price) DESC) AS rank,
+            from_address_hash AS address,
+            CAST(SUM(cumulative_gas_used * gas_price) AS TEXT) AS total_fees
+        FROM transactions
+        WHERE
+            cumulative_gas_used IS NOT NULL
+            AND cumulative_gas_used > 0
+            AND gas_price IS NOT NULL
+            AND gas_price > 0
+        GROUP BY from_address_hash
+        ORDER BY SUM(cumulative_gas_used * gas_price) DESC;
+
+        CREATE UNIQUE INDEX golem_base_leaderboard_biggest_spenders_output_index ON golem_base_leaderboard_biggest_spenders (rank);
+
+        DROP TABLE golem_base_indexed_blocks;
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
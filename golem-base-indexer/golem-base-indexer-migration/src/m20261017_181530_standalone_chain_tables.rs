@@ -0,0 +1,185 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        -- Subset of the Blockscout schema the indexer reads, for running without Blockscout
+        CREATE TABLE IF NOT EXISTS blocks (
+            consensus boolean NOT NULL,
+            difficulty numeric(50,0),
+            gas_limit numeric(100,0) NOT NULL,
+            gas_used numeric(100,0) NOT NULL,
+            hash bytea NOT NULL primary key,
+            miner_hash bytea NOT NULL,
+            nonce bytea NOT NULL,
+            number bigint NOT NULL,
+            parent_hash bytea NOT NULL,
+            size integer,
+            "timestamp" timestamp without time zone NOT NULL,
+            total_difficulty numeric(50,0),
+            inserted_at timestamp without time zone NOT NULL,
+            updated_at timestamp without time zone NOT NULL,
+            refetch_needed boolean DEFAULT false,
+            base_fee_per_gas numeric(100,0),
+            is_empty boolean
+        );
+
+        CREATE TABLE IF NOT EXISTS transactions (
+            cumulative_gas_used numeric(100,0),
+            error character varying(255),
+            gas numeric(100,0) NOT NULL,
+            gas_price numeric(100,0),
+            gas_used numeric(100,0),
+            hash bytea NOT NULL primary key,
+            index integer,
+            input bytea NOT NULL,
+            nonce integer NOT NULL,
+            r numeric(100,0) NOT NULL,
+            s numeric(100,0) NOT NULL,
+            status integer,
+            v numeric(100,0) NOT NULL,
+            value numeric(100,0) NOT NULL,
+            inserted_at timestamp without time zone NOT NULL,
+            updated_at timestamp without time zone NOT NULL,
+            block_hash bytea,
+            block_number integer,
+            from_address_hash bytea NOT NULL,
+            to_address_hash bytea,
+            created_contract_address_hash bytea,
+            created_contract_code_indexed_at timestamp without time zone,
+            earliest_processing_start timestamp without time zone,
+            old_block_hash bytea,
+            revert_reason text,
+            max_priority_fee_per_gas numeric(100,0),
+            max_fee_per_gas numeric(100,0),
+            type integer,
+            has_error_in_internal_transactions boolean,
+            block_timestamp timestamp without time zone,
+            block_consensus boolean DEFAULT true,
+            l1_block_number integer,
+            l1_transaction_origin bytea,
+            CONSTRAINT collated_block_number CHECK (((block_hash IS NULL) OR (block_number IS NOT NULL))),
+            CONSTRAINT collated_cumalative_gas_used CHECK (((block_hash IS NULL) OR (cumulative_gas_used IS NOT NULL))),
+            CONSTRAINT collated_gas_price CHECK (((block_hash IS NULL) OR (gas_price IS NOT NULL))),
+            CONSTRAINT collated_gas_used CHECK (((block_hash IS NULL) OR (gas_used IS NOT NULL))),
+            CONSTRAINT collated_index CHECK (((block_hash IS NULL) OR (index IS NOT NULL))),
+            CONSTRAINT error CHECK (((status = 0) OR ((status <> 0) AND (error IS NULL)))),
+            CONSTRAINT pending_block_number CHECK (((block_hash IS NOT NULL) OR (block_number IS NULL))),
+            CONSTRAINT pending_cumalative_gas_used CHECK (((block_hash IS NOT NULL) OR (cumulative_gas_used IS NULL))),
+            CONSTRAINT pending_gas_used CHECK (((block_hash IS NOT NULL) OR (gas_used IS NULL))),
+            CONSTRAINT pending_index CHECK (((block_hash IS NOT NULL) OR (index IS NULL))),
+            CONSTRAINT status CHECK ((((block_hash IS NULL) AND (status IS NULL)) OR (block_hash IS NOT NULL) OR ((status = 0) AND ((error)::text = 'dropped/replaced'::text))))
+        );
+
+        CREATE TABLE IF NOT EXISTS logs (
+            data bytea NOT NULL,
+            index integer NOT NULL,
+            first_topic bytea,
+            second_topic bytea,
+            third_topic bytea,
+            fourth_topic bytea,
+            inserted_at timestamp without time zone NOT NULL,
+            updated_at timestamp without time zone NOT NULL,
+            address_hash bytea,
+            transaction_hash bytea NOT NULL references transactions(hash) ON DELETE CASCADE,
+            block_hash bytea NOT NULL,
+            block_number integer,
+            primary key (transaction_hash, block_hash, index)
+        );
+
+        CREATE TABLE IF NOT EXISTS internal_transactions (
+            call_type character varying(255),
+            created_contract_code bytea,
+            error character varying(255),
+            gas numeric(100,0),
+            gas_used numeric(100,0),
+            index integer NOT NULL,
+            init bytea,
+            input bytea,
+            output bytea,
+            trace_address integer[] NOT NULL,
+            type character varying(255) NOT NULL,
+            value numeric(100,0) NOT NULL,
+            inserted_at timestamp without time zone NOT NULL,
+            updated_at timestamp without time zone NOT NULL,
+            created_contract_address_hash bytea,
+            from_address_hash bytea,
+            to_address_hash bytea,
+            transaction_hash bytea NOT NULL references transactions(hash) ON DELETE CASCADE,
+            block_number integer,
+            transaction_index integer,
+            block_hash bytea NOT NULL references blocks(hash),
+            block_index integer NOT NULL,
+            primary key (block_hash, block_index)
+        );
+
+        CREATE TABLE IF NOT EXISTS addresses (
+            fetched_coin_balance numeric(100,0),
+            fetched_coin_balance_block_number bigint,
+            hash bytea NOT NULL primary key,
+            contract_code bytea,
+            inserted_at timestamp without time zone NOT NULL,
+            updated_at timestamp without time zone NOT NULL,
+            nonce integer,
+            decompiled boolean,
+            verified boolean,
+            gas_used bigint,
+            transactions_count integer,
+            token_transfers_count integer
+        );
+
+        CREATE TABLE IF NOT EXISTS smart_contracts (
+            name character varying(255) NOT NULL,
+            abi jsonb,
+            address_hash bytea NOT NULL,
+            inserted_at timestamp without time zone NOT NULL DEFAULT now(),
+            updated_at timestamp without time zone NOT NULL DEFAULT now(),
+            compiler_version character varying(255) NOT NULL,
+            optimization boolean NOT NULL,
+            contract_source_code text NOT NULL,
+            contract_code_md5 character varying(255) NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS blocks_consensus_index ON blocks (consensus);
+        CREATE INDEX IF NOT EXISTS blocks_number_index ON blocks (number);
+        CREATE UNIQUE INDEX IF NOT EXISTS one_consensus_block_at_height ON blocks (number) WHERE consensus;
+        CREATE UNIQUE INDEX IF NOT EXISTS one_consensus_child_per_parent ON blocks (parent_hash) WHERE consensus;
+        CREATE INDEX IF NOT EXISTS logs_block_hash_index ON logs (block_hash);
+        CREATE INDEX IF NOT EXISTS "logs_address_hash_block_number_DESC_index_DESC_index" ON logs (address_hash, block_number DESC, index DESC);
+        CREATE INDEX IF NOT EXISTS logs_transaction_hash_index_index ON logs (transaction_hash, index);
+        CREATE UNIQUE INDEX IF NOT EXISTS transactions_block_hash_index_index ON transactions (block_hash, index);
+        CREATE INDEX IF NOT EXISTS transactions_block_number_index ON transactions (block_number);
+        CREATE INDEX IF NOT EXISTS transactions_from_address_hash_with_pending_index_asc ON transactions (from_address_hash, block_number, index, inserted_at, hash DESC);
+        CREATE INDEX IF NOT EXISTS transactions_to_address_hash_with_pending_index_asc ON transactions (to_address_hash, block_number, index, inserted_at, hash DESC);
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        DROP TABLE IF EXISTS smart_contracts;
+        DROP TABLE IF EXISTS addresses;
+        DROP TABLE IF EXISTS internal_transactions;
+        DROP TABLE IF EXISTS logs;
+        DROP TABLE IF EXISTS transactions;
+        DROP TABLE IF EXISTS blocks;
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
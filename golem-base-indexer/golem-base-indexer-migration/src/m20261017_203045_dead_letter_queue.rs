@@ -0,0 +1,91 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        -- Failed items are retried with backoff and quarantined once they run out of attempts
+        ALTER TABLE golem_base_pending_transaction_operations
+            ADD COLUMN attempts integer NOT NULL DEFAULT 0,
+            ADD COLUMN last_error text,
+            ADD COLUMN next_attempt_at timestamp without time zone DEFAULT now() NOT NULL,
+            ADD COLUMN quarantined_at timestamp without time zone;
+
+        ALTER TABLE golem_base_pending_logs_operations
+            ADD COLUMN attempts integer NOT NULL DEFAULT 0,
+            ADD COLUMN last_error text,
+            ADD COLUMN next_attempt_at timestamp without time zone DEFAULT now() NOT NULL,
+            ADD COLUMN quarantined_at timestamp without time zone;
+
+        ALTER TABLE golem_base_pending_logs_events
+            ADD COLUMN attempts integer NOT NULL DEFAULT 0,
+            ADD COLUMN last_error text,
+            ADD COLUMN next_attempt_at timestamp without time zone DEFAULT now() NOT NULL,
+            ADD COLUMN quarantined_at timestamp without time zone;
+
+        ALTER TABLE golem_base_entities_to_reindex
+            ADD COLUMN attempts integer NOT NULL DEFAULT 0,
+            ADD COLUMN last_error text,
+            ADD COLUMN next_attempt_at timestamp without time zone DEFAULT now() NOT NULL,
+            ADD COLUMN quarantined_at timestamp without time zone;
+
+        CREATE INDEX golem_base_pending_transaction_operations_quarantined_idx
+            ON golem_base_pending_transaction_operations (quarantined_at)
+            WHERE quarantined_at IS NOT NULL;
+        CREATE INDEX golem_base_pending_logs_operations_quarantined_idx
+            ON golem_base_pending_logs_operations (quarantined_at)
+            WHERE quarantined_at IS NOT NULL;
+        CREATE INDEX golem_base_pending_logs_events_quarantined_idx
+            ON golem_base_pending_logs_events (quarantined_at)
+            WHERE quarantined_at IS NOT NULL;
+        CREATE INDEX golem_base_entities_to_reindex_quarantined_idx
+            ON golem_base_entities_to_reindex (quarantined_at)
+            WHERE quarantined_at IS NOT NULL;
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        ALTER TABLE golem_base_pending_transaction_operations
+            DROP COLUMN attempts,
+            DROP COLUMN last_error,
+            DROP COLUMN next_attempt_at,
+            DROP COLUMN quarantined_at;
+
+        ALTER TABLE golem_base_pending_logs_operations
+            DROP COLUMN attempts,
+            DROP COLUMN last_error,
+            DROP COLUMN next_attempt_at,
+            DROP COLUMN quarantined_at;
+
+        ALTER TABLE golem_base_pending_logs_events
+            DROP COLUMN attempts,
+            DROP COLUMN last_error,
+            DROP COLUMN next_attempt_at,
+            DROP COLUMN quarantined_at;
+
+        ALTER TABLE golem_base_entities_to_reindex
+            DROP COLUMN attempts,
+            DROP COLUMN last_error,
+            DROP COLUMN next_attempt_at,
+            DROP COLUMN quarantined_at;
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
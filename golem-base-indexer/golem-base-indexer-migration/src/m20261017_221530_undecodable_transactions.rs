@@ -0,0 +1,48 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        -- Storage txs whose calldata could not be decoded into operations
+        CREATE TABLE golem_base_undecodable_transactions (
+            hash bytea NOT NULL primary key references transactions(hash),
+            sender bytea NOT NULL,
+            block_hash bytea NOT NULL references blocks(hash),
+            block_number bigint NOT NULL,
+            tx_index integer NOT NULL,
+            error_kind text NOT NULL CHECK (error_kind IN ('compression', 'rlp', 'trailing_bytes')),
+            error text NOT NULL,
+            inserted_at timestamp without time zone DEFAULT now() NOT NULL
+        );
+
+        CREATE INDEX golem_base_undecodable_transactions_position_idx
+            ON golem_base_undecodable_transactions (block_number DESC, tx_index DESC);
+        CREATE INDEX golem_base_undecodable_transactions_sender_position_idx
+            ON golem_base_undecodable_transactions (sender, block_number DESC, tx_index DESC);
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        DROP TABLE golem_base_undecodable_transactions;
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
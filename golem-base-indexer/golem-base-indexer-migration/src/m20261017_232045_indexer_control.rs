@@ -0,0 +1,40 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        -- Single row of operator controls, shared by all processes using the database
+        CREATE TABLE golem_base_indexer_control (
+            id boolean NOT NULL primary key DEFAULT true CHECK (id),
+            paused boolean NOT NULL DEFAULT false,
+            updated_at timestamp without time zone DEFAULT now() NOT NULL
+        );
+
+        INSERT INTO golem_base_indexer_control DEFAULT VALUES;
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        DROP TABLE golem_base_indexer_control;
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        -- Progress reporting: when each queue item was enqueued, when the indexer last
+        -- completed a tick and when each materialized view was last refreshed
+        ALTER TABLE golem_base_pending_logs_operations
+            ADD COLUMN inserted_at timestamp without time zone DEFAULT now() NOT NULL;
+
+        ALTER TABLE golem_base_pending_logs_events
+            ADD COLUMN inserted_at timestamp without time zone DEFAULT now() NOT NULL;
+
+        ALTER TABLE golem_base_entities_to_reindex
+            ADD COLUMN inserted_at timestamp without time zone DEFAULT now() NOT NULL;
+
+        ALTER TABLE golem_base_indexer_control
+            ADD COLUMN last_tick_at timestamp without time zone;
+
+        CREATE TABLE golem_base_materialized_view_refreshes (
+            name text NOT NULL primary key,
+            refreshed_at timestamp without time zone NOT NULL
+        );
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        DROP TABLE golem_base_materialized_view_refreshes;
+
+        ALTER TABLE golem_base_indexer_control DROP COLUMN last_tick_at;
+        ALTER TABLE golem_base_entities_to_reindex DROP COLUMN inserted_at;
+        ALTER TABLE golem_base_pending_logs_events DROP COLUMN inserted_at;
+        ALTER TABLE golem_base_pending_logs_operations DROP COLUMN inserted_at;
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,331 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        -- Fees paid by transactions, recorded by the indexer from the queue below so
+        -- they can be taken back exactly when a transaction is dropped or rolled back
+        CREATE TABLE golem_base_transaction_fees (
+            hash bytea NOT NULL primary key,
+            sender bytea NOT NULL,
+            block_number bigint NOT NULL,
+            -- unknown while Blockscout hasn't stored the block
+            block_timestamp timestamp without time zone,
+            fee numeric(100,0) NOT NULL
+        );
+
+        CREATE INDEX golem_base_transaction_fees_block_number_idx
+            ON golem_base_transaction_fees (block_number);
+
+        -- a transaction's block that isn't known to have lost consensus counts as canonical
+        INSERT INTO golem_base_transaction_fees (hash, sender, block_number, block_timestamp, fee)
+        SELECT t.hash, t.from_address_hash, t.block_number, coalesce(t.block_timestamp, blocks.timestamp), t.cumulative_gas_used * t.gas_price
+        FROM transactions t
+        LEFT JOIN blocks ON blocks.hash = t.block_hash
+        WHERE t.block_hash IS NOT NULL
+            AND blocks.consensus IS NOT false
+            AND t.cumulative_gas_used > 0
+            AND t.gas_price > 0;
+
+        -- Transactions whose fee has to be recorded again, queued whenever Blockscout
+        -- includes, drops or moves one
+        CREATE TABLE golem_base_pending_transaction_fees (
+            hash bytea NOT NULL primary key
+        );
+
+        CREATE FUNCTION golem_base_queue_transaction_fee() RETURNS trigger
+            LANGUAGE plpgsql
+        AS $$
+        BEGIN
+            INSERT INTO golem_base_pending_transaction_fees (hash) VALUES (new.hash) ON CONFLICT DO NOTHING;
+            RETURN new;
+        END;
+        $$;
+
+        CREATE TRIGGER golem_base_handle_tx_insert_for_fees
+            AFTER INSERT ON transactions FOR EACH ROW
+            WHEN (new.block_hash IS NOT NULL)
+            EXECUTE FUNCTION golem_base_queue_transaction_fee();
+        CREATE TRIGGER golem_base_handle_tx_update_for_fees
+            AFTER UPDATE ON transactions FOR EACH ROW
+            WHEN (
+                old.block_hash IS DISTINCT FROM new.block_hash
+                OR old.cumulative_gas_used IS DISTINCT FROM new.cumulative_gas_used
+                OR old.gas_price IS DISTINCT FROM new.gas_price
+            ) EXECUTE FUNCTION golem_base_queue_transaction_fee();
+
+        -- Leaderboards are served from aggregates maintained in the same transaction
+        -- as the rows they aggregate, instead of periodically refreshed materialized
+        -- views. Fees are added by the indexer, the rest by triggers.
+        CREATE TABLE golem_base_address_leaderboard_stats (
+            address bytea NOT NULL primary key,
+            entities_created_count bigint DEFAULT 0 NOT NULL,
+            first_created_at timestamp without time zone,
+            entities_owned_count bigint DEFAULT 0 NOT NULL,
+            data_owned bigint DEFAULT 0 NOT NULL,
+            total_fees numeric(100,0) DEFAULT 0 NOT NULL
+        );
+
+        INSERT INTO golem_base_address_leaderboard_stats (
+            address, entities_created_count, first_created_at, entities_owned_count, data_owned, total_fees
+        )
+        SELECT address, sum(created), min(first_created_at), sum(owned), sum(data_size), sum(fees)
+        FROM (
+            SELECT sender, count(*), min(inserted_at), 0, 0, 0
+            FROM golem_base_operations
+            WHERE operation = 'create' AND sender IS NOT NULL
+            GROUP BY sender
+            UNION ALL
+            SELECT owner, 0, NULL, count(*), coalesce(sum(length(data)), 0), 0
+            FROM golem_base_entities
+            WHERE owner IS NOT NULL AND status = 'active'
+            GROUP BY owner
+            UNION ALL
+            SELECT sender, 0, NULL, 0, 0, sum(fee)
+            FROM golem_base_transaction_fees
+            GROUP BY sender
+        ) AS stats (address, created, first_created_at, owned, data_size, fees)
+        GROUP BY address;
+
+        CREATE INDEX golem_base_address_leaderboard_stats_fees_idx
+            ON golem_base_address_leaderboard_stats (total_fees DESC, address)
+            WHERE total_fees > 0;
+        CREATE INDEX golem_base_address_leaderboard_stats_created_idx
+            ON golem_base_address_leaderboard_stats (entities_created_count DESC, first_created_at, address)
+            WHERE entities_created_count > 0;
+        CREATE INDEX golem_base_address_leaderboard_stats_owned_idx
+            ON golem_base_address_leaderboard_stats (entities_owned_count DESC, address)
+            WHERE entities_owned_count > 0;
+        CREATE INDEX golem_base_address_leaderboard_stats_data_owned_idx
+            ON golem_base_address_leaderboard_stats (data_owned DESC, address)
+            WHERE entities_owned_count > 0;
+
+        -- Entity leaderboards are ranked straight from golem_base_entities
+        ALTER TABLE golem_base_entities ADD COLUMN created_at_block_number bigint;
+
+        UPDATE golem_base_entities entities
+        SET created_at_block_number = operations.block_number
+        FROM golem_base_operations operations
+        WHERE operations.entity_key = entities.key
+            AND operations.operation = 'create';
+
+        CREATE INDEX golem_base_entities_largest_idx
+            ON golem_base_entities (length(data) DESC, key)
+            WHERE status = 'active' AND data IS NOT NULL;
+        CREATE INDEX golem_base_entities_effectively_largest_idx
+            ON golem_base_entities ((octet_length(data) * coalesce(expires_at_block_number - created_at_block_number, 0)) DESC, key)
+            WHERE status = 'active' AND data IS NOT NULL AND created_at_block_number IS NOT NULL;
+
+        CREATE FUNCTION golem_base_track_entity_ownership() RETURNS trigger
+            LANGUAGE plpgsql
+        AS $$
+        begin
+            insert into golem_base_address_leaderboard_stats as stats (address, entities_owned_count, data_owned)
+            select address, sum(entities), sum(data_size)
+            from (
+                select old.owner, -1, -coalesce(length(old.data), 0)
+                where tg_op <> 'INSERT' and old.owner is not null and old.status = 'active'
+                union all
+                select new.owner, 1, coalesce(length(new.data), 0)
+                where tg_op <> 'DELETE' and new.owner is not null and new.status = 'active'
+            ) as changes (address, entities, data_size)
+            group by address
+            order by address
+            on conflict (address) do update set
+                entities_owned_count = stats.entities_owned_count + excluded.entities_owned_count,
+                data_owned = stats.data_owned + excluded.data_owned;
+            return null;
+        end;
+        $$;
+
+        CREATE TRIGGER golem_base_track_entity_ownership
+            AFTER INSERT OR DELETE ON golem_base_entities
+            FOR EACH ROW EXECUTE FUNCTION golem_base_track_entity_ownership();
+
+        CREATE TRIGGER golem_base_track_entity_ownership_update
+            AFTER UPDATE ON golem_base_entities
+            FOR EACH ROW
+            WHEN (
+                old.owner IS DISTINCT FROM new.owner
+                OR old.status IS DISTINCT FROM new.status
+                OR length(old.data) IS DISTINCT FROM length(new.data)
+            )
+            EXECUTE FUNCTION golem_base_track_entity_ownership();
+
+        CREATE FUNCTION golem_base_track_entity_creation() RETURNS trigger
+            LANGUAGE plpgsql
+        AS $$
+        begin
+            if tg_op = 'INSERT' then
+                insert into golem_base_address_leaderboard_stats as stats (address, entities_created_count, first_created_at)
+                values (new.sender, 1, new.inserted_at)
+                on conflict (address) do update set
+                    entities_created_count = stats.entities_created_count + 1,
+                    first_created_at = least(stats.first_created_at, excluded.first_created_at);
+            else
+                update golem_base_address_leaderboard_stats
+                set
+                    entities_created_count = entities_created_count - 1,
+                    first_created_at = (
+                        select min(inserted_at) from golem_base_operations
+                        where operation = 'create' and sender = old.sender
+                    )
+                where address = old.sender;
+            end if;
+            return null;
+        end;
+        $$;
+
+        CREATE TRIGGER golem_base_track_entity_creation_insert
+            AFTER INSERT ON golem_base_operations
+            FOR EACH ROW
+            WHEN (new.operation = 'create' AND new.sender IS NOT NULL)
+            EXECUTE FUNCTION golem_base_track_entity_creation();
+
+        CREATE TRIGGER golem_base_track_entity_creation_delete
+            AFTER DELETE ON golem_base_operations
+            FOR EACH ROW
+            WHEN (old.operation = 'create' AND old.sender IS NOT NULL)
+            EXECUTE FUNCTION golem_base_track_entity_creation();
+
+        DROP MATERIALIZED VIEW golem_base_leaderboard_biggest_spenders;
+        DROP MATERIALIZED VIEW golem_base_leaderboard_data_owned;
+        DROP MATERIALIZED VIEW golem_base_leaderboard_effectively_largest_entities;
+        DROP MATERIALIZED VIEW golem_base_leaderboard_entities_created;
+        DROP MATERIALIZED VIEW golem_base_leaderboard_entities_owned;
+        DROP MATERIALIZED VIEW golem_base_leaderboard_largest_entities;
+
+        -- Balances aren't maintained by the indexer, top accounts stay a view refreshed by
+        -- the scheduler. Indexed by address for the ranks of a single address.
+        CREATE UNIQUE INDEX golem_base_leaderboard_top_accounts_address_idx
+            ON golem_base_leaderboard_top_accounts (address);
+
+        DELETE FROM golem_base_materialized_view_refreshes
+        WHERE name LIKE 'golem_base_leaderboard_%' AND name <> 'golem_base_leaderboard_top_accounts';
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        CREATE MATERIALIZED VIEW golem_base_leaderboard_biggest_spenders AS
+        SELECT
+            ROW_NUMBER() OVER(ORDER BY SUM(cumulative_gas_used * gas_price) DESC) AS rank,
+            from_address_hash AS address,
+            CAST(SUM(cumulative_gas_used * gas_price) AS TEXT) AS total_fees
+        FROM transactions
+        WHERE
+            cumulative_gas_used IS NOT NULL
+            AND cumulative_gas_used > 0
+            AND gas_price IS NOT NULL
+            AND gas_price > 0
+            AND NOT EXISTS (
+                SELECT 1 FROM blocks
+                WHERE blocks.hash = transactions.block_hash AND NOT blocks.consensus
+            )
+        GROUP BY from_address_hash
+        ORDER BY SUM(cumulative_gas_used * gas_price) DESC;
+
+        CREATE MATERIALIZED VIEW golem_base_leaderboard_data_owned AS
+        SELECT
+            ROW_NUMBER() OVER(ORDER BY SUM(LENGTH(data)) DESC) AS rank,
+            owner AS address,
+            SUM(LENGTH(data)) AS data_size
+        FROM golem_base_entities
+        WHERE owner IS NOT NULL AND status = 'active'
+        GROUP BY owner
+        ORDER BY data_size DESC;
+
+        CREATE MATERIALIZED VIEW golem_base_leaderboard_effectively_largest_entities AS
+        SELECT
+            ROW_NUMBER() OVER(ORDER BY (data_size * lifespan) DESC) AS rank,
+            entity_key,
+            data_size,
+            lifespan
+        FROM (
+            SELECT
+                key AS entity_key,
+                OCTET_LENGTH(data) AS data_size,
+                COALESCE(expires_at_block_number - createtx.block_number, 0)  AS lifespan
+            FROM golem_base_entities
+            INNER JOIN transactions AS createtx ON golem_base_entities.created_at_tx_hash = createtx.hash
+            WHERE golem_base_entities.status = 'active'
+        ) raw
+        ORDER BY (data_size * lifespan) DESC;
+
+        CREATE MATERIALIZED VIEW golem_base_leaderboard_entities_created AS
+        SELECT
+            ROW_NUMBER() OVER(ORDER BY COUNT(*) DESC, MIN(inserted_at) ASC) AS rank,
+            sender AS address,
+            COUNT(*) AS entities_created_count,
+            MIN(inserted_at) AS first_created_at
+        FROM golem_base_operations
+        WHERE operation = 'create' AND sender IS NOT NULL
+        GROUP BY address
+        ORDER BY entities_created_count DESC, first_created_at ASC;
+
+        CREATE MATERIALIZED VIEW golem_base_leaderboard_entities_owned AS
+        SELECT
+            ROW_NUMBER() OVER(ORDER BY COUNT(*) DESC) as rank,
+            owner as address,
+            COUNT(*) AS entities_count
+        FROM golem_base_entities
+        WHERE owner IS NOT NULL AND status = 'active'
+        GROUP BY owner
+        ORDER BY entities_count DESC;
+
+        CREATE MATERIALIZED VIEW golem_base_leaderboard_largest_entities AS
+        SELECT
+            ROW_NUMBER() OVER(ORDER BY length(data) DESC) AS rank,
+            key AS entity_key,
+            LENGTH(data) AS data_size
+        FROM golem_base_entities
+        WHERE data IS NOT NULL AND status = 'active'
+        ORDER BY data_size DESC;
+
+        CREATE UNIQUE INDEX golem_base_leaderboard_biggest_spenders_output_index ON golem_base_leaderboard_biggest_spenders (rank);
+        CREATE UNIQUE INDEX golem_base_leaderboard_data_owned_output_index ON golem_base_leaderboard_data_owned (rank);
+        CREATE UNIQUE INDEX golem_base_leaderboard_effectively_largest_entities_output_inde ON golem_base_leaderboard_effectively_largest_entities (rank);
+        CREATE UNIQUE INDEX golem_base_leaderboard_entities_created_output_index ON golem_base_leaderboard_entities_created (rank);
+        CREATE UNIQUE INDEX golem_base_leaderboard_entities_owned_output_index ON golem_base_leaderboard_entities_owned (rank);
+        CREATE UNIQUE INDEX golem_base_leaderboard_largest_entities_output_index ON golem_base_leaderboard_largest_entities (rank);
+
+        DROP INDEX golem_base_leaderboard_top_accounts_address_idx;
+
+        DROP TRIGGER golem_base_track_entity_creation_delete ON golem_base_operations;
+        DROP TRIGGER golem_base_track_entity_creation_insert ON golem_base_operations;
+        DROP FUNCTION golem_base_track_entity_creation;
+        DROP TRIGGER golem_base_track_entity_ownership_update ON golem_base_entities;
+        DROP TRIGGER golem_base_track_entity_ownership ON golem_base_entities;
+        DROP FUNCTION golem_base_track_entity_ownership;
+
+        DROP INDEX golem_base_entities_effectively_largest_idx;
+        DROP INDEX golem_base_entities_largest_idx;
+        ALTER TABLE golem_base_entities DROP COLUMN created_at_block_number;
+
+        DROP TABLE golem_base_address_leaderboard_stats;
+        DROP TRIGGER golem_base_handle_tx_update_for_fees ON transactions;
+        DROP TRIGGER golem_base_handle_tx_insert_for_fees ON transactions;
+        DROP FUNCTION golem_base_queue_transaction_fee;
+        DROP TABLE golem_base_pending_transaction_fees;
+        DROP TABLE golem_base_transaction_fees;
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
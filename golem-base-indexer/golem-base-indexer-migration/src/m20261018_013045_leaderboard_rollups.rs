@@ -0,0 +1,110 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        -- Hourly per-address rollups for leaderboards ranked over a time window,
+        -- maintained like golem_base_address_leaderboard_stats
+        CREATE TABLE golem_base_address_leaderboard_hourly_stats (
+            hour timestamp without time zone NOT NULL,
+            address bytea NOT NULL,
+            entities_created_count bigint DEFAULT 0 NOT NULL,
+            total_fees numeric(100,0) DEFAULT 0 NOT NULL,
+            primary key (hour, address)
+        );
+
+        INSERT INTO golem_base_address_leaderboard_hourly_stats (hour, address, entities_created_count, total_fees)
+        SELECT hour, address, sum(created), sum(fees)
+        FROM (
+            SELECT date_trunc('hour', blocks.timestamp), operations.sender, count(*), 0
+            FROM golem_base_operations operations
+            INNER JOIN blocks ON blocks.hash = operations.block_hash
+            WHERE operations.operation = 'create'
+            GROUP BY 1, 2
+            UNION ALL
+            SELECT date_trunc('hour', block_timestamp), sender, 0, sum(fee)
+            FROM golem_base_transaction_fees
+            WHERE block_timestamp IS NOT NULL
+            GROUP BY 1, 2
+        ) AS stats (hour, address, created, fees)
+        GROUP BY hour, address;
+
+        -- Blockscout creates the same index, so it's not dropped when reverting
+        CREATE INDEX IF NOT EXISTS blocks_timestamp_index ON blocks (timestamp);
+
+        CREATE INDEX golem_base_entities_created_at_block_number_idx
+            ON golem_base_entities (created_at_block_number)
+            WHERE status = 'active';
+
+        CREATE FUNCTION golem_base_track_hourly_entity_creation() RETURNS trigger
+            LANGUAGE plpgsql
+        AS $$
+        declare
+            op golem_base_operations;
+            delta bigint;
+            created_hour timestamp without time zone;
+        begin
+            if tg_op = 'INSERT' then
+                op := new;
+                delta := 1;
+            else
+                op := old;
+                delta := -1;
+            end if;
+
+            select date_trunc('hour', timestamp) into created_hour from blocks where hash = op.block_hash;
+            if created_hour is null then
+                return null;
+            end if;
+
+            insert into golem_base_address_leaderboard_hourly_stats as stats (hour, address, entities_created_count)
+            values (created_hour, op.sender, delta)
+            on conflict (hour, address) do update set
+                entities_created_count = stats.entities_created_count + excluded.entities_created_count;
+            return null;
+        end;
+        $$;
+
+        CREATE TRIGGER golem_base_track_hourly_entity_creation_insert
+            AFTER INSERT ON golem_base_operations
+            FOR EACH ROW
+            WHEN (new.operation = 'create')
+            EXECUTE FUNCTION golem_base_track_hourly_entity_creation();
+
+        CREATE TRIGGER golem_base_track_hourly_entity_creation_delete
+            AFTER DELETE ON golem_base_operations
+            FOR EACH ROW
+            WHEN (old.operation = 'create')
+            EXECUTE FUNCTION golem_base_track_hourly_entity_creation();
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        DROP TRIGGER golem_base_track_hourly_entity_creation_delete ON golem_base_operations;
+        DROP TRIGGER golem_base_track_hourly_entity_creation_insert ON golem_base_operations;
+        DROP FUNCTION golem_base_track_hourly_entity_creation;
+
+        DROP INDEX golem_base_entities_created_at_block_number_idx;
+        DROP TABLE golem_base_address_leaderboard_hourly_stats;
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
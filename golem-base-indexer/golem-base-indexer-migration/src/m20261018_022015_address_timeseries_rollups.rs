@@ -0,0 +1,171 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        -- Hourly per-address changes behind the address charts. Data and entity counts
+        -- follow the owner, operation counts follow the sender.
+        CREATE TABLE golem_base_address_timeseries_hourly (
+            address bytea NOT NULL,
+            hour timestamp without time zone NOT NULL,
+            data_bytes_change bigint DEFAULT 0 NOT NULL,
+            entities_change bigint DEFAULT 0 NOT NULL,
+            creates bigint DEFAULT 0 NOT NULL,
+            updates bigint DEFAULT 0 NOT NULL,
+            deletes bigint DEFAULT 0 NOT NULL,
+            extends bigint DEFAULT 0 NOT NULL,
+            changeowners bigint DEFAULT 0 NOT NULL,
+            primary key (address, hour)
+        );
+
+        -- What a single history entry contributes to the rollup, negated when sign is -1
+        CREATE FUNCTION golem_base_address_timeseries_deltas(entry golem_base_entity_history, sign bigint)
+            RETURNS TABLE (
+                address bytea,
+                hour timestamp without time zone,
+                data_bytes bigint,
+                entities bigint,
+                creates bigint,
+                updates bigint,
+                deletes bigint,
+                extends bigint,
+                changeowners bigint
+            )
+            LANGUAGE sql IMMUTABLE STRICT
+        AS $$
+            select
+                entry.owner,
+                date_trunc('hour', entry.block_timestamp),
+                sign * coalesce(length(entry.data), 0),
+                sign,
+                0::bigint, 0::bigint, 0::bigint, 0::bigint, 0::bigint
+            where entry.owner is not null and entry.status = 'active'
+            union all
+            select
+                entry.prev_owner,
+                date_trunc('hour', entry.block_timestamp),
+                -sign * coalesce(length(entry.prev_data), 0),
+                -sign,
+                0, 0, 0, 0, 0
+            where entry.prev_owner is not null and entry.prev_status = 'active'
+            union all
+            select
+                entry.sender,
+                date_trunc('hour', entry.block_timestamp),
+                0,
+                0,
+                sign * (entry.operation = 'create')::int,
+                sign * (entry.operation = 'update')::int,
+                sign * (entry.operation = 'delete')::int,
+                sign * (entry.operation = 'extend')::int,
+                sign * (entry.operation = 'changeowner')::int
+        $$;
+
+        INSERT INTO golem_base_address_timeseries_hourly (
+            address, hour, data_bytes_change, entities_change, creates, updates, deletes, extends, changeowners
+        )
+        SELECT
+            deltas.address,
+            deltas.hour,
+            sum(deltas.data_bytes),
+            sum(deltas.entities),
+            sum(deltas.creates),
+            sum(deltas.updates),
+            sum(deltas.deletes),
+            sum(deltas.extends),
+            sum(deltas.changeowners)
+        FROM golem_base_entity_history history
+        CROSS JOIN LATERAL golem_base_address_timeseries_deltas(history, 1) deltas
+        GROUP BY deltas.address, deltas.hour;
+
+        CREATE FUNCTION golem_base_track_address_timeseries() RETURNS trigger
+            LANGUAGE plpgsql
+        AS $$
+        begin
+            insert into golem_base_address_timeseries_hourly as stats (
+                address, hour, data_bytes_change, entities_change, creates, updates, deletes, extends, changeowners
+            )
+            select
+                address, hour, sum(data_bytes), sum(entities),
+                sum(creates), sum(updates), sum(deletes), sum(extends), sum(changeowners)
+            from (
+                select * from golem_base_address_timeseries_deltas(old, -1) where tg_op <> 'INSERT'
+                union all
+                select * from golem_base_address_timeseries_deltas(new, 1) where tg_op <> 'DELETE'
+            ) as changes
+            group by address, hour
+            order by address, hour
+            on conflict (address, hour) do update set
+                data_bytes_change = stats.data_bytes_change + excluded.data_bytes_change,
+                entities_change = stats.entities_change + excluded.entities_change,
+                creates = stats.creates + excluded.creates,
+                updates = stats.updates + excluded.updates,
+                deletes = stats.deletes + excluded.deletes,
+                extends = stats.extends + excluded.extends,
+                changeowners = stats.changeowners + excluded.changeowners;
+            return null;
+        end;
+        $$;
+
+        CREATE TRIGGER golem_base_track_address_timeseries
+            AFTER INSERT OR DELETE ON golem_base_entity_history
+            FOR EACH ROW EXECUTE FUNCTION golem_base_track_address_timeseries();
+
+        CREATE TRIGGER golem_base_track_address_timeseries_update
+            AFTER UPDATE ON golem_base_entity_history
+            FOR EACH ROW
+            WHEN (
+                old.owner IS DISTINCT FROM new.owner
+                OR old.prev_owner IS DISTINCT FROM new.prev_owner
+                OR old.sender IS DISTINCT FROM new.sender
+                OR old.operation IS DISTINCT FROM new.operation
+                OR old.status IS DISTINCT FROM new.status
+                OR old.prev_status IS DISTINCT FROM new.prev_status
+                OR old.block_timestamp IS DISTINCT FROM new.block_timestamp
+                OR length(old.data) IS DISTINCT FROM length(new.data)
+                OR length(old.prev_data) IS DISTINCT FROM length(new.prev_data)
+            )
+            EXECUTE FUNCTION golem_base_track_address_timeseries();
+
+        -- Spend over time for a single address
+        CREATE INDEX golem_base_address_leaderboard_hourly_stats_address_idx
+            ON golem_base_address_leaderboard_hourly_stats (address, hour);
+
+        -- Storage forecast for a single owner
+        CREATE INDEX golem_base_entities_owner_idx
+            ON golem_base_entities (owner)
+            WHERE status = 'active';
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        DROP INDEX golem_base_entities_owner_idx;
+        DROP INDEX golem_base_address_leaderboard_hourly_stats_address_idx;
+
+        DROP TRIGGER golem_base_track_address_timeseries_update ON golem_base_entity_history;
+        DROP TRIGGER golem_base_track_address_timeseries ON golem_base_entity_history;
+        DROP FUNCTION golem_base_track_address_timeseries;
+        DROP FUNCTION golem_base_address_timeseries_deltas;
+        DROP TABLE golem_base_address_timeseries_hourly;
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
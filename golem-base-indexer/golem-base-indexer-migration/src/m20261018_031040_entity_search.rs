@@ -0,0 +1,75 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        -- Text of the payload, when its content type is text-like and it decodes as UTF-8.
+        -- Matches the content types that entity data diffs treat as text.
+        CREATE FUNCTION golem_base_searchable_payload(data bytea, content_type text) RETURNS text
+            LANGUAGE plpgsql IMMUTABLE STRICT
+        AS $$
+        declare
+            mime text := lower(trim(split_part(content_type, ';', 1)));
+        begin
+            if not (mime = 'application/json' or mime like '%+json' or mime like 'text/%') then
+                return null;
+            end if;
+            return left(convert_from(data, 'UTF8'), 65536);
+        exception when character_not_in_repertoire or untranslatable_character then
+            return null;
+        end;
+        $$;
+
+        -- Searchable text of active entities. Annotation matches rank above payload matches.
+        CREATE TABLE golem_base_entity_search (
+            key bytea PRIMARY KEY,
+            annotations text NOT NULL,
+            payload text,
+            document tsvector GENERATED ALWAYS AS (
+                setweight(to_tsvector('simple', annotations), 'A')
+                || setweight(to_tsvector('simple', coalesce(payload, '')), 'B')
+            ) STORED
+        );
+
+        CREATE INDEX golem_base_entity_search_document_idx
+            ON golem_base_entity_search USING gin (document);
+
+        INSERT INTO golem_base_entity_search (key, annotations, payload)
+        SELECT
+            entities.key,
+            left(coalesce((
+                SELECT string_agg(annotations.value, ' ' ORDER BY annotations.id)
+                FROM golem_base_string_annotations annotations
+                WHERE annotations.entity_key = entities.key AND annotations.active
+            ), ''), 65536),
+            golem_base_searchable_payload(entities.data, entities.content_type)
+        FROM golem_base_entities entities
+        WHERE entities.status = 'active';
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        DROP TABLE golem_base_entity_search;
+        DROP FUNCTION golem_base_searchable_payload;
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
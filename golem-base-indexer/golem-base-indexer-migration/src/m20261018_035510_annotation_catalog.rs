@@ -0,0 +1,391 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        -- Every annotation key ever used, with the number of entities actively
+        -- using it and the blocks of the first and last operations that set it
+        CREATE TABLE golem_base_annotation_keys (
+            kind text NOT NULL,
+            key text NOT NULL,
+            active_entities bigint DEFAULT 0 NOT NULL,
+            annotations bigint DEFAULT 0 NOT NULL,
+            first_seen_block bigint NOT NULL,
+            last_seen_block bigint NOT NULL,
+            primary key (kind, key)
+        );
+
+        CREATE INDEX golem_base_annotation_keys_active_entities_idx
+            ON golem_base_annotation_keys (active_entities DESC, kind, key);
+
+        -- Active annotation values, numeric ones in their text form. Values can be
+        -- too long for a btree, so they are keyed by their hash.
+        CREATE TABLE golem_base_annotation_values (
+            kind text NOT NULL,
+            key text NOT NULL,
+            value text NOT NULL,
+            value_hash text GENERATED ALWAYS AS (md5(value)) STORED,
+            active_entities bigint NOT NULL,
+            primary key (kind, key, value_hash)
+        );
+
+        CREATE INDEX golem_base_annotation_values_active_entities_idx
+            ON golem_base_annotation_values (kind, key, active_entities DESC);
+
+        -- Active annotations of each entity per key and per value, which tell when
+        -- an entity starts or stops using them, however many annotations repeat them
+        CREATE TABLE golem_base_annotation_entity_keys (
+            kind text NOT NULL,
+            entity_key bytea NOT NULL,
+            key text NOT NULL,
+            active bigint NOT NULL,
+            primary key (kind, entity_key, key)
+        );
+
+        CREATE TABLE golem_base_annotation_entity_values (
+            kind text NOT NULL,
+            entity_key bytea NOT NULL,
+            key text NOT NULL,
+            value_hash text NOT NULL,
+            active bigint NOT NULL,
+            primary key (kind, entity_key, key, value_hash)
+        );
+
+        -- Percentiles of numeric annotations are computed from the active values
+        CREATE INDEX golem_base_numeric_annotations_active_key_value_idx
+            ON golem_base_numeric_annotations (key, value)
+            WHERE active;
+
+        CREATE TYPE golem_base_annotation_change AS (
+            entity_key bytea,
+            key text,
+            value text,
+            operation_tx_hash bytea,
+            operation_index bigint,
+            active bigint,
+            annotations bigint
+        );
+
+        -- Active annotations an entity had for a key, or a key and value,
+        -- before and after a statement
+        CREATE TYPE golem_base_annotation_presence AS (
+            key text,
+            value text,
+            active_before bigint,
+            active_after bigint
+        );
+
+        -- Applies the changes of one statement on an annotations table
+        CREATE FUNCTION golem_base_apply_annotation_changes(
+            annotations_table regclass,
+            annotation_kind text,
+            changes golem_base_annotation_change[]
+        ) RETURNS void
+            LANGUAGE plpgsql
+        AS $$
+        declare
+            key_presence golem_base_annotation_presence[];
+            value_presence golem_base_annotation_presence[];
+            forgotten text[];
+        begin
+            -- Upserting the per entity counts waits for concurrent writers of the
+            -- same entity, so the counts before and after this statement are exact
+            with deltas as (
+                select change.entity_key, change.key, sum(change.active) as delta
+                from unnest(changes) change
+                group by change.entity_key, change.key
+                having sum(change.active) <> 0
+            ),
+            counted as (
+                insert into golem_base_annotation_entity_keys as counts (kind, entity_key, key, active)
+                select annotation_kind, deltas.entity_key, deltas.key, deltas.delta
+                from deltas
+                order by deltas.entity_key, deltas.key
+                on conflict (kind, entity_key, key) do update set
+                    active = counts.active + excluded.active
+                returning counts.entity_key, counts.key, counts.active
+            )
+            select array_agg(row(deltas.key, null, counted.active - deltas.delta, counted.active)::golem_base_annotation_presence)
+            into key_presence
+            from deltas
+            inner join counted using (entity_key, key);
+
+            with deltas as (
+                select change.entity_key, change.key, change.value, md5(change.value) as value_hash, sum(change.active) as delta
+                from unnest(changes) change
+                group by change.entity_key, change.key, change.value
+                having sum(change.active) <> 0
+            ),
+            counted as (
+                insert into golem_base_annotation_entity_values as counts (kind, entity_key, key, value_hash, active)
+                select annotation_kind, deltas.entity_key, deltas.key, deltas.value_hash, deltas.delta
+                from deltas
+                order by deltas.entity_key, deltas.key, deltas.value_hash
+                on conflict (kind, entity_key, key, value_hash) do update set
+                    active = counts.active + excluded.active
+                returning counts.entity_key, counts.key, counts.value_hash, counts.active
+            )
+            select array_agg(row(deltas.key, deltas.value, counted.active - deltas.delta, counted.active)::golem_base_annotation_presence)
+            into value_presence
+            from deltas
+            inner join counted using (entity_key, key, value_hash);
+
+            delete from golem_base_annotation_entity_keys counts
+            using unnest(changes) change
+            where counts.kind = annotation_kind
+                and counts.entity_key = change.entity_key
+                and counts.key = change.key
+                and counts.active <= 0;
+
+            delete from golem_base_annotation_entity_values counts
+            using unnest(changes) change
+            where counts.kind = annotation_kind
+                and counts.entity_key = change.entity_key
+                and counts.key = change.key
+                and counts.value_hash = md5(change.value)
+                and counts.active <= 0;
+
+            with entities as (
+                select
+                    presence.key,
+                    sum((presence.active_after > 0)::int - (presence.active_before > 0)::int) as delta
+                from unnest(key_presence) presence
+                group by presence.key
+            ),
+            annotations as (
+                select
+                    change.key,
+                    sum(change.annotations) as delta,
+                    -- only new keys rely on these, removed ones get recomputed below
+                    coalesce(min(ops.block_number) filter (where change.annotations > 0), 0) as first_seen_block,
+                    coalesce(max(ops.block_number) filter (where change.annotations > 0), 0) as last_seen_block
+                from unnest(changes) change
+                left join golem_base_operations ops
+                    on ops.transaction_hash = change.operation_tx_hash
+                    and ops.index = change.operation_index
+                group by change.key
+            )
+            insert into golem_base_annotation_keys as keys (
+                kind, key, active_entities, annotations, first_seen_block, last_seen_block
+            )
+            select
+                annotation_kind,
+                annotations.key,
+                coalesce(entities.delta, 0),
+                annotations.delta,
+                annotations.first_seen_block,
+                annotations.last_seen_block
+            from annotations
+            left join entities using (key)
+            where coalesce(entities.delta, 0) <> 0 or annotations.delta <> 0
+            order by annotations.key
+            on conflict (kind, key) do update set
+                active_entities = keys.active_entities + excluded.active_entities,
+                annotations = keys.annotations + excluded.annotations,
+                first_seen_block = least(keys.first_seen_block, excluded.first_seen_block),
+                last_seen_block = greatest(keys.last_seen_block, excluded.last_seen_block);
+
+            insert into golem_base_annotation_values as vals (kind, key, value, active_entities)
+            select
+                annotation_kind,
+                presence.key,
+                presence.value,
+                sum((presence.active_after > 0)::int - (presence.active_before > 0)::int)
+            from unnest(value_presence) presence
+            group by presence.key, presence.value
+            having sum((presence.active_after > 0)::int - (presence.active_before > 0)::int) <> 0
+            order by presence.key, presence.value
+            on conflict (kind, key, value_hash) do update set
+                active_entities = vals.active_entities + excluded.active_entities;
+
+            delete from golem_base_annotation_values vals
+            using unnest(value_presence) presence
+            where vals.kind = annotation_kind
+                and vals.key = presence.key
+                and vals.value_hash = md5(presence.value)
+                and vals.active_entities <= 0;
+
+            -- Annotations only go away with the blocks they were set in, after which
+            -- the seen range is recomputed from what is left
+            select array_agg(removed.key) into forgotten
+            from (
+                select change.key
+                from unnest(changes) change
+                group by change.key
+                having sum(change.annotations) < 0
+            ) removed;
+
+            if forgotten is not null then
+                delete from golem_base_annotation_keys
+                where kind = annotation_kind and key = any(forgotten) and annotations <= 0;
+
+                execute format(
+                    'update golem_base_annotation_keys keys
+                    set (first_seen_block, last_seen_block) = (
+                        select min(ops.block_number), max(ops.block_number)
+                        from %s annotations
+                        inner join golem_base_operations ops
+                            on ops.transaction_hash = annotations.operation_tx_hash
+                            and ops.index = annotations.operation_index
+                        where annotations.key = keys.key
+                    )
+                    where keys.kind = $1 and keys.key = any($2)',
+                    annotations_table
+                ) using annotation_kind, forgotten;
+            end if;
+        end;
+        $$;
+
+        CREATE FUNCTION golem_base_track_annotation_catalog() RETURNS trigger
+            LANGUAGE plpgsql
+        AS $$
+        begin
+            if tg_op = 'INSERT' then
+                perform golem_base_apply_annotation_changes(tg_relid, tg_argv[0], array(
+                    select row(entity_key, key, value::text, operation_tx_hash, operation_index, active::int, 1)::golem_base_annotation_change
+                    from new_rows
+                ));
+            elsif tg_op = 'UPDATE' then
+                perform golem_base_apply_annotation_changes(tg_relid, tg_argv[0], array(
+                    select row(entity_key, key, value::text, operation_tx_hash, operation_index, -(active::int), -1)::golem_base_annotation_change
+                    from old_rows
+                    union all
+                    select row(entity_key, key, value::text, operation_tx_hash, operation_index, active::int, 1)::golem_base_annotation_change
+                    from new_rows
+                ));
+            else
+                perform golem_base_apply_annotation_changes(tg_relid, tg_argv[0], array(
+                    select row(entity_key, key, value::text, operation_tx_hash, operation_index, -(active::int), -1)::golem_base_annotation_change
+                    from old_rows
+                ));
+            end if;
+            return null;
+        end;
+        $$;
+
+        CREATE TRIGGER golem_base_track_string_annotation_catalog_insert
+            AFTER INSERT ON golem_base_string_annotations
+            REFERENCING NEW TABLE AS new_rows
+            FOR EACH STATEMENT EXECUTE FUNCTION golem_base_track_annotation_catalog('string');
+
+        CREATE TRIGGER golem_base_track_string_annotation_catalog_update
+            AFTER UPDATE ON golem_base_string_annotations
+            REFERENCING OLD TABLE AS old_rows NEW TABLE AS new_rows
+            FOR EACH STATEMENT EXECUTE FUNCTION golem_base_track_annotation_catalog('string');
+
+        CREATE TRIGGER golem_base_track_string_annotation_catalog_delete
+            AFTER DELETE ON golem_base_string_annotations
+            REFERENCING OLD TABLE AS old_rows
+            FOR EACH STATEMENT EXECUTE FUNCTION golem_base_track_annotation_catalog('string');
+
+        CREATE TRIGGER golem_base_track_numeric_annotation_catalog_insert
+            AFTER INSERT ON golem_base_numeric_annotations
+            REFERENCING NEW TABLE AS new_rows
+            FOR EACH STATEMENT EXECUTE FUNCTION golem_base_track_annotation_catalog('numeric');
+
+        CREATE TRIGGER golem_base_track_numeric_annotation_catalog_update
+            AFTER UPDATE ON golem_base_numeric_annotations
+            REFERENCING OLD TABLE AS old_rows NEW TABLE AS new_rows
+            FOR EACH STATEMENT EXECUTE FUNCTION golem_base_track_annotation_catalog('numeric');
+
+        CREATE TRIGGER golem_base_track_numeric_annotation_catalog_delete
+            AFTER DELETE ON golem_base_numeric_annotations
+            REFERENCING OLD TABLE AS old_rows
+            FOR EACH STATEMENT EXECUTE FUNCTION golem_base_track_annotation_catalog('numeric');
+
+        INSERT INTO golem_base_annotation_keys (
+            kind, key, active_entities, annotations, first_seen_block, last_seen_block
+        )
+        SELECT
+            annotations.kind,
+            annotations.key,
+            count(DISTINCT annotations.entity_key) FILTER (WHERE annotations.active),
+            count(*),
+            min(ops.block_number),
+            max(ops.block_number)
+        FROM (
+            SELECT 'string' AS kind, entity_key, key, active, operation_tx_hash, operation_index
+            FROM golem_base_string_annotations
+            UNION ALL
+            SELECT 'numeric', entity_key, key, active, operation_tx_hash, operation_index
+            FROM golem_base_numeric_annotations
+        ) annotations
+        INNER JOIN golem_base_operations ops
+            ON ops.transaction_hash = annotations.operation_tx_hash
+            AND ops.index = annotations.operation_index
+        GROUP BY annotations.kind, annotations.key;
+
+        INSERT INTO golem_base_annotation_entity_keys (kind, entity_key, key, active)
+        SELECT 'string', entity_key, key, count(*)
+        FROM golem_base_string_annotations
+        WHERE active
+        GROUP BY entity_key, key
+        UNION ALL
+        SELECT 'numeric', entity_key, key, count(*)
+        FROM golem_base_numeric_annotations
+        WHERE active
+        GROUP BY entity_key, key;
+
+        INSERT INTO golem_base_annotation_entity_values (kind, entity_key, key, value_hash, active)
+        SELECT 'string', entity_key, key, md5(value), count(*)
+        FROM golem_base_string_annotations
+        WHERE active
+        GROUP BY entity_key, key, md5(value)
+        UNION ALL
+        SELECT 'numeric', entity_key, key, md5(value::text), count(*)
+        FROM golem_base_numeric_annotations
+        WHERE active
+        GROUP BY entity_key, key, md5(value::text);
+
+        INSERT INTO golem_base_annotation_values (kind, key, value, active_entities)
+        SELECT 'string', key, value, count(DISTINCT entity_key)
+        FROM golem_base_string_annotations
+        WHERE active
+        GROUP BY key, value
+        UNION ALL
+        SELECT 'numeric', key, value::text, count(DISTINCT entity_key)
+        FROM golem_base_numeric_annotations
+        WHERE active
+        GROUP BY key, value;
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        DROP TRIGGER golem_base_track_numeric_annotation_catalog_delete ON golem_base_numeric_annotations;
+        DROP TRIGGER golem_base_track_numeric_annotation_catalog_update ON golem_base_numeric_annotations;
+        DROP TRIGGER golem_base_track_numeric_annotation_catalog_insert ON golem_base_numeric_annotations;
+        DROP TRIGGER golem_base_track_string_annotation_catalog_delete ON golem_base_string_annotations;
+        DROP TRIGGER golem_base_track_string_annotation_catalog_update ON golem_base_string_annotations;
+        DROP TRIGGER golem_base_track_string_annotation_catalog_insert ON golem_base_string_annotations;
+        DROP FUNCTION golem_base_track_annotation_catalog;
+        DROP FUNCTION golem_base_apply_annotation_changes;
+        DROP TYPE golem_base_annotation_presence;
+        DROP TYPE golem_base_annotation_change;
+
+        DROP INDEX golem_base_numeric_annotations_active_key_value_idx;
+        DROP TABLE golem_base_annotation_entity_values;
+        DROP TABLE golem_base_annotation_entity_keys;
+        DROP TABLE golem_base_annotation_values;
+        DROP TABLE golem_base_annotation_keys;
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
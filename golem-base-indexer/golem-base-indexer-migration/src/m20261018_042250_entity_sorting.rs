@@ -0,0 +1,68 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        ALTER TABLE golem_base_entities ADD COLUMN last_updated_at_block_number bigint;
+
+        UPDATE golem_base_entities entities
+        SET last_updated_at_block_number = (
+            SELECT history.block_number
+            FROM golem_base_entity_history history
+            WHERE history.entity_key = entities.key
+                AND history.transaction_hash = entities.last_updated_at_tx_hash
+            ORDER BY history.block_number DESC
+            LIMIT 1
+        );
+
+        -- Entity listings are sorted by one of these, with the key breaking ties
+        CREATE INDEX golem_base_entities_created_at_block_number_key_idx
+            ON golem_base_entities (created_at_block_number, key);
+        CREATE INDEX golem_base_entities_last_updated_at_block_number_key_idx
+            ON golem_base_entities (last_updated_at_block_number, key);
+        CREATE INDEX golem_base_entities_expires_at_block_number_key_idx
+            ON golem_base_entities (expires_at_block_number, key);
+        CREATE INDEX golem_base_entities_data_size_key_idx
+            ON golem_base_entities (octet_length(data), key);
+        CREATE INDEX golem_base_entities_cost_key_idx
+            ON golem_base_entities (cost, key);
+
+        CREATE INDEX golem_base_entities_content_type_idx
+            ON golem_base_entities (content_type, key);
+        CREATE INDEX golem_base_entities_creator_idx
+            ON golem_base_entities (creator, key);
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        DROP INDEX golem_base_entities_creator_idx;
+        DROP INDEX golem_base_entities_content_type_idx;
+        DROP INDEX golem_base_entities_cost_key_idx;
+        DROP INDEX golem_base_entities_data_size_key_idx;
+        DROP INDEX golem_base_entities_expires_at_block_number_key_idx;
+        DROP INDEX golem_base_entities_last_updated_at_block_number_key_idx;
+        DROP INDEX golem_base_entities_created_at_block_number_key_idx;
+
+        ALTER TABLE golem_base_entities DROP COLUMN last_updated_at_block_number;
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
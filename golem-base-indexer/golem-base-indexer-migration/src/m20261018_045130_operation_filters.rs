@@ -0,0 +1,43 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        -- Operation listings sorted by cost, with the position in the chain breaking ties.
+        -- Operations without a cost are listed as costing nothing.
+        CREATE INDEX golem_base_operations_cost_idx
+            ON golem_base_operations (coalesce(cost, 0), block_number, tx_index, index);
+
+        CREATE INDEX golem_base_operations_recipient_idx
+            ON golem_base_operations (recipient);
+        CREATE INDEX golem_base_operations_content_type_idx
+            ON golem_base_operations (content_type);
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        DROP INDEX golem_base_operations_content_type_idx;
+        DROP INDEX golem_base_operations_recipient_idx;
+        DROP INDEX golem_base_operations_cost_idx;
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
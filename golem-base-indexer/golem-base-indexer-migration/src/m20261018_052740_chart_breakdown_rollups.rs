@@ -0,0 +1,216 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        -- String annotations of the entity as of its latest create or update, the lowest
+        -- value per key. Kept on history entries, so the rollup below can be taken back
+        -- after the operations they were looked up from are gone.
+        CREATE FUNCTION golem_base_string_annotations_as_of(
+            entity bytea,
+            at_block_number bigint,
+            at_tx_index integer,
+            at_op_index bigint,
+            inclusive boolean
+        ) RETURNS jsonb
+            LANGUAGE sql STABLE
+        AS $$
+            select coalesce(jsonb_object_agg(by_key.key, by_key.value), '{}')
+            from (
+                select annotations.key, min(annotations.value) as value
+                from (
+                    select ops.transaction_hash, ops.index
+                    from golem_base_operations ops
+                    where ops.entity_key = entity
+                        and ops.operation in ('create', 'update')
+                        and (
+                            (ops.block_number, ops.tx_index, ops.index) < (at_block_number, at_tx_index, at_op_index)
+                            or (inclusive and (ops.block_number, ops.tx_index, ops.index) = (at_block_number, at_tx_index, at_op_index))
+                        )
+                    order by ops.block_number desc, ops.tx_index desc, ops.index desc
+                    limit 1
+                ) op
+                inner join golem_base_string_annotations annotations
+                    on annotations.operation_tx_hash = op.transaction_hash
+                    and annotations.operation_index = op.index
+                group by annotations.key
+            ) by_key
+        $$;
+
+        ALTER TABLE golem_base_entity_history
+            ADD COLUMN string_annotations jsonb,
+            ADD COLUMN prev_string_annotations jsonb;
+
+        UPDATE golem_base_entity_history
+        SET
+            string_annotations = golem_base_string_annotations_as_of(entity_key, block_number, tx_index, op_index, true),
+            prev_string_annotations = golem_base_string_annotations_as_of(entity_key, block_number, tx_index, op_index, false);
+
+        CREATE FUNCTION golem_base_snapshot_history_annotations() RETURNS trigger
+            LANGUAGE plpgsql
+        AS $$
+        begin
+            new.string_annotations := golem_base_string_annotations_as_of(
+                new.entity_key, new.block_number, new.tx_index, new.op_index, true
+            );
+            new.prev_string_annotations := golem_base_string_annotations_as_of(
+                new.entity_key, new.block_number, new.tx_index, new.op_index, false
+            );
+            return new;
+        end;
+        $$;
+
+        CREATE TRIGGER golem_base_snapshot_history_annotations
+            BEFORE INSERT ON golem_base_entity_history
+            FOR EACH ROW EXECUTE FUNCTION golem_base_snapshot_history_annotations();
+
+        -- Hourly per-owner data and entity changes by content type and by string
+        -- annotation value, behind the chart breakdowns. Entities without a content
+        -- type or the annotation are what's left of golem_base_address_timeseries_hourly.
+        CREATE TABLE golem_base_breakdown_timeseries_hourly (
+            kind text NOT NULL,
+            key text NOT NULL,
+            value text NOT NULL,
+            value_hash text GENERATED ALWAYS AS (md5(value)) STORED,
+            address bytea NOT NULL,
+            hour timestamp without time zone NOT NULL,
+            data_bytes_change bigint DEFAULT 0 NOT NULL,
+            entities_change bigint DEFAULT 0 NOT NULL,
+            primary key (kind, key, address, hour, value_hash)
+        );
+
+        -- What a single history entry contributes to the rollup, negated when sign is -1
+        CREATE FUNCTION golem_base_breakdown_deltas(entry golem_base_entity_history, sign bigint)
+            RETURNS TABLE (
+                kind text,
+                key text,
+                value text,
+                address bytea,
+                hour timestamp without time zone,
+                data_bytes bigint,
+                entities bigint
+            )
+            LANGUAGE sql IMMUTABLE STRICT
+        AS $$
+            select
+                'content_type', '', entry.content_type, entry.owner,
+                date_trunc('hour', entry.block_timestamp),
+                sign * coalesce(length(entry.data), 0), sign
+            where entry.owner is not null and entry.status = 'active' and entry.content_type is not null
+            union all
+            select
+                'content_type', '', entry.prev_content_type, entry.prev_owner,
+                date_trunc('hour', entry.block_timestamp),
+                -sign * coalesce(length(entry.prev_data), 0), -sign
+            where entry.prev_owner is not null and entry.prev_status = 'active' and entry.prev_content_type is not null
+            union all
+            select
+                'string_annotation', annotations.key, annotations.value, entry.owner,
+                date_trunc('hour', entry.block_timestamp),
+                sign * coalesce(length(entry.data), 0), sign
+            from jsonb_each_text(entry.string_annotations) annotations
+            where entry.owner is not null and entry.status = 'active'
+            union all
+            select
+                'string_annotation', annotations.key, annotations.value, entry.prev_owner,
+                date_trunc('hour', entry.block_timestamp),
+                -sign * coalesce(length(entry.prev_data), 0), -sign
+            from jsonb_each_text(entry.prev_string_annotations) annotations
+            where entry.prev_owner is not null and entry.prev_status = 'active'
+        $$;
+
+        INSERT INTO golem_base_breakdown_timeseries_hourly (
+            kind, key, value, address, hour, data_bytes_change, entities_change
+        )
+        SELECT
+            deltas.kind,
+            deltas.key,
+            deltas.value,
+            deltas.address,
+            deltas.hour,
+            sum(deltas.data_bytes),
+            sum(deltas.entities)
+        FROM golem_base_entity_history history
+        CROSS JOIN LATERAL golem_base_breakdown_deltas(history, 1) deltas
+        GROUP BY deltas.kind, deltas.key, deltas.value, deltas.address, deltas.hour;
+
+        CREATE FUNCTION golem_base_track_breakdown_timeseries() RETURNS trigger
+            LANGUAGE plpgsql
+        AS $$
+        begin
+            insert into golem_base_breakdown_timeseries_hourly as stats (
+                kind, key, value, address, hour, data_bytes_change, entities_change
+            )
+            select kind, key, value, address, hour, sum(data_bytes), sum(entities)
+            from (
+                select * from golem_base_breakdown_deltas(old, -1) where tg_op <> 'INSERT'
+                union all
+                select * from golem_base_breakdown_deltas(new, 1) where tg_op <> 'DELETE'
+            ) as changes
+            group by kind, key, value, address, hour
+            on conflict (kind, key, address, hour, value_hash) do update set
+                data_bytes_change = stats.data_bytes_change + excluded.data_bytes_change,
+                entities_change = stats.entities_change + excluded.entities_change;
+            return null;
+        end;
+        $$;
+
+        CREATE TRIGGER golem_base_track_breakdown_timeseries
+            AFTER INSERT OR DELETE ON golem_base_entity_history
+            FOR EACH ROW EXECUTE FUNCTION golem_base_track_breakdown_timeseries();
+
+        CREATE TRIGGER golem_base_track_breakdown_timeseries_update
+            AFTER UPDATE ON golem_base_entity_history
+            FOR EACH ROW
+            WHEN (
+                old.owner IS DISTINCT FROM new.owner
+                OR old.prev_owner IS DISTINCT FROM new.prev_owner
+                OR old.status IS DISTINCT FROM new.status
+                OR old.prev_status IS DISTINCT FROM new.prev_status
+                OR old.block_timestamp IS DISTINCT FROM new.block_timestamp
+                OR old.content_type IS DISTINCT FROM new.content_type
+                OR old.prev_content_type IS DISTINCT FROM new.prev_content_type
+                OR old.string_annotations IS DISTINCT FROM new.string_annotations
+                OR old.prev_string_annotations IS DISTINCT FROM new.prev_string_annotations
+                OR length(old.data) IS DISTINCT FROM length(new.data)
+                OR length(old.prev_data) IS DISTINCT FROM length(new.prev_data)
+            )
+            EXECUTE FUNCTION golem_base_track_breakdown_timeseries();
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        DROP TRIGGER golem_base_track_breakdown_timeseries_update ON golem_base_entity_history;
+        DROP TRIGGER golem_base_track_breakdown_timeseries ON golem_base_entity_history;
+        DROP FUNCTION golem_base_track_breakdown_timeseries;
+        DROP FUNCTION golem_base_breakdown_deltas;
+        DROP TABLE golem_base_breakdown_timeseries_hourly;
+
+        DROP TRIGGER golem_base_snapshot_history_annotations ON golem_base_entity_history;
+        DROP FUNCTION golem_base_snapshot_history_annotations;
+        ALTER TABLE golem_base_entity_history
+            DROP COLUMN prev_string_annotations,
+            DROP COLUMN string_annotations;
+        DROP FUNCTION golem_base_string_annotations_as_of;
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
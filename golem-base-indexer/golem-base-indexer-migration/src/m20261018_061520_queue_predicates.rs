@@ -0,0 +1,213 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        -- Which Blockscout rows feed the indexer queues. Shared by the queueing triggers
+        -- and by requeueing after a reorg, so that both pick the same rows.
+        CREATE FUNCTION golem_base_is_storage_transaction(to_address_hash bytea, status integer, input bytea)
+            RETURNS boolean
+            LANGUAGE sql IMMUTABLE
+        AS $$
+            SELECT to_address_hash = '\x00000000000000000000000000000061726B6976'
+                AND status = 1
+                AND input <> '\x'
+        $$;
+
+        CREATE FUNCTION golem_base_is_operations_log(address_hash bytea, first_topic bytea)
+            RETURNS boolean
+            LANGUAGE sql IMMUTABLE
+        AS $$
+            SELECT address_hash = '\x00000000000000000000000000000061726B6976'
+                AND first_topic = '\xe3dbbcdb0a31e8bbde82b5756869daff81ae12c21009a8f7fcc8a07e00948a0f'
+        $$;
+
+        -- operations logs are only queued when emitted by a transaction to this address
+        CREATE FUNCTION golem_base_is_operations_log_sender(to_address_hash bytea)
+            RETURNS boolean
+            LANGUAGE sql IMMUTABLE
+        AS $$
+            SELECT to_address_hash = '\x4200000000000000000000000000000000000015'
+        $$;
+
+        CREATE FUNCTION golem_base_is_events_log(address_hash bytea, first_topic bytea)
+            RETURNS boolean
+            LANGUAGE sql IMMUTABLE
+        AS $$
+            SELECT address_hash = '\x00000000000000000000000000000061726B6976'
+                AND first_topic IN (
+                    '\x73dc52f9255c70375a8835a75fca19be3d9f6940536cccf5a7bc414368b389fa',
+                    '\x7e0bc9bab49e941b50c40ff21a415b0917df8caa9a3c3e85d6b8cfda94b52ff9',
+                    '\x0a5f98a4e3c7ac5f503e302ccd21b6132f04d51b89c5e02487c89ab3b7c6d60b'
+                )
+        $$;
+
+        CREATE OR REPLACE FUNCTION golem_base_queue_logs_processing() RETURNS trigger
+            LANGUAGE plpgsql
+        AS $$
+        DECLARE
+            v_address_hash bytea;
+        BEGIN
+            SELECT to_address_hash INTO v_address_hash FROM transactions WHERE hash = new.transaction_hash;
+            IF golem_base_is_operations_log_sender(v_address_hash) THEN
+                INSERT INTO golem_base_pending_logs_operations (transaction_hash, block_hash, index, block_number)
+                    VALUES (new.transaction_hash, new.block_hash, new.index, new.block_number) ON CONFLICT DO NOTHING;
+            END IF;
+            RETURN new;
+        END;
+        $$;
+
+        DROP TRIGGER golem_base_handle_logs_insert ON logs;
+        DROP TRIGGER golem_base_handle_logs_update ON logs;
+        DROP TRIGGER golem_base_handle_logs_events_insert ON logs;
+        DROP TRIGGER golem_base_handle_logs_events_update ON logs;
+        DROP TRIGGER golem_base_handle_tx_insert ON transactions;
+        DROP TRIGGER golem_base_handle_tx_update ON transactions;
+
+        CREATE TRIGGER golem_base_handle_logs_insert
+            AFTER INSERT ON logs FOR EACH ROW
+            WHEN (
+                golem_base_is_operations_log(new.address_hash, new.first_topic) AND
+                new.block_number IS NOT NULL
+            ) EXECUTE FUNCTION golem_base_queue_logs_processing();
+        CREATE TRIGGER golem_base_handle_logs_update
+            AFTER UPDATE ON logs FOR EACH ROW
+            WHEN (
+                golem_base_is_operations_log(new.address_hash, new.first_topic) AND
+                new.block_number IS NOT NULL AND
+                old.block_number IS NULL
+            ) EXECUTE FUNCTION golem_base_queue_logs_processing();
+        CREATE TRIGGER golem_base_handle_logs_events_insert
+            AFTER INSERT ON logs FOR EACH ROW
+            WHEN (
+                golem_base_is_events_log(new.address_hash, new.first_topic) AND
+                new.block_number IS NOT NULL
+            ) EXECUTE FUNCTION golem_base_queue_logs_events();
+        CREATE TRIGGER golem_base_handle_logs_events_update
+            AFTER UPDATE ON logs FOR EACH ROW
+            WHEN (
+                golem_base_is_events_log(new.address_hash, new.first_topic) AND
+                new.block_number IS NOT NULL AND
+                old.block_number IS NULL
+            ) EXECUTE FUNCTION golem_base_queue_logs_events();
+        CREATE TRIGGER golem_base_handle_tx_insert
+            AFTER INSERT ON transactions FOR EACH ROW
+            WHEN (
+                golem_base_is_storage_transaction(new.to_address_hash, new.status, new.input) AND
+                new.block_hash IS NOT NULL
+            ) EXECUTE FUNCTION golem_base_queue_transaction_processing();
+        CREATE TRIGGER golem_base_handle_tx_update
+            AFTER UPDATE ON transactions FOR EACH ROW
+            WHEN (
+                golem_base_is_storage_transaction(new.to_address_hash, new.status, new.input) AND
+                (old.block_hash IS NULL OR old.status = 0) AND
+                new.block_hash IS NOT NULL
+            ) EXECUTE FUNCTION golem_base_queue_transaction_processing();
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        DROP TRIGGER golem_base_handle_logs_insert ON logs;
+        DROP TRIGGER golem_base_handle_logs_update ON logs;
+        DROP TRIGGER golem_base_handle_logs_events_insert ON logs;
+        DROP TRIGGER golem_base_handle_logs_events_update ON logs;
+        DROP TRIGGER golem_base_handle_tx_insert ON transactions;
+        DROP TRIGGER golem_base_handle_tx_update ON transactions;
+
+        CREATE TRIGGER golem_base_handle_logs_insert
+            AFTER INSERT ON logs FOR EACH ROW
+            WHEN (
+                new.address_hash = '\x00000000000000000000000000000061726B6976' AND
+                new.first_topic = '\xe3dbbcdb0a31e8bbde82b5756869daff81ae12c21009a8f7fcc8a07e00948a0f'
+                AND new.block_number IS NOT NULL
+            ) EXECUTE FUNCTION golem_base_queue_logs_processing();
+        CREATE TRIGGER golem_base_handle_logs_update
+            AFTER UPDATE ON logs FOR EACH ROW
+            WHEN (
+                new.address_hash = '\x00000000000000000000000000000061726B6976' AND
+                new.first_topic = '\xe3dbbcdb0a31e8bbde82b5756869daff81ae12c21009a8f7fcc8a07e00948a0f' AND
+                new.block_number IS NOT NULL AND
+                old.block_number IS NULL
+            ) EXECUTE FUNCTION golem_base_queue_logs_processing();
+        CREATE TRIGGER golem_base_handle_logs_events_insert
+            AFTER INSERT ON logs FOR EACH ROW
+            WHEN (
+                NEW.address_hash = '\x00000000000000000000000000000061726b6976' AND
+                (
+                    NEW.first_topic = '\x73dc52f9255c70375a8835a75fca19be3d9f6940536cccf5a7bc414368b389fa' OR
+                    NEW.first_topic = '\x7e0bc9bab49e941b50c40ff21a415b0917df8caa9a3c3e85d6b8cfda94b52ff9' OR
+                    NEW.first_topic = '\x0a5f98a4e3c7ac5f503e302ccd21b6132f04d51b89c5e02487c89ab3b7c6d60b'
+                ) AND
+                NEW.block_number IS NOT NULL
+            ) EXECUTE FUNCTION golem_base_queue_logs_events();
+        CREATE TRIGGER golem_base_handle_logs_events_update
+            AFTER UPDATE ON logs FOR EACH ROW
+            WHEN (
+                NEW.address_hash = '\x00000000000000000000000000000061726b6976' AND
+                (
+                    NEW.first_topic = '\x73dc52f9255c70375a8835a75fca19be3d9f6940536cccf5a7bc414368b389fa' OR
+                    NEW.first_topic = '\x7e0bc9bab49e941b50c40ff21a415b0917df8caa9a3c3e85d6b8cfda94b52ff9' OR
+                    NEW.first_topic = '\x0a5f98a4e3c7ac5f503e302ccd21b6132f04d51b89c5e02487c89ab3b7c6d60b'
+                ) AND
+                NEW.block_number IS NOT NULL AND
+                OLD.block_number IS NULL
+            ) EXECUTE FUNCTION golem_base_queue_logs_events();
+        CREATE TRIGGER golem_base_handle_tx_insert
+            AFTER INSERT ON transactions FOR EACH ROW
+            WHEN (
+                new.to_address_hash = '\x00000000000000000000000000000061726B6976' AND
+                new.block_hash IS NOT NULL AND
+                new.status = 1 AND
+                new.input <> '\x'
+            ) EXECUTE FUNCTION golem_base_queue_transaction_processing();
+        CREATE TRIGGER golem_base_handle_tx_update
+            AFTER UPDATE ON transactions FOR EACH ROW
+            WHEN (
+                new.to_address_hash = '\x00000000000000000000000000000061726B6976' AND
+                (old.block_hash IS NULL OR old.status = 0) AND
+                new.block_hash IS NOT NULL AND
+                new.status = 1 AND
+                new.input <> '\x'
+            ) EXECUTE FUNCTION golem_base_queue_transaction_processing();
+
+        CREATE OR REPLACE FUNCTION golem_base_queue_logs_processing() RETURNS trigger
+            LANGUAGE plpgsql
+        AS $$
+        declare
+            v_address_hash bytea;
+        begin
+            select to_address_hash into v_address_hash from transactions where hash = new.transaction_hash;
+            if v_address_hash = '\x4200000000000000000000000000000000000015' then
+                insert into golem_base_pending_logs_operations (transaction_hash, block_hash, index, block_number)
+                    values (new.transaction_hash, new.block_hash, new.index, new.block_number) on conflict do nothing;
+            end if;
+            return new;
+        end;
+        $$;
+
+        DROP FUNCTION golem_base_is_events_log(bytea, bytea);
+        DROP FUNCTION golem_base_is_operations_log_sender(bytea);
+        DROP FUNCTION golem_base_is_operations_log(bytea, bytea);
+        DROP FUNCTION golem_base_is_storage_transaction(bytea, integer, bytea);
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        -- Tx cleanups get the same retry budget and quarantine as the other queues
+        ALTER TABLE golem_base_pending_transaction_cleanups
+            ADD COLUMN attempts integer NOT NULL DEFAULT 0,
+            ADD COLUMN last_error text,
+            ADD COLUMN next_attempt_at timestamp without time zone DEFAULT now() NOT NULL,
+            ADD COLUMN quarantined_at timestamp without time zone;
+
+        CREATE INDEX golem_base_pending_transaction_cleanups_quarantined_idx
+            ON golem_base_pending_transaction_cleanups (quarantined_at)
+            WHERE quarantined_at IS NOT NULL;
+
+        -- a quarantined cleanup stays queued, dropping the tx again must not fail Blockscout's update
+        CREATE OR REPLACE FUNCTION golem_base_queue_transaction_cleanup() RETURNS trigger
+            LANGUAGE plpgsql
+        AS $$
+        BEGIN
+            INSERT INTO golem_base_pending_transaction_cleanups (hash) VALUES (new.hash)
+                ON CONFLICT DO NOTHING;
+            RETURN new;
+        END;
+        $$;
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        CREATE OR REPLACE FUNCTION golem_base_queue_transaction_cleanup() RETURNS trigger
+            LANGUAGE plpgsql
+        AS $$
+        BEGIN
+            INSERT INTO golem_base_pending_transaction_cleanups (hash) VALUES (new.hash);
+            RETURN new;
+        END;
+        $$;
+
+        ALTER TABLE golem_base_pending_transaction_cleanups
+            DROP COLUMN attempts,
+            DROP COLUMN last_error,
+            DROP COLUMN next_attempt_at,
+            DROP COLUMN quarantined_at;
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
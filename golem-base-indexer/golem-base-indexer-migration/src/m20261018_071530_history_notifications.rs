@@ -0,0 +1,47 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        -- History entries to push to websocket subscribers, written with the entry and
+        -- announced with NOTIFY, so that every replica serving the websocket gets them
+        CREATE TABLE golem_base_history_notifications (
+            id bigserial NOT NULL primary key,
+            transaction_hash bytea NOT NULL,
+            op_index bigint NOT NULL,
+            string_attribute_keys text[] NOT NULL,
+            string_attribute_values text[] NOT NULL,
+            numeric_attribute_keys text[] NOT NULL,
+            numeric_attribute_values numeric(21,0)[] NOT NULL,
+            inserted_at timestamp without time zone DEFAULT now() NOT NULL
+        );
+
+        CREATE INDEX golem_base_history_notifications_inserted_at_idx
+            ON golem_base_history_notifications (inserted_at);
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        DROP TABLE golem_base_history_notifications;
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
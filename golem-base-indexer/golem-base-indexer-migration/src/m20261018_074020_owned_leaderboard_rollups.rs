@@ -0,0 +1,96 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        -- Active entities and data each owner gained within the hour, for the owned
+        -- leaderboards ranked over a time window. Follows the owner like the address charts.
+        ALTER TABLE golem_base_address_leaderboard_hourly_stats
+            ADD COLUMN entities_owned_change bigint DEFAULT 0 NOT NULL,
+            ADD COLUMN data_owned_change bigint DEFAULT 0 NOT NULL;
+
+        INSERT INTO golem_base_address_leaderboard_hourly_stats AS stats (
+            hour, address, entities_owned_change, data_owned_change
+        )
+        SELECT deltas.hour, deltas.address, sum(deltas.entities), sum(deltas.data_bytes)
+        FROM golem_base_entity_history history
+        CROSS JOIN LATERAL golem_base_address_timeseries_deltas(history, 1) deltas
+        WHERE deltas.entities <> 0
+        GROUP BY deltas.hour, deltas.address
+        ON CONFLICT (hour, address) DO UPDATE SET
+            entities_owned_change = excluded.entities_owned_change,
+            data_owned_change = excluded.data_owned_change;
+
+        CREATE FUNCTION golem_base_track_hourly_ownership() RETURNS trigger
+            LANGUAGE plpgsql
+        AS $$
+        begin
+            insert into golem_base_address_leaderboard_hourly_stats as stats (
+                hour, address, entities_owned_change, data_owned_change
+            )
+            select hour, address, sum(entities), sum(data_bytes)
+            from (
+                select * from golem_base_address_timeseries_deltas(old, -1) where tg_op <> 'INSERT'
+                union all
+                select * from golem_base_address_timeseries_deltas(new, 1) where tg_op <> 'DELETE'
+            ) as changes
+            where entities <> 0
+            group by hour, address
+            order by hour, address
+            on conflict (hour, address) do update set
+                entities_owned_change = stats.entities_owned_change + excluded.entities_owned_change,
+                data_owned_change = stats.data_owned_change + excluded.data_owned_change;
+            return null;
+        end;
+        $$;
+
+        CREATE TRIGGER golem_base_track_hourly_ownership
+            AFTER INSERT OR DELETE ON golem_base_entity_history
+            FOR EACH ROW EXECUTE FUNCTION golem_base_track_hourly_ownership();
+
+        CREATE TRIGGER golem_base_track_hourly_ownership_update
+            AFTER UPDATE ON golem_base_entity_history
+            FOR EACH ROW
+            WHEN (
+                old.owner IS DISTINCT FROM new.owner
+                OR old.prev_owner IS DISTINCT FROM new.prev_owner
+                OR old.status IS DISTINCT FROM new.status
+                OR old.prev_status IS DISTINCT FROM new.prev_status
+                OR old.block_timestamp IS DISTINCT FROM new.block_timestamp
+                OR length(old.data) IS DISTINCT FROM length(new.data)
+                OR length(old.prev_data) IS DISTINCT FROM length(new.prev_data)
+            )
+            EXECUTE FUNCTION golem_base_track_hourly_ownership();
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        DROP TRIGGER golem_base_track_hourly_ownership_update ON golem_base_entity_history;
+        DROP TRIGGER golem_base_track_hourly_ownership ON golem_base_entity_history;
+        DROP FUNCTION golem_base_track_hourly_ownership;
+
+        ALTER TABLE golem_base_address_leaderboard_hourly_stats
+            DROP COLUMN entities_owned_change,
+            DROP COLUMN data_owned_change;
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
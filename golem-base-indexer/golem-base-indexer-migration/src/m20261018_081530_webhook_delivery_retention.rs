@@ -0,0 +1,35 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        -- Delivered and exhausted deliveries are deleted once they're past retention
+        CREATE INDEX golem_base_webhook_deliveries_inserted_at_idx
+            ON golem_base_webhook_deliveries (inserted_at);
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            r#"
+        DROP INDEX golem_base_webhook_deliveries_inserted_at_idx;
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
@@ -3,18 +3,30 @@
 use const_hex::traits::ToHexExt;
 
 use anyhow::{anyhow, Result};
-use golem_base_indexer_logic::types::{
-    AddressLeaderboardRanks, BlockConsensusInfo, BlockEntitiesCount, BlockGasUsageLimitPoint,
-    BlockOperationPoint, BlockTransactionPoint, ChartInfo, ChartPoint, ConsensusInfo,
-    EntitiesAverages, EntitiesFilter, Entity, EntityDataHistogram, EntityHistoryEntry,
-    EntityHistoryFilter, EntityStatus, EntityWithExpTimestamp, FullEntity,
-    LeaderboardBiggestSpendersItem, LeaderboardDataOwnedItem,
-    LeaderboardEffectivelyLargestEntitiesItem, LeaderboardEntitiesCreatedItem,
-    LeaderboardEntitiesOwnedItem, LeaderboardLargestEntitiesItem, LeaderboardTopAccountsItem,
-    ListEntitiesFilter, ListOperationsFilter, NumericAttribute, NumericAttributeWithRelations,
-    OperationData, OperationFilter, OperationType, OperationView, OperationsCount,
-    OperationsFilter, PaginationMetadata, PaginationParams, StringAttribute,
-    StringAttributeWithRelations, Transaction,
+use chrono::NaiveDateTime;
+use golem_base_indexer_logic::{
+    pagination::Cursor,
+    types::{
+        AddressLeaderboardRanks, AttributeChange, AttributeKeyStats, AttributeKeysFilter,
+        AttributeKind, AttributeValueCount, AttributeValuesFilter, BlockConsensusInfo,
+        BlockEntitiesCount, BlockGasUsageLimitPoint, BlockOperationPoint, BlockTransactionPoint,
+        ByteRangeChange, ChartBreakdown, ChartGroupBy, ChartInfo, ChartPoint, ChartSeries,
+        ConsensusInfo, CursorPaginationMetadata, CursorPaginationParams, DataDiff,
+        EntitiesAverages, EntitiesFilter, EntitiesSort, Entity, EntityDataDiff,
+        EntityDataDiffFilter, EntityDataHistogram, EntityHistoryEntry,
+        EntityHistoryEntryWithAttributes, EntityHistoryFilter, EntityStatus,
+        EntityWithExpTimestamp, ExhaustedWebhookDelivery, FullEntity, IndexerStatus,
+        JsonPatchOperation, LeaderboardBiggestSpendersItem, LeaderboardDataOwnedItem,
+        LeaderboardEffectivelyLargestEntitiesItem, LeaderboardEntitiesCreatedItem,
+        LeaderboardEntitiesOwnedItem, LeaderboardFilter, LeaderboardLargestEntitiesItem,
+        LeaderboardTopAccountsItem, LeaderboardWindow, ListEntitiesFilter, ListOperationsFilter,
+        NewWebhook, NumericAttribute, NumericAttributeStats, NumericAttributeWithRelations,
+        OperationCursor, OperationData, OperationFilter, OperationSortField, OperationType,
+        OperationView, OperationsCount, OperationsFilter, OperationsSort, PaginationMetadata,
+        PaginationParams, QuarantinedItem, QueueDepths, ReindexScope, SearchFilter, SearchResult,
+        StringAttribute, StringAttributeWithRelations, Timestamp, Transaction,
+        UndecodableTransaction, UndecodableTransactionsFilter, Webhook, WebhookFilter,
+    },
 };
 
 pub mod blockscout {
@@ -189,20 +201,63 @@ impl From<v1::OperationType> for OperationType {
     }
 }
 
+fn parse_operation_types(value: &str) -> Result<Vec<OperationType>> {
+    value
+        .split(',')
+        .map(|v| {
+            v1::OperationType::from_str_name(v.trim())
+                .map(Into::into)
+                .ok_or_else(|| {
+                    anyhow!(
+                        "Invalid operation_types: expected CREATE, UPDATE, DELETE, EXTEND or CHANGEOWNER"
+                    )
+                })
+        })
+        .collect()
+}
+
 impl TryFrom<v1::ListOperationsRequest> for ListOperationsFilter {
     type Error = anyhow::Error;
 
     fn try_from(request: v1::ListOperationsRequest) -> Result<Self> {
-        let operation_type =
+        let operation_type: Option<OperationType> =
             v1::operation_type_filter::OperationTypeFilter::try_from(request.operation)
                 .map_err(|_| anyhow!("Invalid operation"))?
                 .into();
+        if operation_type.is_some() && request.operation_types.is_some() {
+            return Err(anyhow!("operation_types requires operation=ALL"));
+        }
+
+        let sort = OperationsSort {
+            field: request
+                .sort_by
+                .map(|v| v.parse().map_err(|err| anyhow!("Invalid sort_by: {err}")))
+                .transpose()?
+                .unwrap_or_default(),
+            direction: request
+                .sort_direction
+                .map(|v| {
+                    v.parse()
+                        .map_err(|err| anyhow!("Invalid sort_direction: {err}"))
+                })
+                .transpose()?
+                .unwrap_or_default(),
+        };
+        let pagination: CursorPaginationParams<OperationCursor> = cursor_pagination_params(
+            request.page,
+            request.page_size,
+            request.cursor,
+            request.with_totals,
+        )?;
+        // cursors carry the cost only when listing by cost
+        if let Some(cursor) = &pagination.cursor {
+            if cursor.cost.is_some() != (sort.field == OperationSortField::Cost) {
+                return Err(anyhow!("Invalid cursor: doesn't match sort_by"));
+            }
+        }
 
         Ok(Self {
-            pagination: PaginationParams {
-                page: request.page.unwrap_or(1).max(1),
-                page_size: request.page_size.unwrap_or(100).clamp(1, 100),
-            },
+            pagination,
             operation_type,
             operations_filter: OperationsFilter {
                 block_number_or_hash: request
@@ -227,7 +282,37 @@ impl TryFrom<v1::ListOperationsRequest> for ListOperationsFilter {
                     .entity_key
                     .map(|key| key.parse().map_err(|_| anyhow!("Invalid entity_key")))
                     .transpose()?,
+                max_block_number: None,
+                recipient: request
+                    .recipient
+                    .map(|addr| addr.parse().map_err(|_| anyhow!("Invalid recipient")))
+                    .transpose()?,
+                content_type: request.content_type,
+                operation_types: request
+                    .operation_types
+                    .map(|v| parse_operation_types(&v))
+                    .transpose()?
+                    .unwrap_or_default(),
+                from_block: request.from_block,
+                to_block: request.to_block,
+                min_cost: request
+                    .min_cost
+                    .map(|v| v.parse().map_err(|_| anyhow!("Invalid min_cost")))
+                    .transpose()?,
+                max_cost: request
+                    .max_cost
+                    .map(|v| v.parse().map_err(|_| anyhow!("Invalid max_cost")))
+                    .transpose()?,
+                from_timestamp: request
+                    .from_timestamp
+                    .map(|v| parse_timestamp("from_timestamp", &v))
+                    .transpose()?,
+                to_timestamp: request
+                    .to_timestamp
+                    .map(|v| parse_timestamp("to_timestamp", &v))
+                    .transpose()?,
             },
+            sort,
         })
     }
 }
@@ -243,6 +328,36 @@ impl From<PaginationMetadata> for v1::Pagination {
     }
 }
 
+impl v1::PaginationNextPage {
+    pub fn from_metadata<C: Cursor>(metadata: &CursorPaginationMetadata<C>) -> Option<Self> {
+        metadata.next_cursor.as_ref().map(|cursor| Self {
+            cursor: cursor.encode(),
+            page_size: metadata.page_size,
+        })
+    }
+}
+
+/// Totals are computed by default only for page-number based requests.
+fn cursor_pagination_params<C: Cursor>(
+    page: Option<u64>,
+    page_size: Option<u64>,
+    cursor: Option<String>,
+    with_totals: Option<bool>,
+) -> Result<CursorPaginationParams<C>> {
+    let cursor = cursor
+        .filter(|v| !v.is_empty())
+        .map(|v| C::decode(&v))
+        .transpose()?;
+    Ok(CursorPaginationParams {
+        pagination: PaginationParams {
+            page: page.unwrap_or(1).max(1),
+            page_size: page_size.unwrap_or(100).clamp(1, 100),
+        },
+        with_totals: with_totals.unwrap_or(cursor.is_none()),
+        cursor,
+    })
+}
+
 impl TryFrom<v1::CountOperationsRequest> for OperationsFilter {
     type Error = anyhow::Error;
 
@@ -270,6 +385,35 @@ impl TryFrom<v1::CountOperationsRequest> for OperationsFilter {
                 .entity_key
                 .map(|key| key.parse().map_err(|_| anyhow!("Invalid entity_key")))
                 .transpose()?,
+            max_block_number: None,
+            recipient: request
+                .recipient
+                .map(|addr| addr.parse().map_err(|_| anyhow!("Invalid recipient")))
+                .transpose()?,
+            content_type: request.content_type,
+            operation_types: request
+                .operation_types
+                .map(|v| parse_operation_types(&v))
+                .transpose()?
+                .unwrap_or_default(),
+            from_block: request.from_block,
+            to_block: request.to_block,
+            min_cost: request
+                .min_cost
+                .map(|v| v.parse().map_err(|_| anyhow!("Invalid min_cost")))
+                .transpose()?,
+            max_cost: request
+                .max_cost
+                .map(|v| v.parse().map_err(|_| anyhow!("Invalid max_cost")))
+                .transpose()?,
+            from_timestamp: request
+                .from_timestamp
+                .map(|v| parse_timestamp("from_timestamp", &v))
+                .transpose()?,
+            to_timestamp: request
+                .to_timestamp
+                .map(|v| parse_timestamp("to_timestamp", &v))
+                .transpose()?,
         })
     }
 }
@@ -314,6 +458,7 @@ impl From<OperationView> for v1::Operation {
             content_type: v.op.operation.content_type(),
             expires_at_timestamp: v.expires_at_timestamp.map(|v| v.to_rfc3339()),
             expires_at_timestamp_sec: v.expires_at_timestamp_sec,
+            finality: None,
         }
     }
 }
@@ -390,14 +535,17 @@ impl TryFrom<v1::GetEntityHistoryRequest> for EntityHistoryFilter {
 
     fn try_from(request: v1::GetEntityHistoryRequest) -> Result<Self> {
         Ok(Self {
-            pagination: PaginationParams {
-                page: request.page.unwrap_or(1).max(1),
-                page_size: request.page_size.unwrap_or(100).clamp(1, 100),
-            },
+            pagination: cursor_pagination_params(
+                request.page,
+                request.page_size,
+                request.cursor,
+                request.with_totals,
+            )?,
             entity_key: request
                 .key
                 .parse()
                 .map_err(|_| anyhow!("Invalid entity_key"))?,
+            max_block_number: None,
         })
     }
 }
@@ -449,6 +597,50 @@ impl From<EntityHistoryEntry> for v1::EntityHistoryEntry {
                 .total_cost
                 .map(|cost_u256| cost_u256.to_string())
                 .unwrap_or("0".into()),
+            string_annotations: vec![],
+            numeric_annotations: vec![],
+            annotations_diff: None,
+            finality: None,
+        }
+    }
+}
+
+impl From<AttributeChange<String>> for v1::StringAnnotationChange {
+    fn from(v: AttributeChange<String>) -> Self {
+        Self {
+            key: v.key,
+            old_value: v.old_value,
+            new_value: v.new_value,
+        }
+    }
+}
+
+impl From<AttributeChange<u64>> for v1::NumericAnnotationChange {
+    fn from(v: AttributeChange<u64>) -> Self {
+        Self {
+            key: v.key,
+            old_value: v.old_value,
+            new_value: v.new_value,
+        }
+    }
+}
+
+impl From<EntityHistoryEntryWithAttributes> for v1::EntityHistoryEntry {
+    fn from(v: EntityHistoryEntryWithAttributes) -> Self {
+        let strings = v.string_attributes_diff;
+        let numerics = v.numeric_attributes_diff;
+        Self {
+            string_annotations: v.string_attributes.into_iter().map(Into::into).collect(),
+            numeric_annotations: v.numeric_attributes.into_iter().map(Into::into).collect(),
+            annotations_diff: Some(v1::AnnotationsDiff {
+                added_string_annotations: strings.added.into_iter().map(Into::into).collect(),
+                removed_string_annotations: strings.removed.into_iter().map(Into::into).collect(),
+                changed_string_annotations: strings.changed.into_iter().map(Into::into).collect(),
+                added_numeric_annotations: numerics.added.into_iter().map(Into::into).collect(),
+                removed_numeric_annotations: numerics.removed.into_iter().map(Into::into).collect(),
+                changed_numeric_annotations: numerics.changed.into_iter().map(Into::into).collect(),
+            }),
+            ..v.entry.into()
         }
     }
 }
@@ -467,6 +659,80 @@ impl TryFrom<v1::GetOperationRequest> for OperationFilter {
     }
 }
 
+fn parse_operation(value: &str) -> Result<OperationFilter> {
+    let (tx_hash, op_index) = value
+        .split_once(':')
+        .ok_or(anyhow!("Invalid operation, expected <tx_hash>:<op_index>"))?;
+    Ok(OperationFilter {
+        tx_hash: tx_hash.parse().map_err(|_| anyhow!("Invalid tx_hash"))?,
+        op_index: op_index.parse().map_err(|_| anyhow!("Invalid op_index"))?,
+    })
+}
+
+impl TryFrom<v1::GetEntityDataDiffRequest> for EntityDataDiffFilter {
+    type Error = anyhow::Error;
+
+    fn try_from(request: v1::GetEntityDataDiffRequest) -> Result<Self> {
+        Ok(Self {
+            key: request
+                .key
+                .parse()
+                .map_err(|_| anyhow!("Invalid entity_key"))?,
+            from: parse_operation(&request.from)?,
+            to: parse_operation(&request.to)?,
+        })
+    }
+}
+
+impl From<JsonPatchOperation> for v1::JsonPatchOperation {
+    fn from(v: JsonPatchOperation) -> Self {
+        Self {
+            op: v.op,
+            path: v.path,
+            from: v.from,
+            value: v.value.map(|v| v.to_string()),
+        }
+    }
+}
+
+impl From<ByteRangeChange> for v1::ByteRangeChange {
+    fn from(v: ByteRangeChange) -> Self {
+        Self {
+            from_offset: v.from_offset,
+            from_length: v.from_length,
+            to_offset: v.to_offset,
+            to_length: v.to_length,
+        }
+    }
+}
+
+impl From<EntityDataDiff> for v1::EntityDataDiff {
+    fn from(v: EntityDataDiff) -> Self {
+        let mut res = Self {
+            from_content_type: v.from_content_type,
+            to_content_type: v.to_content_type,
+            from_size: v.from_size,
+            to_size: v.to_size,
+            ..Default::default()
+        };
+        match v.diff {
+            DataDiff::JsonPatch(patch) => {
+                res.set_format(v1::DataDiffFormat::JsonPatch);
+                res.json_patch = patch.into_iter().map(Into::into).collect();
+            }
+            DataDiff::Unified(diff) => {
+                res.set_format(v1::DataDiffFormat::Unified);
+                res.unified_diff = Some(diff);
+            }
+            DataDiff::ByteRanges(ranges) => {
+                res.set_format(v1::DataDiffFormat::ByteRanges);
+                res.byte_ranges = ranges.into_iter().map(Into::into).collect();
+            }
+        }
+        res
+    }
+}
+
 impl TryFrom<v1::ListEntitiesRequest> for ListEntitiesFilter {
     type Error = anyhow::Error;
 
@@ -492,16 +758,53 @@ impl TryFrom<v1::ListEntitiesRequest> for ListEntitiesFilter {
             _ => return Err(anyhow!("Invalid numeric_attribute filter")),
         };
         Ok(Self {
-            pagination: PaginationParams {
-                page: request.page.unwrap_or(1).max(1),
-                page_size: request.page_size.unwrap_or(100).clamp(1, 100),
-            },
+            pagination: cursor_pagination_params(
+                request.page,
+                request.page_size,
+                request.cursor,
+                request.with_totals,
+            )?,
             entities_filter: EntitiesFilter {
                 status: status.into(),
                 string_attribute,
                 numeric_attribute,
                 owner: request.owner.map(|v| v.parse()).transpose()?,
+                query: request
+                    .query
+                    .map(|v| v.parse().map_err(|err| anyhow!("Invalid query: {err}")))
+                    .transpose()?,
+                content_type: request.content_type,
+                creator: request.creator.map(|v| v.parse()).transpose()?,
+                min_expires_at_block_number: request.min_expires_at_block_number,
+                max_expires_at_block_number: request.max_expires_at_block_number,
+                min_data_size: request.min_data_size,
+                max_data_size: request.max_data_size,
+                min_created_at_block_number: request.min_created_at_block_number,
+                max_created_at_block_number: request.max_created_at_block_number,
+                created_from: request
+                    .created_from
+                    .map(|v| parse_timestamp("created_from", &v))
+                    .transpose()?,
+                created_to: request
+                    .created_to
+                    .map(|v| parse_timestamp("created_to", &v))
+                    .transpose()?,
+            },
+            sort: EntitiesSort {
+                field: request
+                    .sort_by
+                    .map(|v| v.parse().map_err(|err| anyhow!("Invalid sort_by: {err}")))
+                    .transpose()?,
+                direction: request
+                    .sort_direction
+                    .map(|v| {
+                        v.parse()
+                            .map_err(|err| anyhow!("Invalid sort_direction: {err}"))
+                    })
+                    .transpose()?
+                    .unwrap_or_default(),
             },
+            at_block: request.at_block,
         })
     }
 }
@@ -535,6 +838,26 @@ impl TryFrom<v1::CountEntitiesRequest> for EntitiesFilter {
             string_attribute,
             numeric_attribute,
             owner: request.owner.map(|v| v.parse()).transpose()?,
+            query: request
+                .query
+                .map(|v| v.parse().map_err(|err| anyhow!("Invalid query: {err}")))
+                .transpose()?,
+            content_type: request.content_type,
+            creator: request.creator.map(|v| v.parse()).transpose()?,
+            min_expires_at_block_number: request.min_expires_at_block_number,
+            max_expires_at_block_number: request.max_expires_at_block_number,
+            min_data_size: request.min_data_size,
+            max_data_size: request.max_data_size,
+            min_created_at_block_number: request.min_created_at_block_number,
+            max_created_at_block_number: request.max_created_at_block_number,
+            created_from: request
+                .created_from
+                .map(|v| parse_timestamp("created_from", &v))
+                .transpose()?,
+            created_to: request
+                .created_to
+                .map(|v| parse_timestamp("created_to", &v))
+                .transpose()?,
         })
     }
 }
@@ -563,6 +886,153 @@ impl From<BlockEntitiesCount> for v1::BlockStatsCounts {
     }
 }
 
+fn parse_timestamp(name: &str, value: &str) -> Result<Timestamp> {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M")
+        .map(|v| v.and_utc())
+        .map_err(|e| anyhow!("Invalid {name} timestamp: {e}"))
+}
+
+impl TryFrom<v1::LeaderboardRequest> for LeaderboardFilter {
+    type Error = anyhow::Error;
+
+    fn try_from(request: v1::LeaderboardRequest) -> Result<Self> {
+        let window = match request.window.as_deref() {
+            None if request.from.is_none() && request.to.is_none() => None,
+            None => return Err(anyhow!("from and to require a custom window")),
+            Some("custom") => {
+                let from = request
+                    .from
+                    .ok_or(anyhow!("Custom window requires from"))
+                    .and_then(|v| parse_timestamp("from", &v))?;
+                let to = request.to.map(|v| parse_timestamp("to", &v)).transpose()?;
+                if to.is_some_and(|to| to <= from) {
+                    return Err(anyhow!("Custom window must end after it starts"));
+                }
+                Some(LeaderboardWindow::Custom { from, to })
+            }
+            Some(_) if request.from.is_some() || request.to.is_some() => {
+                return Err(anyhow!("from and to require a custom window"))
+            }
+            Some("24h") => Some(LeaderboardWindow::Day),
+            Some("7d") => Some(LeaderboardWindow::Week),
+            Some("30d") => Some(LeaderboardWindow::Month),
+            Some(other) => return Err(anyhow!("Unknown window: {other}")),
+        };
+        Ok(Self {
+            pagination: PaginationParams {
+                page: request.page.unwrap_or(1).max(1),
+                page_size: request.page_size.unwrap_or(100).clamp(1, 100),
+            },
+            window,
+        })
+    }
+}
+
+impl TryFrom<v1::SearchEntitiesRequest> for SearchFilter {
+    type Error = anyhow::Error;
+
+    fn try_from(request: v1::SearchEntitiesRequest) -> Result<Self> {
+        let query = request.q.trim();
+        if query.is_empty() {
+            return Err(anyhow!("Search query can't be empty"));
+        }
+        Ok(Self {
+            query: query.to_string(),
+            pagination: PaginationParams {
+                page: request.page.unwrap_or(1).max(1),
+                page_size: request.page_size.unwrap_or(100).clamp(1, 100),
+            },
+        })
+    }
+}
+
+impl From<SearchResult> for v1::SearchResult {
+    fn from(v: SearchResult) -> Self {
+        Self {
+            entity_key: v.entity_key.to_string(),
+            owner: v.owner.map(|v| v.to_checksum(None)),
+            content_type: v.content_type,
+            rank: v.rank,
+            snippet: v.snippet,
+        }
+    }
+}
+
+impl TryFrom<v1::ListAnnotationKeysRequest> for AttributeKeysFilter {
+    type Error = anyhow::Error;
+
+    fn try_from(request: v1::ListAnnotationKeysRequest) -> Result<Self> {
+        Ok(Self {
+            kind: request.kind.map(|v| v.parse()).transpose()?,
+            prefix: request.prefix,
+            pagination: PaginationParams {
+                page: request.page.unwrap_or(1).max(1),
+                page_size: request.page_size.unwrap_or(100).clamp(1, 100),
+            },
+        })
+    }
+}
+
+impl From<AttributeKeyStats> for v1::AnnotationKey {
+    fn from(v: AttributeKeyStats) -> Self {
+        Self {
+            kind: v.kind.as_str().to_string(),
+            key: v.key,
+            active_entities: v.active_entities,
+            first_seen_block: v.first_seen_block,
+            last_seen_block: v.last_seen_block,
+        }
+    }
+}
+
+impl TryFrom<v1::ListAnnotationValuesRequest> for AttributeValuesFilter {
+    type Error = anyhow::Error;
+
+    fn try_from(request: v1::ListAnnotationValuesRequest) -> Result<Self> {
+        if request.key.is_empty() {
+            return Err(anyhow!("Annotation key can't be empty"));
+        }
+        Ok(Self {
+            kind: request
+                .kind
+                .map(|v| v.parse())
+                .transpose()?
+                .unwrap_or(AttributeKind::String),
+            key: request.key,
+            prefix: request.prefix,
+            pagination: PaginationParams {
+                page: request.page.unwrap_or(1).max(1),
+                page_size: request.page_size.unwrap_or(100).clamp(1, 100),
+            },
+        })
+    }
+}
+
+impl From<AttributeValueCount> for v1::AnnotationValue {
+    fn from(v: AttributeValueCount) -> Self {
+        Self {
+            value: v.value,
+            active_entities: v.active_entities,
+        }
+    }
+}
+
+impl From<NumericAttributeStats> for v1::NumericAnnotationStatsResponse {
+    fn from(v: NumericAttributeStats) -> Self {
+        Self {
+            key: v.key,
+            active_entities: v.active_entities,
+            min: v.min,
+            max: v.max,
+            p25: v.p25,
+            p50: v.p50,
+            p75: v.p75,
+            p90: v.p90,
+            p99: v.p99,
+        }
+    }
+}
+
 impl TryFrom<v1::ListCustomContractTransactionsRequest> for PaginationParams {
     type Error = anyhow::Error;
 
@@ -574,6 +1044,34 @@ impl TryFrom<v1::ListCustomContractTransactionsRequest> for PaginationParams {
     }
 }
 
+impl TryFrom<v1::ListUndecodableTransactionsRequest> for UndecodableTransactionsFilter {
+    type Error = anyhow::Error;
+
+    fn try_from(request: v1::ListUndecodableTransactionsRequest) -> Result<Self> {
+        Ok(Self {
+            pagination: PaginationParams {
+                page: request.page.unwrap_or(1).max(1),
+                page_size: request.page_size.unwrap_or(100).clamp(1, 100),
+            },
+            sender: request.sender.map(|v| v.parse()).transpose()?,
+        })
+    }
+}
+
+impl From<UndecodableTransaction> for v1::UndecodableTransaction {
+    fn from(v: UndecodableTransaction) -> Self {
+        Self {
+            hash: v.hash.to_string(),
+            sender: v.sender.to_checksum(None),
+            block_hash: v.block_hash.to_string(),
+            block_number: v.block_number,
+            index: v.index,
+            error_kind: v.error.kind.as_str().to_string(),
+            error: v.error.message,
+        }
+    }
+}
+
 impl From<Transaction> for v1::Transaction {
     fn from(v: Transaction) -> Self {
         Self {
@@ -645,6 +1143,41 @@ impl From<ChartPoint> for v1::ChartPoint {
     }
 }
 
+impl From<ChartSeries> for v1::ChartSeries {
+    fn from(v: ChartSeries) -> Self {
+        Self {
+            name: v.name,
+            chart: v.chart.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Breakdown requested by the `group_by`, `annotation_key` and `series_limit`
+/// fields shared by the chart requests that support it.
+pub fn chart_breakdown(
+    group_by: Option<String>,
+    annotation_key: Option<String>,
+    series_limit: Option<u64>,
+) -> Result<Option<ChartBreakdown>> {
+    let group_by = match (group_by.as_deref(), annotation_key) {
+        (None, None) if series_limit.is_none() => return Ok(None),
+        (None, _) => return Err(anyhow!("annotation_key and series_limit require group_by")),
+        (Some("content_type"), None) => ChartGroupBy::ContentType,
+        (Some("annotation"), Some(key)) => ChartGroupBy::StringAnnotation(key),
+        (Some("annotation"), None) => {
+            return Err(anyhow!("group_by=annotation requires annotation_key"))
+        }
+        (Some("content_type"), Some(_)) => {
+            return Err(anyhow!("annotation_key requires group_by=annotation"))
+        }
+        (Some(other), _) => return Err(anyhow!("Unknown group_by: {other}")),
+    };
+    Ok(Some(ChartBreakdown {
+        group_by,
+        limit: series_limit.unwrap_or(5).clamp(1, 20),
+    }))
+}
+
 impl From<BlockTransactionPoint> for v1::BlockTransactionPoint {
     fn from(v: BlockTransactionPoint) -> Self {
         Self {
@@ -771,6 +1304,36 @@ impl From<ConsensusInfo> for v1::ConsensusInfoResponse {
     }
 }
 
+/// Readiness is left to the caller, as it depends on configured thresholds.
+impl From<IndexerStatus> for v1::IndexerStatusResponse {
+    fn from(v: IndexerStatus) -> Self {
+        let age = |at| v.age(at).as_secs();
+        Self {
+            ready: false,
+            problems: vec![],
+            latest_block_number: v.latest_block,
+            latest_processed_block_number: v.latest_processed_block,
+            block_lag: v.block_lag(),
+            pending_transactions: v.pending_transactions,
+            pending_transaction_cleanups: v.pending_transaction_cleanups,
+            oldest_queued_item_timestamp: v.oldest_queued_item_at.map(|at| at.to_rfc3339()),
+            oldest_queued_item_age_seconds: v.oldest_queued_item_at.map(age),
+            last_tick_timestamp: v.last_tick_at.map(|at| at.to_rfc3339()),
+            last_tick_age_seconds: v.last_tick_at.map(age),
+            paused: v.paused,
+            materialized_views: v
+                .materialized_views
+                .iter()
+                .map(|view| v1::MaterializedViewStatus {
+                    name: view.name.clone(),
+                    refreshed_at_timestamp: view.refreshed_at.map(|at| at.to_rfc3339()),
+                    staleness_seconds: view.refreshed_at.map(age),
+                })
+                .collect(),
+        }
+    }
+}
+
 impl From<EntitiesAverages> for v1::EntitiesAveragesResponse {
     fn from(v: EntitiesAverages) -> Self {
         Self {
@@ -788,3 +1351,109 @@ impl From<BlockConsensusInfo> for v1::BlockStatsConsensus {
         }
     }
 }
+
+impl TryFrom<v1::CreateWebhookRequest> for NewWebhook {
+    type Error = anyhow::Error;
+
+    fn try_from(request: v1::CreateWebhookRequest) -> Result<Self> {
+        let url: url::Url = request.url.parse()?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(anyhow!("Webhook url must use http or https"));
+        }
+        if request.secret.is_empty() {
+            return Err(anyhow!("Webhook secret must not be empty"));
+        }
+        let annotation = match (request.annotation_key, request.annotation_value) {
+            (Some(key), Some(value)) => Some(StringAttribute { key, value }),
+            (None, None) => None,
+            _ => return Err(anyhow!("Invalid annotation filter")),
+        };
+        Ok(Self {
+            url: url.into(),
+            secret: request.secret,
+            filter: WebhookFilter {
+                owner: request.owner.map(|v| v.parse()).transpose()?,
+                entity_key: request.entity_key.map(|v| v.parse()).transpose()?,
+                annotation,
+            },
+        })
+    }
+}
+
+impl From<Webhook> for v1::Webhook {
+    fn from(v: Webhook) -> Self {
+        let (annotation_key, annotation_value) =
+            v.filter.annotation.map(|v| (v.key, v.value)).unzip();
+        Self {
+            id: v.id,
+            url: v.url,
+            owner: v.filter.owner.map(|v| v.to_checksum(None)),
+            entity_key: v.filter.entity_key.map(|v| v.to_string()),
+            annotation_key,
+            annotation_value,
+            created_at: v.created_at.to_rfc3339(),
+        }
+    }
+}
+
+impl From<ExhaustedWebhookDelivery> for v1::WebhookDelivery {
+    fn from(v: ExhaustedWebhookDelivery) -> Self {
+        Self {
+            id: v.id,
+            webhook_id: v.webhook_id,
+            event: v.event,
+            payload: v.payload.to_string(),
+            attempts: v.attempts.into(),
+            last_error: v.last_error,
+            created_at: v.created_at.to_rfc3339(),
+        }
+    }
+}
+
+impl TryFrom<v1::QueueReindexRequest> for ReindexScope {
+    type Error = anyhow::Error;
+
+    fn try_from(request: v1::QueueReindexRequest) -> Result<Self> {
+        match (
+            request.entity_key,
+            request.owner,
+            request.from_block,
+            request.to_block,
+        ) {
+            (Some(key), None, None, None) => Ok(Self::Entity(key.parse()?)),
+            (None, Some(owner), None, None) => Ok(Self::Owner(owner.parse()?)),
+            (None, None, Some(from), Some(to)) if from <= to => Ok(Self::Blocks { from, to }),
+            _ => Err(anyhow!(
+                "Expected exactly one of entity_key, owner or from_block..to_block"
+            )),
+        }
+    }
+}
+
+impl From<QueueDepths> for v1::QueueDepthsResponse {
+    fn from(v: QueueDepths) -> Self {
+        Self {
+            pending_transactions: v.pending_transactions,
+            pending_transaction_cleanups: v.pending_transaction_cleanups,
+            pending_logs: v.pending_logs,
+            pending_logs_events: v.pending_logs_events,
+            entities_to_reindex: v.entities_to_reindex,
+            quarantined: v.quarantined,
+        }
+    }
+}
+
+impl From<QuarantinedItem> for v1::QuarantinedItem {
+    fn from(v: QuarantinedItem) -> Self {
+        Self {
+            queue: v.queue.as_str().to_string(),
+            key: v.key.to_string(),
+            block_hash: v.block_hash.map(|v| v.to_string()),
+            block_number: v.block_number,
+            index: v.index,
+            attempts: v.attempts,
+            last_error: v.last_error,
+            quarantined_at: v.quarantined_at.to_rfc3339(),
+        }
+    }
+}
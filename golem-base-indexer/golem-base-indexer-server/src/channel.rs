@@ -0,0 +1,120 @@
+use std::{fmt, str::FromStr};
+
+use crate::proto::EntityHistoryEntry;
+use actix_phoenix_channel::{
+    ChannelBroadcaster, ChannelCentral, ChannelConn, ChannelEvent, ChannelHandler,
+};
+use anyhow::{anyhow, Result};
+use golem_base_indexer_logic::{
+    subscriptions::HistorySubscriber,
+    types::{Address, EntityKey, HistoryEntryEvent},
+};
+use serde_json::json;
+
+pub type EntityChannelCentral = ChannelCentral<EntityChannel>;
+
+const HISTORY_ENTRY_EVENT: &str = "history_entry";
+
+/// Topics clients can join to get pushed new entity history entries.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Topic {
+    Entity(EntityKey),
+    Owner(Address),
+    Annotation(String, String),
+}
+
+impl FromStr for Topic {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (kind, value) = s
+            .split_once(':')
+            .ok_or_else(|| anyhow!("topic must be of the form <kind>:<value>"))?;
+        match kind {
+            "entity" => Ok(Self::Entity(value.parse()?)),
+            "owner" => Ok(Self::Owner(value.parse()?)),
+            "annotation" => {
+                let (key, value) = value
+                    .split_once('=')
+                    .ok_or_else(|| anyhow!("annotation topic must be of the form <key>=<value>"))?;
+                Ok(Self::Annotation(key.to_string(), value.to_string()))
+            }
+            _ => Err(anyhow!("unknown topic kind: {kind}")),
+        }
+    }
+}
+
+impl fmt::Display for Topic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Entity(key) => write!(f, "entity:{key:#x}"),
+            Self::Owner(address) => write!(f, "owner:{address:#x}"),
+            Self::Annotation(key, value) => write!(f, "annotation:{key}={value}"),
+        }
+    }
+}
+
+pub struct EntityChannel;
+
+#[async_trait::async_trait]
+impl ChannelHandler for EntityChannel {
+    async fn join_channel(&self, conn: &ChannelConn, event: ChannelEvent) {
+        // subscriptions are matched by exact topic, so only the canonical
+        // (lowercase hex) form is accepted
+        let reason = match event.topic().parse::<Topic>() {
+            Ok(topic) if topic.to_string() == event.topic() => {
+                conn.client().allow_join(&event, &()).await;
+                return;
+            }
+            Ok(topic) => format!("non-canonical topic, use {topic}"),
+            Err(err) => format!("invalid topic: {err}"),
+        };
+        conn.client()
+            .reply_error(&event, &json!({ "reason": reason }))
+            .await;
+    }
+}
+
+/// Broadcasts committed history entries to the entity, owner and
+/// annotation topics they belong to.
+pub struct ChannelHistorySubscriber {
+    broadcaster: ChannelBroadcaster,
+}
+
+impl ChannelHistorySubscriber {
+    pub fn new(broadcaster: ChannelBroadcaster) -> Self {
+        Self { broadcaster }
+    }
+}
+
+impl HistorySubscriber for ChannelHistorySubscriber {
+    fn notify(&self, event: HistoryEntryEvent) {
+        let mut topics = vec![Topic::Entity(event.entry.entity_key)];
+        topics.extend(
+            [event.entry.owner, event.entry.prev_owner]
+                .into_iter()
+                .flatten()
+                .map(Topic::Owner),
+        );
+        topics.extend(
+            event
+                .string_attributes
+                .into_iter()
+                .map(|attribute| Topic::Annotation(attribute.key, attribute.value)),
+        );
+        topics.extend(
+            event
+                .numeric_attributes
+                .into_iter()
+                .map(|attribute| Topic::Annotation(attribute.key, attribute.value.to_string())),
+        );
+        topics.sort();
+        topics.dedup();
+
+        let payload = EntityHistoryEntry::from(event.entry);
+        for topic in topics {
+            self.broadcaster
+                .broadcast((topic.to_string(), HISTORY_ENTRY_EVENT, payload.clone()));
+        }
+    }
+}
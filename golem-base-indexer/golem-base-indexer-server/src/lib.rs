@@ -1,11 +1,15 @@
+mod channel;
 mod indexer;
 mod mat_view_scheduler;
 mod proto;
 mod server;
 mod services;
 mod settings;
+mod webhook_dispatcher;
 
+pub use channel::{ChannelHistorySubscriber, EntityChannel, EntityChannelCentral};
 pub use indexer::run as run_indexer;
 pub use mat_view_scheduler::run as run_mat_view_scheduler;
 pub use server::run as run_server;
 pub use settings::Settings;
+pub use webhook_dispatcher::run as run_webhook_dispatcher;
@@ -1,22 +1,45 @@
 use blockscout_service_launcher::{database, launcher::ConfigSettings};
-use golem_base_indexer_server::{run_indexer, run_mat_view_scheduler, run_server, Settings};
-use migration::Migrator;
+use golem_base_indexer_logic::ingestion::IngestionSettings;
+use golem_base_indexer_server::{
+    run_indexer, run_mat_view_scheduler, run_server, run_webhook_dispatcher, EntityChannel,
+    EntityChannelCentral, Settings,
+};
+use migration::{Migrator, StandaloneMigrator};
+use sea_orm::DatabaseConnection;
+use std::sync::Arc;
+
+async fn initialize_postgres(settings: &Settings) -> anyhow::Result<DatabaseConnection> {
+    match settings.indexer.ingestion {
+        IngestionSettings::Blockscout => {
+            database::initialize_postgres::<Migrator>(&settings.database).await
+        }
+        IngestionSettings::Rpc(_) => {
+            database::initialize_postgres::<StandaloneMigrator>(&settings.database).await
+        }
+    }
+}
 
 #[tokio::main]
 async fn main() -> Result<(), anyhow::Error> {
     let settings = Settings::build().expect("failed to read config");
     tracing_subscriber::fmt::init();
+    let channel = Arc::new(EntityChannelCentral::new(EntityChannel));
 
     if !settings.indexer.api_only {
-        let db_connection = database::initialize_postgres::<Migrator>(&settings.database).await?;
+        let db_connection = initialize_postgres(&settings).await?;
         run_indexer(db_connection.into(), settings.clone()).await?;
 
-        let db_connection = database::initialize_postgres::<Migrator>(&settings.database).await?;
+        let db_connection = initialize_postgres(&settings).await?;
         run_mat_view_scheduler(db_connection.into()).await?;
+
+        if settings.webhooks.enabled {
+            let db_connection = initialize_postgres(&settings).await?;
+            run_webhook_dispatcher(db_connection.into(), settings.clone()).await?;
+        }
     }
 
-    let db_connection = database::initialize_postgres::<Migrator>(&settings.database).await?;
-    run_server(db_connection.into(), settings.clone()).await?;
+    let db_connection = initialize_postgres(&settings).await?;
+    run_server(db_connection.into(), settings.clone(), channel).await?;
 
     Ok(())
 }
@@ -1,17 +1,24 @@
 use crate::{
+    channel::{ChannelHistorySubscriber, EntityChannelCentral},
     proto::{
         golem_base_indexer_service_actix::route_golem_base_indexer_service,
+        golem_base_indexer_service_server::GolemBaseIndexerServiceServer,
         health_actix::route_health, health_server::HealthServer,
     },
     services::{ExternalServices, GolemBaseIndexerService, HealthService},
     settings::Settings,
 };
+use actix_phoenix_channel::configure_channel_websocket_route;
 use anyhow::{Context, Result};
 use blockscout_endpoint_swagger::route_swagger;
 use blockscout_service_launcher::{launcher, launcher::LaunchSettings};
-use golem_base_indexer_logic::services::{BlockscoutService, RpcService};
+use golem_base_indexer_logic::{
+    services::{BlockscoutService, RpcService},
+    subscriptions::HistoryListener,
+};
 use reqwest::Url;
 use sea_orm::DatabaseConnection;
+use tokio::time::sleep;
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
@@ -23,12 +30,17 @@ const SERVICE_NAME: &str = "golem_base_indexer_server";
 struct Router {
     golem_base_indexer: Arc<GolemBaseIndexerService>,
     health: Arc<HealthService>,
+    channel: Arc<EntityChannelCentral>,
     swagger_path: PathBuf,
 }
 
 impl Router {
     pub fn grpc_router(&self) -> tonic::transport::server::Router {
-        tonic::transport::Server::builder().add_service(HealthServer::from_arc(self.health.clone()))
+        tonic::transport::Server::builder()
+            .add_service(HealthServer::from_arc(self.health.clone()))
+            .add_service(GolemBaseIndexerServiceServer::from_arc(
+                self.golem_base_indexer.clone(),
+            ))
     }
 }
 
@@ -42,6 +54,8 @@ impl launcher::HttpRouter for Router {
         service_config.configure(|config| {
             route_golem_base_indexer_service(config, self.golem_base_indexer.clone())
         });
+        service_config
+            .configure(|config| configure_channel_websocket_route(config, self.channel.clone()));
         service_config.configure(|config| {
             route_swagger(
                 config,
@@ -80,17 +94,48 @@ pub fn setup_external_services(settings: &Settings) -> Result<ExternalServices>
 pub async fn run(
     db_connection: Arc<DatabaseConnection>,
     settings: Settings,
+    channel: Arc<EntityChannelCentral>,
 ) -> Result<(), anyhow::Error> {
-    let health = Arc::new(HealthService::default());
+    settings
+        .admin
+        .validate()
+        .context("invalid admin settings")?;
+
+    let health = Arc::new(HealthService::new(
+        db_connection.clone(),
+        settings.readiness.clone(),
+    ));
 
     let services = setup_external_services(&settings)?;
 
+    // every replica serving the websocket gets the entries indexed by any of them
+    let listener = HistoryListener::new(
+        db_connection.clone(),
+        Arc::new(ChannelHistorySubscriber::new(channel.channel_broadcaster())),
+    );
+    let delay = settings.indexer.restart_delay;
+    tokio::spawn(async move {
+        loop {
+            if let Err(err) = listener.run().await {
+                tracing::error!(error = ?err, ?delay, "history listener failed, retrying");
+            }
+            sleep(delay).await;
+        }
+    });
+
     // TODO: init services here
-    let golem_base_indexer = Arc::new(GolemBaseIndexerService::new(db_connection, services));
+    let golem_base_indexer = Arc::new(GolemBaseIndexerService::new(
+        db_connection,
+        services,
+        settings.admin,
+        settings.readiness,
+        settings.webhooks,
+    ));
 
     let router = Router {
         golem_base_indexer,
         health,
+        channel,
         swagger_path: settings.swagger_path,
     };
 
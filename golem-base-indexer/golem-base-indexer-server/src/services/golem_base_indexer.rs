@@ -1,14 +1,25 @@
-use crate::proto::{
-    golem_base_indexer_service_server::GolemBaseIndexerService as GolemBaseIndexer, *,
+use crate::{
+    proto::{golem_base_indexer_service_server::GolemBaseIndexerService as GolemBaseIndexer, *},
+    settings::AdminSettings,
 };
 use golem_base_indexer_logic::{
-    repository,
+    mat_view_scheduler::MatViewScheduler,
+    repository::{self, timeseries::breakdown::BreakdownMetric},
     services::{BlockscoutService, RpcService},
-    types::{ConsensusInfo, ListOperationsFilter, OperationType, OperationsFilter},
+    status::{self, ReadinessSettings},
+    types::{
+        Confirmation, ConsensusBlocksInfo, ConsensusInfo, DataTooLargeToDiff, EntityHistoryFilter,
+        ListEntitiesFilter, ListOperationsFilter, NewWebhook, OperationType, OperationsFilter,
+        ReindexScope,
+    },
+    webhooks::{self, WebhookSettings},
 };
 use sea_orm::DatabaseConnection;
-use std::sync::Arc;
-use tonic::{Request, Response, Status};
+use std::{collections::HashSet, sync::Arc};
+use tonic::{metadata::MetadataMap, Request, Response, Status};
+
+/// Upper bound on the number of keys in a single batch entity lookup.
+const MAX_BATCH_ENTITIES: usize = 100;
 
 pub struct ExternalServices {
     pub l2_blockscout: Arc<BlockscoutService>,
@@ -18,12 +29,103 @@ pub struct ExternalServices {
 pub struct GolemBaseIndexerService {
     db: Arc<DatabaseConnection>,
     services: ExternalServices,
+    admin: AdminSettings,
+    readiness: ReadinessSettings,
+    webhooks: WebhookSettings,
 }
 
 impl GolemBaseIndexerService {
-    pub fn new(db: Arc<DatabaseConnection>, services: ExternalServices) -> Self {
-        Self { db, services }
+    pub fn new(
+        db: Arc<DatabaseConnection>,
+        services: ExternalServices,
+        admin: AdminSettings,
+        readiness: ReadinessSettings,
+        webhooks: WebhookSettings,
+    ) -> Self {
+        Self {
+            db,
+            services,
+            admin,
+            readiness,
+            webhooks,
+        }
+    }
+
+    /// Admin endpoints require an `Authorization: Bearer <token>` header matching the configured token.
+    fn admin_auth_error(&self, metadata: &MetadataMap) -> Option<Status> {
+        let Some(expected) = &self.admin.token else {
+            return Some(Status::permission_denied("Admin API is disabled"));
+        };
+        let provided = metadata
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "));
+        match provided {
+            Some(token) if tokens_match(token.as_bytes(), expected.as_bytes()) => None,
+            _ => Some(Status::unauthenticated("Invalid admin token")),
+        }
     }
+
+    async fn set_indexer_paused(&self, paused: bool) -> Result<IndexerState, Status> {
+        repository::control::set_paused(&*self.db, paused)
+            .await
+            .map_err(|err| {
+                tracing::error!(?err, "failed to set indexer state");
+                Status::internal("failed to set indexer state")
+            })?;
+        Ok(IndexerState { paused })
+    }
+
+    async fn consensus_blocks_info(&self) -> Result<ConsensusBlocksInfo, Status> {
+        self.services
+            .l3_rpc
+            .get_consensus_blocks_info_cached()
+            .await
+            .map_err(|err| {
+                tracing::error!(?err, "failed to query consensus blocks info");
+                Status::unavailable("chain heads are unavailable")
+            })
+    }
+
+    /// Chain heads for marking finality. Without a `confirmation` an unavailable RPC
+    /// isn't an error, finality is then left unset.
+    async fn finality_blocks_info(
+        &self,
+        confirmation: Option<Confirmation>,
+    ) -> Result<Option<ConsensusBlocksInfo>, Status> {
+        match confirmation {
+            Some(_) => Ok(Some(self.consensus_blocks_info().await?)),
+            None => match self
+                .services
+                .l3_rpc
+                .get_consensus_blocks_info_cached()
+                .await
+            {
+                Ok(info) => Ok(Some(info)),
+                Err(err) => {
+                    tracing::warn!(?err, "failed to query consensus blocks info for finality");
+                    Ok(None)
+                }
+            },
+        }
+    }
+
+    /// Number of the chain head requested by the `confirmation` parameter.
+    async fn confirmed_head(&self, confirmation: Option<String>) -> Result<Option<u64>, Status> {
+        let confirmation = confirmation
+            .map(|v| v.parse::<Confirmation>())
+            .transpose()
+            .map_err(|err| Status::invalid_argument(format!("Invalid confirmation: {err}")))?;
+        match confirmation {
+            Some(confirmation) => Ok(Some(self.consensus_blocks_info().await?.head(confirmation))),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Compares without short-circuiting, so response times don't leak how much of the token matched.
+fn tokens_match(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
 }
 
 #[async_trait::async_trait]
@@ -38,8 +140,10 @@ impl GolemBaseIndexer for GolemBaseIndexerService {
             .key
             .parse()
             .map_err(|_| Status::invalid_argument("Invalid entity key"))?;
+        let head = self.confirmed_head(inner.confirmation).await?;
+        let at_block = inner.at_block.into_iter().chain(head).min();
 
-        let entity = repository::entities::get_full_entity(&*self.db, key)
+        let entity = repository::entities::get_full_entity(&*self.db, key, at_block)
             .await
             .map_err(|err| {
                 tracing::error!(?err, "failed to query entity");
@@ -47,32 +151,89 @@ impl GolemBaseIndexer for GolemBaseIndexerService {
             })?
             .ok_or(Status::not_found("entity not found"))?;
 
-        let string_attributes =
-            repository::attributes::find_active_string_attributes(&*self.db, key)
-                .await
-                .map_err(|err| {
-                    tracing::error!(?err, "failed to query attributes");
-                    Status::internal("failed to query attributes")
-                })?;
-
-        let numeric_attributes =
-            repository::attributes::find_active_numeric_attributes(&*self.db, key)
-                .await
-                .map_err(|err| {
-                    tracing::error!(?err, "failed to query attributes");
-                    Status::internal("failed to query attributes")
-                })?;
+        let (string_attributes, numeric_attributes) = match at_block {
+            Some(block_number) => tokio::try_join!(
+                repository::attributes::find_string_attributes_at_block(
+                    &*self.db,
+                    key,
+                    block_number
+                ),
+                repository::attributes::find_numeric_attributes_at_block(
+                    &*self.db,
+                    key,
+                    block_number
+                ),
+            ),
+            None => tokio::try_join!(
+                repository::attributes::find_active_string_attributes(&*self.db, key),
+                repository::attributes::find_active_numeric_attributes(&*self.db, key),
+            ),
+        }
+        .map_err(|err| {
+            tracing::error!(?err, "failed to query attributes");
+            Status::internal("failed to query attributes")
+        })?;
 
         let entity = FullEntity::new(entity, string_attributes, numeric_attributes);
 
         Ok(Response::new(entity))
     }
 
+    async fn get_entities(
+        &self,
+        request: Request<GetEntitiesRequest>,
+    ) -> Result<Response<GetEntitiesResponse>, Status> {
+        let inner = request.into_inner();
+
+        if inner.keys.len() > MAX_BATCH_ENTITIES {
+            return Err(Status::invalid_argument(format!(
+                "At most {MAX_BATCH_ENTITIES} keys can be requested at once"
+            )));
+        }
+        let mut keys = Vec::with_capacity(inner.keys.len());
+        let mut seen = HashSet::new();
+        for key in &inner.keys {
+            let key = key
+                .parse()
+                .map_err(|_| Status::invalid_argument(format!("Invalid entity key: {key}")))?;
+            if seen.insert(key) {
+                keys.push(key);
+            }
+        }
+
+        let (entities, mut string_attributes, mut numeric_attributes) = tokio::try_join!(
+            repository::entities::get_full_entities(&*self.db, &keys),
+            repository::attributes::find_entities_active_string_attributes(&*self.db, &keys),
+            repository::attributes::find_entities_active_numeric_attributes(&*self.db, &keys),
+        )
+        .map_err(|err| {
+            tracing::error!(?err, "failed to query entities");
+            Status::internal("failed to query entities")
+        })?;
+
+        let items = entities
+            .into_iter()
+            .map(|entity| {
+                let string_attributes = string_attributes.remove(&entity.key).unwrap_or_default();
+                let numeric_attributes = numeric_attributes.remove(&entity.key).unwrap_or_default();
+                FullEntity::new(entity, string_attributes, numeric_attributes)
+            })
+            .collect();
+
+        Ok(Response::new(GetEntitiesResponse { items }))
+    }
+
     async fn get_operation(
         &self,
         request: Request<GetOperationRequest>,
     ) -> Result<Response<v1::EntityHistoryEntry>, Status> {
-        let inner = request.into_inner();
+        let mut inner = request.into_inner();
+        let confirmation = inner
+            .confirmation
+            .take()
+            .map(|v| v.parse::<Confirmation>())
+            .transpose()
+            .map_err(|err| Status::invalid_argument(format!("Invalid confirmation: {err}")))?;
 
         let filter = inner.try_into().map_err(|err| {
             Status::invalid_argument(format!("Invalid entity operation filter: {err}"))
@@ -86,18 +247,39 @@ impl GolemBaseIndexer for GolemBaseIndexerService {
             })?
             .ok_or(Status::not_found("operation not found"))?;
 
-        Ok(Response::new(operation.into()))
+        let blocks_info = self.finality_blocks_info(confirmation).await?;
+        if let (Some(confirmation), Some(blocks_info)) = (confirmation, &blocks_info) {
+            if operation.block_number > blocks_info.head(confirmation) {
+                return Err(Status::not_found("operation not found"));
+            }
+        }
+
+        let operation = repository::entities::with_attributes(&*self.db, vec![operation])
+            .await
+            .map_err(|err| {
+                tracing::error!(?err, "failed to query operation attributes");
+                Status::internal("failed to query operation attributes")
+            })?
+            .pop()
+            .ok_or(Status::internal("failed to query operation attributes"))?;
+        let finality = blocks_info.map(|v| v.finality(operation.entry.block_number));
+
+        Ok(Response::new(v1::EntityHistoryEntry {
+            finality: finality.map(|v| v.as_str().to_string()),
+            ..operation.into()
+        }))
     }
 
     async fn list_entities(
         &self,
         request: Request<ListEntitiesRequest>,
     ) -> Result<Response<ListEntitiesResponse>, Status> {
-        let inner = request.into_inner();
-        let filter = inner.try_into().map_err(|err| {
-            tracing::error!(?err, "Invalid filter");
-            Status::invalid_argument("Invalid filter")
-        })?;
+        let mut inner = request.into_inner();
+        let head = self.confirmed_head(inner.confirmation.take()).await?;
+        let mut filter: ListEntitiesFilter = inner
+            .try_into()
+            .map_err(|err| Status::invalid_argument(format!("Invalid entities filter: {err}")))?;
+        filter.at_block = filter.at_block.into_iter().chain(head).min();
         let (entities, pagination) = repository::entities::list_entities(&*self.db, filter)
             .await
             .map_err(|err| {
@@ -106,11 +288,12 @@ impl GolemBaseIndexer for GolemBaseIndexerService {
             })?;
 
         let items = entities.into_iter().map(Into::into).collect();
-        let pagination = pagination.into();
+        let next_page_params = PaginationNextPage::from_metadata(&pagination);
 
         Ok(Response::new(ListEntitiesResponse {
             items,
-            pagination: Some(pagination),
+            pagination: pagination.totals.map(Into::into),
+            next_page_params,
         }))
     }
 
@@ -118,12 +301,13 @@ impl GolemBaseIndexer for GolemBaseIndexerService {
         &self,
         request: Request<CountEntitiesRequest>,
     ) -> Result<Response<CountEntitiesResponse>, Status> {
-        let inner = request.into_inner();
-        let filter = inner.try_into().map_err(|err| {
-            tracing::error!(?err, "Invalid filter");
-            Status::invalid_argument("Invalid filter")
-        })?;
-        let count = repository::entities::count_entities(&*self.db, filter)
+        let mut inner = request.into_inner();
+        let head = self.confirmed_head(inner.confirmation.take()).await?;
+        let at_block = inner.at_block.into_iter().chain(head).min();
+        let filter = inner
+            .try_into()
+            .map_err(|err| Status::invalid_argument(format!("Invalid entities filter: {err}")))?;
+        let count = repository::entities::count_entities(&*self.db, filter, at_block)
             .await
             .map_err(|err| {
                 tracing::error!(?err, "failed to query entities");
@@ -137,10 +321,20 @@ impl GolemBaseIndexer for GolemBaseIndexerService {
         &self,
         request: Request<ListOperationsRequest>,
     ) -> Result<Response<ListOperationsResponse>, Status> {
-        let inner = request.into_inner();
-        let filter: ListOperationsFilter = inner
+        let mut inner = request.into_inner();
+        let confirmation = inner
+            .confirmation
+            .take()
+            .map(|v| v.parse::<Confirmation>())
+            .transpose()
+            .map_err(|err| Status::invalid_argument(format!("Invalid confirmation: {err}")))?;
+        let mut filter: ListOperationsFilter = inner
             .try_into()
             .map_err(|err| Status::invalid_argument(format!("Invalid operations filter: {err}")))?;
+        let blocks_info = self.finality_blocks_info(confirmation).await?;
+        filter.operations_filter.max_block_number = confirmation
+            .zip(blocks_info.as_ref())
+            .map(|(confirmation, blocks_info)| blocks_info.head(confirmation));
 
         let (operations, pagination) = repository::operations::list_operations(&*self.db, filter)
             .await
@@ -149,13 +343,24 @@ impl GolemBaseIndexer for GolemBaseIndexerService {
                 Status::internal("failed to query operations")
             })?;
 
-        let items = operations.into_iter().map(Into::into).collect();
-        let pagination = pagination.into();
+        let items = operations
+            .into_iter()
+            .map(|operation| {
+                let finality = blocks_info
+                    .as_ref()
+                    .map(|v| v.finality(operation.op.metadata.block_number));
+                Operation {
+                    finality: finality.map(|v| v.as_str().to_string()),
+                    ..operation.into()
+                }
+            })
+            .collect();
+        let next_page_params = PaginationNextPage::from_metadata(&pagination);
 
         Ok(Response::new(ListOperationsResponse {
             items,
-            // for some reason, the protobuf forces pagination to be an option
-            pagination: Some(pagination),
+            pagination: pagination.totals.map(Into::into),
+            next_page_params,
         }))
     }
 
@@ -163,10 +368,12 @@ impl GolemBaseIndexer for GolemBaseIndexerService {
         &self,
         request: Request<CountOperationsRequest>,
     ) -> Result<Response<CountOperationsResponse>, Status> {
-        let inner = request.into_inner();
-        let filter: OperationsFilter = inner
+        let mut inner = request.into_inner();
+        let head = self.confirmed_head(inner.confirmation.take()).await?;
+        let mut filter: OperationsFilter = inner
             .try_into()
             .map_err(|e| Status::invalid_argument(format!("Invalid operations filter: {e}")))?;
+        filter.max_block_number = head;
 
         let operations_count = repository::operations::count_operations(&*self.db, filter)
             .await
@@ -184,11 +391,21 @@ impl GolemBaseIndexer for GolemBaseIndexerService {
         &self,
         request: Request<GetEntityHistoryRequest>,
     ) -> Result<Response<GetEntityHistoryResponse>, Status> {
-        let inner = request.into_inner();
-
-        let filter = inner.try_into().map_err(|err| {
+        let mut inner = request.into_inner();
+        let confirmation = inner
+            .confirmation
+            .take()
+            .map(|v| v.parse::<Confirmation>())
+            .transpose()
+            .map_err(|err| Status::invalid_argument(format!("Invalid confirmation: {err}")))?;
+
+        let mut filter: EntityHistoryFilter = inner.try_into().map_err(|err| {
             Status::invalid_argument(format!("Invalid entity history filter: {err}"))
         })?;
+        let blocks_info = self.finality_blocks_info(confirmation).await?;
+        filter.max_block_number = confirmation
+            .zip(blocks_info.as_ref())
+            .map(|(confirmation, blocks_info)| blocks_info.head(confirmation));
 
         let (items, pagination) = repository::entities::get_entity_history(&*self.db, filter)
             .await
@@ -196,13 +413,55 @@ impl GolemBaseIndexer for GolemBaseIndexerService {
                 tracing::error!(?err, "failed to query entity history");
                 Status::internal("failed to query entity history")
             })?;
+        let items = repository::entities::with_attributes(&*self.db, items)
+            .await
+            .map_err(|err| {
+                tracing::error!(?err, "failed to query entity history attributes");
+                Status::internal("failed to query entity history attributes")
+            })?;
 
         Ok(Response::new(GetEntityHistoryResponse {
-            items: items.into_iter().map(Into::into).collect(),
-            pagination: Some(pagination.into()),
+            items: items
+                .into_iter()
+                .map(|item| {
+                    let finality = blocks_info
+                        .as_ref()
+                        .map(|v| v.finality(item.entry.block_number));
+                    v1::EntityHistoryEntry {
+                        finality: finality.map(|v| v.as_str().to_string()),
+                        ..item.into()
+                    }
+                })
+                .collect(),
+            next_page_params: PaginationNextPage::from_metadata(&pagination),
+            pagination: pagination.totals.map(Into::into),
         }))
     }
 
+    async fn get_entity_data_diff(
+        &self,
+        request: Request<GetEntityDataDiffRequest>,
+    ) -> Result<Response<EntityDataDiff>, Status> {
+        let inner = request.into_inner();
+
+        let filter = inner.try_into().map_err(|err| {
+            Status::invalid_argument(format!("Invalid entity data diff filter: {err}"))
+        })?;
+
+        let diff = repository::entities::get_entity_data_diff(&*self.db, filter)
+            .await
+            .map_err(|err| match err.downcast_ref::<DataTooLargeToDiff>() {
+                Some(err) => Status::failed_precondition(err.to_string()),
+                None => {
+                    tracing::error!(?err, "failed to diff entity data");
+                    Status::internal("failed to diff entity data")
+                }
+            })?
+            .ok_or(Status::not_found("operation not found"))?;
+
+        Ok(Response::new(diff.into()))
+    }
+
     async fn address_stats(
         &self,
         request: Request<AddressStatsRequest>,
@@ -352,6 +611,29 @@ impl GolemBaseIndexer for GolemBaseIndexerService {
         }))
     }
 
+    async fn list_undecodable_transactions(
+        &self,
+        request: Request<ListUndecodableTransactionsRequest>,
+    ) -> Result<Response<ListUndecodableTransactionsResponse>, Status> {
+        let inner = request.into_inner();
+        let filter = inner.try_into().map_err(|err| {
+            Status::invalid_argument(format!("Invalid undecodable transactions filter: {err}"))
+        })?;
+
+        let (transactions, pagination) =
+            repository::transactions::list_undecodable(&*self.db, filter)
+                .await
+                .map_err(|err| {
+                    tracing::error!(?err, "failed to query undecodable transactions");
+                    Status::internal("failed to query undecodable transactions")
+                })?;
+
+        Ok(Response::new(ListUndecodableTransactionsResponse {
+            items: transactions.into_iter().map(Into::into).collect(),
+            pagination: Some(pagination.into()),
+        }))
+    }
+
     async fn entities_averages(
         &self,
         _request: Request<Empty>,
@@ -366,6 +648,91 @@ impl GolemBaseIndexer for GolemBaseIndexerService {
         Ok(Response::new(entities_averages.into()))
     }
 
+    async fn search_entities(
+        &self,
+        request: Request<SearchEntitiesRequest>,
+    ) -> Result<Response<SearchEntitiesResponse>, Status> {
+        let inner = request.into_inner();
+        let filter = inner
+            .try_into()
+            .map_err(|err| Status::invalid_argument(format!("Invalid search: {err}")))?;
+
+        let (items, pagination) = repository::search::search_entities(&*self.db, filter)
+            .await
+            .map_err(|err| {
+                tracing::error!(?err, "failed to search entities");
+                Status::internal("failed to search entities")
+            })?;
+
+        Ok(Response::new(SearchEntitiesResponse {
+            items: items.into_iter().map(Into::into).collect(),
+            pagination: Some(pagination.into()),
+        }))
+    }
+
+    async fn list_annotation_keys(
+        &self,
+        request: Request<ListAnnotationKeysRequest>,
+    ) -> Result<Response<ListAnnotationKeysResponse>, Status> {
+        let inner = request.into_inner();
+        let filter = inner.try_into().map_err(|err| {
+            Status::invalid_argument(format!("Invalid annotation keys filter: {err}"))
+        })?;
+
+        let (keys, pagination) = repository::attributes::list_attribute_keys(&*self.db, filter)
+            .await
+            .map_err(|err| {
+                tracing::error!(?err, "failed to list annotation keys");
+                Status::internal("failed to list annotation keys")
+            })?;
+
+        Ok(Response::new(ListAnnotationKeysResponse {
+            items: keys.into_iter().map(Into::into).collect(),
+            pagination: Some(pagination.into()),
+        }))
+    }
+
+    async fn list_annotation_values(
+        &self,
+        request: Request<ListAnnotationValuesRequest>,
+    ) -> Result<Response<ListAnnotationValuesResponse>, Status> {
+        let inner = request.into_inner();
+        let filter = inner.try_into().map_err(|err| {
+            Status::invalid_argument(format!("Invalid annotation values filter: {err}"))
+        })?;
+
+        let (values, pagination) = repository::attributes::list_attribute_values(&*self.db, filter)
+            .await
+            .map_err(|err| {
+                tracing::error!(?err, "failed to list annotation values");
+                Status::internal("failed to list annotation values")
+            })?;
+
+        Ok(Response::new(ListAnnotationValuesResponse {
+            items: values.into_iter().map(Into::into).collect(),
+            pagination: Some(pagination.into()),
+        }))
+    }
+
+    async fn numeric_annotation_stats(
+        &self,
+        request: Request<NumericAnnotationStatsRequest>,
+    ) -> Result<Response<NumericAnnotationStatsResponse>, Status> {
+        let NumericAnnotationStatsRequest { key } = request.into_inner();
+        if key.is_empty() {
+            return Err(Status::invalid_argument("annotation key can't be empty"));
+        }
+
+        let stats = repository::attributes::numeric_attribute_stats(&*self.db, key)
+            .await
+            .map_err(|err| {
+                tracing::error!(?err, "failed to get numeric annotation stats");
+                Status::internal("failed to get numeric annotation stats")
+            })?;
+
+        Ok(Response::new(stats.into()))
+    }
+
     async fn address_leaderboard_ranks(
         &self,
         request: Request<AddressLeaderboardRanksRequest>,
@@ -397,8 +764,39 @@ impl GolemBaseIndexer for GolemBaseIndexerService {
             .resolution
             .try_into()
             .map_err(|_| Status::invalid_argument("Unsupported chart resolution"))?;
+        let address = inner
+            .address
+            .map(|address| address.parse())
+            .transpose()
+            .map_err(|err| {
+                tracing::error!(?err, "invalid address");
+                Status::invalid_argument("invalid address")
+            })?;
+        let breakdown = golem_base_indexer_proto::chart_breakdown(
+            inner.group_by,
+            inner.annotation_key,
+            inner.series_limit,
+        )
+        .map_err(|err| Status::invalid_argument(format!("Invalid chart breakdown: {err}")))?;
+        let series = match breakdown {
+            Some(breakdown) => repository::timeseries::breakdown::timeseries_breakdown(
+                &*self.db,
+                inner.from.clone(),
+                inner.to.clone(),
+                resolution,
+                address,
+                BreakdownMetric::DataUsage,
+                &breakdown,
+            )
+            .await
+            .map_err(|err| {
+                tracing::error!(?err, "failed to query data usage chart breakdown");
+                Status::internal("failed to query data usage chart breakdown")
+            })?,
+            None => vec![],
+        };
         let (points, info) = repository::timeseries::data_usage::timeseries_data_usage(
-            &*self.db, inner.from, inner.to, resolution,
+            &*self.db, inner.from, inner.to, resolution, address,
         )
         .await
         .map_err(|err| {
@@ -409,6 +807,7 @@ impl GolemBaseIndexer for GolemBaseIndexerService {
         Ok(Response::new(ChartResponse {
             chart: points.into_iter().map(Into::into).collect(),
             info: Some(info.into()),
+            series: series.into_iter().map(Into::into).collect(),
         }))
     }
 
@@ -421,9 +820,17 @@ impl GolemBaseIndexer for GolemBaseIndexerService {
             .resolution
             .try_into()
             .map_err(|_| Status::invalid_argument("Unsupported chart resolution"))?;
+        let address = inner
+            .address
+            .map(|address| address.parse())
+            .transpose()
+            .map_err(|err| {
+                tracing::error!(?err, "invalid address");
+                Status::invalid_argument("invalid address")
+            })?;
 
         let (points, info) = repository::timeseries::storage_forecast::timeseries_storage_forecast(
-            &*self.db, &inner.to, resolution,
+            &*self.db, &inner.to, resolution, address,
         )
         .await
         .map_err(|err| {
@@ -434,6 +841,7 @@ impl GolemBaseIndexer for GolemBaseIndexerService {
         Ok(Response::new(ChartResponse {
             chart: points.into_iter().map(Into::into).collect(),
             info: Some(info.into()),
+            series: vec![],
         }))
     }
 
@@ -470,8 +878,16 @@ impl GolemBaseIndexer for GolemBaseIndexerService {
             .try_into()
             .map_err(|_| Status::invalid_argument("Invalid operation filter"))?;
         let operation: Option<OperationType> = operation.into();
+        let address = inner
+            .address
+            .map(|address| address.parse())
+            .transpose()
+            .map_err(|err| {
+                tracing::error!(?err, "invalid address");
+                Status::invalid_argument("invalid address")
+            })?;
         let (points, info) = repository::timeseries::operation_count::timeseries_operation_count(
-            &*self.db, inner.from, inner.to, resolution, operation,
+            &*self.db, inner.from, inner.to, resolution, operation, address,
         )
         .await
         .map_err(|err| {
@@ -494,8 +910,39 @@ impl GolemBaseIndexer for GolemBaseIndexerService {
             .resolution
             .try_into()
             .map_err(|_| Status::invalid_argument("Unsupported chart resolution"))?;
+        let address = inner
+            .address
+            .map(|address| address.parse())
+            .transpose()
+            .map_err(|err| {
+                tracing::error!(?err, "invalid address");
+                Status::invalid_argument("invalid address")
+            })?;
+        let breakdown = golem_base_indexer_proto::chart_breakdown(
+            inner.group_by,
+            inner.annotation_key,
+            inner.series_limit,
+        )
+        .map_err(|err| Status::invalid_argument(format!("Invalid chart breakdown: {err}")))?;
+        let series = match breakdown {
+            Some(breakdown) => repository::timeseries::breakdown::timeseries_breakdown(
+                &*self.db,
+                inner.from.clone(),
+                inner.to.clone(),
+                resolution,
+                address,
+                BreakdownMetric::EntityCount,
+                &breakdown,
+            )
+            .await
+            .map_err(|err| {
+                tracing::error!(?err, "failed to query entity count chart breakdown");
+                Status::internal("failed to query entity count chart breakdown")
+            })?,
+            None => vec![],
+        };
         let (points, info) = repository::timeseries::entity_count::timeseries_entity_count(
-            &*self.db, inner.from, inner.to, resolution,
+            &*self.db, inner.from, inner.to, resolution, address,
         )
         .await
         .map_err(|err| {
@@ -506,6 +953,40 @@ impl GolemBaseIndexer for GolemBaseIndexerService {
         Ok(Response::new(ChartResponse {
             chart: points.into_iter().map(Into::into).collect(),
             info: Some(info.into()),
+            series: series.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn chart_spend(
+        &self,
+        request: Request<ChartSpendRequest>,
+    ) -> Result<Response<ChartResponse>, Status> {
+        let inner = request.into_inner();
+        let resolution = inner
+            .resolution
+            .try_into()
+            .map_err(|_| Status::invalid_argument("Unsupported chart resolution"))?;
+        let address = inner
+            .address
+            .map(|address| address.parse())
+            .transpose()
+            .map_err(|err| {
+                tracing::error!(?err, "invalid address");
+                Status::invalid_argument("invalid address")
+            })?;
+        let (points, info) = repository::timeseries::spend::timeseries_spend(
+            &*self.db, inner.from, inner.to, resolution, address,
+        )
+        .await
+        .map_err(|err| {
+            tracing::error!(?err, "failed to query spend chart");
+            Status::internal("failed to query spend chart")
+        })?;
+
+        Ok(Response::new(ChartResponse {
+            chart: points.into_iter().map(Into::into).collect(),
+            info: Some(info.into()),
+            series: vec![],
         }))
     }
 
@@ -574,7 +1055,7 @@ impl GolemBaseIndexer for GolemBaseIndexerService {
     // Leaderboards
     async fn leaderboard_biggest_spenders(
         &self,
-        request: Request<PaginationRequest>,
+        request: Request<LeaderboardRequest>,
     ) -> Result<Response<LeaderboardBiggestSpendersResponse>, Status> {
         let inner = request.into_inner();
         let filter = inner.try_into().map_err(|err| {
@@ -620,7 +1101,7 @@ impl GolemBaseIndexer for GolemBaseIndexerService {
 
     async fn leaderboard_entities_created(
         &self,
-        request: Request<PaginationRequest>,
+        request: Request<LeaderboardRequest>,
     ) -> Result<Response<LeaderboardEntitiesCreatedResponse>, Status> {
         let inner = request.into_inner();
         let filter = inner.try_into().map_err(|err| {
@@ -643,7 +1124,7 @@ impl GolemBaseIndexer for GolemBaseIndexerService {
 
     async fn leaderboard_entities_owned(
         &self,
-        request: Request<PaginationRequest>,
+        request: Request<LeaderboardRequest>,
     ) -> Result<Response<LeaderboardEntitiesOwnedResponse>, Status> {
         let inner = request.into_inner();
         let filter = inner.try_into().map_err(|err| {
@@ -666,7 +1147,7 @@ impl GolemBaseIndexer for GolemBaseIndexerService {
 
     async fn leaderboard_data_owned(
         &self,
-        request: Request<PaginationRequest>,
+        request: Request<LeaderboardRequest>,
     ) -> Result<Response<LeaderboardDataOwnedResponse>, Status> {
         let inner = request.into_inner();
         let filter = inner.try_into().map_err(|err| {
@@ -689,7 +1170,7 @@ impl GolemBaseIndexer for GolemBaseIndexerService {
 
     async fn leaderboard_largest_entities(
         &self,
-        request: Request<PaginationRequest>,
+        request: Request<LeaderboardRequest>,
     ) -> Result<Response<LeaderboardLargestEntitiesResponse>, Status> {
         let inner = request.into_inner();
         let filter = inner.try_into().map_err(|err| {
@@ -712,7 +1193,7 @@ impl GolemBaseIndexer for GolemBaseIndexerService {
 
     async fn leaderboard_effectively_largest_entities(
         &self,
-        request: Request<PaginationRequest>,
+        request: Request<LeaderboardRequest>,
     ) -> Result<Response<LeaderboardEffectivelyLargestEntitiesResponse>, Status> {
         let inner = request.into_inner();
         let filter = inner.try_into().map_err(|err| {
@@ -739,7 +1220,7 @@ impl GolemBaseIndexer for GolemBaseIndexerService {
 
     async fn leaderboard_entities_by_btl(
         &self,
-        request: Request<PaginationRequest>,
+        request: Request<LeaderboardRequest>,
     ) -> Result<Response<LeaderboardEntitiesByBtlResponse>, Status> {
         let inner = request.into_inner();
         let filter = inner.try_into().map_err(|err| {
@@ -771,10 +1252,358 @@ impl GolemBaseIndexer for GolemBaseIndexerService {
 
         Ok(Response::new(
             ConsensusInfo {
-                blocks: blocks_result.unwrap_or_default(),
+                blocks: blocks_result
+                    .inspect_err(|err| {
+                        tracing::error!(?err, "failed to query consensus blocks info")
+                    })
+                    .unwrap_or_default(),
                 gas: gas_result.unwrap_or_default(),
             }
             .into(),
         ))
     }
+
+    async fn get_indexer_status(
+        &self,
+        _request: Request<Empty>,
+    ) -> Result<Response<IndexerStatusResponse>, Status> {
+        let status = status::indexer_status(self.db.clone())
+            .await
+            .map_err(|err| {
+                tracing::error!(?err, "failed to get indexer status");
+                Status::internal("failed to get indexer status")
+            })?;
+        let problems = status.problems(&self.readiness);
+
+        Ok(Response::new(IndexerStatusResponse {
+            ready: problems.is_empty(),
+            problems,
+            ..status.into()
+        }))
+    }
+
+    async fn create_webhook(
+        &self,
+        request: Request<CreateWebhookRequest>,
+    ) -> Result<Response<Webhook>, Status> {
+        if let Some(status) = self.admin_auth_error(request.metadata()) {
+            return Err(status);
+        }
+        let inner = request.into_inner();
+        let webhook: NewWebhook = inner
+            .try_into()
+            .map_err(|err| Status::invalid_argument(format!("Invalid webhook: {err}")))?;
+        if !self.webhooks.allow_private_targets {
+            webhooks::check_target(&webhook.url)
+                .map_err(|err| Status::invalid_argument(format!("Invalid webhook: {err}")))?;
+        }
+
+        let webhook = repository::webhooks::create_webhook(&*self.db, webhook)
+            .await
+            .map_err(|err| {
+                tracing::error!(?err, "failed to create webhook");
+                Status::internal("failed to create webhook")
+            })?;
+
+        Ok(Response::new(webhook.into()))
+    }
+
+    async fn list_webhooks(
+        &self,
+        request: Request<PaginationRequest>,
+    ) -> Result<Response<ListWebhooksResponse>, Status> {
+        if let Some(status) = self.admin_auth_error(request.metadata()) {
+            return Err(status);
+        }
+        let inner = request.into_inner();
+        let pagination = inner
+            .try_into()
+            .map_err(|err| Status::invalid_argument(format!("Invalid pagination: {err}")))?;
+
+        let (webhooks, pagination) = repository::webhooks::list_webhooks(&*self.db, pagination)
+            .await
+            .map_err(|err| {
+                tracing::error!(?err, "failed to list webhooks");
+                Status::internal("failed to list webhooks")
+            })?;
+
+        Ok(Response::new(ListWebhooksResponse {
+            items: webhooks.into_iter().map(Into::into).collect(),
+            pagination: Some(pagination.into()),
+        }))
+    }
+
+    async fn delete_webhook(
+        &self,
+        request: Request<DeleteWebhookRequest>,
+    ) -> Result<Response<Empty>, Status> {
+        if let Some(status) = self.admin_auth_error(request.metadata()) {
+            return Err(status);
+        }
+        let DeleteWebhookRequest { id } = request.into_inner();
+
+        let deleted = repository::webhooks::delete_webhook(&*self.db, id)
+            .await
+            .map_err(|err| {
+                tracing::error!(?err, "failed to delete webhook");
+                Status::internal("failed to delete webhook")
+            })?;
+
+        if !deleted {
+            return Err(Status::not_found("Webhook not found"));
+        }
+
+        Ok(Response::new(Empty {}))
+    }
+
+    async fn list_exhausted_webhook_deliveries(
+        &self,
+        request: Request<PaginationRequest>,
+    ) -> Result<Response<ListWebhookDeliveriesResponse>, Status> {
+        if let Some(status) = self.admin_auth_error(request.metadata()) {
+            return Err(status);
+        }
+        let inner = request.into_inner();
+        let pagination = inner
+            .try_into()
+            .map_err(|err| Status::invalid_argument(format!("Invalid pagination: {err}")))?;
+
+        let (deliveries, pagination) = repository::webhooks::list_exhausted_deliveries(
+            &*self.db,
+            self.webhooks.max_attempts,
+            pagination,
+        )
+        .await
+        .map_err(|err| {
+            tracing::error!(?err, "failed to list exhausted webhook deliveries");
+            Status::internal("failed to list exhausted webhook deliveries")
+        })?;
+
+        Ok(Response::new(ListWebhookDeliveriesResponse {
+            items: deliveries.into_iter().map(Into::into).collect(),
+            pagination: Some(pagination.into()),
+        }))
+    }
+
+    async fn redeliver_webhook_delivery(
+        &self,
+        request: Request<RedeliverWebhookDeliveryRequest>,
+    ) -> Result<Response<Empty>, Status> {
+        if let Some(status) = self.admin_auth_error(request.metadata()) {
+            return Err(status);
+        }
+        let RedeliverWebhookDeliveryRequest { id } = request.into_inner();
+
+        let redelivered =
+            repository::webhooks::redeliver(&*self.db, id, self.webhooks.max_attempts)
+                .await
+                .map_err(|err| {
+                    tracing::error!(?err, "failed to redeliver webhook delivery");
+                    Status::internal("failed to redeliver webhook delivery")
+                })?;
+
+        if !redelivered {
+            return Err(Status::not_found("Exhausted webhook delivery not found"));
+        }
+
+        Ok(Response::new(Empty {}))
+    }
+
+    async fn list_quarantined_items(
+        &self,
+        request: Request<ListQuarantinedItemsRequest>,
+    ) -> Result<Response<ListQuarantinedItemsResponse>, Status> {
+        if let Some(status) = self.admin_auth_error(request.metadata()) {
+            return Err(status);
+        }
+        let ListQuarantinedItemsRequest {
+            queue,
+            page,
+            page_size,
+        } = request.into_inner();
+        let queue = queue
+            .map(|v| v.parse())
+            .transpose()
+            .map_err(|err| Status::invalid_argument(format!("Invalid queue: {err}")))?;
+        let pagination = PaginationRequest { page, page_size }
+            .try_into()
+            .map_err(|err| Status::invalid_argument(format!("Invalid pagination: {err}")))?;
+
+        let (items, pagination) =
+            repository::queues::list_quarantined(&*self.db, queue, pagination)
+                .await
+                .map_err(|err| {
+                    tracing::error!(?err, "failed to list quarantined items");
+                    Status::internal("failed to list quarantined items")
+                })?;
+
+        Ok(Response::new(ListQuarantinedItemsResponse {
+            items: items.into_iter().map(Into::into).collect(),
+            pagination: Some(pagination.into()),
+        }))
+    }
+
+    async fn get_quarantined_items(
+        &self,
+        request: Request<QuarantinedItemsRequest>,
+    ) -> Result<Response<GetQuarantinedItemsResponse>, Status> {
+        if let Some(status) = self.admin_auth_error(request.metadata()) {
+            return Err(status);
+        }
+        let QuarantinedItemsRequest { queue, key } = request.into_inner();
+        let queue = queue
+            .parse()
+            .map_err(|err| Status::invalid_argument(format!("Invalid queue: {err}")))?;
+        let key = key
+            .parse()
+            .map_err(|err| Status::invalid_argument(format!("Invalid key: {err}")))?;
+
+        let items = repository::queues::get_quarantined(&*self.db, queue, key)
+            .await
+            .map_err(|err| {
+                tracing::error!(?err, "failed to get quarantined items");
+                Status::internal("failed to get quarantined items")
+            })?;
+
+        if items.is_empty() {
+            return Err(Status::not_found("No quarantined items found"));
+        }
+
+        Ok(Response::new(GetQuarantinedItemsResponse {
+            items: items.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn requeue_quarantined_items(
+        &self,
+        request: Request<QuarantinedItemsRequest>,
+    ) -> Result<Response<RequeueQuarantinedItemsResponse>, Status> {
+        if let Some(status) = self.admin_auth_error(request.metadata()) {
+            return Err(status);
+        }
+        let QuarantinedItemsRequest { queue, key } = request.into_inner();
+        let queue = queue
+            .parse()
+            .map_err(|err| Status::invalid_argument(format!("Invalid queue: {err}")))?;
+        let key = key
+            .parse()
+            .map_err(|err| Status::invalid_argument(format!("Invalid key: {err}")))?;
+
+        let requeued = repository::queues::requeue_quarantined(&*self.db, queue, key)
+            .await
+            .map_err(|err| {
+                tracing::error!(?err, "failed to requeue quarantined items");
+                Status::internal("failed to requeue quarantined items")
+            })?;
+
+        if requeued == 0 {
+            return Err(Status::not_found("No quarantined items found"));
+        }
+
+        Ok(Response::new(RequeueQuarantinedItemsResponse { requeued }))
+    }
+
+    async fn queue_reindex(
+        &self,
+        request: Request<QueueReindexRequest>,
+    ) -> Result<Response<QueueReindexResponse>, Status> {
+        if let Some(status) = self.admin_auth_error(request.metadata()) {
+            return Err(status);
+        }
+        let scope: ReindexScope = request
+            .into_inner()
+            .try_into()
+            .map_err(|err| Status::invalid_argument(format!("Invalid reindex request: {err}")))?;
+
+        let queued = repository::entities::queue_reindex(&*self.db, scope)
+            .await
+            .map_err(|err| {
+                tracing::error!(?err, "failed to queue reindex");
+                Status::internal("failed to queue reindex")
+            })?;
+
+        if queued == 0 {
+            return Err(Status::not_found("No entities found"));
+        }
+
+        Ok(Response::new(QueueReindexResponse { queued }))
+    }
+
+    async fn get_indexer_state(
+        &self,
+        request: Request<Empty>,
+    ) -> Result<Response<IndexerState>, Status> {
+        if let Some(status) = self.admin_auth_error(request.metadata()) {
+            return Err(status);
+        }
+        let paused = repository::control::is_paused(&*self.db)
+            .await
+            .map_err(|err| {
+                tracing::error!(?err, "failed to get indexer state");
+                Status::internal("failed to get indexer state")
+            })?;
+
+        Ok(Response::new(IndexerState { paused }))
+    }
+
+    async fn pause_indexer(
+        &self,
+        request: Request<Empty>,
+    ) -> Result<Response<IndexerState>, Status> {
+        if let Some(status) = self.admin_auth_error(request.metadata()) {
+            return Err(status);
+        }
+        Ok(Response::new(self.set_indexer_paused(true).await?))
+    }
+
+    async fn resume_indexer(
+        &self,
+        request: Request<Empty>,
+    ) -> Result<Response<IndexerState>, Status> {
+        if let Some(status) = self.admin_auth_error(request.metadata()) {
+            return Err(status);
+        }
+        Ok(Response::new(self.set_indexer_paused(false).await?))
+    }
+
+    async fn queue_depths(
+        &self,
+        request: Request<Empty>,
+    ) -> Result<Response<QueueDepthsResponse>, Status> {
+        if let Some(status) = self.admin_auth_error(request.metadata()) {
+            return Err(status);
+        }
+        let depths = repository::queues::queue_depths(&*self.db)
+            .await
+            .map_err(|err| {
+                tracing::error!(?err, "failed to get queue depths");
+                Status::internal("failed to get queue depths")
+            })?;
+
+        Ok(Response::new(depths.into()))
+    }
+
+    async fn refresh_materialized_view(
+        &self,
+        request: Request<RefreshMaterializedViewRequest>,
+    ) -> Result<Response<Empty>, Status> {
+        if let Some(status) = self.admin_auth_error(request.metadata()) {
+            return Err(status);
+        }
+        let RefreshMaterializedViewRequest { name } = request.into_inner();
+
+        let scheduler = MatViewScheduler::new(self.db.clone());
+        if !scheduler.is_scheduled(&name) {
+            return Err(Status::not_found("Materialized view not found"));
+        }
+        scheduler
+            .try_refresh_named_view(&name)
+            .await
+            .map_err(|err| {
+                tracing::error!(?err, "failed to refresh materialized view");
+                Status::internal("failed to refresh materialized view")
+            })?;
+
+        Ok(Response::new(Empty {}))
+    }
 }
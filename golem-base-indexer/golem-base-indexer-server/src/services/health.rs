@@ -1,18 +1,59 @@
 use crate::proto::{
     health_check_response, health_server::Health, HealthCheckRequest, HealthCheckResponse,
 };
+use golem_base_indexer_logic::status::{self, ReadinessSettings};
+use sea_orm::DatabaseConnection;
+use std::sync::Arc;
 
-#[derive(Default)]
-pub struct HealthService {}
+/// Checked by readiness probes; fails when the indexer falls behind.
+const READINESS_SERVICE: &str = "readiness";
+
+pub struct HealthService {
+    db: Arc<DatabaseConnection>,
+    readiness: ReadinessSettings,
+}
+
+impl HealthService {
+    pub fn new(db: Arc<DatabaseConnection>, readiness: ReadinessSettings) -> Self {
+        Self { db, readiness }
+    }
+
+    async fn is_ready(&self) -> bool {
+        match status::indexer_status(self.db.clone()).await {
+            Ok(status) => {
+                let problems = status.problems(&self.readiness);
+                if !problems.is_empty() {
+                    tracing::warn!(?problems, "indexer is not ready");
+                }
+                problems.is_empty()
+            }
+            Err(err) => {
+                tracing::error!(?err, "failed to get indexer status");
+                false
+            }
+        }
+    }
+}
 
 #[async_trait::async_trait]
 impl Health for HealthService {
     async fn check(
         &self,
-        _request: tonic::Request<HealthCheckRequest>,
+        request: tonic::Request<HealthCheckRequest>,
     ) -> Result<tonic::Response<HealthCheckResponse>, tonic::Status> {
+        // other service names keep reporting liveness, as probes configured with them expect
+        let serving = match request.into_inner().service.as_deref() {
+            Some(READINESS_SERVICE) => self.is_ready().await,
+            _ => true,
+        };
+        let status = if serving {
+            health_check_response::ServingStatus::Serving
+        } else {
+            health_check_response::ServingStatus::NotServing
+        };
+
         Ok(tonic::Response::new(HealthCheckResponse {
-            status: health_check_response::ServingStatus::Serving.into(),
+            status: status.into(),
         }))
     }
 }
@@ -4,7 +4,9 @@ use blockscout_service_launcher::{
     database::{DatabaseConnectSettings, DatabaseSettings},
     launcher::{ConfigSettings, MetricsSettings, ServerSettings},
 };
-use golem_base_indexer_logic::IndexerSettings;
+use golem_base_indexer_logic::{
+    status::ReadinessSettings, webhooks::WebhookSettings, IndexerSettings,
+};
 use serde::Deserialize;
 
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
@@ -21,6 +23,12 @@ pub struct Settings {
     pub swagger_path: PathBuf,
     #[serde(default)]
     pub external_services: ExternalServicesSettings,
+    #[serde(default)]
+    pub webhooks: WebhookSettings,
+    #[serde(default)]
+    pub admin: AdminSettings,
+    #[serde(default)]
+    pub readiness: ReadinessSettings,
 }
 
 fn default_swagger_path() -> PathBuf {
@@ -57,6 +65,27 @@ impl Default for ExternalServicesSettings {
     }
 }
 
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct AdminSettings {
+    /// Bearer token for the `/api/v1/admin` endpoints, which are disabled when unset.
+    pub token: Option<String>,
+}
+
+impl AdminSettings {
+    /// A blank token would let requests with an empty bearer through, so it's refused.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self
+            .token
+            .as_deref()
+            .is_some_and(|token| token.trim().is_empty())
+        {
+            anyhow::bail!("admin token must not be empty");
+        }
+        Ok(())
+    }
+}
+
 impl Settings {
     pub fn default(database_url: String) -> Self {
         Self {
@@ -71,6 +100,9 @@ impl Settings {
             },
             indexer: Default::default(),
             external_services: Default::default(),
+            webhooks: Default::default(),
+            admin: Default::default(),
+            readiness: Default::default(),
         }
     }
 }
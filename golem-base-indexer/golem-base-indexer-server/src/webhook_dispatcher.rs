@@ -0,0 +1,25 @@
+use std::sync::Arc;
+
+use crate::settings::Settings;
+use golem_base_indexer_logic::webhooks::WebhookDispatcher;
+use sea_orm::DatabaseConnection;
+use tokio::time::sleep;
+
+pub async fn run(
+    db_connection: Arc<DatabaseConnection>,
+    settings: Settings,
+) -> Result<(), anyhow::Error> {
+    let dispatcher = WebhookDispatcher::new(db_connection, settings.webhooks)?;
+    let delay = settings.indexer.restart_delay;
+
+    tokio::spawn(async move {
+        loop {
+            if let Err(err) = dispatcher.run().await {
+                tracing::error!(error = ?err, ?delay, "webhook dispatcher failed, retrying");
+            }
+            sleep(delay).await;
+        }
+    });
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use golem_base_indexer_server::Settings;
+use reqwest::{Method, RequestBuilder, StatusCode, Url};
+use serde_json::Value;
+
+pub const ADMIN_TOKEN: &str = "test-admin-token";
+
+/// Settings setup enabling the admin API with [`ADMIN_TOKEN`].
+pub fn with_admin_token(mut settings: Settings) -> Settings {
+    settings.admin.token = Some(ADMIN_TOKEN.to_string());
+    settings
+}
+
+/// Request to an admin endpoint, authorized with [`ADMIN_TOKEN`].
+pub fn request(method: Method, base: &Url, path: &str) -> RequestBuilder {
+    reqwest::Client::new()
+        .request(method, base.join(path).unwrap())
+        .bearer_auth(ADMIN_TOKEN)
+}
+
+pub async fn get(base: &Url, path: &str) -> Value {
+    let response = request(Method::GET, base, path).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK, "GET {path}");
+    response.json().await.unwrap()
+}
+
+pub async fn post(base: &Url, path: &str, body: &Value) -> Value {
+    let response = request(Method::POST, base, path)
+        .json(body)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK, "POST {path}");
+    response.json().await.unwrap()
+}
@@ -1,15 +1,22 @@
 #![allow(dead_code)]
 use blockscout_service_launcher::{test_database::TestDbGuard, test_server};
-use golem_base_indexer_server::Settings;
+use golem_base_indexer_server::{EntityChannel, EntityChannelCentral, Settings};
 use migration::{
     from_sql, Alias, DbErr, DynIden, IntoIden, MigrationName, MigrationTrait, MigratorTrait,
     SchemaManager,
 };
 use reqwest::Url;
 use sea_orm::{ConnectionTrait, Statement, TransactionTrait};
+use std::{
+    net::{SocketAddr, TcpListener},
+    sync::Arc,
+};
 
+pub mod admin;
 pub mod assert_json;
+pub mod pagination;
 pub mod sample;
+pub mod storage;
 pub mod utils;
 
 pub struct TestMigrator;
@@ -49,6 +56,20 @@ pub async fn init_db(db_prefix: &str, test_name: &str) -> TestDbGuard {
 }
 
 pub async fn init_golem_base_indexer_server<F>(db: TestDbGuard, settings_setup: F) -> Url
+where
+    F: Fn(Settings) -> Settings,
+{
+    init_golem_base_indexer_server_with_grpc(db, settings_setup)
+        .await
+        .0
+}
+
+/// Starts the server with the gRPC endpoint enabled and returns
+/// both the HTTP base url and the gRPC url.
+pub async fn init_golem_base_indexer_server_with_grpc<F>(
+    db: TestDbGuard,
+    settings_setup: F,
+) -> (Url, Url)
 where
     F: Fn(Settings) -> Settings,
 {
@@ -59,18 +80,27 @@ where
         settings.database.connect_options.max_connections = Some(50);
         let (server_settings, base) = test_server::get_test_server_settings();
         settings.server = server_settings;
+        settings.server.grpc.enabled = true;
+        settings.server.grpc.addr = SocketAddr::from(([127, 0, 0, 1], get_free_port()));
         settings.metrics.enabled = false;
 
         (settings_setup(settings), base)
     };
+    let grpc = Url::parse(&format!("http://{}", settings.server.grpc.addr)).unwrap();
 
     let client = db.client();
+    let channel = Arc::new(EntityChannelCentral::new(EntityChannel));
     test_server::init_server(
-        || golem_base_indexer_server::run_server(client, settings),
+        || golem_base_indexer_server::run_server(client, settings, channel),
         &base,
     )
     .await;
-    base
+    (base, grpc)
+}
+
+fn get_free_port() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.local_addr().unwrap().port()
 }
 
 #[allow(dead_code)]
@@ -0,0 +1,27 @@
+use blockscout_service_launcher::test_server;
+use reqwest::Url;
+use serde_json::Value;
+
+/// Follows `next_page_params` from the `first` page of `path` until exhausted
+/// and returns all collected items.
+pub async fn collect_pages(base: &Url, path: &str, first: Value) -> Vec<Value> {
+    let mut items = Vec::new();
+    let mut response = first;
+    loop {
+        items.extend(response["items"].as_array().unwrap().iter().cloned());
+        let Some(cursor) = response["next_page_params"]["cursor"].as_str() else {
+            break;
+        };
+        let separator = if path.contains('?') { '&' } else { '?' };
+        response =
+            test_server::send_get_request(base, &format!("{path}{separator}cursor={cursor}")).await;
+        assert!(response["pagination"].is_null());
+    }
+    items
+}
+
+/// All items of a cursor-paginated listing, starting from its first page.
+pub async fn list_all(base: &Url, path: &str) -> Vec<Value> {
+    let first = test_server::send_get_request(base, path).await;
+    collect_pages(base, path, first).await
+}
@@ -0,0 +1,50 @@
+use arkiv_storage_tx::{Create, StorageTransaction, Update};
+use golem_base_indexer_logic::types::{EntityKey, TxHash};
+
+use super::sample::{Block, Transaction};
+
+/// Storage transaction creating a single entity with the given payload.
+pub fn create(payload: &[u8]) -> StorageTransaction {
+    creates(&[payload])
+}
+
+/// Storage transaction creating one entity per payload.
+pub fn creates(payloads: &[&[u8]]) -> StorageTransaction {
+    StorageTransaction {
+        creates: payloads
+            .iter()
+            .map(|payload| Create {
+                btl: 100,
+                payload: payload.to_vec().into(),
+                ..Default::default()
+            })
+            .collect(),
+        ..Default::default()
+    }
+}
+
+/// Storage transaction replacing the payload of an entity.
+pub fn update(key: EntityKey, payload: &[u8]) -> StorageTransaction {
+    StorageTransaction {
+        updates: vec![Update {
+            entity_key: key,
+            btl: 100,
+            payload: payload.to_vec().into(),
+            ..Default::default()
+        }],
+        ..Default::default()
+    }
+}
+
+/// Block with a single transaction carrying `operations`.
+pub fn block(number: u64, tx_hash: TxHash, operations: StorageTransaction) -> Block {
+    Block {
+        number,
+        transactions: vec![Transaction {
+            hash: Some(tx_hash),
+            operations,
+            ..Default::default()
+        }],
+        ..Default::default()
+    }
+}
@@ -0,0 +1,185 @@
+use crate::helpers;
+
+use arkiv_storage_tx::{ChangeOwner, Create, StorageTransaction};
+use blockscout_service_launcher::test_server;
+use chrono::{DateTime, DurationRound, TimeDelta, Utc};
+use golem_base_indexer_logic::{
+    arkiv::entity_key,
+    types::{Address, TxHash},
+    Indexer,
+};
+use pretty_assertions::assert_eq;
+use reqwest::{StatusCode, Url};
+use serde_json::Value;
+
+use crate::helpers::sample::{Block, Transaction};
+
+fn format_hour(dt: DateTime<Utc>) -> String {
+    dt.format("%Y-%m-%d%%20%H:%M").to_string()
+}
+
+/// Values of an hourly chart between `from` and `to`.
+async fn hourly_values(base: &Url, chart: &str, query: &str) -> Vec<String> {
+    let response: Value = test_server::send_get_request(
+        base,
+        &format!("/api/v1/chart/{chart}?resolution=HOUR&{query}"),
+    )
+    .await;
+    response["chart"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|point| point["value"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_address_charts_work() {
+    let db = helpers::init_db("test", "address_charts_work").await;
+    let client = db.client();
+    let base = helpers::init_golem_base_indexer_server(db, |x| x).await;
+
+    let alice = Address::repeat_byte(0xaa);
+    let bob = Address::repeat_byte(0xbb);
+    let alice_tx = TxHash::repeat_byte(1);
+    let bob_tx = TxHash::repeat_byte(2);
+    let start = (Utc::now() - TimeDelta::hours(3))
+        .duration_trunc(TimeDelta::hours(1))
+        .unwrap();
+    let creates = |payloads: &[&[u8]]| StorageTransaction {
+        creates: payloads
+            .iter()
+            .map(|payload| Create {
+                btl: 10800,
+                payload: payload.to_vec().into(),
+                ..Default::default()
+            })
+            .collect(),
+        ..Default::default()
+    };
+    helpers::sample::insert_data_multi(
+        &*client,
+        vec![
+            Block {
+                number: 1,
+                timestamp: Some(start),
+                transactions: vec![
+                    Transaction {
+                        hash: Some(alice_tx),
+                        sender: alice,
+                        operations: creates(&[b"first", b"second entity"]),
+                        ..Default::default()
+                    },
+                    Transaction {
+                        hash: Some(bob_tx),
+                        sender: bob,
+                        operations: creates(&[b"bob"]),
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            },
+            Block {
+                number: 2,
+                timestamp: Some(start + TimeDelta::hours(1)),
+                transactions: vec![
+                    Transaction {
+                        hash: Some(TxHash::repeat_byte(3)),
+                        sender: alice,
+                        operations: StorageTransaction {
+                            change_owners: vec![ChangeOwner {
+                                entity_key: entity_key(
+                                    alice_tx,
+                                    b"second entity".to_vec().into(),
+                                    1,
+                                ),
+                                new_owner: bob,
+                            }],
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                    Transaction {
+                        hash: Some(TxHash::repeat_byte(4)),
+                        sender: bob,
+                        operations: StorageTransaction {
+                            deletes: vec![entity_key(bob_tx, b"bob".to_vec().into(), 0)],
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            },
+        ],
+    )
+    .await
+    .unwrap();
+    Indexer::new(client.clone(), Default::default())
+        .tick()
+        .await
+        .unwrap();
+
+    // served from the rollups, so no view refresh is needed
+    let range = format!(
+        "from={}&to={}",
+        format_hour(start),
+        format_hour(start + TimeDelta::hours(2))
+    );
+    let query = |address: Address| format!("{range}&address={address}");
+    assert_eq!(
+        hourly_values(&base, "data-usage", &query(alice)).await,
+        ["18", "5"]
+    );
+    assert_eq!(
+        hourly_values(&base, "data-usage", &query(bob)).await,
+        ["3", "13"]
+    );
+    assert_eq!(
+        hourly_values(&base, "entity-count", &query(alice)).await,
+        ["2", "1"]
+    );
+    assert_eq!(
+        hourly_values(&base, "entity-count", &query(bob)).await,
+        ["1", "1"]
+    );
+    assert_eq!(
+        hourly_values(
+            &base,
+            "operation-count",
+            &format!("{}&operation=ALL", query(alice))
+        )
+        .await,
+        ["2", "1"]
+    );
+    assert_eq!(
+        hourly_values(&base, "spend", &query(alice)).await,
+        ["10000", "10000"]
+    );
+    assert_eq!(
+        hourly_values(&base, "spend", &range).await,
+        ["20000", "20000"]
+    );
+
+    // alice's remaining entity expires 6 hours after the latest block
+    let forecast = hourly_values(
+        &base,
+        "storage-forecast",
+        &format!(
+            "to={}&address={alice}",
+            format_hour(start + TimeDelta::hours(10))
+        ),
+    )
+    .await;
+    assert_eq!(forecast, ["5", "5", "5", "5", "5", "0", "0"]);
+
+    let status = reqwest::get(
+        base.join("/api/v1/chart/data-usage?resolution=HOUR&address=0x1234")
+            .unwrap(),
+    )
+    .await
+    .unwrap()
+    .status();
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
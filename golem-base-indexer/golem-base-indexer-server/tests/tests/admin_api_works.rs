@@ -0,0 +1,261 @@
+use crate::helpers;
+
+use golem_base_indexer_logic::{
+    arkiv::entity_key,
+    mat_view_scheduler::MatViewScheduler,
+    types::{Address, TxHash},
+    Indexer, IndexerSettings,
+};
+use golem_base_indexer_server::{EntityChannel, EntityChannelCentral, Settings};
+use pretty_assertions::assert_eq;
+use reqwest::{Method, StatusCode, Url};
+use serde_json::{json, Value};
+use std::{sync::Arc, time::Duration};
+
+use crate::helpers::sample::{Block, Transaction};
+use crate::helpers::storage::create;
+
+async fn reindex_status(base: &Url, body: &Value) -> StatusCode {
+    helpers::admin::request(Method::POST, base, "/api/v1/admin/reindex")
+        .json(body)
+        .send()
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_admin_api_is_disabled_without_token() {
+    let db = helpers::init_db("test", "admin_api_is_disabled_without_token").await;
+    let base = helpers::init_golem_base_indexer_server(db, |x| x).await;
+
+    let status = helpers::admin::request(Method::GET, &base, "/api/v1/admin/queues")
+        .send()
+        .await
+        .unwrap()
+        .status();
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_admin_api_rejects_blank_token() {
+    let db = helpers::init_db("test", "admin_api_rejects_blank_token").await;
+
+    for token in ["", "  "] {
+        let mut settings = Settings::default(db.db_url());
+        settings.admin.token = Some(token.to_string());
+        let channel = Arc::new(EntityChannelCentral::new(EntityChannel));
+        let err = golem_base_indexer_server::run_server(db.client(), settings, channel)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            format!("{err:#}"),
+            "invalid admin settings: admin token must not be empty"
+        );
+    }
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_admin_api_works() {
+    let db = helpers::init_db("test", "admin_api_works").await;
+    let client = db.client();
+    let base = helpers::init_golem_base_indexer_server(db, helpers::admin::with_admin_token).await;
+
+    // authentication
+    let queues = base.join("/api/v1/admin/queues").unwrap();
+    let status = reqwest::get(queues.clone()).await.unwrap().status();
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let status = reqwest::Client::new()
+        .get(queues)
+        .bearer_auth("wrong-token")
+        .send()
+        .await
+        .unwrap()
+        .status();
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let owner = Address::repeat_byte(0xaa);
+    let other = Address::repeat_byte(0xbb);
+    let first = TxHash::repeat_byte(1);
+    let second = TxHash::repeat_byte(2);
+    let third = TxHash::repeat_byte(3);
+    let first_key = entity_key(first, b"first".to_vec().into(), 0);
+    helpers::sample::insert_data_multi(
+        &*client,
+        vec![
+            Block {
+                number: 1,
+                transactions: vec![
+                    Transaction {
+                        hash: Some(first),
+                        sender: owner,
+                        operations: create(b"first"),
+                        ..Default::default()
+                    },
+                    Transaction {
+                        hash: Some(second),
+                        sender: owner,
+                        operations: create(b"second"),
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            },
+            Block {
+                number: 2,
+                transactions: vec![Transaction {
+                    hash: Some(third),
+                    sender: other,
+                    operations: create(b"third"),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        ],
+    )
+    .await
+    .unwrap();
+
+    let depths = helpers::admin::get(&base, "/api/v1/admin/queues").await;
+    assert_eq!(
+        depths,
+        json!({
+            "pending_transactions": "3",
+            "pending_transaction_cleanups": "0",
+            "pending_logs": "0",
+            "pending_logs_events": "0",
+            "entities_to_reindex": "0",
+            "quarantined": "0",
+        })
+    );
+
+    let indexer = Indexer::new(client.clone(), Default::default());
+    indexer.tick().await.unwrap();
+    let depths = helpers::admin::get(&base, "/api/v1/admin/queues").await;
+    assert_eq!(depths["pending_transactions"], "0");
+
+    // reindexing
+    for (body, queued) in [
+        (json!({ "entity_key": first_key.to_string() }), "1"),
+        (json!({ "owner": owner.to_string() }), "2"),
+        (json!({ "from_block": "1", "to_block": "2" }), "3"),
+    ] {
+        let response = helpers::admin::post(&base, "/api/v1/admin/reindex", &body).await;
+        assert_eq!(response["queued"], queued, "{body}");
+    }
+
+    let depths = helpers::admin::get(&base, "/api/v1/admin/queues").await;
+    assert_eq!(depths["entities_to_reindex"], "3");
+    indexer.tick().await.unwrap();
+    let depths = helpers::admin::get(&base, "/api/v1/admin/queues").await;
+    assert_eq!(depths["entities_to_reindex"], "0");
+
+    for body in [
+        json!({}),
+        json!({ "entity_key": "0x1" }),
+        json!({ "from_block": "1" }),
+        json!({ "from_block": "2", "to_block": "1" }),
+        json!({ "owner": owner.to_string(), "from_block": "1", "to_block": "2" }),
+    ] {
+        assert_eq!(
+            reindex_status(&base, &body).await,
+            StatusCode::BAD_REQUEST,
+            "{body}"
+        );
+    }
+    assert_eq!(
+        reindex_status(
+            &base,
+            &json!({ "owner": Address::repeat_byte(0xcc).to_string() })
+        )
+        .await,
+        StatusCode::NOT_FOUND
+    );
+    assert_eq!(
+        reindex_status(&base, &json!({ "from_block": "10", "to_block": "20" })).await,
+        StatusCode::NOT_FOUND
+    );
+
+    // materialized views
+    let view = MatViewScheduler::new(client.clone())
+        .get_mat_view_settings()
+        .remove(0)
+        .name;
+    let response = helpers::admin::post(
+        &base,
+        &format!("/api/v1/admin/materialized-views/{view}/refresh"),
+        &json!({}),
+    )
+    .await;
+    assert_eq!(response, json!({}));
+    let status = helpers::admin::request(
+        Method::POST,
+        &base,
+        "/api/v1/admin/materialized-views/golem_base_entities/refresh",
+    )
+    .json(&json!({}))
+    .send()
+    .await
+    .unwrap()
+    .status();
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_admin_api_pauses_indexer() {
+    let db = helpers::init_db("test", "admin_api_pauses_indexer").await;
+    let client = db.client();
+    let base = helpers::init_golem_base_indexer_server(db, helpers::admin::with_admin_token).await;
+
+    let state = helpers::admin::get(&base, "/api/v1/admin/indexer").await;
+    assert_eq!(state, json!({ "paused": false }));
+    let state = helpers::admin::post(&base, "/api/v1/admin/indexer/pause", &json!({})).await;
+    assert_eq!(state, json!({ "paused": true }));
+    let state = helpers::admin::get(&base, "/api/v1/admin/indexer").await;
+    assert_eq!(state, json!({ "paused": true }));
+
+    let indexer = Indexer::new(
+        client.clone(),
+        IndexerSettings {
+            polling_interval: Duration::from_millis(50),
+            ..Default::default()
+        },
+    );
+    let indexer = tokio::spawn(indexer.run());
+
+    let tx_hash = TxHash::repeat_byte(1);
+    helpers::sample::insert_data(
+        &*client,
+        Block {
+            number: 1,
+            transactions: vec![Transaction {
+                hash: Some(tx_hash),
+                operations: create(b"paused"),
+                ..Default::default()
+            }],
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    let pending = || async {
+        helpers::admin::get(&base, "/api/v1/admin/queues").await["pending_transactions"].clone()
+    };
+
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert_eq!(pending().await, "1");
+
+    let state = helpers::admin::post(&base, "/api/v1/admin/indexer/resume", &json!({})).await;
+    assert_eq!(state, json!({ "paused": false }));
+    let mut attempts = 0;
+    while pending().await != "0" {
+        attempts += 1;
+        assert!(attempts < 50, "indexer did not resume");
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    indexer.abort();
+}
@@ -0,0 +1,244 @@
+use crate::helpers;
+
+use arkiv_storage_tx::{Create, NumericAttribute, StorageTransaction, StringAttribute, Update};
+use blockscout_service_launcher::test_server;
+use golem_base_indexer_logic::{
+    arkiv::entity_key,
+    types::{Address, TxHash},
+    Indexer,
+};
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+
+use crate::helpers::sample::{Block, Transaction};
+
+fn create(payload: &[u8], string: Option<(&str, &str)>, size: u64) -> Create {
+    Create {
+        btl: 1000,
+        payload: payload.to_vec().into(),
+        string_attributes: string
+            .map(|(key, value)| StringAttribute {
+                key: key.to_string(),
+                value: value.to_string(),
+            })
+            .into_iter()
+            .collect(),
+        numeric_attributes: vec![NumericAttribute {
+            key: "size".to_string(),
+            value: size,
+        }],
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_annotation_catalog_works() {
+    let db = helpers::init_db("test", "annotation_catalog_works").await;
+    let client = db.client();
+    let base = helpers::init_golem_base_indexer_server(db, |x| x).await;
+
+    let tx = TxHash::repeat_byte(1);
+    let photo = b"photo";
+    let other = b"other";
+    helpers::sample::insert_data_multi(
+        &*client,
+        vec![
+            Block {
+                number: 1,
+                transactions: vec![Transaction {
+                    hash: Some(tx),
+                    sender: Address::repeat_byte(0xaa),
+                    operations: StorageTransaction {
+                        creates: vec![
+                            create(b"first", Some(("app", "chat")), 10),
+                            create(b"second", Some(("app", "chat")), 20),
+                            create(photo, Some(("app", "photos")), 30),
+                            create(other, Some(("type", "x")), 40),
+                        ],
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            Block {
+                number: 2,
+                transactions: vec![Transaction {
+                    hash: Some(TxHash::repeat_byte(2)),
+                    sender: Address::repeat_byte(0xaa),
+                    operations: StorageTransaction {
+                        updates: vec![Update {
+                            entity_key: entity_key(tx, photo.to_vec().into(), 2),
+                            btl: 1000,
+                            payload: photo.to_vec().into(),
+                            string_attributes: vec![StringAttribute {
+                                key: "app".to_string(),
+                                value: "chat".to_string(),
+                            }],
+                            numeric_attributes: vec![NumericAttribute {
+                                key: "size".to_string(),
+                                value: 35,
+                            }],
+                            ..Default::default()
+                        }],
+                        deletes: vec![entity_key(tx, other.to_vec().into(), 3)],
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        ],
+    )
+    .await
+    .unwrap();
+    Indexer::new(client.clone(), Default::default())
+        .tick()
+        .await
+        .unwrap();
+
+    // keys of deleted entities are still listed, with no active entities
+    let response: Value = test_server::send_get_request(&base, "/api/v1/annotations/keys").await;
+    assert_eq!(
+        response["items"],
+        json!([
+            {
+                "kind": "numeric",
+                "key": "size",
+                "active_entities": "3",
+                "first_seen_block": "1",
+                "last_seen_block": "2",
+            },
+            {
+                "kind": "string",
+                "key": "app",
+                "active_entities": "3",
+                "first_seen_block": "1",
+                "last_seen_block": "2",
+            },
+            {
+                "kind": "string",
+                "key": "type",
+                "active_entities": "0",
+                "first_seen_block": "1",
+                "last_seen_block": "1",
+            },
+        ])
+    );
+    let response: Value =
+        test_server::send_get_request(&base, "/api/v1/annotations/keys?prefix=a").await;
+    assert_eq!(response["items"].as_array().unwrap().len(), 1);
+    assert_eq!(response["items"][0]["key"], "app");
+    let response: Value =
+        test_server::send_get_request(&base, "/api/v1/annotations/keys?kind=numeric").await;
+    assert_eq!(response["items"].as_array().unwrap().len(), 1);
+    assert_eq!(response["items"][0]["key"], "size");
+
+    // values that are no longer used by any active entity are dropped
+    let response: Value =
+        test_server::send_get_request(&base, "/api/v1/annotations/values?key=app").await;
+    assert_eq!(
+        response["items"],
+        json!([{"value": "chat", "active_entities": "3"}])
+    );
+    let response: Value = test_server::send_get_request(
+        &base,
+        "/api/v1/annotations/values?key=size&kind=numeric&page_size=2",
+    )
+    .await;
+    assert_eq!(
+        response["items"],
+        json!([
+            {"value": "10", "active_entities": "1"},
+            {"value": "20", "active_entities": "1"},
+        ])
+    );
+    assert_eq!(response["pagination"]["total_items"], "3");
+
+    let response: Value =
+        test_server::send_get_request(&base, "/api/v1/annotations/numeric-stats?key=size").await;
+    assert_eq!(
+        response,
+        json!({
+            "key": "size",
+            "active_entities": "3",
+            "min": "10",
+            "max": "35",
+            "p25": "10",
+            "p50": "20",
+            "p75": "35",
+            "p90": "35",
+            "p99": "35",
+        })
+    );
+    let response: Value =
+        test_server::send_get_request(&base, "/api/v1/annotations/numeric-stats?key=missing").await;
+    assert_eq!(response["active_entities"], "0");
+    assert_eq!(response["min"], Value::Null);
+
+    // an entity repeating a key counts once, and values too long for a btree are fine
+    let long_value = "x".repeat(8000);
+    helpers::sample::insert_data_multi(
+        &*client,
+        vec![Block {
+            number: 3,
+            transactions: vec![Transaction {
+                hash: Some(TxHash::repeat_byte(3)),
+                sender: Address::repeat_byte(0xaa),
+                operations: StorageTransaction {
+                    creates: vec![Create {
+                        btl: 1000,
+                        payload: b"repeated".to_vec().into(),
+                        string_attributes: vec![
+                            StringAttribute {
+                                key: "app".to_string(),
+                                value: "chat".to_string(),
+                            },
+                            StringAttribute {
+                                key: "app".to_string(),
+                                value: long_value.clone(),
+                            },
+                        ],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+    )
+    .await
+    .unwrap();
+    Indexer::new(client.clone(), Default::default())
+        .tick()
+        .await
+        .unwrap();
+
+    let response: Value =
+        test_server::send_get_request(&base, "/api/v1/annotations/keys?prefix=app").await;
+    assert_eq!(response["items"][0]["active_entities"], "4");
+    let response: Value =
+        test_server::send_get_request(&base, "/api/v1/annotations/values?key=app").await;
+    assert_eq!(
+        response["items"],
+        json!([
+            {"value": "chat", "active_entities": "4"},
+            {"value": long_value, "active_entities": "1"},
+        ])
+    );
+
+    for path in [
+        "/api/v1/annotations/keys?kind=bogus",
+        "/api/v1/annotations/values?key=",
+        "/api/v1/annotations/numeric-stats?key=",
+    ] {
+        let status = reqwest::get(base.join(path).unwrap())
+            .await
+            .unwrap()
+            .status();
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{path}");
+    }
+}
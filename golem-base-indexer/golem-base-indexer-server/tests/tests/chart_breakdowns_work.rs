@@ -0,0 +1,202 @@
+use crate::helpers;
+
+use arkiv_storage_tx::{Create, StorageTransaction, StringAttribute, Update};
+use blockscout_service_launcher::test_server;
+use chrono::{DateTime, DurationRound, TimeDelta, Utc};
+use golem_base_indexer_logic::{
+    arkiv::entity_key,
+    types::{Address, TxHash},
+    Indexer,
+};
+use pretty_assertions::assert_eq;
+use reqwest::{StatusCode, Url};
+use serde_json::{json, Value};
+
+use crate::helpers::sample::{Block, Transaction};
+
+fn format_hour(dt: DateTime<Utc>) -> String {
+    dt.format("%Y-%m-%d%%20%H:%M").to_string()
+}
+
+fn create(payload: &[u8], content_type: &str, app: Option<&str>) -> Create {
+    Create {
+        btl: 10800,
+        content_type: content_type.to_string(),
+        payload: payload.to_vec().into(),
+        string_attributes: app
+            .map(|app| StringAttribute {
+                key: "app".to_string(),
+                value: app.to_string(),
+            })
+            .into_iter()
+            .collect(),
+        ..Default::default()
+    }
+}
+
+/// Series names with their values, in the order they are returned.
+async fn series(base: &Url, chart: &str, query: &str) -> Value {
+    let response: Value =
+        test_server::send_get_request(base, &format!("/api/v1/chart/{chart}?{query}")).await;
+    response["series"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|series| {
+            let values: Vec<&Value> = series["chart"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|point| &point["value"])
+                .collect();
+            json!([series["name"], values])
+        })
+        .collect()
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_chart_breakdowns_work() {
+    let db = helpers::init_db("test", "chart_breakdowns_work").await;
+    let client = db.client();
+    let base = helpers::init_golem_base_indexer_server(db, |x| x).await;
+
+    let alice = Address::repeat_byte(0xaa);
+    let bob = Address::repeat_byte(0xbb);
+    let alice_tx = TxHash::repeat_byte(1);
+    let start = (Utc::now() - TimeDelta::hours(3))
+        .duration_trunc(TimeDelta::hours(1))
+        .unwrap();
+    let photo = b"bbbbbbbbbbbbbbbbbbbb";
+    let json = b"ccccc";
+    helpers::sample::insert_data_multi(
+        &*client,
+        vec![
+            Block {
+                number: 1,
+                timestamp: Some(start),
+                transactions: vec![
+                    Transaction {
+                        hash: Some(alice_tx),
+                        sender: alice,
+                        operations: StorageTransaction {
+                            creates: vec![
+                                create(b"aaaaaaaaaa", "text/plain", Some("chat")),
+                                create(photo, "image/png", Some("photos")),
+                                create(json, "application/json", Some("chat")),
+                                create(b"dd", "text/plain", None),
+                            ],
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                    Transaction {
+                        hash: Some(TxHash::repeat_byte(2)),
+                        sender: bob,
+                        operations: StorageTransaction {
+                            creates: vec![create(b"bob", "text/plain", Some("chat"))],
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            },
+            Block {
+                number: 2,
+                timestamp: Some(start + TimeDelta::hours(1)),
+                transactions: vec![Transaction {
+                    hash: Some(TxHash::repeat_byte(3)),
+                    sender: alice,
+                    operations: StorageTransaction {
+                        updates: vec![Update {
+                            entity_key: entity_key(alice_tx, photo.to_vec().into(), 1),
+                            btl: 10800,
+                            content_type: "text/plain".to_string(),
+                            payload: b"bbbb".to_vec().into(),
+                            string_attributes: vec![StringAttribute {
+                                key: "app".to_string(),
+                                value: "chat".to_string(),
+                            }],
+                            ..Default::default()
+                        }],
+                        deletes: vec![entity_key(alice_tx, json.to_vec().into(), 2)],
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        ],
+    )
+    .await
+    .unwrap();
+    Indexer::new(client.clone(), Default::default())
+        .tick()
+        .await
+        .unwrap();
+
+    let range = format!(
+        "resolution=HOUR&from={}&to={}",
+        format_hour(start),
+        format_hour(start + TimeDelta::hours(2))
+    );
+
+    // image/png and application/json are gone by now, so they end up in "other"
+    assert_eq!(
+        series(
+            &base,
+            "data-usage",
+            &format!("{range}&group_by=content_type")
+        )
+        .await,
+        json!([["text/plain", ["15", "19"]], ["other", ["25", "0"]]])
+    );
+    assert_eq!(
+        series(
+            &base,
+            "data-usage",
+            &format!("{range}&group_by=annotation&annotation_key=app&series_limit=1")
+        )
+        .await,
+        json!([["chat", ["18", "17"]], ["other", ["22", "2"]]])
+    );
+    assert_eq!(
+        series(
+            &base,
+            "entity-count",
+            &format!("{range}&group_by=annotation&annotation_key=app&address={alice}")
+        )
+        .await,
+        json!([["chat", ["2", "2"]], ["other", ["2", "1"]]])
+    );
+
+    // the unsplit chart is still returned
+    let response: Value = test_server::send_get_request(
+        &base,
+        &format!("/api/v1/chart/data-usage?{range}&group_by=content_type"),
+    )
+    .await;
+    assert_eq!(response["info"]["id"], "golemBaseDataUsage");
+    assert!(response["chart"].is_array());
+    let response: Value =
+        test_server::send_get_request(&base, &format!("/api/v1/chart/data-usage?{range}")).await;
+    assert_eq!(response["series"], json!([]));
+
+    for query in [
+        "group_by=annotation",
+        "group_by=content_type&annotation_key=app",
+        "annotation_key=app",
+        "series_limit=3",
+        "group_by=owner",
+    ] {
+        let status = reqwest::get(
+            base.join(&format!("/api/v1/chart/entity-count?{range}&{query}"))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .status();
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{query}");
+    }
+}
@@ -0,0 +1,222 @@
+use crate::helpers::{self, utils::gen_block_resp};
+
+use blockscout_service_launcher::test_server;
+use golem_base_indexer_logic::{arkiv::entity_key, types::TxHash, Indexer};
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+use serde_json::Value;
+use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+use crate::helpers::storage::{block, create, update};
+
+async fn status(base: &reqwest::Url, path: &str) -> StatusCode {
+    reqwest::get(base.join(path).unwrap())
+        .await
+        .unwrap()
+        .status()
+}
+
+fn field(items: &Value, name: &str) -> Vec<String> {
+    items
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item[name].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_confirmation_levels_work() {
+    let db = helpers::init_db("test", "confirmation_levels_work").await;
+    let client = db.client();
+
+    let rpc_mock = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            gen_block_resp(3, 30, 0), // latest
+            gen_block_resp(2, 20, 1), // safe
+            gen_block_resp(1, 10, 2), // finalized
+        ])))
+        .mount(&rpc_mock)
+        .await;
+
+    let base = helpers::init_golem_base_indexer_server(db, |mut x| {
+        x.external_services.l3_rpc_url = rpc_mock.uri();
+        x
+    })
+    .await;
+
+    let create_tx = TxHash::random();
+    let safe_update_tx = TxHash::random();
+    let latest_update_tx = TxHash::random();
+    let latest_create_tx = TxHash::random();
+    let key = entity_key(create_tx, b"v1".to_vec().into(), 0);
+    let latest_key = entity_key(latest_create_tx, b"new".to_vec().into(), 0);
+
+    helpers::sample::insert_data_multi(
+        &*client,
+        vec![
+            block(1, create_tx, create(b"v1")),
+            block(2, safe_update_tx, update(key, b"v2")),
+            block(3, latest_update_tx, update(key, b"v3")),
+            block(4, latest_create_tx, create(b"new")),
+        ],
+    )
+    .await
+    .unwrap();
+    Indexer::new(client.clone(), Default::default())
+        .tick()
+        .await
+        .unwrap();
+
+    // the heads are fetched for finality even before anything cached them
+    let history: Value =
+        test_server::send_get_request(&base, &format!("/api/v1/entity/{key}/history")).await;
+    assert_eq!(history["items"][0]["finality"], "finalized");
+
+    // entities
+    for (confirmation, data) in [("latest", "v3"), ("safe", "v2"), ("finalized", "v1")] {
+        let entity: Value = test_server::send_get_request(
+            &base,
+            &format!("/api/v1/entity/{key}?confirmation={confirmation}"),
+        )
+        .await;
+        assert_eq!(entity["data"], format!("0x{}", hex::encode(data)));
+    }
+    let entity: Value = test_server::send_get_request(
+        &base,
+        &format!("/api/v1/entity/{key}?confirmation=latest&at_block=1"),
+    )
+    .await;
+    assert_eq!(entity["data"], format!("0x{}", hex::encode("v1")));
+    assert_eq!(
+        status(&base, &format!("/api/v1/entity/{latest_key}")).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        status(
+            &base,
+            &format!("/api/v1/entity/{latest_key}?confirmation=latest")
+        )
+        .await,
+        StatusCode::NOT_FOUND
+    );
+
+    let entities: Value =
+        test_server::send_get_request(&base, "/api/v1/entities?status=ALL&confirmation=finalized")
+            .await;
+    assert_eq!(field(&entities["items"], "key"), vec![key.to_string()]);
+    for (query, count) in [
+        ("status=ALL", "2"),
+        ("status=ALL&confirmation=latest", "1"),
+        ("status=ALL&confirmation=latest&at_block=4", "1"),
+        ("status=ALL&at_block=4", "2"),
+    ] {
+        let entities: Value =
+            test_server::send_get_request(&base, &format!("/api/v1/entities/count?{query}")).await;
+        assert_eq!(entities["count"], count, "{query}");
+    }
+
+    // history
+    let history: Value =
+        test_server::send_get_request(&base, &format!("/api/v1/entity/{key}/history")).await;
+    assert_eq!(
+        field(&history["items"], "finality"),
+        vec!["finalized", "safe", "latest"]
+    );
+    let history: Value = test_server::send_get_request(
+        &base,
+        &format!("/api/v1/entity/{key}/history?confirmation=safe"),
+    )
+    .await;
+    assert_eq!(
+        field(&history["items"], "transaction_hash"),
+        vec![create_tx.to_string(), safe_update_tx.to_string()]
+    );
+
+    // operations
+    let operation: Value =
+        test_server::send_get_request(&base, &format!("/api/v1/operation/{safe_update_tx}/0"))
+            .await;
+    assert_eq!(operation["finality"], "safe");
+    assert_eq!(
+        status(
+            &base,
+            &format!("/api/v1/operation/{latest_update_tx}/0?confirmation=safe")
+        )
+        .await,
+        StatusCode::NOT_FOUND
+    );
+
+    let operations: Value =
+        test_server::send_get_request(&base, "/api/v1/operations?operation=ALL").await;
+    assert_eq!(
+        field(&operations["items"], "finality"),
+        vec!["finalized", "safe", "latest", "latest"]
+    );
+    let operations: Value = test_server::send_get_request(
+        &base,
+        "/api/v1/operations?operation=ALL&confirmation=finalized",
+    )
+    .await;
+    assert_eq!(
+        field(&operations["items"], "transaction_hash"),
+        vec![create_tx.to_string()]
+    );
+    for (confirmation, creates, updates) in [
+        ("latest", "1", "2"),
+        ("safe", "1", "1"),
+        ("finalized", "1", "0"),
+    ] {
+        let counts: Value = test_server::send_get_request(
+            &base,
+            &format!("/api/v1/operations/count?confirmation={confirmation}"),
+        )
+        .await;
+        assert_eq!(counts["create_count"], creates, "{confirmation}");
+        assert_eq!(counts["update_count"], updates, "{confirmation}");
+    }
+
+    assert_eq!(
+        status(&base, &format!("/api/v1/entity/{key}?confirmation=unsafe")).await,
+        StatusCode::BAD_REQUEST
+    );
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_confirmation_without_chain_heads() {
+    let db = helpers::init_db("test", "confirmation_without_chain_heads").await;
+    let client = db.client();
+
+    let rpc_mock = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&rpc_mock)
+        .await;
+
+    let base = helpers::init_golem_base_indexer_server(db, |mut x| {
+        x.external_services.l3_rpc_url = rpc_mock.uri();
+        x
+    })
+    .await;
+
+    assert_eq!(
+        status(&base, "/api/v1/operations?operation=ALL&confirmation=safe").await,
+        StatusCode::SERVICE_UNAVAILABLE
+    );
+
+    helpers::sample::insert_data_multi(&*client, vec![block(1, TxHash::random(), create(b"v1"))])
+        .await
+        .unwrap();
+    Indexer::new(client.clone(), Default::default())
+        .tick()
+        .await
+        .unwrap();
+
+    // finality is only left unset when the heads can't be fetched
+    let operations: Value =
+        test_server::send_get_request(&base, "/api/v1/operations?operation=ALL").await;
+    assert_eq!(operations["items"][0]["finality"], Value::Null);
+}
@@ -0,0 +1,149 @@
+use crate::helpers;
+
+use arkiv_storage_tx::{Create, StorageTransaction, Update};
+use blockscout_service_launcher::test_server;
+use golem_base_indexer_logic::{types::EntityKey, Indexer};
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+use serde_json::Value;
+
+use crate::helpers::{
+    pagination::collect_pages,
+    sample::{Block, Transaction},
+};
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_cursor_pagination_works() {
+    let db = helpers::init_db("test", "cursor_pagination_works").await;
+    let client = db.client();
+    let base = helpers::init_golem_base_indexer_server(db, |x| x).await;
+    let entity_key = EntityKey::random();
+
+    helpers::sample::insert_data(
+        &*client,
+        Block {
+            number: 1,
+            transactions: vec![Transaction {
+                operations: StorageTransaction {
+                    creates: vec![Create::default(); 5],
+                    ..Default::default()
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    for number in 2..=4 {
+        helpers::sample::insert_data(
+            &*client,
+            Block {
+                number,
+                transactions: vec![Transaction {
+                    operations: StorageTransaction {
+                        updates: vec![
+                            Update {
+                                entity_key,
+                                btl: 100,
+                                ..Default::default()
+                            };
+                            2
+                        ],
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    Indexer::new(client, Default::default())
+        .tick()
+        .await
+        .unwrap();
+
+    // entities, ordered by key
+    let path = "/api/v1/entities?status=ALL&page_size=2";
+    let first: Value = test_server::send_get_request(&base, path).await;
+    assert_eq!(first["pagination"]["total_items"], "6");
+    assert_eq!(first["next_page_params"]["page_size"], "2");
+    let keys: Vec<String> = collect_pages(&base, path, first)
+        .await
+        .iter()
+        .map(|item| item["key"].to_string())
+        .collect();
+    let mut sorted = keys.clone();
+    sorted.sort();
+    sorted.dedup();
+    assert_eq!(keys.len(), 6);
+    assert_eq!(keys, sorted);
+
+    // operations, ordered by position
+    let path = "/api/v1/operations?operation=ALL&page_size=4";
+    let first: Value = test_server::send_get_request(&base, path).await;
+    assert_eq!(first["pagination"]["total_items"], "11");
+    let all: Value = test_server::send_get_request(&base, "/api/v1/operations?operation=ALL").await;
+    assert!(all["next_page_params"].is_null());
+    let expected: Vec<String> = all["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["op_index"].to_string() + &item["transaction_hash"].to_string())
+        .collect();
+    let mut response = first;
+    let mut ops = Vec::new();
+    loop {
+        ops.extend(
+            response["items"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|item| item["op_index"].to_string() + &item["transaction_hash"].to_string()),
+        );
+        let Some(cursor) = response["next_page_params"]["cursor"].as_str() else {
+            break;
+        };
+        response = test_server::send_get_request(
+            &base,
+            &format!("{path}&cursor={cursor}&with_totals=true"),
+        )
+        .await;
+        assert_eq!(response["pagination"]["total_items"], "11");
+    }
+    assert_eq!(ops, expected);
+
+    // history, ordered by position
+    let path = format!("/api/v1/entity/{entity_key}/history?page_size=4");
+    let first: Value = test_server::send_get_request(&base, &path).await;
+    assert_eq!(first["pagination"]["total_items"], "6");
+    let blocks: Vec<String> = collect_pages(&base, &path, first)
+        .await
+        .iter()
+        .map(|item| item["block_number"].to_string())
+        .collect();
+    assert_eq!(
+        blocks,
+        ["\"2\"", "\"2\"", "\"3\"", "\"3\"", "\"4\"", "\"4\""]
+    );
+
+    // without totals
+    let response: Value = test_server::send_get_request(
+        &base,
+        "/api/v1/entities?status=ALL&page_size=5&with_totals=false",
+    )
+    .await;
+    assert!(response["pagination"].is_null());
+    assert_eq!(response["items"].as_array().unwrap().len(), 5);
+    assert!(response["next_page_params"]["cursor"].is_string());
+
+    // invalid cursor
+    let response = reqwest::get(base.join("/api/v1/operations?cursor=zz").unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,330 @@
+use crate::helpers;
+
+use arkiv_storage_tx::{Create, StorageTransaction};
+use blockscout_service_launcher::test_server;
+use golem_base_indexer_logic::{
+    arkiv::entity_key,
+    types::{BlockHash, TxHash},
+    Indexer, IndexerSettings, RetrySettings,
+};
+use pretty_assertions::assert_eq;
+use reqwest::{Method, StatusCode};
+use sea_orm::{ConnectionTrait, Statement};
+use serde_json::Value;
+use std::time::Duration;
+
+use crate::helpers::sample::{Block, Transaction};
+use crate::helpers::storage::{block, create};
+
+const EVENTS_TX_HASH: &str = "0xdf1c6dd5c0ca10d6b440dab586eadff97b4c98f184f10886bb52eb489ee3098d";
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_dead_letter_queue_works() {
+    let db = helpers::init_db("test", "dead_letter_queue_works").await;
+    let client = db.client();
+    let base = helpers::init_golem_base_indexer_server(db, helpers::admin::with_admin_token).await;
+
+    // quarantine is part of the admin API
+    let quarantine = base.join("/api/v1/admin/quarantine").unwrap();
+    let status = reqwest::get(quarantine.clone()).await.unwrap().status();
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let status = reqwest::Client::new()
+        .post(
+            base.join(&format!(
+                "/api/v1/admin/quarantine/transactions/{}/requeue",
+                TxHash::ZERO
+            ))
+            .unwrap(),
+        )
+        .json(&serde_json::json!({}))
+        .send()
+        .await
+        .unwrap()
+        .status();
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let indexer = Indexer::new(
+        client.clone(),
+        IndexerSettings {
+            retries: RetrySettings {
+                max_attempts: 2,
+                initial_backoff: Duration::ZERO,
+                max_backoff: Duration::ZERO,
+            },
+            ..Default::default()
+        },
+    );
+
+    let tx_hash = TxHash::repeat_byte(1);
+    let block_hash = BlockHash::repeat_byte(2);
+    let payload = b"poison".to_vec();
+    let key = entity_key(tx_hash, payload.clone().into(), 0);
+    helpers::sample::insert_data(
+        &*client,
+        Block {
+            number: 1,
+            hash: Some(block_hash),
+            transactions: vec![Transaction {
+                hash: Some(tx_hash),
+                operations: StorageTransaction {
+                    creates: vec![Create {
+                        btl: 100,
+                        payload: payload.into(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    // the tx points at a block the indexer can't find, so processing it keeps failing
+    let move_block = |from: BlockHash, to: BlockHash| {
+        let client = client.clone();
+        async move {
+            client
+                .execute(Statement::from_sql_and_values(
+                    client.get_database_backend(),
+                    "update blocks set hash = $2 where hash = $1",
+                    [from.as_slice().into(), to.as_slice().into()],
+                ))
+                .await
+                .unwrap();
+        }
+    };
+    move_block(block_hash, BlockHash::repeat_byte(3)).await;
+
+    indexer.tick().await.unwrap();
+    let response: Value = helpers::admin::get(&base, "/api/v1/admin/quarantine").await;
+    assert_eq!(response["items"], Value::Array(vec![]));
+
+    indexer.tick().await.unwrap();
+    let response: Value =
+        helpers::admin::get(&base, "/api/v1/admin/quarantine?queue=transactions").await;
+    let items = response["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["queue"], "transactions");
+    assert_eq!(items[0]["key"], tx_hash.to_string());
+    assert_eq!(items[0]["attempts"], "2");
+    assert!(items[0]["last_error"]
+        .as_str()
+        .unwrap()
+        .contains("disappeared"));
+
+    let response: Value =
+        helpers::admin::get(&base, "/api/v1/admin/quarantine?queue=reindexes").await;
+    assert_eq!(response["items"], Value::Array(vec![]));
+
+    let response: Value = helpers::admin::get(
+        &base,
+        &format!("/api/v1/admin/quarantine/transactions/{tx_hash}"),
+    )
+    .await;
+    assert_eq!(response["items"].as_array().unwrap().len(), 1);
+
+    // quarantined items are not retried anymore
+    indexer.tick().await.unwrap();
+    let response: Value = helpers::admin::get(
+        &base,
+        &format!("/api/v1/admin/quarantine/transactions/{tx_hash}"),
+    )
+    .await;
+    assert_eq!(response["items"][0]["attempts"], "2");
+
+    let status = helpers::admin::request(
+        Method::GET,
+        &base,
+        &format!("/api/v1/admin/quarantine/logs/{tx_hash}"),
+    )
+    .send()
+    .await
+    .unwrap()
+    .status();
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let status = helpers::admin::request(
+        Method::GET,
+        &base,
+        &format!("/api/v1/admin/quarantine/unknown/{tx_hash}"),
+    )
+    .send()
+    .await
+    .unwrap()
+    .status();
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // once the underlying problem is fixed, requeued items get processed
+    move_block(BlockHash::repeat_byte(3), block_hash).await;
+    let requeue = |queue: &'static str| {
+        let request = helpers::admin::request(
+            Method::POST,
+            &base,
+            &format!("/api/v1/admin/quarantine/{queue}/{tx_hash}/requeue"),
+        );
+        async move { request.json(&serde_json::json!({})).send().await.unwrap() }
+    };
+    let response = requeue("transactions").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response: Value = response.json().await.unwrap();
+    assert_eq!(response["requeued"], "1");
+    assert_eq!(
+        requeue("transactions").await.status(),
+        StatusCode::NOT_FOUND
+    );
+
+    indexer.tick().await.unwrap();
+    let response: Value = helpers::admin::get(&base, "/api/v1/admin/quarantine").await;
+    assert_eq!(response["items"], Value::Array(vec![]));
+    let entity: Value =
+        test_server::send_get_request(&base, &format!("/api/v1/entity/{key}")).await;
+    assert_eq!(entity["key"], key.to_string());
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_failing_log_event_does_not_fail_others() {
+    let db = helpers::init_db("test", "failing_log_event_does_not_fail_others").await;
+    let client = db.client();
+    let base = helpers::init_golem_base_indexer_server(db, helpers::admin::with_admin_token).await;
+    helpers::load_data(&*client, include_str!("../fixtures/sample_events.sql")).await;
+    let indexer = Indexer::new(
+        client.clone(),
+        IndexerSettings {
+            retries: RetrySettings {
+                max_attempts: 1,
+                initial_backoff: Duration::ZERO,
+                max_backoff: Duration::ZERO,
+            },
+            ..Default::default()
+        },
+    );
+
+    // storing the cost of the first create is a database error
+    client
+        .execute_unprepared(
+            "alter table golem_base_operations add constraint poisoned_cost check (cost <> 255)",
+        )
+        .await
+        .unwrap();
+    indexer.tick().await.unwrap();
+
+    let response: Value =
+        helpers::admin::get(&base, "/api/v1/admin/quarantine?queue=logs_events").await;
+    let items = response["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert!(items[0]["last_error"]
+        .as_str()
+        .unwrap()
+        .contains("poisoned_cost"));
+
+    let operation: Value =
+        test_server::send_get_request(&base, &format!("/api/v1/operation/{EVENTS_TX_HASH}/1"))
+            .await;
+    assert_eq!(operation["cost"], "1234567890");
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_failing_tx_cleanup_does_not_fail_others() {
+    let db = helpers::init_db("test", "failing_tx_cleanup_does_not_fail_others").await;
+    let client = db.client();
+    let base = helpers::init_golem_base_indexer_server(db, helpers::admin::with_admin_token).await;
+    let indexer = Indexer::new(
+        client.clone(),
+        IndexerSettings {
+            retries: RetrySettings {
+                max_attempts: 1,
+                initial_backoff: Duration::ZERO,
+                max_backoff: Duration::ZERO,
+            },
+            ..Default::default()
+        },
+    );
+
+    let poisoned_tx = TxHash::repeat_byte(1);
+    let other_tx = TxHash::repeat_byte(2);
+    helpers::sample::insert_data_multi(
+        &*client,
+        vec![
+            block(1, poisoned_tx, create(b"poisoned")),
+            block(2, other_tx, create(b"other")),
+        ],
+    )
+    .await
+    .unwrap();
+    indexer.tick().await.unwrap();
+    let poisoned_key = entity_key(poisoned_tx, b"poisoned".to_vec().into(), 0);
+    let other_key = entity_key(other_tx, b"other".to_vec().into(), 0);
+    let entity_status = |key| {
+        let url = base.join(&format!("/api/v1/entity/{key}")).unwrap();
+        async move { reqwest::get(url).await.unwrap().status() }
+    };
+    assert_eq!(entity_status(poisoned_key).await, StatusCode::OK);
+    assert_eq!(entity_status(other_key).await, StatusCode::OK);
+
+    // rolling back the operations of the first tx is a database error
+    client
+        .execute_unprepared(&format!(
+            r#"
+            create function poison_cleanup() returns trigger language plpgsql
+                as $$ begin raise exception 'poisoned_cleanup'; end $$;
+            create trigger poisoned_cleanup before delete on golem_base_operations
+                for each row when (old.transaction_hash = '\x{}')
+                execute function poison_cleanup();
+            "#,
+            hex::encode(poisoned_tx)
+        ))
+        .await
+        .unwrap();
+    // both txs drop out of their blocks
+    client
+        .execute(Statement::from_sql_and_values(
+            client.get_database_backend(),
+            r#"
+            update transactions set
+                block_hash = null, block_number = null, index = null,
+                cumulative_gas_used = null, gas_used = null, status = null
+            where hash in ($1, $2)
+            "#,
+            [poisoned_tx.as_slice().into(), other_tx.as_slice().into()],
+        ))
+        .await
+        .unwrap();
+
+    indexer.tick().await.unwrap();
+    assert_eq!(entity_status(other_key).await, StatusCode::NOT_FOUND);
+    assert_eq!(entity_status(poisoned_key).await, StatusCode::OK);
+    let response: Value =
+        helpers::admin::get(&base, "/api/v1/admin/quarantine?queue=transaction_cleanups").await;
+    let items = response["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["key"], poisoned_tx.to_string());
+    assert!(items[0]["last_error"]
+        .as_str()
+        .unwrap()
+        .contains("poisoned_cleanup"));
+
+    client
+        .execute_unprepared("drop trigger poisoned_cleanup on golem_base_operations")
+        .await
+        .unwrap();
+    let response = helpers::admin::request(
+        Method::POST,
+        &base,
+        &format!("/api/v1/admin/quarantine/transaction_cleanups/{poisoned_tx}/requeue"),
+    )
+    .json(&serde_json::json!({}))
+    .send()
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    indexer.tick().await.unwrap();
+    assert_eq!(entity_status(poisoned_key).await, StatusCode::NOT_FOUND);
+    let response: Value = helpers::admin::get(&base, "/api/v1/admin/quarantine").await;
+    assert_eq!(response["items"], Value::Array(vec![]));
+}
@@ -0,0 +1,274 @@
+use crate::helpers;
+
+use arkiv_storage_tx::{
+    ChangeOwner, Create, Extend, NumericAttribute, StorageTransaction, StringAttribute, Update,
+};
+use blockscout_service_launcher::test_server;
+use golem_base_indexer_logic::{
+    arkiv::entity_key,
+    types::{Address, TxHash},
+    Indexer,
+};
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+
+use crate::helpers::sample::{Block, Transaction};
+
+fn string_attribute(key: &str, value: &str) -> StringAttribute {
+    StringAttribute {
+        key: key.to_string(),
+        value: value.to_string(),
+    }
+}
+
+async fn list_keys(base: &reqwest::Url, query: &str) -> Vec<String> {
+    let response: Value =
+        test_server::send_get_request(base, &format!("/api/v1/entities?{query}")).await;
+    response["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["key"].as_str().unwrap().to_string())
+        .collect()
+}
+
+async fn count(base: &reqwest::Url, query: &str) -> String {
+    let response: Value =
+        test_server::send_get_request(base, &format!("/api/v1/entities/count?{query}")).await;
+    response["count"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_entities_at_block_work() {
+    let db = helpers::init_db("test", "entities_at_block_work").await;
+    let client = db.client();
+    let base = helpers::init_golem_base_indexer_server(db, |x| x).await;
+
+    let creator = Address::random();
+    let new_owner = Address::random();
+    let other_sender = Address::random();
+    let note_tx = TxHash::random();
+    let other_tx = TxHash::random();
+    let note_key = entity_key(note_tx, b"first".to_vec().into(), 0);
+    let other_key = entity_key(other_tx, b"other".to_vec().into(), 0);
+
+    helpers::sample::insert_data_multi(
+        &*client,
+        vec![
+            Block {
+                number: 1,
+                transactions: vec![
+                    Transaction {
+                        hash: Some(note_tx),
+                        sender: creator,
+                        operations: StorageTransaction {
+                            creates: vec![Create {
+                                btl: 100,
+                                payload: b"first".to_vec().into(),
+                                string_attributes: vec![string_attribute("type", "note")],
+                                numeric_attributes: vec![NumericAttribute {
+                                    key: "version".to_string(),
+                                    value: 1,
+                                }],
+                                ..Default::default()
+                            }],
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                    Transaction {
+                        hash: Some(other_tx),
+                        sender: other_sender,
+                        operations: StorageTransaction {
+                            creates: vec![Create {
+                                btl: 100,
+                                payload: b"other".to_vec().into(),
+                                string_attributes: vec![string_attribute("type", "note")],
+                                ..Default::default()
+                            }],
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            },
+            Block {
+                number: 2,
+                transactions: vec![Transaction {
+                    sender: creator,
+                    operations: StorageTransaction {
+                        updates: vec![Update {
+                            entity_key: note_key,
+                            btl: 100,
+                            payload: b"second".to_vec().into(),
+                            string_attributes: vec![string_attribute("type", "draft")],
+                            numeric_attributes: vec![NumericAttribute {
+                                key: "version".to_string(),
+                                value: 2,
+                            }],
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            Block {
+                number: 3,
+                transactions: vec![Transaction {
+                    sender: creator,
+                    operations: StorageTransaction {
+                        extensions: vec![Extend {
+                            entity_key: note_key,
+                            number_of_blocks: 50,
+                        }],
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            Block {
+                number: 4,
+                transactions: vec![Transaction {
+                    sender: other_sender,
+                    operations: StorageTransaction {
+                        deletes: vec![other_key],
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            Block {
+                number: 5,
+                transactions: vec![Transaction {
+                    sender: creator,
+                    operations: StorageTransaction {
+                        change_owners: vec![ChangeOwner {
+                            entity_key: note_key,
+                            new_owner,
+                        }],
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        ],
+    )
+    .await
+    .unwrap();
+
+    Indexer::new(client.clone(), Default::default())
+        .tick()
+        .await
+        .unwrap();
+
+    // get entity
+    let at_block = |number: u64| format!("/api/v1/entity/{note_key}?at_block={number}");
+    let entity: Value = test_server::send_get_request(&base, &at_block(1)).await;
+    assert_eq!(entity["data"], json!(format!("0x{}", hex::encode("first"))));
+    assert_eq!(entity["owner"], json!(creator.to_checksum(None)));
+    assert_eq!(entity["creator"], json!(creator.to_checksum(None)));
+    assert_eq!(entity["expires_at_block_number"], "101");
+    assert_eq!(entity["updated_at_block_number"], "1");
+    assert_eq!(entity["created_at_block_number"], "1");
+    assert_eq!(
+        entity["string_annotations"],
+        json!([{ "key": "type", "value": "note", "related_entities": "2" }])
+    );
+    assert_eq!(
+        entity["numeric_annotations"],
+        json!([{ "key": "version", "value": "1", "related_entities": "1" }])
+    );
+
+    let entity: Value = test_server::send_get_request(&base, &at_block(2)).await;
+    assert_eq!(
+        entity["data"],
+        json!(format!("0x{}", hex::encode("second")))
+    );
+    assert_eq!(entity["expires_at_block_number"], "102");
+    assert_eq!(
+        entity["string_annotations"],
+        json!([{ "key": "type", "value": "draft", "related_entities": "1" }])
+    );
+
+    // extends keep the attributes of the latest update
+    let entity: Value = test_server::send_get_request(&base, &at_block(3)).await;
+    assert_eq!(entity["expires_at_block_number"], "152");
+    assert_eq!(entity["updated_at_block_number"], "3");
+    assert_eq!(
+        entity["string_annotations"],
+        json!([{ "key": "type", "value": "draft", "related_entities": "1" }])
+    );
+
+    // the latest block matches the current state
+    let current: Value =
+        test_server::send_get_request(&base, &format!("/api/v1/entity/{note_key}")).await;
+    let latest: Value = test_server::send_get_request(&base, &at_block(5)).await;
+    assert_eq!(latest, current);
+    assert_eq!(latest["owner"], json!(new_owner.to_checksum(None)));
+
+    // not created yet
+    let response = reqwest::get(base.join(&at_block(0)).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // list entities
+    let note = format!("{note_key:#x}");
+    let other = format!("{other_key:#x}");
+    let mut both = vec![note.clone(), other.clone()];
+    both.sort();
+    assert_eq!(
+        list_keys(
+            &base,
+            "at_block=1&status=ACTIVE&string_annotation_key=type&string_annotation_value=note"
+        )
+        .await,
+        both
+    );
+    assert_eq!(
+        list_keys(
+            &base,
+            "at_block=2&status=ACTIVE&string_annotation_key=type&string_annotation_value=note"
+        )
+        .await,
+        vec![other.clone()]
+    );
+    assert_eq!(
+        list_keys(&base, "at_block=2&status=ACTIVE&query=version%3E1").await,
+        vec![note.clone()]
+    );
+    assert_eq!(
+        list_keys(&base, &format!("at_block=3&status=ACTIVE&owner={creator}")).await,
+        vec![note.clone()]
+    );
+    assert_eq!(list_keys(&base, "at_block=3&status=ACTIVE").await, both);
+    assert_eq!(
+        list_keys(&base, "at_block=4&status=ACTIVE").await,
+        vec![note.clone()]
+    );
+    assert_eq!(
+        list_keys(&base, "at_block=4&status=DELETED").await,
+        vec![other.clone()]
+    );
+    assert!(list_keys(&base, "at_block=0&status=ALL").await.is_empty());
+
+    let response: Value =
+        test_server::send_get_request(&base, "/api/v1/entities?at_block=4&status=ALL").await;
+    let items = response["items"].as_array().unwrap();
+    assert_eq!(response["pagination"]["total_items"], "2");
+    let other_item = items.iter().find(|item| item["key"] == other).unwrap();
+    assert_eq!(other_item["status"], "DELETED");
+    assert_eq!(other_item["data"], Value::Null);
+
+    assert_eq!(count(&base, "at_block=3&status=ACTIVE").await, "2");
+    assert_eq!(count(&base, "at_block=4&status=ACTIVE").await, "1");
+    assert_eq!(count(&base, "at_block=0&status=ALL").await, "0");
+}
@@ -0,0 +1,170 @@
+use crate::helpers;
+
+use arkiv_storage_tx::{Create, StorageTransaction, Update};
+use blockscout_service_launcher::test_server;
+use golem_base_indexer_logic::{
+    arkiv::entity_key,
+    types::{EntityKey, TxHash, MAX_DIFF_PAYLOAD_SIZE},
+    Indexer,
+};
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+
+use crate::helpers::sample::{Block, Transaction};
+
+struct Versions {
+    key: EntityKey,
+    create_tx: TxHash,
+    update_tx: TxHash,
+    blocks: Vec<Block>,
+}
+
+fn versions(number: u64, content_type: &str, first: &[u8], second: &[u8]) -> Versions {
+    let create_tx = TxHash::random();
+    let update_tx = TxHash::random();
+    let key = entity_key(create_tx, first.to_vec().into(), 0);
+    let blocks = vec![
+        Block {
+            number,
+            transactions: vec![Transaction {
+                hash: Some(create_tx),
+                operations: StorageTransaction {
+                    creates: vec![Create {
+                        btl: 100,
+                        content_type: content_type.to_string(),
+                        payload: first.to_vec().into(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        },
+        Block {
+            number: number + 1,
+            transactions: vec![Transaction {
+                hash: Some(update_tx),
+                operations: StorageTransaction {
+                    updates: vec![Update {
+                        entity_key: key,
+                        btl: 100,
+                        content_type: content_type.to_string(),
+                        payload: second.to_vec().into(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        },
+    ];
+    Versions {
+        key,
+        create_tx,
+        update_tx,
+        blocks,
+    }
+}
+
+fn diff_path(versions: &Versions) -> String {
+    format!(
+        "/api/v1/entity/{}/diff?from={}:0&to={}:0",
+        versions.key, versions.create_tx, versions.update_tx
+    )
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_entity_data_diff_works() {
+    let db = helpers::init_db("test", "entity_data_diff_works").await;
+    let client = db.client();
+    let base = helpers::init_golem_base_indexer_server(db, |x| x).await;
+
+    let json_entity = versions(
+        1,
+        "application/json",
+        br#"{"name":"note","tags":["a"]}"#,
+        br#"{"name":"draft","tags":["a",{"b":1}]}"#,
+    );
+    let text_entity = versions(3, "text/plain", b"one\ntwo\n", b"one\n2\n");
+    let binary_entity = versions(
+        5,
+        "application/octet-stream",
+        &[1, 2, 3, 4],
+        &[1, 9, 3, 4, 5],
+    );
+    let large = vec![0; MAX_DIFF_PAYLOAD_SIZE + 1];
+    let large_entity = versions(7, "application/octet-stream", &[1], &large);
+    let blocks = [&json_entity, &text_entity, &binary_entity, &large_entity]
+        .into_iter()
+        .flat_map(|v| v.blocks.clone())
+        .collect();
+    helpers::sample::insert_data_multi(&*client, blocks)
+        .await
+        .unwrap();
+
+    Indexer::new(client.clone(), Default::default())
+        .tick()
+        .await
+        .unwrap();
+
+    let diff: Value = test_server::send_get_request(&base, &diff_path(&json_entity)).await;
+    assert_eq!(diff["format"], "JSON_PATCH");
+    assert_eq!(diff["from_content_type"], "application/json");
+    assert_eq!(diff["from_size"], "28");
+    let mut patch = diff["json_patch"].as_array().unwrap().clone();
+    patch.sort_by_key(|op| op["path"].as_str().unwrap().to_string());
+    assert_eq!(
+        patch,
+        vec![
+            json!({ "op": "replace", "path": "/name", "from": null, "value": "\"draft\"" }),
+            json!({ "op": "add", "path": "/tags/1", "from": null, "value": "{\"b\":1}" }),
+        ]
+    );
+
+    let diff: Value = test_server::send_get_request(&base, &diff_path(&text_entity)).await;
+    assert_eq!(diff["format"], "UNIFIED");
+    assert_eq!(
+        diff["unified_diff"],
+        "--- from\n+++ to\n@@ -1,2 +1,2 @@\n one\n-two\n+2\n"
+    );
+
+    let diff: Value = test_server::send_get_request(&base, &diff_path(&binary_entity)).await;
+    assert_eq!(diff["format"], "BYTE_RANGES");
+    assert_eq!(diff["to_size"], "5");
+    assert_eq!(
+        diff["byte_ranges"],
+        json!([
+            { "from_offset": "1", "from_length": "1", "to_offset": "1", "to_length": "1" },
+            { "from_offset": "4", "from_length": "0", "to_offset": "4", "to_length": "1" },
+        ])
+    );
+
+    let response = reqwest::get(base.join(&diff_path(&large_entity)).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let response: Value = response.json().await.unwrap();
+    assert_eq!(
+        response["message"],
+        "payload of 1048577 bytes is larger than the 1048576 bytes that can be diffed"
+    );
+
+    // operations of another entity
+    let path = format!(
+        "/api/v1/entity/{}/diff?from={}:0&to={}:0",
+        json_entity.key, json_entity.create_tx, text_entity.update_tx
+    );
+    let response = reqwest::get(base.join(&path).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let path = format!(
+        "/api/v1/entity/{}/diff?from={}&to={}:0",
+        json_entity.key, json_entity.create_tx, json_entity.update_tx
+    );
+    let response = reqwest::get(base.join(&path).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,261 @@
+use crate::helpers;
+
+use arkiv_storage_tx::{Create, StorageTransaction, Update};
+use blockscout_service_launcher::test_server;
+use chrono::{DateTime, DurationRound, TimeDelta, Utc};
+use golem_base_indexer_logic::{
+    arkiv::entity_key,
+    types::{Address, TxHash},
+    Indexer,
+};
+use pretty_assertions::assert_eq;
+use reqwest::{StatusCode, Url};
+use serde_json::Value;
+
+use crate::helpers::{
+    pagination::list_all,
+    sample::{Block, Transaction},
+};
+
+fn format_minute(dt: DateTime<Utc>) -> String {
+    dt.format("%Y-%m-%d%%20%H:%M").to_string()
+}
+
+fn create(payload: &[u8], content_type: &str, btl: u64) -> Create {
+    Create {
+        btl,
+        content_type: content_type.to_string(),
+        payload: payload.to_vec().into(),
+        ..Default::default()
+    }
+}
+
+/// Keys of all listed entities, following `next_page_params` until exhausted.
+async fn list(base: &Url, query: &str) -> Vec<String> {
+    list_all(base, &format!("/api/v1/entities?{query}"))
+        .await
+        .iter()
+        .map(|item| item["key"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_entity_sorting_and_ranges_work() {
+    let db = helpers::init_db("test", "entity_sorting_and_ranges_work").await;
+    let client = db.client();
+    let base = helpers::init_golem_base_indexer_server(db, |x| x).await;
+
+    let alice = Address::repeat_byte(0xaa);
+    let bob = Address::repeat_byte(0xbb);
+    let alice_tx = TxHash::repeat_byte(1);
+    let bob_tx = TxHash::repeat_byte(2);
+    let start = (Utc::now() - TimeDelta::hours(3))
+        .duration_trunc(TimeDelta::hours(1))
+        .unwrap();
+    let note = b"aaaaaaaaaa";
+    let doc = b"bbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+    let picture = b"cccccccccccccccccccc";
+    helpers::sample::insert_data_multi(
+        &*client,
+        vec![
+            Block {
+                number: 1,
+                timestamp: Some(start),
+                transactions: vec![Transaction {
+                    hash: Some(alice_tx),
+                    sender: alice,
+                    operations: StorageTransaction {
+                        creates: vec![
+                            create(note, "text/plain", 100),
+                            create(doc, "application/json", 50),
+                        ],
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            Block {
+                number: 2,
+                timestamp: Some(start + TimeDelta::hours(1)),
+                transactions: vec![Transaction {
+                    hash: Some(bob_tx),
+                    sender: bob,
+                    operations: StorageTransaction {
+                        creates: vec![create(picture, "text/plain", 200)],
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            Block {
+                number: 3,
+                timestamp: Some(start + TimeDelta::hours(2)),
+                transactions: vec![Transaction {
+                    hash: Some(TxHash::repeat_byte(3)),
+                    sender: alice,
+                    operations: StorageTransaction {
+                        updates: vec![Update {
+                            entity_key: entity_key(alice_tx, note.to_vec().into(), 0),
+                            btl: 300,
+                            content_type: "text/plain".to_string(),
+                            payload: b"aaaaa".to_vec().into(),
+                            ..Default::default()
+                        }],
+                        deletes: vec![entity_key(alice_tx, doc.to_vec().into(), 1)],
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        ],
+    )
+    .await
+    .unwrap();
+    Indexer::new(client.clone(), Default::default())
+        .tick()
+        .await
+        .unwrap();
+
+    let note = entity_key(alice_tx, note.to_vec().into(), 0).to_string();
+    let doc = entity_key(alice_tx, doc.to_vec().into(), 1).to_string();
+    let picture = entity_key(bob_tx, picture.to_vec().into(), 0).to_string();
+
+    // deleted entities expire at the block they were deleted in
+    assert_eq!(
+        list(&base, "status=ACTIVE&sort_by=expires_at_block").await,
+        [picture.as_str(), note.as_str()]
+    );
+    assert_eq!(
+        list(&base, "status=ALL&sort_by=expires_at_block&page_size=1").await,
+        [doc.as_str(), picture.as_str(), note.as_str()]
+    );
+
+    // the deleted entity has no data, so it sorts as the largest one
+    assert_eq!(
+        list(&base, "status=ALL&sort_by=data_size&page_size=1").await,
+        [note.as_str(), picture.as_str(), doc.as_str()]
+    );
+    assert_eq!(
+        list(
+            &base,
+            "status=ALL&sort_by=data_size&sort_direction=desc&page_size=1"
+        )
+        .await,
+        [doc.as_str(), picture.as_str(), note.as_str()]
+    );
+    assert_eq!(
+        list(
+            &base,
+            "status=ACTIVE&sort_by=created_block&sort_direction=desc&page_size=1"
+        )
+        .await,
+        [picture.as_str(), note.as_str()]
+    );
+
+    // ties are broken by the key, in the same direction
+    let mut updated_last = [note.as_str(), doc.as_str()];
+    updated_last.sort();
+    updated_last.reverse();
+    assert_eq!(
+        list(
+            &base,
+            "status=ALL&sort_by=updated_block&sort_direction=desc&page_size=1"
+        )
+        .await,
+        [updated_last[0], updated_last[1], picture.as_str()]
+    );
+
+    let response: Value =
+        test_server::send_get_request(&base, "/api/v1/entities?status=ALL&sort_by=cost").await;
+    let costs: Vec<u64> = response["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["cost"].as_str().unwrap().parse().unwrap())
+        .collect();
+    assert_eq!(costs.len(), 3);
+    assert!(costs.is_sorted());
+
+    assert_eq!(
+        list(
+            &base,
+            "status=ACTIVE&content_type=text/plain&sort_by=created_block"
+        )
+        .await,
+        [note.as_str(), picture.as_str()]
+    );
+    assert_eq!(
+        list(&base, &format!("status=ACTIVE&creator={bob}")).await,
+        [picture.as_str()]
+    );
+    assert_eq!(
+        list(&base, "status=ACTIVE&min_expires_at_block_number=250").await,
+        [note.as_str()]
+    );
+    assert_eq!(
+        list(&base, "status=ALL&max_expires_at_block_number=250").await,
+        [doc.as_str(), picture.as_str()]
+    );
+    assert_eq!(
+        list(&base, "status=ACTIVE&min_data_size=6&max_data_size=20").await,
+        [picture.as_str()]
+    );
+    assert_eq!(
+        list(&base, "status=ACTIVE&min_created_at_block_number=2").await,
+        [picture.as_str()]
+    );
+    assert_eq!(
+        list(
+            &base,
+            &format!(
+                "status=ALL&sort_by=created_block&max_created_at_block_number=1&created_from={}",
+                format_minute(start)
+            )
+        )
+        .await
+        .len(),
+        2
+    );
+    assert_eq!(
+        list(
+            &base,
+            &format!(
+                "status=ACTIVE&created_from={}&created_to={}",
+                format_minute(start + TimeDelta::hours(1)),
+                format_minute(start + TimeDelta::hours(2))
+            )
+        )
+        .await,
+        [picture.as_str()]
+    );
+
+    let response: Value = test_server::send_get_request(
+        &base,
+        &format!(
+            "/api/v1/entities/count?status=ALL&content_type=text/plain&created_to={}",
+            format_minute(start + TimeDelta::hours(1))
+        ),
+    )
+    .await;
+    assert_eq!(response["count"], "1");
+
+    for query in [
+        "sort_by=owner",
+        "sort_direction=up",
+        "created_from=yesterday",
+        "creator=alice",
+    ] {
+        let status = reqwest::get(
+            base.join(&format!("/api/v1/entities?status=ACTIVE&{query}"))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .status();
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{query}");
+    }
+}
@@ -0,0 +1,79 @@
+use crate::helpers;
+
+use blockscout_service_launcher::test_server;
+use golem_base_indexer_logic::Indexer;
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+
+const MISSING_KEY: &str = "0x0000000000000000000000000000000000000000000000000000000000000001";
+
+async fn post_batch(base: &reqwest::Url, body: Value) -> reqwest::Response {
+    reqwest::Client::new()
+        .post(base.join("/api/v1/entities/batch").unwrap())
+        .json(&body)
+        .send()
+        .await
+        .unwrap()
+}
+
+/// Annotation order isn't part of the contract, so it is normalized before comparing.
+fn sort_annotations(mut entity: Value) -> Value {
+    for field in ["string_annotations", "numeric_annotations"] {
+        if let Some(annotations) = entity[field].as_array_mut() {
+            annotations.sort_by_key(|v| v.to_string());
+        }
+    }
+    entity
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_get_entities_batch_works() {
+    let db = helpers::init_db("test", "get_entities_batch_works").await;
+    let client = db.client();
+    let base = helpers::init_golem_base_indexer_server(db, |x| x).await;
+    helpers::load_data(&*client, include_str!("../fixtures/sample_data.sql")).await;
+
+    Indexer::new(client, Default::default())
+        .tick()
+        .await
+        .unwrap();
+
+    let entities: Value = test_server::send_get_request(&base, "/api/v1/entities?status=ALL").await;
+    let mut keys: Vec<String> = entities["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v["key"].as_str().unwrap().to_string())
+        .collect();
+    assert!(keys.len() > 1);
+    keys.reverse();
+
+    // every entity matches the single lookup, in request order
+    let mut requested = keys.clone();
+    requested.insert(1, MISSING_KEY.to_string());
+    requested.push(keys[0].clone());
+    let response = post_batch(&base, json!({ "keys": requested })).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response: Value = response.json().await.unwrap();
+    let items = response["items"].as_array().unwrap();
+    assert_eq!(items.len(), keys.len());
+    for (key, item) in keys.iter().zip(items) {
+        let expected: Value =
+            test_server::send_get_request(&base, &format!("/api/v1/entity/{key}")).await;
+        assert_eq!(sort_annotations(item.clone()), sort_annotations(expected));
+    }
+
+    let response = post_batch(&base, json!({ "keys": [] })).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response: Value = response.json().await.unwrap();
+    assert_eq!(response, json!({ "items": [] }));
+
+    let response = post_batch(&base, json!({ "keys": ["0x1234"] })).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let too_many = vec![MISSING_KEY; 101];
+    let response = post_batch(&base, json!({ "keys": too_many })).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,80 @@
+use crate::helpers;
+
+use golem_base_indexer_logic::Indexer;
+use golem_base_indexer_proto::blockscout::golem_base_indexer::v1::{
+    entity_status_filter::EntityStatusFilter,
+    golem_base_indexer_service_client::GolemBaseIndexerServiceClient, ChartEntityCountRequest,
+    ChartResolution, GetEntityHistoryRequest, GetEntityRequest, ListEntitiesRequest,
+};
+use helpers::utils::refresh_timeseries;
+use pretty_assertions::assert_eq;
+use std::sync::Arc;
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_grpc_endpoints_work() {
+    let db = helpers::init_db("test", "grpc_endpoints_work").await;
+    let client = db.client();
+    let (_, grpc) = helpers::init_golem_base_indexer_server_with_grpc(db, |x| x).await;
+    helpers::load_data(&*client, include_str!("../fixtures/sample_data.sql")).await;
+
+    Indexer::new(client.clone(), Default::default())
+        .tick()
+        .await
+        .unwrap();
+    refresh_timeseries(Arc::clone(&client)).await.unwrap();
+
+    let mut grpc_client = GolemBaseIndexerServiceClient::connect(grpc.to_string())
+        .await
+        .unwrap();
+
+    let entities = grpc_client
+        .list_entities(ListEntitiesRequest {
+            status: EntityStatusFilter::All.into(),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(entities.items.len(), 6);
+    assert_eq!(entities.pagination.unwrap().total_items, 6);
+
+    let key = entities.items[0].key.clone();
+    let history = grpc_client
+        .get_entity_history(GetEntityHistoryRequest {
+            key: key.clone(),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(!history.items.is_empty());
+    assert!(history.items.iter().all(|entry| entry.entity_key == key));
+
+    let chart = grpc_client
+        .chart_entity_count(ChartEntityCountRequest {
+            from: Some("2025-07-22 11:00".to_string()),
+            to: Some("2025-07-22 12:00".to_string()),
+            resolution: ChartResolution::Hour.into(),
+            address: None,
+            group_by: None,
+            annotation_key: None,
+            series_limit: None,
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(chart.info.unwrap().id, "golemBaseEntityCount");
+    assert_eq!(chart.chart.len(), 1);
+    assert_eq!(chart.chart[0].value, "4");
+
+    let status = grpc_client
+        .get_entity(GetEntityRequest {
+            key: "invalid".to_string(),
+            at_block: None,
+            confirmation: None,
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+}
@@ -0,0 +1,198 @@
+use crate::helpers;
+
+use arkiv_storage_tx::{
+    Create, Extend, NumericAttribute, StorageTransaction, StringAttribute, Update,
+};
+use blockscout_service_launcher::test_server;
+use golem_base_indexer_logic::{arkiv::entity_key, types::TxHash, Indexer};
+use pretty_assertions::assert_eq;
+use serde_json::{json, Value};
+
+use crate::helpers::sample::{Block, Transaction};
+
+fn string_attribute(key: &str, value: &str) -> StringAttribute {
+    StringAttribute {
+        key: key.to_string(),
+        value: value.to_string(),
+    }
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_history_annotations_work() {
+    let db = helpers::init_db("test", "history_annotations_work").await;
+    let client = db.client();
+    let base = helpers::init_golem_base_indexer_server(db, |x| x).await;
+
+    let create_tx = TxHash::random();
+    let update_tx = TxHash::random();
+    let key = entity_key(create_tx, b"data".to_vec().into(), 0);
+
+    helpers::sample::insert_data_multi(
+        &*client,
+        vec![
+            Block {
+                number: 1,
+                transactions: vec![Transaction {
+                    hash: Some(create_tx),
+                    operations: StorageTransaction {
+                        creates: vec![Create {
+                            btl: 100,
+                            payload: b"data".to_vec().into(),
+                            string_attributes: vec![
+                                string_attribute("type", "note"),
+                                string_attribute("lang", "en"),
+                            ],
+                            numeric_attributes: vec![NumericAttribute {
+                                key: "version".to_string(),
+                                value: 1,
+                            }],
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            Block {
+                number: 2,
+                transactions: vec![Transaction {
+                    hash: Some(update_tx),
+                    operations: StorageTransaction {
+                        updates: vec![Update {
+                            entity_key: key,
+                            btl: 100,
+                            string_attributes: vec![
+                                string_attribute("type", "draft"),
+                                string_attribute("tag", "x"),
+                            ],
+                            numeric_attributes: vec![NumericAttribute {
+                                key: "version".to_string(),
+                                value: 1,
+                            }],
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            Block {
+                number: 3,
+                transactions: vec![Transaction {
+                    operations: StorageTransaction {
+                        extensions: vec![Extend {
+                            entity_key: key,
+                            number_of_blocks: 10,
+                        }],
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            Block {
+                number: 4,
+                transactions: vec![Transaction {
+                    operations: StorageTransaction {
+                        deletes: vec![key],
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        ],
+    )
+    .await
+    .unwrap();
+
+    Indexer::new(client.clone(), Default::default())
+        .tick()
+        .await
+        .unwrap();
+
+    let history: Value =
+        test_server::send_get_request(&base, &format!("/api/v1/entity/{key}/history")).await;
+    let items = history["items"].as_array().unwrap();
+    assert_eq!(items.len(), 4);
+
+    // create
+    assert_eq!(
+        items[0]["string_annotations"],
+        json!([
+            { "key": "type", "value": "note" },
+            { "key": "lang", "value": "en" },
+        ])
+    );
+    assert_eq!(
+        items[0]["annotations_diff"]["added_string_annotations"],
+        items[0]["string_annotations"]
+    );
+    assert_eq!(
+        items[0]["annotations_diff"]["added_numeric_annotations"],
+        json!([{ "key": "version", "value": "1" }])
+    );
+
+    // update
+    let update_diff = json!({
+        "added_string_annotations": [{ "key": "tag", "value": "x" }],
+        "removed_string_annotations": [{ "key": "lang", "value": "en" }],
+        "changed_string_annotations": [
+            { "key": "type", "old_value": "note", "new_value": "draft" },
+        ],
+        "added_numeric_annotations": [],
+        "removed_numeric_annotations": [],
+        "changed_numeric_annotations": [],
+    });
+    assert_eq!(items[1]["annotations_diff"], update_diff);
+    assert_eq!(
+        items[1]["numeric_annotations"],
+        json!([{ "key": "version", "value": "1" }])
+    );
+
+    // extend keeps the annotations
+    assert_eq!(
+        items[2]["string_annotations"],
+        items[1]["string_annotations"]
+    );
+    assert_eq!(
+        items[2]["annotations_diff"]["added_string_annotations"],
+        json!([])
+    );
+    assert_eq!(
+        items[2]["annotations_diff"]["removed_string_annotations"],
+        json!([])
+    );
+
+    // delete removes them
+    assert_eq!(items[3]["string_annotations"], json!([]));
+    assert_eq!(
+        items[3]["annotations_diff"]["removed_string_annotations"],
+        items[2]["string_annotations"]
+    );
+    assert_eq!(
+        items[3]["annotations_diff"]["removed_numeric_annotations"],
+        json!([{ "key": "version", "value": "1" }])
+    );
+
+    // diffs don't depend on the page
+    let page: Value = test_server::send_get_request(
+        &base,
+        &format!("/api/v1/entity/{key}/history?page=2&page_size=1"),
+    )
+    .await;
+    assert_eq!(page["items"][0], items[1]);
+    let page: Value = test_server::send_get_request(
+        &base,
+        &format!("/api/v1/entity/{key}/history?page=4&page_size=1"),
+    )
+    .await;
+    assert_eq!(page["items"][0], items[3]);
+
+    let operation: Value =
+        test_server::send_get_request(&base, &format!("/api/v1/operation/{update_tx}/0")).await;
+    assert_eq!(operation, items[1]);
+}
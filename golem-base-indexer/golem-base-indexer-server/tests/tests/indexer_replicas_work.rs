@@ -0,0 +1,206 @@
+use crate::helpers;
+
+use arkiv_storage_tx::{
+    ChangeOwner, Create, Extend, NumericAttribute, StorageTransaction, StringAttribute, Update,
+};
+use golem_base_indexer_logic::{
+    arkiv::entity_key,
+    leader::{LeaderRole, Leadership},
+    repository,
+    types::{Address, BlockHash, EntityKey, TxHash},
+    Indexer, IndexerSettings,
+};
+use pretty_assertions::assert_eq;
+use sea_orm::{ConnectionTrait, DatabaseConnection, Statement};
+use serde_json::Value;
+use std::sync::Arc;
+
+use crate::helpers::sample::{Block, Transaction};
+
+const BLOCKS: u8 = 30;
+const TXS_PER_BLOCK: u8 = 5;
+
+fn tx_hash(block: u8, tx: u8) -> TxHash {
+    let mut hash = [0u8; 32];
+    hash[0] = block;
+    hash[1] = tx;
+    hash.into()
+}
+
+fn payload(block: u8, tx: u8, op: u8) -> Vec<u8> {
+    format!("{block}-{tx}-{op}").into_bytes()
+}
+
+fn created_key(block: u8, tx: u8, op: u8) -> EntityKey {
+    entity_key(tx_hash(block, tx), payload(block, tx, op).into(), op.into())
+}
+
+/// Every tx creates two entities, then updates, extends, deletes and transfers
+/// entities created in the preceding blocks.
+fn blocks() -> Vec<Block> {
+    (1..=BLOCKS)
+        .map(|block| Block {
+            number: block.into(),
+            hash: Some(BlockHash::repeat_byte(block)),
+            transactions: (0..TXS_PER_BLOCK)
+                .map(|tx| {
+                    let mut operations = StorageTransaction {
+                        creates: (0..2)
+                            .map(|op| Create {
+                                btl: 100,
+                                payload: payload(block, tx, op).into(),
+                                string_attributes: vec![StringAttribute {
+                                    key: "block".to_string(),
+                                    value: block.to_string(),
+                                }],
+                                numeric_attributes: vec![NumericAttribute {
+                                    key: "tx".to_string(),
+                                    value: tx.into(),
+                                }],
+                                ..Default::default()
+                            })
+                            .collect(),
+                        ..Default::default()
+                    };
+                    if block > 1 {
+                        operations.updates.push(Update {
+                            entity_key: created_key(block - 1, tx, 0),
+                            btl: 50,
+                            payload: b"updated".to_vec().into(),
+                            ..Default::default()
+                        });
+                        operations.extensions.push(Extend {
+                            entity_key: created_key(block - 1, tx, 1),
+                            number_of_blocks: 10,
+                        });
+                    }
+                    if block > 2 && tx == 0 {
+                        operations.deletes.push(created_key(block - 2, 1, 0));
+                    }
+                    if block > 1 && tx == 2 {
+                        operations.change_owners.push(ChangeOwner {
+                            entity_key: created_key(block - 1, 2, 1),
+                            new_owner: Address::repeat_byte(0xcc),
+                        });
+                    }
+                    Transaction {
+                        hash: Some(tx_hash(block, tx)),
+                        sender: Address::repeat_byte(tx + 1),
+                        operations,
+                        ..Default::default()
+                    }
+                })
+                .collect(),
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn replica(db: Arc<DatabaseConnection>) -> Indexer {
+    Indexer::new(
+        db,
+        IndexerSettings {
+            concurrency: 4,
+            ..Default::default()
+        },
+    )
+}
+
+async fn is_drained(db: &DatabaseConnection) -> bool {
+    let depths = repository::queues::queue_depths(db).await.unwrap();
+    depths.pending_transactions == 0
+        && depths.pending_transaction_cleanups == 0
+        && depths.pending_logs == 0
+        && depths.pending_logs_events == 0
+        && depths.entities_to_reindex == 0
+}
+
+/// Indexed state, without columns depending on the order rows got written in.
+async fn dump(db: &DatabaseConnection) -> Vec<(&'static str, Vec<Value>)> {
+    let mut dump = vec![];
+    for (table, order) in [
+        ("golem_base_entities", "key"),
+        ("golem_base_operations", "transaction_hash, index"),
+        ("golem_base_entity_history", "transaction_hash, op_index"),
+        (
+            "golem_base_string_annotations",
+            "operation_tx_hash, operation_index, key",
+        ),
+        (
+            "golem_base_numeric_annotations",
+            "operation_tx_hash, operation_index, key",
+        ),
+        ("golem_base_block_stats", "block_number"),
+    ] {
+        let rows = db
+            .query_all(Statement::from_string(
+                db.get_database_backend(),
+                format!(
+                    "select (to_jsonb(t) - 'id' - 'inserted_at' - 'updated_at')::text as row from {table} t order by {order}"
+                ),
+            ))
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|row| serde_json::from_str(&row.try_get::<String>("", "row").unwrap()).unwrap())
+            .collect();
+        dump.push((table, rows));
+    }
+    dump
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_indexer_replicas_work() {
+    let single = helpers::init_db("test", "indexer_replicas_work_single").await;
+    let single = single.client();
+    helpers::sample::insert_data_multi(&*single, blocks())
+        .await
+        .unwrap();
+    let indexer = replica(single.clone());
+    while !is_drained(&single).await {
+        indexer.tick().await.unwrap();
+    }
+
+    let replicated = helpers::init_db("test", "indexer_replicas_work_replicated").await;
+    let replicated = replicated.client();
+    helpers::sample::insert_data_multi(&*replicated, blocks())
+        .await
+        .unwrap();
+    let first = replica(replicated.clone());
+    let second = replica(replicated.clone());
+    let mut ticks = 0;
+    while !is_drained(&replicated).await {
+        ticks += 1;
+        assert!(ticks < 20, "replicas did not drain the queues");
+        tokio::try_join!(first.tick(), second.tick()).unwrap();
+    }
+
+    let expected = dump(&single).await;
+    assert!(expected.iter().all(|(_, rows)| !rows.is_empty()));
+    assert_eq!(dump(&replicated).await, expected);
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_single_leader_is_elected() {
+    let db = helpers::init_db("test", "single_leader_is_elected").await;
+    let client = db.client();
+
+    let first = Leadership::new(client.clone(), LeaderRole::Chain);
+    let second = Leadership::new(client.clone(), LeaderRole::Chain);
+    let other_role = Leadership::new(client.clone(), LeaderRole::MaterializedViews);
+    assert!(first.is_leader().await);
+    assert!(!second.is_leader().await);
+    assert!(first.is_leader().await);
+    assert!(other_role.is_leader().await);
+
+    // leadership is handed over once the leader goes away
+    drop(first);
+    let mut attempts = 0;
+    while !second.is_leader().await {
+        attempts += 1;
+        assert!(attempts < 50, "leadership was not handed over");
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+}
@@ -0,0 +1,112 @@
+use crate::helpers;
+
+use blockscout_service_launcher::test_server;
+use golem_base_indexer_logic::{mat_view_scheduler::MatViewScheduler, types::TxHash, Indexer};
+use pretty_assertions::assert_eq;
+use sea_orm::{ConnectionTrait, Statement};
+use serde_json::{json, Value};
+
+use crate::helpers::storage::{block, create};
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_indexer_status_works() {
+    let db = helpers::init_db("test", "indexer_status_works").await;
+    let client = db.client();
+    let base = helpers::init_golem_base_indexer_server(db, |mut settings| {
+        settings.readiness.max_block_lag = 1;
+        settings
+    })
+    .await;
+    let readiness = || test_server::send_get_request(&base, "/health?service=readiness");
+
+    helpers::sample::insert_data_multi(
+        &*client,
+        (1..=3u8)
+            .map(|n| block(n.into(), TxHash::repeat_byte(n), create(&[n])))
+            .collect(),
+    )
+    .await
+    .unwrap();
+
+    let status: Value = test_server::send_get_request(&base, "/api/v1/indexer/status").await;
+    assert_eq!(status["ready"], false);
+    assert_eq!(status["latest_block_number"], "3");
+    assert_eq!(status["latest_processed_block_number"], "0");
+    assert_eq!(status["block_lag"], "3");
+    assert_eq!(status["pending_transactions"], "3");
+    assert_eq!(status["pending_transaction_cleanups"], "0");
+    assert!(status["oldest_queued_item_timestamp"].is_string());
+    assert!(status["last_tick_timestamp"].is_null());
+    assert_eq!(status["paused"], false);
+    let problems = status["problems"].as_array().unwrap();
+    assert!(problems.contains(&json!("indexer is 3 blocks behind")));
+    assert!(problems.contains(&json!("indexer has not completed a tick yet")));
+
+    // liveness doesn't depend on indexing progress
+    let health: Value = test_server::send_get_request(&base, "/health").await;
+    assert_eq!(health, json!({"status": "SERVING"}));
+    let health: Value = readiness().await;
+    assert_eq!(health, json!({"status": "NOT_SERVING"}));
+
+    let indexer = Indexer::new(client.clone(), Default::default());
+    indexer.tick().await.unwrap();
+    let status: Value = test_server::send_get_request(&base, "/api/v1/indexer/status").await;
+    assert_eq!(status["latest_processed_block_number"], "3");
+    assert_eq!(status["block_lag"], "0");
+    assert_eq!(status["pending_transactions"], "0");
+    assert!(status["oldest_queued_item_timestamp"].is_null());
+    let tick_age: u64 = status["last_tick_age_seconds"]
+        .as_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(tick_age < 5);
+    let views = status["materialized_views"].as_array().unwrap();
+    assert!(!views.is_empty());
+    assert!(views.iter().all(|v| v["refreshed_at_timestamp"].is_null()));
+
+    let scheduler = MatViewScheduler::new(client.clone());
+    for view in scheduler.get_mat_view_settings() {
+        scheduler.refresh_named_view(&view.name).await;
+    }
+    let status: Value = test_server::send_get_request(&base, "/api/v1/indexer/status").await;
+    assert_eq!(status["problems"], json!([]));
+    assert_eq!(status["ready"], true);
+    assert!(status["materialized_views"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|v| v["staleness_seconds"]
+            .as_str()
+            .unwrap()
+            .parse::<u64>()
+            .unwrap()
+            < 5));
+    let health: Value = readiness().await;
+    assert_eq!(health, json!({"status": "SERVING"}));
+
+    // an indexer that stopped ticking is not ready anymore
+    client
+        .execute(Statement::from_string(
+            client.get_database_backend(),
+            "update golem_base_indexer_control set last_tick_at = now() - interval '1 hour'",
+        ))
+        .await
+        .unwrap();
+    let status: Value = test_server::send_get_request(&base, "/api/v1/indexer/status").await;
+    assert_eq!(status["last_tick_age_seconds"], "3600");
+    assert_eq!(
+        status["problems"],
+        json!(["last indexer tick completed 3600s ago"])
+    );
+    let health: Value = readiness().await;
+    assert_eq!(health, json!({"status": "NOT_SERVING"}));
+
+    // liveness is still reported for any other service name
+    for service in ["", "golem-base-indexer", "unknown"] {
+        let health: Value =
+            test_server::send_get_request(&base, &format!("/health?service={service}")).await;
+        assert_eq!(health, json!({"status": "SERVING"}), "service: {service}");
+    }
+}
@@ -5,9 +5,8 @@ use golem_base_indexer_logic::{
     types::{Address, CurrencyAmount},
     Indexer,
 };
-use helpers::{sample::insert_gas_transactions, utils::refresh_leaderboards};
+use helpers::sample::insert_gas_transactions;
 use pretty_assertions::assert_eq;
-use std::sync::Arc;
 
 use crate::helpers::assert_json::{assert_fields, assert_fields_array, assert_has_keys};
 
@@ -31,7 +30,6 @@ async fn test_list_biggest_spenders_endpoint() {
         .await
         .unwrap();
     indexer.tick().await.unwrap();
-    refresh_leaderboards(Arc::clone(&client)).await.unwrap();
 
     let response: serde_json::Value = test_server::send_get_request(
         &base,
@@ -67,7 +65,6 @@ async fn test_list_biggest_spenders_endpoint() {
         .unwrap();
     }
     indexer.tick().await.unwrap();
-    refresh_leaderboards(Arc::clone(&client)).await.unwrap();
 
     let response: serde_json::Value = test_server::send_get_request(
         &base,
@@ -7,10 +7,8 @@ use golem_base_indexer_logic::{types::EntityKey, Indexer};
 use helpers::{
     assert_json::{assert_fields, assert_fields_array},
     sample::{Block, Transaction},
-    utils::refresh_leaderboards,
 };
 use pretty_assertions::assert_eq;
-use std::sync::Arc;
 
 #[tokio::test]
 #[ignore = "Needs database to run"]
@@ -158,7 +156,6 @@ async fn test_list_addresses_by_entities_created() {
     .await
     .unwrap();
     indexer.tick().await.unwrap();
-    refresh_leaderboards(Arc::clone(&client)).await.unwrap();
 
     let response: serde_json::Value =
         test_server::send_get_request(&base, "/api/v1/leaderboard/entities-created").await;
@@ -7,10 +7,8 @@ use golem_base_indexer_logic::Indexer;
 use helpers::{
     assert_json::{assert_fields, assert_fields_array},
     sample::{Block, Transaction},
-    utils::refresh_leaderboards,
 };
 use pretty_assertions::assert_eq;
-use std::sync::Arc;
 
 #[tokio::test]
 #[ignore = "Needs database to run"]
@@ -91,7 +89,6 @@ async fn test_list_addresses_by_entities_owned() {
         .tick()
         .await
         .unwrap();
-    refresh_leaderboards(Arc::clone(&client)).await.unwrap();
 
     let response: serde_json::Value =
         test_server::send_get_request(&base, "/api/v1/leaderboard/entities-owned").await;
@@ -8,10 +8,8 @@ use golem_base_indexer_logic::{types::EntityKey, Indexer};
 use helpers::{
     assert_json::assert_fields_array,
     sample::{Block, Transaction},
-    utils::refresh_leaderboards,
 };
 use pretty_assertions::assert_eq;
-use std::sync::Arc;
 
 #[tokio::test]
 #[ignore = "Needs database to run"]
@@ -62,7 +60,6 @@ async fn test_list_largest_entities_endpoint() {
     .await
     .unwrap();
     indexer.tick().await.unwrap();
-    refresh_leaderboards(Arc::clone(&client)).await.unwrap();
 
     let response: serde_json::Value = test_server::send_get_request(
         &base,
@@ -0,0 +1,223 @@
+use crate::helpers;
+
+use arkiv_storage_tx::{ChangeOwner, StorageTransaction};
+use blockscout_service_launcher::test_server;
+use chrono::{TimeDelta, Utc};
+use golem_base_indexer_logic::{
+    arkiv::entity_key,
+    types::{Address, TxHash},
+    Indexer,
+};
+use pretty_assertions::assert_eq;
+use reqwest::{StatusCode, Url};
+use serde_json::{json, Value};
+
+use crate::helpers::sample::{Block, Transaction};
+use crate::helpers::storage::creates;
+
+/// Addresses or entity keys on the leaderboard, in rank order.
+async fn ranked(base: &Url, leaderboard: &str, query: &str) -> Vec<Value> {
+    let response: Value =
+        test_server::send_get_request(base, &format!("/api/v1/leaderboard/{leaderboard}?{query}"))
+            .await;
+    response["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| {
+            item.get("address")
+                .or(item.get("entity_key"))
+                .or(item.get("key"))
+                .unwrap()
+                .clone()
+        })
+        .collect()
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_leaderboard_windows_work() {
+    let db = helpers::init_db("test", "leaderboard_windows_work").await;
+    let client = db.client();
+    let base = helpers::init_golem_base_indexer_server(db, |x| x).await;
+
+    let alice = Address::repeat_byte(0xaa);
+    let bob = Address::repeat_byte(0xbb);
+    let old = Utc::now() - TimeDelta::days(10);
+    let transferred = old + TimeDelta::days(2);
+    let recent = Utc::now() - TimeDelta::hours(2);
+    helpers::sample::insert_data_multi(
+        &*client,
+        vec![
+            Block {
+                number: 1,
+                timestamp: Some(old),
+                transactions: vec![Transaction {
+                    hash: Some(TxHash::repeat_byte(1)),
+                    sender: alice,
+                    operations: creates(&[b"first", b"second", b"third"]),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            // alice hands one of her old entities over to bob
+            Block {
+                number: 2,
+                timestamp: Some(transferred),
+                transactions: vec![Transaction {
+                    hash: Some(TxHash::repeat_byte(3)),
+                    sender: alice,
+                    operations: StorageTransaction {
+                        change_owners: vec![ChangeOwner {
+                            entity_key: entity_key(
+                                TxHash::repeat_byte(1),
+                                b"second".to_vec().into(),
+                                1,
+                            ),
+                            new_owner: bob,
+                        }],
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            Block {
+                number: 3,
+                timestamp: Some(recent),
+                transactions: vec![Transaction {
+                    hash: Some(TxHash::repeat_byte(2)),
+                    sender: bob,
+                    operations: creates(&[b"bob"]),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        ],
+    )
+    .await
+    .unwrap();
+    Indexer::new(client.clone(), Default::default())
+        .tick()
+        .await
+        .unwrap();
+
+    let alice = json!(alice.to_string());
+    let bob = json!(bob.to_string());
+    assert_eq!(
+        ranked(&base, "entities-created", "").await,
+        vec![alice.clone(), bob.clone()]
+    );
+    assert_eq!(
+        ranked(&base, "entities-created", "window=30d").await,
+        vec![alice.clone(), bob.clone()]
+    );
+    for leaderboard in [
+        "entities-created",
+        "biggest-spenders",
+        "entities-owned",
+        "data-owned",
+    ] {
+        assert_eq!(
+            ranked(&base, leaderboard, "window=24h").await,
+            vec![bob.clone()],
+            "{leaderboard}"
+        );
+        assert_eq!(
+            ranked(&base, leaderboard, "window=7d").await,
+            vec![bob.clone()],
+            "{leaderboard}"
+        );
+    }
+    for leaderboard in [
+        "largest-entities",
+        "effectively-largest-entities",
+        "entities-by-btl",
+    ] {
+        assert_eq!(
+            ranked(&base, leaderboard, "").await.len(),
+            4,
+            "{leaderboard}"
+        );
+        assert_eq!(
+            ranked(&base, leaderboard, "window=24h").await.len(),
+            1,
+            "{leaderboard}"
+        );
+    }
+
+    let response: Value =
+        test_server::send_get_request(&base, "/api/v1/leaderboard/biggest-spenders?window=24h")
+            .await;
+    assert_eq!(response["items"][0]["total_fees"], "10000");
+    assert_eq!(response["pagination"]["total_items"], "1");
+
+    // custom windows around the old block only
+    let from = (old - TimeDelta::days(1)).format("%Y-%m-%d %H:%M");
+    let to = (old + TimeDelta::days(1)).format("%Y-%m-%d %H:%M");
+    let custom = format!("window=custom&from={from}&to={to}");
+    assert_eq!(
+        ranked(&base, "entities-created", &custom).await,
+        vec![alice.clone()]
+    );
+    assert_eq!(
+        ranked(&base, "entities-owned", &custom).await,
+        vec![alice.clone()]
+    );
+    assert_eq!(
+        ranked(&base, "data-owned", &custom).await,
+        vec![alice.clone()]
+    );
+    assert_eq!(ranked(&base, "largest-entities", &custom).await.len(), 3);
+
+    // owned leaderboards rank what owners gained within the window, not what
+    // they own now out of the entities created within it
+    let transfer = format!(
+        "window=custom&from={}&to={}",
+        (transferred - TimeDelta::days(1)).format("%Y-%m-%d %H:%M"),
+        (transferred + TimeDelta::days(1)).format("%Y-%m-%d %H:%M"),
+    );
+    let response: Value = test_server::send_get_request(
+        &base,
+        &format!("/api/v1/leaderboard/entities-owned?{transfer}"),
+    )
+    .await;
+    assert_eq!(
+        response["items"],
+        json!([{ "rank": "1", "address": bob, "entities_count": "1" }])
+    );
+    let response: Value =
+        test_server::send_get_request(&base, &format!("/api/v1/leaderboard/data-owned?{transfer}"))
+            .await;
+    assert_eq!(
+        response["items"],
+        json!([{ "rank": "1", "address": bob, "data_size": "6" }])
+    );
+    assert!(ranked(&base, "entities-created", &transfer)
+        .await
+        .is_empty());
+
+    let custom = format!("window=custom&from={from}");
+    assert_eq!(
+        ranked(&base, "entities-created", &custom).await,
+        vec![alice, bob]
+    );
+
+    for query in [
+        "window=1y",
+        "from=2025-01-01 00:00",
+        "window=24h&from=2025-01-01 00:00",
+        "window=custom",
+        "window=custom&from=2025-01-01",
+        "window=custom&from=2025-01-02 00:00&to=2025-01-01 00:00",
+    ] {
+        let status = reqwest::get(
+            base.join(&format!("/api/v1/leaderboard/entities-created?{query}"))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .status();
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{query}");
+    }
+}
@@ -0,0 +1,254 @@
+use crate::helpers;
+
+use arkiv_storage_tx::{ChangeOwner, StorageTransaction};
+use blockscout_service_launcher::test_server;
+use golem_base_indexer_logic::{
+    arkiv::entity_key,
+    types::{Address, TxHash},
+    Indexer,
+};
+use pretty_assertions::assert_eq;
+use reqwest::Url;
+use sea_orm::{ConnectionTrait, DatabaseConnection, Statement};
+use serde_json::{json, Value};
+
+use crate::helpers::sample::{Block, Transaction};
+use crate::helpers::storage::creates;
+
+async fn leaderboard(base: &Url, name: &str) -> Value {
+    let response: Value =
+        test_server::send_get_request(base, &format!("/api/v1/leaderboard/{name}")).await;
+    response["items"].clone()
+}
+
+/// Aggregates recomputed from scratch, which the maintained ones must match.
+async fn stats_are_consistent(db: &DatabaseConnection) -> bool {
+    db.query_one(Statement::from_string(
+        db.get_database_backend(),
+        r#"
+        select not exists (
+            select owner, count(*), sum(length(data))
+            from golem_base_entities
+            where status = 'active'
+            group by owner
+            except
+            select address, entities_owned_count, data_owned
+            from golem_base_address_leaderboard_stats
+            where entities_owned_count > 0
+        ) and not exists (
+            select sender, count(*)
+            from golem_base_operations
+            where operation = 'create'
+            group by sender
+            except
+            select address, entities_created_count
+            from golem_base_address_leaderboard_stats
+            where entities_created_count > 0
+        ) as consistent
+        "#,
+    ))
+    .await
+    .unwrap()
+    .unwrap()
+    .try_get("", "consistent")
+    .unwrap()
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_leaderboards_update_incrementally() {
+    let db = helpers::init_db("test", "leaderboards_update_incrementally").await;
+    let client = db.client();
+    let base = helpers::init_golem_base_indexer_server(db, |x| x).await;
+    let indexer = Indexer::new(client.clone(), Default::default());
+
+    let alice = Address::repeat_byte(0xaa);
+    let bob = Address::repeat_byte(0xbb);
+    let alice_tx = TxHash::repeat_byte(1);
+    let bob_tx = TxHash::repeat_byte(2);
+    helpers::sample::insert_data(
+        &*client,
+        Block {
+            number: 1,
+            transactions: vec![
+                Transaction {
+                    hash: Some(alice_tx),
+                    sender: alice,
+                    operations: creates(&[b"first", b"second entity"]),
+                    ..Default::default()
+                },
+                Transaction {
+                    hash: Some(bob_tx),
+                    sender: bob,
+                    operations: creates(&[b"bob"]),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    indexer.tick().await.unwrap();
+
+    // no refresh needed, rankings follow the indexed state right away
+    assert_eq!(
+        leaderboard(&base, "entities-owned").await,
+        json!([
+            { "rank": "1", "address": alice.to_string(), "entities_count": "2" },
+            { "rank": "2", "address": bob.to_string(), "entities_count": "1" },
+        ])
+    );
+    assert_eq!(
+        leaderboard(&base, "data-owned").await,
+        json!([
+            { "rank": "1", "address": alice.to_string(), "data_size": "18" },
+            { "rank": "2", "address": bob.to_string(), "data_size": "3" },
+        ])
+    );
+    assert!(stats_are_consistent(&client).await);
+
+    let second_entity = entity_key(alice_tx, b"second entity".to_vec().into(), 1);
+    let bob_entity = entity_key(bob_tx, b"bob".to_vec().into(), 0);
+    helpers::sample::insert_data(
+        &*client,
+        Block {
+            number: 2,
+            transactions: vec![
+                Transaction {
+                    hash: Some(TxHash::repeat_byte(3)),
+                    sender: alice,
+                    operations: StorageTransaction {
+                        change_owners: vec![ChangeOwner {
+                            entity_key: second_entity,
+                            new_owner: bob,
+                        }],
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                Transaction {
+                    hash: Some(TxHash::repeat_byte(4)),
+                    sender: bob,
+                    operations: StorageTransaction {
+                        deletes: vec![bob_entity],
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    indexer.tick().await.unwrap();
+
+    assert_eq!(
+        leaderboard(&base, "data-owned").await,
+        json!([
+            { "rank": "1", "address": bob.to_string(), "data_size": "13" },
+            { "rank": "2", "address": alice.to_string(), "data_size": "5" },
+        ])
+    );
+    assert_eq!(
+        leaderboard(&base, "largest-entities").await[0]["entity_key"],
+        second_entity.to_string()
+    );
+    let ranks: Value =
+        test_server::send_get_request(&base, &format!("/api/v1/address/{bob}/leaderboard-ranks"))
+            .await;
+    assert_eq!(ranks["data_owned"], "1");
+    assert_eq!(ranks["entities_created"], "2");
+    assert!(stats_are_consistent(&client).await);
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_biggest_spenders_count_every_transaction() {
+    let db = helpers::init_db("test", "biggest_spenders_count_every_transaction").await;
+    let client = db.client();
+    let base = helpers::init_golem_base_indexer_server(db, |x| x).await;
+    let indexer = Indexer::new(client.clone(), Default::default());
+
+    let alice = Address::repeat_byte(0xaa);
+    let bob = Address::repeat_byte(0xbb);
+    let failed_tx = TxHash::repeat_byte(3);
+    let dropped_tx = TxHash::repeat_byte(4);
+    helpers::sample::insert_data(
+        &*client,
+        Block {
+            number: 1,
+            transactions: vec![
+                Transaction {
+                    hash: Some(TxHash::repeat_byte(1)),
+                    sender: alice,
+                    operations: creates(&[b"stored"]),
+                    ..Default::default()
+                },
+                Transaction {
+                    hash: Some(TxHash::repeat_byte(2)),
+                    sender: alice,
+                    to: Some(Address::repeat_byte(0xcc)),
+                    ..Default::default()
+                },
+                Transaction {
+                    hash: Some(failed_tx),
+                    sender: bob,
+                    operations: creates(&[b"failed"]),
+                    ..Default::default()
+                },
+                Transaction {
+                    hash: Some(dropped_tx),
+                    sender: bob,
+                    to: Some(Address::repeat_byte(0xcc)),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    client
+        .execute(Statement::from_sql_and_values(
+            client.get_database_backend(),
+            "update transactions set status = 0 where hash = $1",
+            [failed_tx.as_slice().into()],
+        ))
+        .await
+        .unwrap();
+    indexer.tick().await.unwrap();
+
+    // transfers and failed txs pay fees too
+    assert_eq!(
+        leaderboard(&base, "biggest-spenders").await,
+        json!([
+            { "rank": "1", "address": alice.to_string(), "total_fees": "20000" },
+            { "rank": "2", "address": bob.to_string(), "total_fees": "20000" },
+        ])
+    );
+
+    client
+        .execute(Statement::from_sql_and_values(
+            client.get_database_backend(),
+            r#"
+            update transactions set
+                block_hash = null, block_number = null, index = null,
+                cumulative_gas_used = null, gas_used = null, status = null
+            where hash = $1
+            "#,
+            [dropped_tx.as_slice().into()],
+        ))
+        .await
+        .unwrap();
+    indexer.tick().await.unwrap();
+
+    assert_eq!(
+        leaderboard(&base, "biggest-spenders").await,
+        json!([
+            { "rank": "1", "address": alice.to_string(), "total_fees": "20000" },
+            { "rank": "2", "address": bob.to_string(), "total_fees": "10000" },
+        ])
+    );
+}
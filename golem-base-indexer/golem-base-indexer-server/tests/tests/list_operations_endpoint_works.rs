@@ -64,6 +64,7 @@ async fn test_list_operations_endpoint_works() {
                 "cost": "0",
                 "expires_at_timestamp": null,
                 "expires_at_timestamp_sec": null,
+                "finality": null,
             }
         ],
         "pagination": {
@@ -71,7 +72,8 @@ async fn test_list_operations_endpoint_works() {
             "page_size": "100",
             "total_items": "1",
             "total_pages": "1"
-        }
+        },
+        "next_page_params": null
     });
     assert_eq!(response, expected);
 
@@ -96,6 +98,7 @@ async fn test_list_operations_endpoint_works() {
             "cost": "0",
             "expires_at_timestamp": "2025-07-22T12:38:16+00:00",
             "expires_at_timestamp_sec": "1753187896",
+            "finality": null,
         },
         ],
         "pagination": {
@@ -103,7 +106,8 @@ async fn test_list_operations_endpoint_works() {
             "page_size": "100",
             "total_items": "1",
             "total_pages": "1"
-        }
+        },
+        "next_page_params": null
     });
     assert_eq!(response, expected);
     let response: serde_json::Value = test_server::send_get_request(
@@ -149,6 +153,7 @@ async fn test_list_operations_endpoint_works() {
                 "cost": "0",
                 "expires_at_timestamp": "2025-07-22T12:38:16+00:00",
                 "expires_at_timestamp_sec": "1753187896",
+                "finality": null,
             },
         ],
         "pagination": {
@@ -156,7 +161,8 @@ async fn test_list_operations_endpoint_works() {
             "page_size": "100",
             "total_items": "1",
             "total_pages": "1"
-        }
+        },
+        "next_page_params": null
     });
     assert_eq!(response, expected);
 
@@ -165,7 +171,7 @@ async fn test_list_operations_endpoint_works() {
         "page_size": "100",
         "total_items": "0",
         "total_pages": "0"
-    }});
+    }, "next_page_params": null});
 
     let response: serde_json::Value = test_server::send_get_request(
         &base,
@@ -0,0 +1,138 @@
+use crate::helpers::{self, pagination::list_all};
+
+use blockscout_service_launcher::test_server;
+use golem_base_indexer_logic::Indexer;
+use pretty_assertions::assert_eq;
+use reqwest::{StatusCode, Url};
+use serde_json::Value;
+
+const ARKIV: &str = "0x00000000000000000000000000000061726b6976";
+
+/// Positions of all listed operations as `block/index`, following
+/// `next_page_params` until exhausted.
+async fn list(base: &Url, query: &str) -> Vec<String> {
+    list_all(base, &format!("/api/v1/operations?operation=ALL&{query}"))
+        .await
+        .iter()
+        .map(|item| {
+            format!(
+                "{}/{}",
+                item["block_number"].as_str().unwrap(),
+                item["index"].as_str().unwrap()
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_list_operations_filters_work() {
+    let db = helpers::init_db("test", "list_operations_filters_work").await;
+    let client = db.client();
+    let base = helpers::init_golem_base_indexer_server(db, |x| x).await;
+    helpers::load_data(&*client, include_str!("../fixtures/sample_events.sql")).await;
+    Indexer::new(client, Default::default())
+        .tick()
+        .await
+        .unwrap();
+
+    // deletes and owner changes have no cost, so they sort as costing nothing
+    let by_cost = [
+        "3500/2", "3500/4", "3500/6", "3498/0", "3500/0", "3496/0", "3499/0", "3497/0", "3500/1",
+        "3500/3", "3500/5",
+    ];
+    assert_eq!(list(&base, "sort_by=cost&page_size=2").await, by_cost);
+    let mut by_cost_desc = by_cost;
+    by_cost_desc.reverse();
+    assert_eq!(
+        list(&base, "sort_by=cost&sort_direction=desc&page_size=3").await,
+        by_cost_desc
+    );
+    let by_time = list(&base, "").await;
+    assert_eq!(by_time.len(), 11);
+    let mut by_time_desc = by_time.clone();
+    by_time_desc.reverse();
+    assert_eq!(
+        list(&base, "sort_by=time&sort_direction=desc&page_size=4").await,
+        by_time_desc
+    );
+
+    assert_eq!(
+        list(&base, "operation_types=UPDATE,%20EXTEND").await,
+        ["3500/3", "3500/4", "3500/5"]
+    );
+    let status = reqwest::get(
+        base.join("/api/v1/operations?operation=UPDATE&operation_types=CREATE,UPDATE")
+            .unwrap(),
+    )
+    .await
+    .unwrap()
+    .status();
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    assert_eq!(
+        list(&base, "from_block=3497&to_block=3499").await,
+        ["3497/0", "3498/0", "3499/0"]
+    );
+    assert_eq!(
+        list(&base, "min_cost=1000&max_cost=70000").await,
+        ["3496/0", "3497/0", "3499/0"]
+    );
+    assert_eq!(
+        list(&base, "max_cost=0").await,
+        ["3500/2", "3500/4", "3500/6"]
+    );
+    assert_eq!(list(&base, "content_type=plain/text").await.len(), 8);
+    assert_eq!(list(&base, &format!("recipient={ARKIV}")).await.len(), 11);
+    assert_eq!(
+        list(
+            &base,
+            "recipient=0x0000000000000000000000000000000000000001"
+        )
+        .await,
+        Vec::<String>::new()
+    );
+    assert_eq!(
+        list(
+            &base,
+            "from_timestamp=2025-11-22%2020:26&to_timestamp=2025-11-22%2020:27"
+        )
+        .await
+        .len(),
+        11
+    );
+    assert_eq!(
+        list(&base, "to_timestamp=2025-11-22%2020:26").await,
+        Vec::<String>::new()
+    );
+
+    let response: Value = test_server::send_get_request(
+        &base,
+        "/api/v1/operations/count?operation_types=CREATE,EXTEND&min_cost=1000",
+    )
+    .await;
+    assert_eq!(response["create_count"], "4");
+    assert_eq!(response["extend_count"], "1");
+    assert_eq!(response["update_count"], "0");
+
+    for query in [
+        "sort_by=size",
+        "sort_direction=up",
+        "operation_types=CREATE,BOGUS",
+        "operation_types=create",
+        "min_cost=-1",
+        "from_timestamp=yesterday",
+        "recipient=arkiv",
+        // a cursor of the listing sorted by time
+        "sort_by=cost&cursor=000000000000000000000000000000000000000000000000",
+    ] {
+        let status = reqwest::get(
+            base.join(&format!("/api/v1/operations?operation=ALL&{query}"))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .status();
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{query}");
+    }
+}
@@ -1,19 +1,34 @@
+mod address_charts_work;
 mod address_leaderboard_ranks;
+mod admin_api_works;
+mod annotation_catalog_works;
 mod block_stats;
+mod chart_breakdowns_work;
+mod confirmation_levels_work;
 mod creator_field_works;
+mod cursor_pagination_works;
+mod dead_letter_queue_works;
+mod entities_at_block_work;
 mod entities_averages;
+mod entity_data_diff_works;
+mod entity_sorting_and_ranges_work;
 mod events_operation_cost;
 mod expired_entities_attributes_get_deactivated;
 mod expired_entities_dont_get_updated;
 mod get_address_stats_endpoint_works;
 mod get_consensus_info;
+mod get_entities_batch_works;
 mod get_entity_data_size_histogram;
 mod get_entity_endpoint_works;
 mod get_entity_history_endpoint_works;
 mod get_entity_with_timestamp_overflow;
 mod get_operation_endpoint;
 mod get_operations_count_endpoint_works;
+mod grpc_endpoints_work;
+mod history_annotations_work;
 mod inactive_attributes_are_ignored;
+mod indexer_replicas_work;
+mod indexer_status_works;
 mod leaderboard_biggest_spenders;
 mod leaderboard_data_owned;
 mod leaderboard_effectively_largest_entities;
@@ -22,19 +37,26 @@ mod leaderboard_entities_created;
 mod leaderboard_entities_owned;
 mod leaderboard_largest_entities;
 mod leaderboard_top_accounts;
+mod leaderboard_windows_work;
+mod leaderboards_update_incrementally;
 mod list_custom_contract_transactions;
 mod list_entities_endpoint_works;
 mod list_operations_endpoint_works;
+mod list_operations_filters_work;
 mod logic_works;
 mod logs_queue_cleaned_correctly;
 mod multiple_attributes_per_entity_work;
 mod non_unique_attributes_work;
+mod query_language_works;
 mod queue_cleaned_correctly;
 mod related_attributes_work;
+mod reorg_handled_by_block_consensus;
 mod reorg_handled_correctly_create;
 mod reorg_handled_correctly_delete_extend;
 mod reorg_handled_correctly_extend_delete;
 mod reorg_handled_correctly_update;
+mod rpc_ingestion_works;
+mod search_works;
 mod startup_works;
 mod timeseries_block_gas_usage_limit;
 mod timeseries_block_operations;
@@ -43,3 +65,6 @@ mod timeseries_data_usage;
 mod timeseries_entity_count;
 mod timeseries_operation_count;
 mod timeseries_storage_forecast;
+mod undecodable_transactions_work;
+mod webhooks_work;
+mod websocket_subscriptions_work;
@@ -0,0 +1,195 @@
+use crate::helpers;
+
+use arkiv_storage_tx::{Create, NumericAttribute, StorageTransaction, StringAttribute};
+use bytes::Bytes;
+use golem_base_indexer_logic::Indexer;
+use pretty_assertions::assert_eq;
+use reqwest::{StatusCode, Url};
+use serde_json::Value;
+
+use crate::helpers::{
+    sample::{Block, Transaction},
+    utils::bytes_to_hex,
+};
+
+fn create(name: &str, strings: &[(&str, &str)], numbers: &[(&str, u64)]) -> Create {
+    Create {
+        btl: 1000,
+        payload: name.as_bytes().to_vec().into(),
+        string_attributes: strings
+            .iter()
+            .map(|(key, value)| StringAttribute {
+                key: key.to_string(),
+                value: value.to_string(),
+            })
+            .collect(),
+        numeric_attributes: numbers
+            .iter()
+            .map(|(key, value)| NumericAttribute {
+                key: key.to_string(),
+                value: *value,
+            })
+            .collect(),
+        ..Default::default()
+    }
+}
+
+async fn send_query(base: &Url, path: &str, query: &str) -> reqwest::Response {
+    let mut url = base.join(path).unwrap();
+    url.query_pairs_mut()
+        .append_pair("status", "ALL")
+        .append_pair("query", query);
+    reqwest::get(url).await.unwrap()
+}
+
+async fn query_entities(base: &Url, query: &str) -> Vec<String> {
+    let response = send_query(base, "/api/v1/entities", query).await;
+    assert_eq!(response.status(), StatusCode::OK, "query: {query}");
+    let response: Value = response.json().await.unwrap();
+    let mut data: Vec<String> = response["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["data"].as_str().unwrap().to_string())
+        .collect();
+    data.sort();
+
+    let response = send_query(base, "/api/v1/entities/count", query).await;
+    assert_eq!(response.status(), StatusCode::OK, "query: {query}");
+    let response: Value = response.json().await.unwrap();
+    assert_eq!(response["count"].as_str().unwrap(), data.len().to_string());
+
+    data
+}
+
+fn names(names: &[&str]) -> Vec<String> {
+    let mut data: Vec<String> = names
+        .iter()
+        .map(|name| bytes_to_hex(&Bytes::copy_from_slice(name.as_bytes())))
+        .collect();
+    data.sort();
+    data
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_query_language_works() {
+    let db = helpers::init_db("test", "query_language_works").await;
+    let client = db.client();
+    let base = helpers::init_golem_base_indexer_server(db, |x| x).await;
+
+    helpers::sample::insert_data(
+        &*client,
+        Block {
+            number: 1,
+            transactions: vec![Transaction {
+                operations: StorageTransaction {
+                    creates: vec![
+                        create(
+                            "e1",
+                            &[("type", "note"), ("tag", "urgent-1")],
+                            &[("priority", 5)],
+                        ),
+                        create(
+                            "e2",
+                            &[("type", "note"), ("tag", "urgent")],
+                            &[("priority", 1)],
+                        ),
+                        create(
+                            "e3",
+                            &[("type", "note"), ("tag", "later")],
+                            &[("priority", 4)],
+                        ),
+                        create(
+                            "e4",
+                            &[("type", "task"), ("tag", "50%off")],
+                            &[("priority", 10)],
+                        ),
+                        create("e5", &[("tag", "500ff"), ("quote", "say \"hi\"")], &[]),
+                    ],
+                    ..Default::default()
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    Indexer::new(client, Default::default())
+        .tick()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        query_entities(&base, r#"type = "note""#).await,
+        names(&["e1", "e2", "e3"])
+    );
+    assert_eq!(
+        query_entities(
+            &base,
+            r#"type = "note" && (priority > 4 || tag ~ "urgent*")"#
+        )
+        .await,
+        names(&["e1", "e2"])
+    );
+    assert_eq!(
+        query_entities(&base, r#"type = "note" && priority > 4 || tag ~ "urgent*""#).await,
+        names(&["e1", "e2"])
+    );
+    assert_eq!(
+        query_entities(
+            &base,
+            r#"tag ~ "urgent*" || type = "note" && priority >= 4"#
+        )
+        .await,
+        names(&["e1", "e2", "e3"])
+    );
+    assert_eq!(
+        query_entities(&base, r#"!(type = "note")"#).await,
+        names(&["e4", "e5"])
+    );
+    assert_eq!(
+        query_entities(&base, r#"type != "note""#).await,
+        names(&["e4"])
+    );
+    assert_eq!(
+        query_entities(&base, "priority >= 4 && priority < 10").await,
+        names(&["e1", "e3"])
+    );
+    assert_eq!(
+        query_entities(&base, "priority != 4 && !priority <= 1").await,
+        names(&["e1", "e4"])
+    );
+    assert_eq!(
+        query_entities(&base, r#"tag ~ "50%*""#).await,
+        names(&["e4"])
+    );
+    assert_eq!(
+        query_entities(&base, r#"tag ~ "urgent?*" && tag !~ "*1""#).await,
+        names(&[])
+    );
+    assert_eq!(
+        query_entities(&base, r#"quote = "say \"hi\"""#).await,
+        names(&["e5"])
+    );
+
+    for path in ["/api/v1/entities", "/api/v1/entities/count"] {
+        let response = send_query(&base, path, r#"type = "note" &&"#).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let response: Value = response.json().await.unwrap();
+        assert_eq!(
+            response["message"].as_str().unwrap(),
+            "Invalid entities filter: Invalid query: Expected attribute key or '(' at position 16"
+        );
+    }
+    let deep = format!("{}type = \"note\"{}", "(".repeat(1000), ")".repeat(1000));
+    let response = send_query(&base, "/api/v1/entities", &deep).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let response: Value = response.json().await.unwrap();
+    assert_eq!(
+        response["message"].as_str().unwrap(),
+        "Invalid entities filter: Invalid query: Query is nested deeper than 32 levels at position 32"
+    );
+}
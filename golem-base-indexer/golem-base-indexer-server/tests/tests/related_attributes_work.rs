@@ -5,7 +5,9 @@ use arkiv_storage_tx::{Create, NumericAttribute, StorageTransaction, StringAttri
 use blockscout_service_launcher::test_server;
 use golem_base_indexer_logic::{
     repository,
-    types::{EntitiesFilter, EntityStatus, ListEntitiesFilter, PaginationParams},
+    types::{
+        CursorPaginationParams, EntitiesFilter, EntityStatus, ListEntitiesFilter, PaginationParams,
+    },
     Indexer,
 };
 use pretty_assertions::assert_eq;
@@ -121,11 +123,19 @@ async fn test_related_attributes_work() {
                 }),
                 numeric_attribute: None,
                 owner: None,
+                query: None,
+                ..Default::default()
             },
-            pagination: PaginationParams {
-                page: 0,
-                page_size: 100,
+            pagination: CursorPaginationParams {
+                pagination: PaginationParams {
+                    page: 0,
+                    page_size: 100,
+                },
+                cursor: None,
+                with_totals: false,
             },
+            sort: Default::default(),
+            at_block: None,
         },
     )
     .await
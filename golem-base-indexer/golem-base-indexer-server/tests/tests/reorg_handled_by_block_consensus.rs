@@ -0,0 +1,192 @@
+use crate::helpers;
+
+use arkiv_storage_tx::{Create, StorageTransaction, StringAttribute};
+use blockscout_service_launcher::test_server;
+use golem_base_indexer_logic::{
+    arkiv::entity_key,
+    types::{BlockHash, EntityKey, TxHash},
+    Indexer,
+};
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+use serde_json::Value;
+
+use crate::helpers::sample::{Block, Transaction};
+use crate::helpers::storage::update;
+
+fn create(payload: &[u8], tag: &str) -> StorageTransaction {
+    StorageTransaction {
+        creates: vec![Create {
+            btl: 100,
+            payload: payload.to_vec().into(),
+            string_attributes: vec![StringAttribute {
+                key: "tag".to_string(),
+                value: tag.to_string(),
+            }],
+            ..Default::default()
+        }],
+        ..Default::default()
+    }
+}
+
+fn block(number: u64, hash: u8, transactions: Vec<(TxHash, StorageTransaction)>) -> Block {
+    Block {
+        number,
+        hash: Some(BlockHash::repeat_byte(hash)),
+        transactions: transactions
+            .into_iter()
+            .map(|(hash, operations)| Transaction {
+                hash: Some(hash),
+                operations,
+                ..Default::default()
+            })
+            .collect(),
+        ..Default::default()
+    }
+}
+
+async fn entity_status(base: &reqwest::Url, key: EntityKey) -> StatusCode {
+    reqwest::get(base.join(&format!("/api/v1/entity/{key}")).unwrap())
+        .await
+        .unwrap()
+        .status()
+}
+
+async fn total_fees(base: &reqwest::Url) -> Value {
+    let spenders: Value =
+        test_server::send_get_request(base, "/api/v1/leaderboard/biggest-spenders").await;
+    spenders["items"][0]["total_fees"].clone()
+}
+
+async fn history_tx_hashes(base: &reqwest::Url, key: EntityKey) -> Vec<String> {
+    let history: Value =
+        test_server::send_get_request(base, &format!("/api/v1/entity/{key}/history")).await;
+    history["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["transaction_hash"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_reorg_handled_by_block_consensus() {
+    let db = helpers::init_db("test", "reorg_handled_by_block_consensus").await;
+    let client = db.client();
+    let indexer = Indexer::new(db.client(), Default::default());
+    let base = helpers::init_golem_base_indexer_server(db, |x| x).await;
+
+    let create_kept = TxHash::repeat_byte(1);
+    let update_moved = TxHash::repeat_byte(2);
+    let create_dropped = TxHash::repeat_byte(3);
+    let create_late = TxHash::repeat_byte(4);
+    let create_forked = TxHash::repeat_byte(5);
+    let kept = entity_key(create_kept, b"kept".to_vec().into(), 0);
+    let dropped = entity_key(create_dropped, b"dropped".to_vec().into(), 0);
+    let late = entity_key(create_late, b"late".to_vec().into(), 0);
+    let forked = entity_key(create_forked, b"forked".to_vec().into(), 0);
+
+    helpers::sample::insert_data_multi(
+        &*client,
+        vec![
+            block(1, 0x11, vec![(create_kept, create(b"kept", "kept"))]),
+            block(
+                2,
+                0x12,
+                vec![
+                    (update_moved, update(kept, b"updated")),
+                    (create_dropped, create(b"dropped", "dropped")),
+                ],
+            ),
+            block(3, 0x13, vec![(create_late, create(b"late", "late"))]),
+        ],
+    )
+    .await
+    .unwrap();
+    indexer.tick().await.unwrap();
+
+    assert_eq!(
+        history_tx_hashes(&base, kept).await,
+        vec![create_kept.to_string(), update_moved.to_string()]
+    );
+    assert_eq!(entity_status(&base, dropped).await, StatusCode::OK);
+    assert_eq!(entity_status(&base, late).await, StatusCode::OK);
+    assert_eq!(total_fees(&base).await, "40000");
+
+    // blocks 2 and 3 are replaced by a fork without any update of their txs,
+    // the update is included again one block later
+    helpers::sample::lose_consensus(&*client, 2).await.unwrap();
+    helpers::sample::insert_data_multi(
+        &*client,
+        vec![
+            block(2, 0x22, vec![(create_forked, create(b"forked", "forked"))]),
+            block(3, 0x23, vec![]),
+        ],
+    )
+    .await
+    .unwrap();
+    helpers::sample::move_transaction(&*client, update_moved, BlockHash::repeat_byte(0x23), 3)
+        .await
+        .unwrap();
+    indexer.tick().await.unwrap();
+
+    assert_eq!(entity_status(&base, dropped).await, StatusCode::NOT_FOUND);
+    assert_eq!(entity_status(&base, late).await, StatusCode::NOT_FOUND);
+    assert_eq!(entity_status(&base, forked).await, StatusCode::OK);
+    // fees of the dropped txs are taken back, the moved update is charged once
+    assert_eq!(total_fees(&base).await, "30000");
+
+    let entity: Value =
+        test_server::send_get_request(&base, &format!("/api/v1/entity/{kept}")).await;
+    assert_eq!(entity["data"], format!("0x{}", hex::encode("updated")));
+    assert_eq!(entity["updated_at_block_number"], "3");
+    let history = history_tx_hashes(&base, kept).await;
+    assert_eq!(
+        history,
+        vec![create_kept.to_string(), update_moved.to_string()]
+    );
+
+    let operation = reqwest::get(
+        base.join(&format!("/api/v1/operation/{create_dropped}/0"))
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(operation.status(), StatusCode::NOT_FOUND);
+
+    let dropped_annotations: Value = test_server::send_get_request(
+        &base,
+        "/api/v1/entities?status=ALL&string_annotation_key=tag&string_annotation_value=dropped",
+    )
+    .await;
+    assert_eq!(dropped_annotations["items"], serde_json::json!([]));
+
+    let operations: Value =
+        test_server::send_get_request(&base, "/api/v1/operations?operation=ALL").await;
+    let mut blocks: Vec<_> = operations["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|op| {
+            (
+                op["block_number"].as_str().unwrap().to_string(),
+                op["block_hash"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect();
+    blocks.sort();
+    assert_eq!(
+        blocks,
+        vec![
+            ("1".to_string(), BlockHash::repeat_byte(0x11).to_string()),
+            ("2".to_string(), BlockHash::repeat_byte(0x22).to_string()),
+            ("3".to_string(), BlockHash::repeat_byte(0x23).to_string()),
+        ]
+    );
+
+    // nothing is rolled back again once the fork is handled
+    indexer.tick().await.unwrap();
+    assert_eq!(history_tx_hashes(&base, kept).await, history);
+    assert_eq!(entity_status(&base, forked).await, StatusCode::OK);
+}
@@ -0,0 +1,202 @@
+use crate::helpers;
+use crate::helpers::storage::create;
+
+use alloy_primitives::Bytes;
+use arkiv_storage_tx::StorageTransaction;
+use blockscout_service_launcher::test_server;
+use golem_base_indexer_logic::{
+    arkiv::entity_key,
+    ingestion::{IngestionSettings, RpcIngestionSettings},
+    types::{BlockHash, EntityKey, TxHash},
+    Indexer, IndexerSettings,
+};
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use wiremock::{
+    matchers::{body_partial_json, method},
+    Mock, MockServer, ResponseTemplate,
+};
+
+struct RpcBlock {
+    number: u64,
+    hash: u8,
+    parent_hash: u8,
+    transactions: Vec<(TxHash, StorageTransaction)>,
+}
+
+fn rpc_response(result: Value) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({
+        "jsonrpc": "2.0",
+        "id": 0,
+        "result": result,
+    }))
+}
+
+async fn mount_rpc(server: &MockServer, request: Value, result: Value) {
+    Mock::given(method("POST"))
+        .and(body_partial_json(request))
+        .respond_with(rpc_response(result))
+        .mount(server)
+        .await;
+}
+
+async fn mount_chain(server: &MockServer, blocks: Vec<RpcBlock>) {
+    server.reset().await;
+    let head = blocks.iter().map(|b| b.number).max().unwrap_or_default();
+    mount_rpc(
+        server,
+        json!({"method": "eth_blockNumber"}),
+        json!(format!("0x{head:x}")),
+    )
+    .await;
+
+    for block in blocks {
+        let number = format!("0x{:x}", block.number);
+        let (transactions, receipts): (Vec<_>, Vec<_>) = block
+            .transactions
+            .into_iter()
+            .enumerate()
+            .map(|(index, (hash, operations))| {
+                let input: Bytes = operations.try_into().unwrap();
+                (
+                    json!({
+                        "hash": hash,
+                        "transactionIndex": format!("0x{index:x}"),
+                        "from": "0x0000000000000000000000000000000000000001",
+                        "to": "0x00000000000000000000000000000061726b6976",
+                        "input": input,
+                        "nonce": format!("0x{index:x}"),
+                        "gas": "0x100000",
+                        "gasPrice": "0x1",
+                        "value": "0x0",
+                        "type": "0x2",
+                    }),
+                    json!({
+                        "transactionHash": hash,
+                        "status": "0x1",
+                        "gasUsed": "0x100",
+                        "cumulativeGasUsed": format!("0x{:x}", 0x100 * (index + 1)),
+                        "effectiveGasPrice": "0x1",
+                        "contractAddress": null,
+                        "logs": [],
+                    }),
+                )
+            })
+            .unzip();
+
+        mount_rpc(
+            server,
+            json!({"method": "eth_getBlockByNumber", "params": [number, true]}),
+            json!({
+                "hash": BlockHash::repeat_byte(block.hash),
+                "parentHash": BlockHash::repeat_byte(block.parent_hash),
+                "number": number,
+                "timestamp": format!("0x{:x}", 1_700_000_000 + block.number),
+                "miner": "0x0000000000000000000000000000000000000000",
+                "gasLimit": "0x1000000",
+                "gasUsed": "0x0",
+                "transactions": transactions,
+            }),
+        )
+        .await;
+        mount_rpc(
+            server,
+            json!({"method": "eth_getBlockReceipts", "params": [number]}),
+            json!(receipts),
+        )
+        .await;
+    }
+}
+
+async fn entity_status(base: &reqwest::Url, key: EntityKey) -> StatusCode {
+    reqwest::get(base.join(&format!("/api/v1/entity/{key}")).unwrap())
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_rpc_ingestion_works() {
+    let db = helpers::init_db("test", "rpc_ingestion_works").await;
+    let client = db.client();
+    let rpc_mock = MockServer::start().await;
+    let indexer = Indexer::new(
+        client.clone(),
+        IndexerSettings {
+            ingestion: IngestionSettings::Rpc(RpcIngestionSettings {
+                url: rpc_mock.uri().parse().unwrap(),
+                start_block: 1,
+                batch_size: 10,
+            }),
+            ..Default::default()
+        },
+    );
+    let base = helpers::init_golem_base_indexer_server(db, |x| x).await;
+
+    let create_kept = TxHash::repeat_byte(1);
+    let create_dropped = TxHash::repeat_byte(2);
+    let create_forked = TxHash::repeat_byte(3);
+    let kept = entity_key(create_kept, b"kept".to_vec().into(), 0);
+    let dropped = entity_key(create_dropped, b"dropped".to_vec().into(), 0);
+    let forked = entity_key(create_forked, b"forked".to_vec().into(), 0);
+
+    mount_chain(
+        &rpc_mock,
+        vec![
+            RpcBlock {
+                number: 1,
+                hash: 0x11,
+                parent_hash: 0x00,
+                transactions: vec![(create_kept, create(b"kept"))],
+            },
+            RpcBlock {
+                number: 2,
+                hash: 0x12,
+                parent_hash: 0x11,
+                transactions: vec![(create_dropped, create(b"dropped"))],
+            },
+        ],
+    )
+    .await;
+    indexer.tick().await.unwrap();
+
+    assert_eq!(entity_status(&base, kept).await, StatusCode::OK);
+    assert_eq!(entity_status(&base, dropped).await, StatusCode::OK);
+
+    // nothing new on chain, nothing changes
+    indexer.tick().await.unwrap();
+    assert_eq!(entity_status(&base, dropped).await, StatusCode::OK);
+
+    // block 2 is replaced, which is noticed when its successor does not link to it
+    mount_chain(
+        &rpc_mock,
+        vec![
+            RpcBlock {
+                number: 2,
+                hash: 0x22,
+                parent_hash: 0x11,
+                transactions: vec![(create_forked, create(b"forked"))],
+            },
+            RpcBlock {
+                number: 3,
+                hash: 0x23,
+                parent_hash: 0x22,
+                transactions: vec![],
+            },
+        ],
+    )
+    .await;
+    indexer.tick().await.unwrap();
+    assert_eq!(entity_status(&base, dropped).await, StatusCode::NOT_FOUND);
+
+    indexer.tick().await.unwrap();
+    assert_eq!(entity_status(&base, kept).await, StatusCode::OK);
+    assert_eq!(entity_status(&base, dropped).await, StatusCode::NOT_FOUND);
+    assert_eq!(entity_status(&base, forked).await, StatusCode::OK);
+
+    let entity: Value =
+        test_server::send_get_request(&base, &format!("/api/v1/entity/{forked}")).await;
+    assert_eq!(entity["created_at_block_number"], "2");
+}
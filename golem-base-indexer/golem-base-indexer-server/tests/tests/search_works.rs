@@ -0,0 +1,212 @@
+use crate::helpers;
+
+use arkiv_storage_tx::{Create, StorageTransaction, StringAttribute, Update};
+use blockscout_service_launcher::test_server;
+use golem_base_indexer_logic::{
+    arkiv::entity_key,
+    types::{Address, TxHash},
+    Indexer,
+};
+use pretty_assertions::assert_eq;
+use reqwest::{StatusCode, Url};
+use serde_json::{json, Value};
+
+use crate::helpers::sample::{Block, Transaction};
+
+fn create(payload: &[u8], content_type: &str, name: Option<&str>) -> Create {
+    Create {
+        btl: 1000,
+        content_type: content_type.to_string(),
+        payload: payload.to_vec().into(),
+        string_attributes: name
+            .map(|name| StringAttribute {
+                key: "name".to_string(),
+                value: name.to_string(),
+            })
+            .into_iter()
+            .collect(),
+        ..Default::default()
+    }
+}
+
+/// Keys and snippets of the results, in ranked order.
+async fn search(base: &Url, q: &str) -> Vec<(String, String)> {
+    let response: Value =
+        test_server::send_get_request(base, &format!("/api/v1/search?q={q}")).await;
+    response["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| {
+            (
+                item["entity_key"].as_str().unwrap().to_string(),
+                item["snippet"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_search_works() {
+    let db = helpers::init_db("test", "search_works").await;
+    let client = db.client();
+    let base = helpers::init_golem_base_indexer_server(db, |x| x).await;
+
+    let tx = TxHash::repeat_byte(1);
+    let story = b"The quick brown fox jumps over the lazy dog";
+    let json = br#"{"note": "fox den location"}"#;
+    let photo = b"fox fox fox";
+    let markup = b"<b>fox</b> & friends";
+    let deleted = b"a fox that is gone";
+    let key = |payload: &[u8], index| entity_key(tx, payload.to_vec().into(), index).to_string();
+    helpers::sample::insert_data_multi(
+        &*client,
+        vec![
+            Block {
+                number: 1,
+                transactions: vec![Transaction {
+                    hash: Some(tx),
+                    sender: Address::repeat_byte(0xaa),
+                    operations: StorageTransaction {
+                        creates: vec![
+                            create(story, "text/plain", None),
+                            create(json, "application/json", None),
+                            create(photo, "image/png", Some("red fox")),
+                            create(markup, "text/html", None),
+                            create(deleted, "text/plain", None),
+                        ],
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            Block {
+                number: 2,
+                transactions: vec![Transaction {
+                    hash: Some(TxHash::repeat_byte(2)),
+                    sender: Address::repeat_byte(0xaa),
+                    operations: StorageTransaction {
+                        updates: vec![Update {
+                            entity_key: entity_key(tx, story.to_vec().into(), 0),
+                            btl: 1000,
+                            content_type: "text/plain".to_string(),
+                            payload: b"The slow turtle".to_vec().into(),
+                            ..Default::default()
+                        }],
+                        deletes: vec![entity_key(tx, deleted.to_vec().into(), 4)],
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        ],
+    )
+    .await
+    .unwrap();
+    Indexer::new(client.clone(), Default::default())
+        .tick()
+        .await
+        .unwrap();
+
+    // annotation matches rank first, binary payloads and deleted entities are not searched
+    let results = search(&base, "fox").await;
+    assert_eq!(
+        results[0],
+        (key(photo, 2), "red <mark>fox</mark>".to_string())
+    );
+    let mut rest = results[1..].to_vec();
+    rest.sort();
+    let mut expected = vec![
+        (
+            key(json, 1),
+            r#"{"note": "<mark>fox</mark> den location"}"#.to_string(),
+        ),
+        (
+            key(markup, 3),
+            "&lt;b&gt;<mark>fox</mark>&lt;/b&gt; &amp; friends".to_string(),
+        ),
+    ];
+    expected.sort();
+    assert_eq!(rest, expected);
+
+    // the payload is searched as it is after the update
+    assert_eq!(search(&base, "quick").await, []);
+    assert_eq!(
+        search(&base, "turtle").await,
+        [(key(story, 0), "The slow <mark>turtle</mark>".to_string())]
+    );
+    assert_eq!(search(&base, "%22den%20location%22").await.len(), 1);
+    assert_eq!(search(&base, "fox%20-den").await.len(), 2);
+
+    let response: Value =
+        test_server::send_get_request(&base, "/api/v1/search?q=fox&page_size=1&page=2").await;
+    assert_eq!(response["items"].as_array().unwrap().len(), 1);
+    assert_eq!(
+        response["pagination"],
+        json!({"page": "2", "page_size": "1", "total_pages": "3", "total_items": "3"})
+    );
+
+    let status = reqwest::get(base.join("/api/v1/search?q=%20").unwrap())
+        .await
+        .unwrap()
+        .status();
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_search_caps_annotations() {
+    let db = helpers::init_db("test", "search_caps_annotations").await;
+    let client = db.client();
+    let base = helpers::init_golem_base_indexer_server(db, |x| x).await;
+
+    // far more distinct words than a tsvector can hold
+    let tx = TxHash::repeat_byte(1);
+    let string_attributes = std::iter::once("needle".to_string())
+        .chain((0..300).map(|i| {
+            (0..900)
+                .map(|j| format!("w{i}x{j}"))
+                .collect::<Vec<_>>()
+                .join(" ")
+        }))
+        .enumerate()
+        .map(|(i, value)| StringAttribute {
+            key: format!("tag{i}"),
+            value,
+        })
+        .collect();
+    helpers::sample::insert_data(
+        &*client,
+        Block {
+            number: 1,
+            transactions: vec![Transaction {
+                hash: Some(tx),
+                operations: StorageTransaction {
+                    creates: vec![Create {
+                        btl: 1000,
+                        payload: b"haystack".to_vec().into(),
+                        content_type: "text/plain".to_string(),
+                        string_attributes,
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    Indexer::new(client.clone(), Default::default())
+        .tick()
+        .await
+        .unwrap();
+
+    let key = entity_key(tx, b"haystack".to_vec().into(), 0).to_string();
+    assert_eq!(search(&base, "needle").await[0].0, key);
+    assert_eq!(search(&base, "haystack").await[0].0, key);
+}
@@ -52,6 +52,7 @@ async fn chart_data_usage_should_work() {
     let expected: Value = json!({
         "info": chart_info(),
         "chart": points,
+        "series": [],
     });
 
     assert_eq!(response, expected);
@@ -72,6 +73,7 @@ async fn chart_data_usage_should_work() {
     let expected: Value = json!({
         "info": chart_info(),
         "chart": points,
+        "series": [],
     });
 
     assert_eq!(response, expected);
@@ -114,6 +116,7 @@ async fn chart_data_usage_should_work_without_data_indexed() {
     let expected: Value = json!({
         "info": chart_info(),
         "chart": points,
+        "series": [],
     });
 
     assert_eq!(response, expected);
@@ -141,6 +144,7 @@ async fn chart_data_usage_should_work_without_data_indexed() {
     let expected: Value = json!({
         "info": chart_info(),
         "chart": points,
+        "series": [],
     });
 
     assert_eq!(response, expected);
@@ -52,6 +52,7 @@ async fn chart_entity_count_should_work() {
     let expected: Value = json!({
         "info": chart_info(),
         "chart": points,
+        "series": [],
     });
 
     assert_eq!(response, expected);
@@ -72,6 +73,7 @@ async fn chart_entity_count_should_work() {
     let expected: Value = json!({
         "info": chart_info(),
         "chart": points,
+        "series": [],
     });
 
     assert_eq!(response, expected);
@@ -118,6 +120,7 @@ async fn chart_entity_count_should_work_without_data_indexed() {
     let expected: Value = json!({
         "info": chart_info(),
         "chart": points,
+        "series": [],
     });
 
     assert_eq!(response, expected);
@@ -145,6 +148,7 @@ async fn chart_entity_count_should_work_without_data_indexed() {
     let expected: Value = json!({
         "info": chart_info(),
         "chart": points,
+        "series": [],
     });
 
     assert_eq!(response, expected);
@@ -119,6 +119,7 @@ async fn storage_forecast_hourly_should_work() {
     let expected: Value = json!({
         "info": info,
         "chart": points,
+        "series": [],
     });
 
     assert_eq!(response, expected);
@@ -206,6 +207,7 @@ async fn storage_forecast_daily_should_work() {
     let expected: Value = json!({
         "info": info,
         "chart": points,
+        "series": [],
     });
 
     assert_eq!(response, expected);
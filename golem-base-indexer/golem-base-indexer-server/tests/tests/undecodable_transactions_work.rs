@@ -0,0 +1,117 @@
+use crate::helpers;
+
+use blockscout_service_launcher::test_server;
+use golem_base_indexer_logic::{
+    arkiv::entity_key,
+    types::{Address, TxHash},
+    Indexer,
+};
+use pretty_assertions::assert_eq;
+use sea_orm::{ConnectionTrait, Statement};
+use serde_json::Value;
+
+use crate::helpers::sample::{Block, Transaction};
+use crate::helpers::storage::create;
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_undecodable_transactions_work() {
+    let db = helpers::init_db("test", "undecodable_transactions_work").await;
+    let client = db.client();
+    let base = helpers::init_golem_base_indexer_server(db, |x| x).await;
+
+    let not_brotli = TxHash::repeat_byte(1);
+    let trailing_bytes = TxHash::repeat_byte(2);
+    let valid = TxHash::repeat_byte(3);
+    let alice = Address::repeat_byte(0xa);
+    let bob = Address::repeat_byte(0xb);
+    helpers::sample::insert_data(
+        &*client,
+        Block {
+            number: 1,
+            transactions: vec![
+                Transaction {
+                    hash: Some(not_brotli),
+                    sender: alice,
+                    ..Default::default()
+                },
+                Transaction {
+                    hash: Some(trailing_bytes),
+                    sender: bob,
+                    ..Default::default()
+                },
+                Transaction {
+                    hash: Some(valid),
+                    sender: alice,
+                    operations: create(b"valid"),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let mut rlp = alloy_rlp::encode(create(b"trailing"));
+    rlp.extend_from_slice(&[0x01, 0x02]);
+    let mut compressed = Vec::new();
+    brotli::BrotliCompress(&mut &rlp[..], &mut compressed, &Default::default()).unwrap();
+    for (hash, input) in [
+        (not_brotli, b"definitely not brotli".to_vec()),
+        (trailing_bytes, compressed),
+    ] {
+        client
+            .execute(Statement::from_sql_and_values(
+                client.get_database_backend(),
+                "update transactions set input = $2 where hash = $1",
+                [hash.as_slice().into(), input.into()],
+            ))
+            .await
+            .unwrap();
+    }
+
+    Indexer::new(client.clone(), Default::default())
+        .tick()
+        .await
+        .unwrap();
+
+    let response: Value =
+        test_server::send_get_request(&base, "/api/v1/transactions/undecodable").await;
+    let items = response["items"].as_array().unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0]["hash"], trailing_bytes.to_string());
+    assert_eq!(items[0]["sender"], bob.to_checksum(None));
+    assert_eq!(items[0]["block_number"], "1");
+    assert_eq!(items[0]["index"], "1");
+    assert_eq!(items[0]["error_kind"], "trailing_bytes");
+    assert_eq!(items[1]["hash"], not_brotli.to_string());
+    assert_eq!(items[1]["error_kind"], "compression");
+
+    let response: Value = test_server::send_get_request(
+        &base,
+        &format!("/api/v1/transactions/undecodable?sender={alice}"),
+    )
+    .await;
+    let items = response["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["hash"], not_brotli.to_string());
+
+    // undecodable txs are marked processed and don't hold back the rest of the block
+    let queue: i64 = client
+        .query_one(Statement::from_string(
+            client.get_database_backend(),
+            "select count(*) from golem_base_pending_transaction_operations;",
+        ))
+        .await
+        .unwrap()
+        .unwrap()
+        .try_get_by_index(0)
+        .unwrap();
+    assert_eq!(queue, 0);
+
+    let key = entity_key(valid, b"valid".to_vec().into(), 0);
+    let entity: Value =
+        test_server::send_get_request(&base, &format!("/api/v1/entity/{key}")).await;
+    assert_eq!(entity["key"], key.to_string());
+}
@@ -0,0 +1,430 @@
+use crate::helpers;
+
+use arkiv_storage_tx::{Create, StorageTransaction, StringAttribute};
+use golem_base_indexer_logic::{
+    arkiv::entity_key,
+    repository,
+    types::{Address, EntityKey, TxHash},
+    webhooks::{sign, WebhookDispatcher, WebhookSettings, SIGNATURE_HEADER},
+    Indexer,
+};
+use pretty_assertions::assert_eq;
+use reqwest::{Method, StatusCode};
+use sea_orm::{ConnectionTrait, DbBackend, Statement};
+use serde_json::{json, Value};
+use std::time::Duration;
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+use crate::helpers::{
+    sample::{Block, Transaction},
+    storage::{block, create, update},
+};
+
+async fn received(server: &MockServer, route: &str) -> Vec<wiremock::Request> {
+    server
+        .received_requests()
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|request| request.url.path() == route)
+        .collect()
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_webhooks_work() {
+    let db = helpers::init_db("test", "webhooks_work").await;
+    let client = db.client();
+    // the mock receiver listens on localhost
+    let base = helpers::init_golem_base_indexer_server(db, |settings| {
+        let mut settings = helpers::admin::with_admin_token(settings);
+        settings.webhooks.allow_private_targets = true;
+        settings
+    })
+    .await;
+    let mock = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/flaky"))
+        .respond_with(ResponseTemplate::new(500))
+        .up_to_n_times(1)
+        .mount(&mock)
+        .await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mock)
+        .await;
+
+    let tx_hash = TxHash::random();
+    let sender = Address::random();
+    let payload = b"note".to_vec();
+    let key = entity_key(tx_hash, payload.clone().into(), 0);
+
+    let owner_hook: Value = helpers::admin::post(
+        &base,
+        "/api/v1/admin/webhooks",
+        &json!({
+            "url": format!("{}/owner", mock.uri()),
+            "secret": "owner-secret",
+            "owner": sender.to_string(),
+        }),
+    )
+    .await;
+    assert_eq!(owner_hook["owner"], sender.to_checksum(None));
+    let _: Value = helpers::admin::post(
+        &base,
+        "/api/v1/admin/webhooks",
+        &json!({
+            "url": format!("{}/flaky", mock.uri()),
+            "secret": "annotation-secret",
+            "annotation_key": "type",
+            "annotation_value": "note",
+        }),
+    )
+    .await;
+    let other_hook: Value = helpers::admin::post(
+        &base,
+        "/api/v1/admin/webhooks",
+        &json!({
+            "url": format!("{}/other", mock.uri()),
+            "secret": "other-secret",
+            "entity_key": EntityKey::random().to_string(),
+        }),
+    )
+    .await;
+
+    // invalid registrations
+    for body in [
+        json!({ "url": "ftp://example.com", "secret": "s" }),
+        json!({ "url": "not a url", "secret": "s" }),
+        json!({ "url": mock.uri(), "secret": "" }),
+        json!({ "url": mock.uri(), "secret": "s", "annotation_key": "type" }),
+        json!({ "url": mock.uri(), "secret": "s", "owner": "0x1" }),
+    ] {
+        let response = helpers::admin::request(Method::POST, &base, "/api/v1/admin/webhooks")
+            .json(&body)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{body}");
+    }
+
+    helpers::sample::insert_data(
+        &*client,
+        Block {
+            number: 1,
+            transactions: vec![Transaction {
+                hash: Some(tx_hash),
+                sender,
+                operations: StorageTransaction {
+                    creates: vec![Create {
+                        btl: 100,
+                        payload: payload.into(),
+                        string_attributes: vec![StringAttribute {
+                            key: "type".to_string(),
+                            value: "note".to_string(),
+                        }],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    Indexer::new(client.clone(), Default::default())
+        .tick()
+        .await
+        .unwrap();
+
+    let dispatcher = WebhookDispatcher::new(
+        client.clone(),
+        WebhookSettings {
+            initial_backoff: Duration::ZERO,
+            allow_private_targets: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    dispatcher.tick().await.unwrap();
+
+    let requests = received(&mock, "/owner").await;
+    assert_eq!(requests.len(), 1);
+    let request = &requests[0];
+    assert_eq!(request.headers["x-golem-base-event"], "entity.created");
+    assert_eq!(
+        request.headers[SIGNATURE_HEADER],
+        sign("owner-secret", &request.body).as_str()
+    );
+    let body: Value = serde_json::from_slice(&request.body).unwrap();
+    assert_eq!(body["event"], "entity.created");
+    assert_eq!(body["entity_key"], json!(format!("{key:#x}")));
+    assert_eq!(body["block_number"], 1);
+    assert_eq!(body["owner"], json!(format!("{sender:#x}")));
+    assert!(received(&mock, "/other").await.is_empty());
+
+    // the failed delivery is retried on the next tick
+    assert_eq!(received(&mock, "/flaky").await.len(), 1);
+    dispatcher.tick().await.unwrap();
+    let requests = received(&mock, "/flaky").await;
+    assert_eq!(requests.len(), 2);
+    assert_eq!(
+        requests[1].headers[SIGNATURE_HEADER],
+        sign("annotation-secret", &requests[1].body).as_str()
+    );
+    assert_eq!(received(&mock, "/owner").await.len(), 1);
+
+    // nothing is delivered twice
+    dispatcher.tick().await.unwrap();
+    assert_eq!(mock.received_requests().await.unwrap().len(), 3);
+
+    let list: Value = helpers::admin::get(&base, "/api/v1/admin/webhooks").await;
+    assert_eq!(list["pagination"]["total_items"], "3");
+    assert_eq!(list["items"][0], owner_hook);
+
+    let id = other_hook["id"].as_str().unwrap();
+    let delete = |id: String| {
+        let request = helpers::admin::request(
+            Method::DELETE,
+            &base,
+            &format!("/api/v1/admin/webhooks/{id}"),
+        );
+        async move { request.send().await.unwrap() }
+    };
+    assert_eq!(delete(id.to_string()).await.status(), StatusCode::OK);
+    assert_eq!(delete(id.to_string()).await.status(), StatusCode::NOT_FOUND);
+
+    let list: Value = helpers::admin::get(&base, "/api/v1/admin/webhooks").await;
+    assert_eq!(list["pagination"]["total_items"], "2");
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_webhooks_reject_private_targets() {
+    let db = helpers::init_db("test", "webhooks_reject_private_targets").await;
+    let base = helpers::init_golem_base_indexer_server(db, helpers::admin::with_admin_token).await;
+
+    let body = json!({ "url": "https://example.com/hook", "secret": "s" });
+    let status = reqwest::Client::new()
+        .post(base.join("/api/v1/admin/webhooks").unwrap())
+        .json(&body)
+        .send()
+        .await
+        .unwrap()
+        .status();
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let _: Value = helpers::admin::post(&base, "/api/v1/admin/webhooks", &body).await;
+
+    for url in [
+        "http://127.0.0.1:8050/hook",
+        "http://169.254.169.254/latest/meta-data",
+        "http://10.0.0.1/hook",
+        "http://[::1]/hook",
+        "http://localhost/hook",
+    ] {
+        let response = helpers::admin::request(Method::POST, &base, "/api/v1/admin/webhooks")
+            .json(&json!({ "url": url, "secret": "s" }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{url}");
+    }
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_webhooks_announce_late_operations() {
+    let db = helpers::init_db("test", "webhooks_announce_late_operations").await;
+    let client = db.client();
+    let base = helpers::init_golem_base_indexer_server(db, helpers::admin::with_admin_token).await;
+    let _: Value = helpers::admin::post(
+        &base,
+        "/api/v1/admin/webhooks",
+        &json!({ "url": "https://example.com/hook", "secret": "secret" }),
+    )
+    .await;
+
+    let create_tx = TxHash::repeat_byte(1);
+    let key = entity_key(create_tx, b"first".to_vec().into(), 0);
+    let indexer = Indexer::new(client.clone(), Default::default());
+    helpers::sample::insert_data_multi(
+        &*client,
+        vec![
+            block(1, create_tx, create(b"first")),
+            block(3, TxHash::repeat_byte(3), update(key, b"third")),
+        ],
+    )
+    .await
+    .unwrap();
+    indexer.tick().await.unwrap();
+
+    // an operation positioned before the latest one is indexed afterwards
+    helpers::sample::insert_data(
+        &*client,
+        block(2, TxHash::repeat_byte(2), update(key, b"second")),
+    )
+    .await
+    .unwrap();
+    indexer.tick().await.unwrap();
+
+    let deliveries: Vec<(String, i64)> = client
+        .query_all(Statement::from_string(
+            DbBackend::Postgres,
+            "select event, (payload->>'block_number')::bigint from golem_base_webhook_deliveries order by id",
+        ))
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|row| {
+            (
+                row.try_get_by_index(0).unwrap(),
+                row.try_get_by_index(1).unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        deliveries,
+        [
+            ("entity.created".to_string(), 1),
+            ("entity.updated".to_string(), 3),
+            ("entity.updated".to_string(), 2),
+        ]
+    );
+
+    // replicas don't claim the same deliveries
+    let lease = Duration::from_secs(60);
+    let claimed = repository::webhooks::claim_due_deliveries(&*client, 10, 2, lease)
+        .await
+        .unwrap();
+    assert_eq!(claimed.len(), 2);
+    let claimed_again = repository::webhooks::claim_due_deliveries(&*client, 10, 10, lease)
+        .await
+        .unwrap();
+    assert_eq!(claimed_again.len(), 1);
+    assert!(claimed.iter().all(|d| d.id != claimed_again[0].id));
+    assert!(
+        repository::webhooks::claim_due_deliveries(&*client, 10, 10, lease)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_webhooks_redeliver_exhausted_deliveries() {
+    let db = helpers::init_db("test", "webhooks_redeliver_exhausted_deliveries").await;
+    let client = db.client();
+    let base = helpers::init_golem_base_indexer_server(db, |settings| {
+        let mut settings = helpers::admin::with_admin_token(settings);
+        settings.webhooks.max_attempts = 1;
+        settings
+    })
+    .await;
+    let mock = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/flaky"))
+        .respond_with(ResponseTemplate::new(500))
+        .up_to_n_times(1)
+        .mount(&mock)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/gone"))
+        .respond_with(ResponseTemplate::new(410))
+        .mount(&mock)
+        .await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mock)
+        .await;
+
+    let flaky_hook: Value = helpers::admin::post(
+        &base,
+        "/api/v1/admin/webhooks",
+        &json!({ "url": format!("{}/flaky", mock.uri()), "secret": "secret" }),
+    )
+    .await;
+    let gone_hook: Value = helpers::admin::post(
+        &base,
+        "/api/v1/admin/webhooks",
+        &json!({ "url": format!("{}/gone", mock.uri()), "secret": "secret" }),
+    )
+    .await;
+
+    helpers::sample::insert_data(&*client, block(1, TxHash::repeat_byte(1), create(b"note")))
+        .await
+        .unwrap();
+    Indexer::new(client.clone(), Default::default())
+        .tick()
+        .await
+        .unwrap();
+
+    let settings = WebhookSettings {
+        max_attempts: 1,
+        initial_backoff: Duration::ZERO,
+        allow_private_targets: true,
+        ..Default::default()
+    };
+    let dispatcher = WebhookDispatcher::new(client.clone(), settings.clone()).unwrap();
+    dispatcher.tick().await.unwrap();
+    dispatcher.tick().await.unwrap();
+    // neither is retried once it's out of attempts
+    assert_eq!(received(&mock, "/flaky").await.len(), 1);
+    assert_eq!(received(&mock, "/gone").await.len(), 1);
+
+    let exhausted_path = "/api/v1/admin/webhooks/deliveries/exhausted";
+    let exhausted: Value = helpers::admin::get(&base, exhausted_path).await;
+    assert_eq!(exhausted["pagination"]["total_items"], "2");
+    let items = exhausted["items"].as_array().unwrap();
+    let flaky = items
+        .iter()
+        .find(|item| item["webhook_id"] == flaky_hook["id"])
+        .unwrap();
+    assert_eq!(flaky["event"], "entity.created");
+    assert_eq!(flaky["attempts"], "1");
+    assert!(flaky["last_error"].as_str().unwrap().contains("500"));
+    let payload: Value = serde_json::from_str(flaky["payload"].as_str().unwrap()).unwrap();
+    assert_eq!(payload["block_number"], 1);
+
+    let redeliver = |id: &str| {
+        let request = helpers::admin::request(
+            Method::POST,
+            &base,
+            &format!("/api/v1/admin/webhooks/deliveries/{id}/redeliver"),
+        )
+        .json(&json!({}));
+        async move { request.send().await.unwrap().status() }
+    };
+    let id = flaky["id"].as_str().unwrap();
+    assert_eq!(redeliver(id).await, StatusCode::OK);
+    // it's pending again, not exhausted
+    assert_eq!(redeliver(id).await, StatusCode::NOT_FOUND);
+    assert_eq!(redeliver("1000").await, StatusCode::NOT_FOUND);
+
+    dispatcher.tick().await.unwrap();
+    assert_eq!(received(&mock, "/flaky").await.len(), 2);
+    assert_eq!(received(&mock, "/gone").await.len(), 1);
+
+    let exhausted: Value = helpers::admin::get(&base, exhausted_path).await;
+    assert_eq!(exhausted["pagination"]["total_items"], "1");
+    assert_eq!(exhausted["items"][0]["webhook_id"], gone_hook["id"]);
+
+    // both are kept until they're past retention
+    assert_eq!(dispatcher.cleanup().await.unwrap(), 0);
+    let dispatcher = WebhookDispatcher::new(
+        client.clone(),
+        WebhookSettings {
+            retention: Duration::ZERO,
+            ..settings
+        },
+    )
+    .unwrap();
+    assert_eq!(dispatcher.cleanup().await.unwrap(), 2);
+    let exhausted: Value = helpers::admin::get(&base, exhausted_path).await;
+    assert_eq!(exhausted["pagination"]["total_items"], "0");
+}
@@ -0,0 +1,207 @@
+use crate::helpers;
+
+use arkiv_storage_tx::{Create, StorageTransaction, StringAttribute, Update};
+use futures::{SinkExt, StreamExt};
+use golem_base_indexer_logic::{
+    arkiv::entity_key,
+    types::{Address, TxHash},
+    Indexer,
+};
+use pretty_assertions::assert_eq;
+use serde_json::{json, Value};
+use std::time::Duration;
+use tokio::{net::TcpStream, time::timeout};
+use tokio_tungstenite::{tungstenite::Message, MaybeTlsStream, WebSocketStream};
+
+use crate::helpers::sample::{Block, Transaction};
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+async fn send_event(socket: &mut Socket, reference: &str, topic: &str, event: &str) {
+    let message = json!([reference, reference, topic, event, {}]).to_string();
+    socket.send(Message::text(message)).await.unwrap();
+}
+
+/// Returns the next event as a `(topic, event, payload)` triple,
+/// or `None` if nothing arrives in time.
+async fn next_event(socket: &mut Socket) -> Option<(String, String, Value)> {
+    loop {
+        let message = timeout(Duration::from_secs(2), socket.next())
+            .await
+            .ok()?
+            .unwrap()
+            .unwrap();
+        if let Message::Text(text) = message {
+            let event: Value = serde_json::from_str(&text).unwrap();
+            return Some((
+                event[2].as_str().unwrap().to_string(),
+                event[3].as_str().unwrap().to_string(),
+                event[4].clone(),
+            ));
+        }
+    }
+}
+
+async fn next_history_entries(socket: &mut Socket, count: usize) -> Vec<(String, Value)> {
+    let mut entries = Vec::new();
+    for _ in 0..count {
+        let (topic, event, payload) = next_event(socket).await.expect("missing event");
+        assert_eq!(event, "history_entry");
+        entries.push((topic, payload));
+    }
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    assert!(next_event(socket).await.is_none());
+    entries
+}
+
+#[tokio::test]
+#[ignore = "Needs database to run"]
+async fn test_websocket_subscriptions_work() {
+    let db = helpers::init_db("test", "websocket_subscriptions_work").await;
+    let client = db.client();
+    let base = helpers::init_golem_base_indexer_server(db, |x| x).await;
+    // the indexer runs apart from the server, entries reach it through the database
+    let indexer = Indexer::new(client.clone(), Default::default());
+
+    let mut url = base.join("/socket/websocket?vsn=2.0.0").unwrap();
+    url.set_scheme("ws").unwrap();
+    let (mut socket, _) = tokio_tungstenite::connect_async(url.as_str())
+        .await
+        .unwrap();
+
+    let tx_hash = TxHash::random();
+    let sender = Address::random();
+    let payload = b"note".to_vec();
+    let key = entity_key(tx_hash, payload.clone().into(), 0);
+    let entity_topic = format!("entity:{key:#x}");
+    let owner_topic = format!("owner:{sender:#x}");
+    let annotation_topic = "annotation:type=note".to_string();
+
+    for (reference, topic) in [
+        ("1", &entity_topic),
+        ("2", &owner_topic),
+        ("3", &annotation_topic),
+    ] {
+        send_event(&mut socket, reference, topic, "phx_join").await;
+        let (reply_topic, event, payload) = next_event(&mut socket).await.unwrap();
+        assert_eq!(&reply_topic, topic);
+        assert_eq!(event, "phx_reply");
+        assert_eq!(payload["status"], "ok");
+    }
+
+    // invalid and non-canonical topics are rejected
+    for topic in ["entity:nope", "unknown:1", &format!("owner:{sender}")] {
+        send_event(&mut socket, "4", topic, "phx_join").await;
+        let (_, event, _) = next_event(&mut socket).await.unwrap();
+        assert_eq!(event, "phx_error");
+    }
+
+    helpers::sample::insert_data(
+        &*client,
+        Block {
+            number: 1,
+            transactions: vec![Transaction {
+                hash: Some(tx_hash),
+                sender,
+                operations: StorageTransaction {
+                    creates: vec![Create {
+                        btl: 100,
+                        payload: payload.into(),
+                        string_attributes: vec![StringAttribute {
+                            key: "type".to_string(),
+                            value: "note".to_string(),
+                        }],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    indexer.tick().await.unwrap();
+
+    let entries = next_history_entries(&mut socket, 3).await;
+    let topics: Vec<_> = entries.iter().map(|(topic, _)| topic.clone()).collect();
+    let mut expected = vec![
+        annotation_topic.clone(),
+        entity_topic.clone(),
+        owner_topic.clone(),
+    ];
+    expected.sort();
+    assert_eq!(topics, expected);
+    for (_, payload) in &entries {
+        assert_eq!(payload["entity_key"], json!(format!("{key:#x}")));
+        assert_eq!(payload["operation"], "CREATE");
+        assert_eq!(payload["block_number"], "1");
+    }
+
+    // the update drops the annotation, so only entity and owner get notified
+    helpers::sample::insert_data(
+        &*client,
+        Block {
+            number: 2,
+            transactions: vec![Transaction {
+                sender,
+                operations: StorageTransaction {
+                    updates: vec![Update {
+                        entity_key: key,
+                        btl: 100,
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    indexer.tick().await.unwrap();
+
+    let entries = next_history_entries(&mut socket, 2).await;
+    let mut expected = vec![entity_topic.clone(), owner_topic.clone()];
+    expected.sort();
+    assert_eq!(
+        entries
+            .iter()
+            .map(|(topic, _)| topic.clone())
+            .collect::<Vec<_>>(),
+        expected
+    );
+    for (_, payload) in &entries {
+        assert_eq!(payload["operation"], "UPDATE");
+        assert_eq!(payload["block_number"], "2");
+    }
+
+    // nothing new is pushed once the client leaves the topics
+    send_event(&mut socket, "5", &entity_topic, "phx_leave").await;
+    let (_, event, _) = next_event(&mut socket).await.unwrap();
+    assert_eq!(event, "phx_reply");
+    send_event(&mut socket, "6", &owner_topic, "phx_leave").await;
+    let (_, event, _) = next_event(&mut socket).await.unwrap();
+    assert_eq!(event, "phx_reply");
+    helpers::sample::insert_data(
+        &*client,
+        Block {
+            number: 3,
+            transactions: vec![Transaction {
+                sender,
+                operations: StorageTransaction {
+                    deletes: vec![key],
+                    ..Default::default()
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    indexer.tick().await.unwrap();
+    assert!(next_event(&mut socket).await.is_none());
+}
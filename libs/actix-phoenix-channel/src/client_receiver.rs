@@ -1,7 +1,7 @@
 use crate::{event::ChannelEvent, subscription_registry::SubscriptionRegistry};
 use async_broadcast::Receiver as BroadcastReceiver;
 use async_channel::Receiver;
-use futures_lite::{Stream, StreamExt, stream::Race};
+use futures_lite::{Stream, StreamExt};
 use std::{
     pin::Pin,
     task::{Context, Poll},
@@ -10,7 +10,8 @@ use std::{
 #[derive(Debug)]
 pub struct ClientReceiver {
     subscriptions: SubscriptionRegistry,
-    race: Pin<Box<Race<BroadcastReceiver<ChannelEvent>, Receiver<ChannelEvent>>>>,
+    individual: Pin<Box<Receiver<ChannelEvent>>>,
+    broadcast: Pin<Box<BroadcastReceiver<ChannelEvent>>>,
 }
 
 impl ClientReceiver {
@@ -20,7 +21,8 @@ impl ClientReceiver {
         subscriptions: SubscriptionRegistry,
     ) -> Self {
         Self {
-            race: Box::pin(broadcast.race(individual)),
+            individual: Box::pin(individual),
+            broadcast: Box::pin(broadcast),
             subscriptions,
         }
     }
@@ -31,7 +33,15 @@ impl Stream for ClientReceiver {
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         loop {
-            match self.race.poll_next(cx) {
+            // events sent to this client directly (e.g. join replies) are
+            // delivered regardless of its subscriptions
+            if let Poll::Ready(Some(event)) = self.individual.poll_next(cx) {
+                if let Ok(text) = event.serialize() {
+                    break Poll::Ready(Some(text));
+                }
+                continue;
+            }
+            match self.broadcast.poll_next(cx) {
                 Poll::Ready(Some(event)) if !self.subscriptions.subscribes(&event) => continue,
                 Poll::Ready(Some(event)) => {
                     if let Ok(text) = event.serialize() {
@@ -44,3 +54,68 @@ impl Stream for ClientReceiver {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_lite::future::block_on;
+    use pretty_assertions::assert_eq;
+
+    fn text(event: impl Into<ChannelEvent>) -> String {
+        event.into().serialize().unwrap()
+    }
+
+    #[test]
+    fn filters_broadcasts_by_subscription() {
+        let (_individual_sender, individual) = async_channel::unbounded();
+        let (broadcast_sender, broadcast) = async_broadcast::broadcast(16);
+        let subscriptions = SubscriptionRegistry::default();
+        subscriptions.join("joined".to_string());
+        let mut receiver = ClientReceiver::new(individual, broadcast, subscriptions);
+
+        block_on(async {
+            broadcast_sender
+                .broadcast(("other", "update").into())
+                .await
+                .unwrap();
+            broadcast_sender
+                .broadcast(("joined", "update").into())
+                .await
+                .unwrap();
+            assert_eq!(receiver.next().await, Some(text(("joined", "update"))));
+        });
+    }
+
+    #[test]
+    fn delivers_individual_events_regardless_of_subscription() {
+        let (individual_sender, individual) = async_channel::unbounded();
+        let (broadcast_sender, broadcast) = async_broadcast::broadcast(16);
+        let subscriptions = SubscriptionRegistry::default();
+        subscriptions.join("joined".to_string());
+        let mut receiver = ClientReceiver::new(individual, broadcast, subscriptions);
+
+        block_on(async {
+            broadcast_sender
+                .broadcast(("joined", "update").into())
+                .await
+                .unwrap();
+            individual_sender
+                .send(("other", "phx_reply").into())
+                .await
+                .unwrap();
+            // direct events go first, even when a broadcast is already waiting
+            assert_eq!(receiver.next().await, Some(text(("other", "phx_reply"))));
+            assert_eq!(receiver.next().await, Some(text(("joined", "update"))));
+        });
+    }
+
+    #[test]
+    fn ends_with_the_broadcast() {
+        let (_individual_sender, individual) = async_channel::unbounded();
+        let (broadcast_sender, broadcast) = async_broadcast::broadcast::<ChannelEvent>(16);
+        let mut receiver = ClientReceiver::new(individual, broadcast, Default::default());
+        drop(broadcast_sender);
+
+        assert_eq!(block_on(receiver.next()), None);
+    }
+}